@@ -0,0 +1,76 @@
+extern crate sfml;
+
+use sfml::graphics::*;
+use sfml::system::*;
+use sfml::window::*;
+
+const PALETTE: [Color; 6] = [
+    Color::WHITE,
+    Color::RED,
+    Color::GREEN,
+    Color::BLUE,
+    Color::YELLOW,
+    Color::MAGENTA,
+];
+
+fn main() {
+    // Both windows live on the main thread, which polls their events and draws them.
+    // Drawing to a window makes its OpenGL context active, so nothing else is needed
+    // to switch from one to the other.
+    let mut scene = RenderWindow::builder()
+        .title("Scene - click to paint")
+        .size(640, 480)
+        .style(Style::CLOSE)
+        .position(Vector2i::new(100, 100))
+        .framerate_limit(60)
+        .build()
+        .unwrap();
+    let mut palette = RenderWindow::builder()
+        .title("Palette")
+        .size(60, PALETTE.len() as u32 * 60)
+        .style(Style::TITLEBAR)
+        .position(Vector2i::new(760, 100))
+        .build()
+        .unwrap();
+
+    let mut color = 0;
+    let mut dots: Vec<CircleShape> = Vec::new();
+    let mut swatch = RectangleShape::with_size(Vector2f::new(50., 50.));
+
+    while scene.is_open() {
+        while let Some(event) = scene.poll_event() {
+            match event {
+                Event::Closed => scene.close(),
+                Event::MouseButtonPressed { x, y, .. } => {
+                    let mut dot = CircleShape::new(10., 20);
+                    dot.set_origin((10., 10.));
+                    dot.set_position((x as f32, y as f32));
+                    dot.set_fill_color(PALETTE[color]);
+                    dots.push(dot);
+                }
+                _ => {}
+            }
+        }
+        // The palette must be polled too, or the system considers it frozen
+        while let Some(event) = palette.poll_event() {
+            if let Event::MouseButtonPressed { y, .. } = event {
+                color = (y.max(0) as usize / 60).min(PALETTE.len() - 1);
+            }
+        }
+
+        scene.clear(Color::BLACK);
+        for dot in &dots {
+            scene.draw(dot);
+        }
+        scene.display();
+
+        palette.clear(Color::rgb(40, 40, 40));
+        for (i, &swatch_color) in PALETTE.iter().enumerate() {
+            swatch.set_position((5., i as f32 * 60. + 5.));
+            swatch.set_fill_color(swatch_color);
+            swatch.set_outline_thickness(if i == color { 3. } else { 0. });
+            palette.draw(&swatch);
+        }
+        palette.display();
+    }
+}
@@ -8,7 +8,7 @@ use sfml::window::*;
 trait Effect: Drawable {
     fn update(&mut self, t: f32, x: f32, y: f32);
     fn name(&self) -> &str;
-    fn as_drawable(&self) -> &Drawable;
+    fn as_drawable(&self) -> &dyn Drawable;
 }
 
 struct Pixelate<'t> {
@@ -28,12 +28,11 @@ impl<'t> Pixelate<'t> {
 }
 
 impl<'t> Drawable for Pixelate<'t> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        mut states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
-        states.shader = Some(&self.shader);
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let states = RenderStates {
+            shader: Some(&self.shader),
+            ..states
+        };
         target.draw_with_renderstates(&self.sprite, states);
     }
 }
@@ -46,7 +45,7 @@ impl<'t> Effect for Pixelate<'t> {
     fn name(&self) -> &str {
         "pixelate"
     }
-    fn as_drawable(&self) -> &Drawable {
+    fn as_drawable(&self) -> &dyn Drawable {
         self
     }
 }
@@ -93,12 +92,11 @@ impl<'fo> WaveBlur<'fo> {
 }
 
 impl<'fo> Drawable for WaveBlur<'fo> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        mut states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
-        states.shader = Some(&self.shader);
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let states = RenderStates {
+            shader: Some(&self.shader),
+            ..states
+        };
         target.draw_with_renderstates(&self.text, states);
     }
 }
@@ -114,7 +112,7 @@ impl<'fo> Effect for WaveBlur<'fo> {
     fn name(&self) -> &str {
         "wave + blur"
     }
-    fn as_drawable(&self) -> &Drawable {
+    fn as_drawable(&self) -> &dyn Drawable {
         self
     }
 }
@@ -152,12 +150,11 @@ impl StormBlink {
 }
 
 impl Drawable for StormBlink {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        mut states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
-        states.shader = Some(&self.shader);
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let states = RenderStates {
+            shader: Some(&self.shader),
+            ..states
+        };
         target.draw_with_renderstates(&self.points, states);
     }
 }
@@ -176,7 +173,7 @@ impl Effect for StormBlink {
     fn name(&self) -> &str {
         "storm + blink"
     }
-    fn as_drawable(&self) -> &Drawable {
+    fn as_drawable(&self) -> &dyn Drawable {
         self
     }
 }
@@ -215,12 +212,11 @@ impl<'t> Edge<'t> {
 }
 
 impl<'t> Drawable for Edge<'t> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        mut states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
-        states.shader = Some(&self.shader);
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let states = RenderStates {
+            shader: Some(&self.shader),
+            ..states
+        };
         target.draw_with_renderstates(&Sprite::with_texture(self.surface.texture()), states);
     }
 }
@@ -245,7 +241,7 @@ impl<'t> Effect for Edge<'t> {
         }
         self.surface.display();
     }
-    fn as_drawable(&self) -> &Drawable {
+    fn as_drawable(&self) -> &dyn Drawable {
         self
     }
     fn name(&self) -> &str {
@@ -2,4 +2,5 @@ extern crate sfml_build;
 
 fn main() {
     sfml_build::link_csfml("audio");
+    sfml_build::link_openal();
 }
@@ -6,6 +6,8 @@
 extern crate csfml_system_sys; // manually added
 use csfml_system_sys::*; // manually added
 
+pub mod openal; // manually added
+
 extern "C" {
     pub fn sfListener_setGlobalVolume(volume: f32);
 }
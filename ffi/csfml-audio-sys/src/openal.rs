@@ -0,0 +1,29 @@
+//! The few OpenAL functions used directly, for what CSFML doesn't expose.
+//!
+//! OpenAL is linked by the build script, along with CSFML.
+
+use std::os::raw::{c_char, c_int, c_void};
+
+pub enum ALCdevice {}
+pub enum ALCcontext {}
+
+pub const ALC_DEVICE_SPECIFIER: c_int = 0x1005;
+pub const ALC_DEFAULT_DEVICE_SPECIFIER: c_int = 0x1004;
+pub const ALC_DEFAULT_ALL_DEVICES_SPECIFIER: c_int = 0x1012;
+pub const ALC_ALL_DEVICES_SPECIFIER: c_int = 0x1013;
+pub const ALC_CONNECTED: c_int = 0x313;
+
+/// `alcReopenDeviceSOFT`, from the `ALC_SOFT_reopen_device` extension.
+pub type LPALCREOPENDEVICESOFT =
+    unsafe extern "C" fn(*mut ALCdevice, *const c_char, *const c_int) -> c_char;
+
+extern "C" {
+    pub fn alcGetString(device: *mut ALCdevice, param: c_int) -> *const c_char;
+    pub fn alcIsExtensionPresent(device: *mut ALCdevice, name: *const c_char) -> c_char;
+    pub fn alcGetProcAddress(device: *mut ALCdevice, name: *const c_char) -> *mut c_void;
+    pub fn alcGetIntegerv(device: *mut ALCdevice, param: c_int, size: c_int, values: *mut c_int);
+    pub fn alcOpenDevice(name: *const c_char) -> *mut ALCdevice;
+    pub fn alcCloseDevice(device: *mut ALCdevice) -> c_char;
+    pub fn alcGetCurrentContext() -> *mut ALCcontext;
+    pub fn alcGetContextsDevice(context: *mut ALCcontext) -> *mut ALCdevice;
+}
@@ -2,4 +2,6 @@ extern crate sfml_build;
 
 fn main() {
     sfml_build::link_csfml("graphics");
+    // For the scissor test, which CSFML doesn't expose
+    sfml_build::link_opengl();
 }
@@ -1221,7 +1221,7 @@ extern "C" {
     pub fn sfRenderTexture_createWithSettings(
         width: ::std::os::raw::c_uint,
         height: ::std::os::raw::c_uint,
-        settings: sfContextSettings,
+        settings: *const sfContextSettings,
     ) -> *mut sfRenderTexture;
 }
 extern "C" {
@@ -2887,3 +2887,18 @@ extern "C" {
 extern "C" {
     pub fn sfView_zoom(view: *mut sfView, factor: f32);
 }
+
+// <manually added>
+// OpenGL functions CSFML doesn't wrap, used for the clip rectangle of render targets.
+// OpenGL is linked by the build script.
+pub const GL_SCISSOR_TEST: u32 = 0x0C11;
+extern "system" {
+    pub fn glEnable(cap: u32);
+}
+extern "system" {
+    pub fn glDisable(cap: u32);
+}
+extern "system" {
+    pub fn glScissor(x: i32, y: i32, width: i32, height: i32);
+}
+// </manually added>
@@ -23,3 +23,22 @@ pub fn link_csfml(lib_name: &str) {
     // Link to the csfml library
     println!("cargo:rustc-link-lib=csfml-{}", lib_name);
 }
+
+// Link to a system library used directly, outside of CSFML, named after the target OS
+fn link_system_lib(windows: &str, macos_framework: &str, other: &str) {
+    match var("CARGO_CFG_TARGET_OS").as_ref().map(String::as_str) {
+        Ok("windows") => println!("cargo:rustc-link-lib={}", windows),
+        Ok("macos") => println!("cargo:rustc-link-lib=framework={}", macos_framework),
+        _ => println!("cargo:rustc-link-lib={}", other),
+    }
+}
+
+// Link to OpenGL, for the few calls made outside of CSFML
+pub fn link_opengl() {
+    link_system_lib("opengl32", "OpenGL", "GL");
+}
+
+// Link to OpenAL, for the few calls made outside of CSFML
+pub fn link_openal() {
+    link_system_lib("OpenAL32", "OpenAL", "openal");
+}
@@ -0,0 +1,224 @@
+use crate::audio::{LoadError, Music, SoundBuffer};
+use crate::system::SfBox;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// A closure run once on another thread.
+///
+/// Boxed `FnOnce` closures can only be called directly from Rust 1.35.
+pub(super) trait Task: Send {
+    fn run(self: Box<Self>);
+}
+
+impl<F: FnOnce() + Send> Task for F {
+    fn run(self: Box<Self>) {
+        (*self)()
+    }
+}
+
+type Job = Box<dyn Task>;
+
+/// Loads sound buffers and musics in the background.
+///
+/// Loading a big sound buffer decodes the whole file, which can take long enough to stall
+/// a game loop for several frames. An asset loader hands the loads over to a small pool of
+/// worker threads, and returns a [`LoadHandle`] right away, which is polled every frame
+/// until the asset is ready.
+///
+/// Buffers and musics can be used from any thread once loaded (see the thread safety notes
+/// of [`SoundBuffer`] and [`Music`]). For a single load, [`SoundBuffer::load_async`] spawns
+/// a thread of its own.
+///
+/// Dropping the loader waits for the loads in progress to finish.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{AssetLoader, Sound};
+///
+/// let loader = AssetLoader::new(2);
+/// let mut ambience = loader.load_sound_buffer("ambience.wav");
+/// let mut sound = None;
+/// loop {
+///     if sound.is_none() {
+///         if let Some(buffer) = ambience.poll() {
+///             let mut ambience = Sound::with_shared_buffer(buffer.unwrap().into());
+///             ambience.play();
+///             sound = Some(ambience);
+///         }
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// [`SoundBuffer`]: crate::audio::SoundBuffer
+/// [`Music`]: crate::audio::Music
+/// [`SoundBuffer::load_async`]: crate::audio::SoundBuffer::load_async
+#[derive(Debug)]
+pub struct AssetLoader {
+    jobs: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl AssetLoader {
+    /// Create a new loader, loading up to `threads` assets at the same time.
+    ///
+    /// A loader always has at least one thread.
+    pub fn new(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..threads.max(1))
+            .map(|i| {
+                let receiver = Arc::clone(&receiver);
+                thread::Builder::new()
+                    .name(format!("sfml-asset-loader-{}", i))
+                    .spawn(move || loop {
+                        let job = match receiver.lock() {
+                            Ok(receiver) => receiver.recv(),
+                            Err(poisoned) => poisoned.into_inner().recv(),
+                        };
+                        match job {
+                            // A panicking load fails its handle, not the worker
+                            Ok(job) => drop(panic::catch_unwind(AssertUnwindSafe(|| job.run()))),
+                            Err(_) => break,
+                        }
+                    })
+                    .expect("Failed to spawn an asset loader thread")
+            })
+            .collect();
+        AssetLoader {
+            jobs: Some(sender),
+            workers,
+        }
+    }
+    /// Load a sound buffer from a file in the background.
+    ///
+    /// See [`SoundBuffer::from_file`].
+    ///
+    /// [`SoundBuffer::from_file`]: crate::audio::SoundBuffer::from_file
+    pub fn load_sound_buffer<P: AsRef<Path>>(&self, path: P) -> LoadHandle<SfBox<SoundBuffer>> {
+        let path = path.as_ref().to_path_buf();
+        self.load(move || load_sound_buffer(&path))
+    }
+    /// Open a music from a file in the background.
+    ///
+    /// Opening a music only reads the header of the file, but that can still be slow
+    /// on a busy disk or a network drive. See [`Music::from_file`].
+    ///
+    /// [`Music::from_file`]: crate::audio::Music::from_file
+    pub fn load_music<P: AsRef<Path>>(&self, path: P) -> LoadHandle<Music<'static>> {
+        let path = path.as_ref().to_path_buf();
+        self.load(move || Music::from_file(path))
+    }
+    /// Run any loading function in the background.
+    ///
+    /// # Arguments
+    /// * load - The function loading the asset, called from a worker thread
+    pub fn load<T, F>(&self, load: F) -> LoadHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, LoadError> + Send + 'static,
+    {
+        let (handle, sender) = LoadHandle::new();
+        let job: Job = Box::new(move || {
+            // The handle may have been dropped already, nobody is waiting then.
+            let _ = sender.send(load());
+        });
+        if let Some(ref jobs) = self.jobs {
+            // The workers only stop when the loader is dropped
+            jobs.send(job).expect("Asset loader threads stopped");
+        }
+        handle
+    }
+}
+
+impl Drop for AssetLoader {
+    fn drop(&mut self) {
+        // Closing the channel stops the workers once they are done with the pending loads
+        drop(self.jobs.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+pub(super) fn load_sound_buffer(path: &Path) -> Result<SfBox<SoundBuffer>, LoadError> {
+    SoundBuffer::from_file(path).ok_or_else(|| LoadError::from_file(path))
+}
+
+/// An asset being loaded in the background, by an [`AssetLoader`] or a `load_async` function.
+#[derive(Debug)]
+pub struct LoadHandle<T> {
+    receiver: Receiver<Result<T, LoadError>>,
+    done: bool,
+}
+
+impl<T> LoadHandle<T> {
+    pub(super) fn new() -> (Self, Sender<Result<T, LoadError>>) {
+        let (sender, receiver) = mpsc::channel();
+        let handle = LoadHandle {
+            receiver,
+            done: false,
+        };
+        (handle, sender)
+    }
+    /// Get the asset if it is loaded, without blocking.
+    ///
+    /// Returns `None` while the asset is loading, then the result of the load once.
+    /// The handle is done after that, and keeps returning `None`.
+    ///
+    /// A load whose function panicked fails with [`LoadError::Decode`].
+    pub fn poll(&mut self) -> Option<Result<T, LoadError>> {
+        if self.done {
+            return None;
+        }
+        match self.receiver.try_recv() {
+            Ok(result) => {
+                self.done = true;
+                Some(result)
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.done = true;
+                Some(Err(LoadError::Decode))
+            }
+        }
+    }
+    /// Block until the asset is loaded, and get the result of the load.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result was already taken by [`poll`].
+    ///
+    /// [`poll`]: LoadHandle::poll
+    pub fn wait(self) -> Result<T, LoadError> {
+        assert!(!self.done, "LoadHandle: the asset was already taken");
+        // The sender is only dropped without sending if the loading function panicked
+        self.receiver.recv().unwrap_or(Err(LoadError::Decode))
+    }
+    /// Tell whether the result of the load was taken by [`poll`].
+    ///
+    /// [`poll`]: LoadHandle::poll
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+#[test]
+fn test_load() {
+    let loader = AssetLoader::new(2);
+    let mut handle = loader.load(|| Ok(42));
+    let failed = loader.load::<(), _>(|| Err(LoadError::NotFound));
+    let panicked = loader.load::<(), _>(|| panic!("failed to load"));
+    assert_eq!(failed.wait(), Err(LoadError::NotFound));
+    assert_eq!(panicked.wait(), Err(LoadError::Decode));
+    while !handle.is_done() {
+        if let Some(result) = handle.poll() {
+            assert_eq!(result, Ok(42));
+        }
+    }
+    assert_eq!(handle.poll(), None);
+}
@@ -0,0 +1,70 @@
+use crate::audio::csfml_audio_sys::openal::{alcCloseDevice, alcGetCurrentContext, alcOpenDevice};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Whether sounds and musics are null ones, see `set_null_backend`.
+static BACKEND: AtomicUsize = AtomicUsize::new(UNKNOWN);
+
+/// The backend is picked when the first sound or music is created.
+const UNKNOWN: usize = 0;
+const SFML: usize = 1;
+const NULL: usize = 2;
+
+/// Tell whether audio can be played on this system.
+///
+/// Without an output device (a server without a sound card, a container running tests...),
+/// SFML prints errors on every audio call. This opens the default output device of the
+/// system with OpenAL, and closes it right away, without going through SFML.
+///
+/// Sounds and musics are null ones on such systems, see [`set_null_backend`].
+pub fn is_available() -> bool {
+    unsafe {
+        // SFML keeps its device open while it has a context
+        if !alcGetCurrentContext().is_null() {
+            return true;
+        }
+        let device = alcOpenDevice(ptr::null());
+        if device.is_null() {
+            false
+        } else {
+            let _ = alcCloseDevice(device);
+            true
+        }
+    }
+}
+
+/// Make the sounds and musics created from now on null ones, or real ones again.
+///
+/// A null [`Sound`] or [`Music`] doesn't call SFML at all. It goes through the motions of
+/// playing like a [`NullSource`]: its status, playing offset and properties behave as if
+/// it was playing, without any output. A null music reads the format and duration of its
+/// file in Rust instead, for the ogg, flac and wav files.
+///
+/// By default, sounds and musics are null ones when audio isn't available on the system,
+/// which is probed when the first of them is created, see [`is_available`]. Games then run
+/// silently on machines without a sound device. Tests can make them null ones regardless,
+/// to run the same way on every machine.
+///
+/// Sounds and musics that already exist stay as they are.
+///
+/// [`Sound`]: crate::audio::Sound
+/// [`Music`]: crate::audio::Music
+/// [`NullSource`]: crate::audio::NullSource
+pub fn set_null_backend(null: bool) {
+    BACKEND.store(if null { NULL } else { SFML }, Ordering::SeqCst);
+}
+
+/// Tell whether the sounds and musics created now are null ones, see [`set_null_backend`].
+pub fn is_null_backend() -> bool {
+    match BACKEND.load(Ordering::SeqCst) {
+        UNKNOWN => {
+            let backend = if is_available() { SFML } else { NULL };
+            // Another thread may have picked a backend in the meantime
+            match BACKEND.compare_exchange(UNKNOWN, backend, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => backend == NULL,
+                Err(picked) => picked == NULL,
+            }
+        }
+        backend => backend == NULL,
+    }
+}
@@ -2,9 +2,12 @@ use crate::audio::csfml_audio_sys::*;
 use crate::audio::SoundBuffer;
 use crate::sf_bool_ext::SfBoolExt;
 use crate::system::Time;
-use csfml_system_sys::{sfBool, sfInt16, sfTrue};
+use csfml_system_sys::{sfBool, sfFalse, sfInt16};
+use std::error::Error;
 use std::ffi::{CStr, CString};
+use std::fmt;
 use std::os::raw::c_void;
+use std::panic;
 
 /// Trait for processing captured sound data.
 ///
@@ -54,6 +57,10 @@ use std::os::raw::c_void;
 /// It is important to keep this in mind, because you may have to take care of
 /// synchronization issues if you share data between threads.
 ///
+/// Panics inside any of these methods are caught before they can unwind into SFML;
+/// a panicking [`on_start`] aborts the capture, and a panicking [`on_process_samples`]
+/// stops it.
+///
 /// [`on_start`]: SoundRecorder::on_start
 /// [`on_stop`]: SoundRecorder::on_stop
 /// [`on_process_samples`]: SoundRecorder::on_process_samples
@@ -95,7 +102,13 @@ pub struct SoundRecorderDriver<'a, R: 'a> {
 
 unsafe extern "C" fn on_start_callback<R: SoundRecorder>(user_data: *mut c_void) -> sfBool {
     let recorder = user_data as *mut R;
-    sfBool::from_bool((*recorder).on_start())
+    match panic::catch_unwind(panic::AssertUnwindSafe(|| (*recorder).on_start())) {
+        Ok(start) => sfBool::from_bool(start),
+        Err(_) => {
+            eprintln!("sound_recorder: Aborting recording because `on_start` panicked.");
+            sfFalse
+        }
+    }
 }
 
 unsafe extern "C" fn on_process_callback<R: SoundRecorder>(
@@ -104,12 +117,24 @@ unsafe extern "C" fn on_process_callback<R: SoundRecorder>(
     user_data: *mut c_void,
 ) -> sfBool {
     let recorder = user_data as *mut R;
-    sfBool::from_bool((*recorder).on_process_samples(::std::slice::from_raw_parts(data, len)))
+    let samples = ::std::slice::from_raw_parts(data, len);
+    match panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        (*recorder).on_process_samples(samples)
+    })) {
+        Ok(keep_recording) => sfBool::from_bool(keep_recording),
+        Err(_) => {
+            eprintln!("sound_recorder: Stopping recording because `on_process_samples` panicked.");
+            sfFalse
+        }
+    }
 }
 
 unsafe extern "C" fn on_stop_callback<R: SoundRecorder>(user_data: *mut c_void) {
     let recorder = user_data as *mut R;
-    (*recorder).on_stop()
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| (*recorder).on_stop()));
+    if result.is_err() {
+        eprintln!("sound_recorder: `on_stop` panicked.");
+    }
 }
 
 macro_rules! device_common {
@@ -128,8 +153,10 @@ macro_rules! device_common {
         /// This function sets the audio capture device to the device with the given name.
         /// It can be called on the fly (i.e: while recording).
         /// If you do so while recording and opening the device fails, it stops the recording.
+        ///
+        /// The names of the available devices can be retrieved with [`available_devices`].
         pub fn set_device(&mut self, name: &str) -> Result<(), SetDeviceError> {
-            let name = CString::new(name).unwrap();
+            let name = CString::new(name).map_err(|_| SetDeviceError)?;
             let success = unsafe {
                 #[allow(trivial_casts)]
                 sfSoundRecorder_setDevice(self.ffi_handle as _, name.as_ptr()).to_bool()
@@ -140,7 +167,7 @@ macro_rules! device_common {
                 Err(SetDeviceError)
             }
         }
-    }
+    };
 }
 
 impl<'a, R: SoundRecorder> SoundRecorderDriver<'a, R> {
@@ -196,12 +223,28 @@ impl<'a, R: SoundRecorder> SoundRecorderDriver<'a, R> {
     ///
     /// This method allows you to specify the number of channels used for recording.
     /// Currently only 16-bit mono and 16-bit stereo are supported.
+    /// The channel count is applied when the capture starts, so it must be set beforehand.
+    ///
+    /// When stereo is requested, the samples given to [`SoundRecorder::on_process_samples`]
+    /// are interleaved (left, right, left, right, ...).
+    ///
+    /// Whether the capture device supports the channel count can only be known once it is
+    /// opened: if it doesn't, [`start`] fails.
     ///
     /// # Parameters
     /// * `channel_count`   Number of channels.
     ///                     Currently only mono (1) and stereo (2) are supported.
-    pub fn set_channel_count(&mut self, channel_count: u32) {
+    ///
+    /// Returns an error, leaving the channel count unchanged, if `channel_count`
+    /// is not supported.
+    ///
+    /// [`start`]: SoundRecorderDriver::start
+    pub fn set_channel_count(&mut self, channel_count: u32) -> Result<(), ChannelCountError> {
+        if channel_count != 1 && channel_count != 2 {
+            return Err(ChannelCountError(channel_count));
+        }
         unsafe { sfSoundRecorder_setChannelCount(self.ffi_handle, channel_count) }
+        Ok(())
     }
     /// Get the number of channels used by this recorder.
     ///
@@ -245,6 +288,29 @@ impl<'a, S> Drop for SoundRecorderDriver<'a, S> {
 ///
 /// As usual, don't forget to call the [`is_available`] function before using this type
 /// (see [`SoundRecorder`] for more details about this).
+///
+/// A `SoundBufferRecorder` always records in mono. To record in stereo, drive a custom
+/// [`SoundRecorder`] collecting the samples, with
+/// [`SoundRecorderDriver::set_channel_count`], and create the buffer with
+/// [`SoundBuffer::from_samples`].
+///
+/// # Example
+///
+/// ```no_run
+/// use sfml::audio::{capture, Sound, SoundBufferRecorder};
+///
+/// if capture::is_available() {
+///     let mut recorder = SoundBufferRecorder::new();
+///     recorder.start(44_100);
+///     // ... record for a while ...
+///     recorder.stop();
+///     // The buffer can be played back, saved to a file, copied...
+///     let buffer = recorder.buffer();
+///     buffer.save_to_file("recording.ogg");
+///     let mut sound = Sound::with_buffer(buffer);
+///     sound.play();
+/// }
+/// ```
 #[derive(Debug)]
 pub struct SoundBufferRecorder {
     ffi_handle: *mut sfSoundBufferRecorder,
@@ -254,6 +320,32 @@ pub struct SoundBufferRecorder {
 #[derive(Debug, Clone, Copy)]
 pub struct SetDeviceError;
 
+impl fmt::Display for SetDeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("failed to set the audio capture device")
+    }
+}
+
+impl Error for SetDeviceError {}
+
+/// Error trying to set an unsupported capture channel count.
+///
+/// It holds the rejected channel count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelCountError(pub u32);
+
+impl fmt::Display for ChannelCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unsupported capture channel count {} (only 1 and 2 are supported)",
+            self.0
+        )
+    }
+}
+
+impl Error for ChannelCountError {}
+
 impl SoundBufferRecorder {
     /// Create a new sound buffer recorder
     pub fn new() -> SoundBufferRecorder {
@@ -273,11 +365,17 @@ impl SoundBufferRecorder {
     ///
     /// # Arguments
     /// * sample_rate - Desired capture rate, in number of samples per second
+    ///
+    /// Return whether the start of capture was successful
     pub fn start(&mut self, sample_rate: u32) -> bool {
-        unsafe { sfSoundBufferRecorder_start(self.ffi_handle, sample_rate) == sfTrue }
+        unsafe { sfSoundBufferRecorder_start(self.ffi_handle, sample_rate).to_bool() }
     }
 
     /// Stop the capture of a sound recorder
+    ///
+    /// Once stopped, the captured audio is available through [`buffer`].
+    ///
+    /// [`buffer`]: SoundBufferRecorder::buffer
     pub fn stop(&mut self) {
         unsafe { sfSoundBufferRecorder_stop(self.ffi_handle) }
     }
@@ -369,6 +467,9 @@ pub fn available_devices() -> Vec<String> {
     unsafe {
         let mut count = 0;
         let device_names = sfSoundRecorder_getAvailableDevices(&mut count);
+        if device_names.is_null() {
+            return Vec::new();
+        }
         let device_names = ::std::slice::from_raw_parts(device_names, count);
         let mut names = Vec::new();
         for c_str_ptr in device_names {
@@ -0,0 +1,83 @@
+use crate::audio::SoundSource;
+use crate::system::Time;
+use std::f32::consts::FRAC_PI_2;
+
+/// Crossfade between two sound sources.
+///
+/// A crossfader ramps the volume of an outgoing source down while ramping the volume of
+/// an incoming source up, over a given duration. It uses an equal-power curve, so the
+/// perceived loudness stays constant during the transition instead of dipping halfway.
+///
+/// The crossfader doesn't own the sources, nor does it start or stop them:
+/// play the incoming source when the crossfade starts, feed both sources to [`update`]
+/// every frame, and stop the outgoing one when [`update`] reports that the crossfade
+/// is finished.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Crossfader, Music};
+/// use sfml::system::{Clock, Time};
+///
+/// let mut battle = Music::from_file("battle.ogg").unwrap();
+/// let mut victory = Music::from_file("victory.ogg").unwrap();
+/// battle.play();
+/// // ...
+/// let mut crossfader = Crossfader::new(Time::seconds(2.), 100.);
+/// victory.play();
+/// let mut clock = Clock::start();
+/// while !crossfader.update(clock.restart(), &mut battle, &mut victory) {
+///     // ...
+/// }
+/// battle.stop();
+/// ```
+///
+/// [`update`]: Crossfader::update
+#[derive(Debug, Clone, Copy)]
+pub struct Crossfader {
+    duration: Time,
+    elapsed: Time,
+    volume: f32,
+}
+
+impl Crossfader {
+    /// Create a new crossfade.
+    ///
+    /// # Arguments
+    /// * duration - Duration of the crossfade
+    /// * volume - Volume the incoming source reaches at the end of the crossfade,
+    ///   which is also the volume the outgoing source starts from
+    pub fn new(duration: Time, volume: f32) -> Self {
+        Crossfader {
+            duration,
+            elapsed: Time::ZERO,
+            volume,
+        }
+    }
+    /// Advance the crossfade by `dt`, and apply the new volumes to the sources.
+    ///
+    /// Returns `true` once the crossfade is finished.
+    pub fn update<A, B>(&mut self, dt: Time, outgoing: &mut A, incoming: &mut B) -> bool
+    where
+        A: SoundSource,
+        B: SoundSource,
+    {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        let angle = self.progress() * FRAC_PI_2;
+        outgoing.set_volume(self.volume * angle.cos());
+        incoming.set_volume(self.volume * angle.sin());
+        self.is_finished()
+    }
+    /// Get the progress of the crossfade, from 0 (start) to 1 (finished).
+    pub fn progress(&self) -> f32 {
+        if self.duration <= Time::ZERO {
+            1.
+        } else {
+            self.elapsed.as_seconds() / self.duration.as_seconds()
+        }
+    }
+    /// Tell whether the crossfade is finished.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
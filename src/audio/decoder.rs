@@ -0,0 +1,391 @@
+use crate::audio::{LoadError, SoundStream, StreamMusic};
+use crate::system::Time;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL, CODEC_TYPE_OPUS};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+use symphonia::core::io::{MediaSource, MediaSourceStream, ReadOnlySource};
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use symphonia::core::units;
+
+/// A [`SoundStream`] decoding audio files in Rust, with [symphonia].
+///
+/// This requires the `symphonia` feature.
+///
+/// It plays the formats SFML can't decode by itself, most notably MP3
+/// (the decoder of SFML 2.5 handles ogg/vorbis, flac and wav only). It handles the usual
+/// formats too, AAC and ALAC in MP4 containers included.
+///
+/// Symphonia has no Opus decoder: Opus files are decoded with libopus instead, through the
+/// [opus] crate, which requires the `opus` feature too. Only mono and stereo Opus files
+/// are supported. Without the feature, opening an Opus file fails with
+/// [`LoadError::UnsupportedFormat`].
+///
+/// A decoder stream is played like any other sound stream, with a [`SoundStreamPlayer`].
+/// For an API closer to [`Music`], see [`DecodedMusic`], which owns both.
+///
+/// [symphonia]: https://docs.rs/symphonia
+/// [opus]: https://docs.rs/opus
+/// [`Music`]: crate::audio::Music
+/// [`SoundStreamPlayer`]: crate::audio::SoundStreamPlayer
+pub struct DecoderStream {
+    format: Box<dyn FormatReader>,
+    decoder: Codec,
+    track_id: u32,
+    channel_count: u32,
+    sample_rate: u32,
+    duration: Option<Time>,
+    /// Frames decoded before the start of the audio, counted by the timestamps of the packets.
+    ///
+    /// Only Opus has some, the pre-skip of its encoder.
+    delay: u64,
+    /// Timestamp of the end of the audio, decoded frames after it are dropped.
+    ///
+    /// Only kept for Opus, whose last packet is padded.
+    end_ts: Option<u64>,
+    /// Timestamp to reach after a seek, decoded frames before it are dropped.
+    seek_ts: u64,
+    samples: Vec<i16>,
+}
+
+/// What decodes the packets of the track.
+enum Codec {
+    Symphonia(Box<dyn Decoder>),
+    #[cfg(feature = "opus")]
+    Opus(opus::Decoder),
+}
+
+/// Maximum number of frames in an Opus packet, 120 ms at 48 kHz.
+#[cfg(feature = "opus")]
+const OPUS_MAX_FRAMES: usize = 5760;
+
+impl DecoderStream {
+    /// Open the audio file at `path`.
+    ///
+    /// The extension of the file is used as a hint to find out its format.
+    ///
+    /// Return the stream, or the reason why it could not be opened
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let path = path.as_ref();
+        let mut hint = Hint::new();
+        if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+            let _ = hint.with_extension(extension);
+        }
+        Self::open(Box::new(File::open(path)?), &hint)
+    }
+    /// Open an audio file from a stream (a struct implementing Read and Seek).
+    ///
+    /// The stream is read from the streaming thread, hence the `Send` and `Sync` bounds.
+    ///
+    /// Return the stream, or the reason why it could not be opened
+    pub fn from_reader<R>(reader: R) -> Result<Self, LoadError>
+    where
+        R: Read + Seek + Send + Sync + 'static,
+    {
+        Self::open(Box::new(Source(reader)), &Hint::new())
+    }
+    /// Open an audio stream from a source that can't seek, like a network socket.
+    ///
+    /// This is meant for live sources such as internet radios, which are played as they
+    /// arrive: seeking the returned stream fails, and it can't loop.
+    ///
+    /// # Arguments
+    /// * reader - The source of the data
+    /// * extension - The usual file extension of the format of the data (`"mp3"` for
+    ///   example), used as a hint to find it out
+    ///
+    /// Return the stream, or the reason why it could not be opened
+    pub fn from_live_reader<R>(reader: R, extension: Option<&str>) -> Result<Self, LoadError>
+    where
+        R: Read + Send + Sync + 'static,
+    {
+        let mut hint = Hint::new();
+        if let Some(extension) = extension {
+            let _ = hint.with_extension(extension);
+        }
+        Self::open(Box::new(ReadOnlySource::new(reader)), &hint)
+    }
+    /// Open an audio file from its data in memory.
+    ///
+    /// Return the stream, or the reason why it could not be opened
+    pub fn from_memory(data: Vec<u8>) -> Result<Self, LoadError> {
+        Self::open(Box::new(Cursor::new(data)), &Hint::new())
+    }
+    fn open(source: Box<dyn MediaSource>, hint: &Hint) -> Result<Self, LoadError> {
+        let stream = MediaSourceStream::new(source, Default::default());
+        let probed = symphonia::default::get_probe().format(
+            hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )?;
+        let format = probed.format;
+        let track = format
+            .tracks()
+            .iter()
+            .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or(LoadError::UnsupportedFormat)?;
+        let params = &track.codec_params;
+        let channel_count = params
+            .channels
+            .map_or(0, |channels| channels.count() as u32);
+        let sample_rate = params.sample_rate.unwrap_or(0);
+        // SFML can't play a stream without these
+        if channel_count == 0 || sample_rate == 0 {
+            return Err(LoadError::Decode);
+        }
+        let (decoder, delay, end_ts) = if params.codec == CODEC_TYPE_OPUS {
+            (
+                Self::opus_decoder(channel_count, sample_rate)?,
+                u64::from(params.delay.unwrap_or(0)),
+                params
+                    .n_frames
+                    .map(|frames| frames.saturating_sub(params.padding.unwrap_or(0).into())),
+            )
+        } else {
+            let decoder =
+                symphonia::default::get_codecs().make(params, &DecoderOptions::default())?;
+            (Codec::Symphonia(decoder), 0, None)
+        };
+        let duration = end_ts
+            .or(params.n_frames)
+            .map(|frames| Time::seconds(frames.saturating_sub(delay) as f32 / sample_rate as f32));
+        Ok(DecoderStream {
+            track_id: track.id,
+            format,
+            decoder,
+            channel_count,
+            sample_rate,
+            duration,
+            delay,
+            end_ts,
+            seek_ts: delay,
+            samples: Vec::new(),
+        })
+    }
+    #[cfg(feature = "opus")]
+    fn opus_decoder(channel_count: u32, sample_rate: u32) -> Result<Codec, LoadError> {
+        let channels = match channel_count {
+            1 => opus::Channels::Mono,
+            2 => opus::Channels::Stereo,
+            _ => return Err(LoadError::UnsupportedFormat),
+        };
+        opus::Decoder::new(sample_rate, channels)
+            .map(Codec::Opus)
+            .map_err(|_| LoadError::Decode)
+    }
+    #[cfg(not(feature = "opus"))]
+    fn opus_decoder(_channel_count: u32, _sample_rate: u32) -> Result<Codec, LoadError> {
+        Err(LoadError::UnsupportedFormat)
+    }
+    /// Decode the next packet of the track into `self.samples`.
+    ///
+    /// Returns `false` at the end of the stream, or on unrecoverable errors.
+    fn decode_next(&mut self) -> bool {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(ref e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    return false
+                }
+                Err(e) => {
+                    eprintln!("decoder: Failed to read packet: {}", e);
+                    return false;
+                }
+            };
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+            let channel_count = self.channel_count as usize;
+            let ts = packet.ts();
+            match self.decoder {
+                Codec::Symphonia(ref mut decoder) => {
+                    let decoded = match decoder.decode(&packet) {
+                        Ok(decoded) => decoded,
+                        // The packet is corrupted, skip it
+                        Err(SymphoniaError::DecodeError(_)) => continue,
+                        Err(e) => {
+                            eprintln!("decoder: Failed to decode packet: {}", e);
+                            return false;
+                        }
+                    };
+                    if ts + decoded.frames() as u64 <= self.seek_ts {
+                        continue;
+                    }
+                    let mut buffer =
+                        SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+                    buffer.copy_interleaved_ref(decoded);
+                    self.samples.clear();
+                    self.samples.extend_from_slice(buffer.samples());
+                }
+                // Packets before the seek target are decoded too, as the next ones depend
+                // on them
+                #[cfg(feature = "opus")]
+                Codec::Opus(ref mut decoder) => {
+                    self.samples.resize(OPUS_MAX_FRAMES * channel_count, 0);
+                    match decoder.decode(packet.buf(), &mut self.samples, false) {
+                        Ok(frames) => self.samples.truncate(frames * channel_count),
+                        // The packet is corrupted, skip it
+                        Err(_) => continue,
+                    }
+                    if ts + (self.samples.len() / channel_count) as u64 <= self.seek_ts {
+                        continue;
+                    }
+                }
+            }
+            // Audio tracks count their timestamps in frames
+            if let Some(end_ts) = self.end_ts {
+                let len = end_ts.saturating_sub(ts) as usize * channel_count;
+                self.samples.truncate(len);
+            }
+            let skip = self.seek_ts.saturating_sub(ts) as usize * channel_count;
+            self.seek_ts = 0;
+            let _ = self.samples.drain(..skip.min(self.samples.len()));
+            if !self.samples.is_empty() {
+                return true;
+            }
+        }
+    }
+}
+
+impl SoundStream for DecoderStream {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        let keep_playing = self.decode_next();
+        if !keep_playing {
+            self.samples.clear();
+        }
+        (&mut self.samples[..], keep_playing)
+    }
+    fn seek(&mut self, offset: Time) {
+        let seconds = f64::from(offset.as_seconds().max(0.));
+        let to = if self.delay > 0 {
+            // The timestamps count the frames of the delay, in frames of the track
+            SeekTo::TimeStamp {
+                ts: (seconds * f64::from(self.sample_rate)) as u64 + self.delay,
+                track_id: self.track_id,
+            }
+        } else {
+            SeekTo::Time {
+                time: units::Time::new(seconds.trunc() as u64, seconds.fract()),
+                track_id: Some(self.track_id),
+            }
+        };
+        match self.format.seek(SeekMode::Accurate, to) {
+            Ok(seeked) => self.seek_ts = seeked.required_ts,
+            Err(e) => eprintln!("decoder: Failed to seek: {}", e),
+        }
+        match self.decoder {
+            Codec::Symphonia(ref mut decoder) => decoder.reset(),
+            #[cfg(feature = "opus")]
+            Codec::Opus(ref mut decoder) => {
+                let _ = decoder.reset_state();
+            }
+        }
+    }
+    fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+    fn duration(&self) -> Option<Time> {
+        self.duration
+    }
+}
+
+impl fmt::Debug for DecoderStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecoderStream")
+            .field("track_id", &self.track_id)
+            .field("channel_count", &self.channel_count)
+            .field("sample_rate", &self.sample_rate)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+impl From<SymphoniaError> for LoadError {
+    fn from(error: SymphoniaError) -> Self {
+        match error {
+            SymphoniaError::IoError(e) => e.into(),
+            SymphoniaError::Unsupported(_) => LoadError::UnsupportedFormat,
+            _ => LoadError::Decode,
+        }
+    }
+}
+
+/// A reader handed over to symphonia.
+struct Source<R>(R);
+
+impl<R: Read> Read for Source<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for Source<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+impl<R: Read + Seek + Send + Sync> MediaSource for Source<R> {
+    fn is_seekable(&self) -> bool {
+        true
+    }
+    fn byte_len(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// A music decoded in Rust, with [symphonia].
+///
+/// This requires the `symphonia` feature.
+///
+/// This is a [`StreamMusic`] playing a [`DecoderStream`]. It offers the same interface as
+/// [`Music`], for the formats SFML can't decode (see [`DecoderStream`]).
+///
+/// Like a [`Music`], it can be moved to a thread dedicated to audio.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::DecodedMusic;
+///
+/// let mut music = DecodedMusic::from_file("theme.mp3").unwrap();
+/// music.set_looping(true);
+/// music.play();
+/// ```
+///
+/// [symphonia]: https://docs.rs/symphonia
+/// [`Music`]: crate::audio::Music
+pub type DecodedMusic = StreamMusic<DecoderStream>;
+
+impl StreamMusic<DecoderStream> {
+    /// Create a new music and load it from a file.
+    ///
+    /// See [`DecoderStream::from_file`].
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        DecoderStream::from_file(path).map(Self::new)
+    }
+    /// Create a new music and load it from a stream.
+    ///
+    /// See [`DecoderStream::from_reader`].
+    pub fn from_reader<R>(reader: R) -> Result<Self, LoadError>
+    where
+        R: Read + Seek + Send + Sync + 'static,
+    {
+        DecoderStream::from_reader(reader).map(Self::new)
+    }
+    /// Create a new music and load it from memory.
+    ///
+    /// See [`DecoderStream::from_memory`].
+    pub fn from_memory(data: Vec<u8>) -> Result<Self, LoadError> {
+        DecoderStream::from_memory(data).map(Self::new)
+    }
+}
@@ -0,0 +1,203 @@
+//! Selection of the audio output device.
+//!
+//! This requires the `openal` feature.
+//!
+//! SFML always plays through the default output device of the system, and offers no way of
+//! choosing another one. These functions go through OpenAL, which SFML is built on, to list
+//! the output devices and move the playback of SFML to one of them.
+//!
+//! SFML opens the device when the first audio object (sound, buffer, music, ...) is created,
+//! and closes it once all of them are destroyed. The functions acting on the current device
+//! therefore need at least one audio object to be alive, and the choice of device is lost
+//! when the last one is dropped.
+//!
+//! Listing devices works with any OpenAL implementation, but switching them requires the
+//! `ALC_SOFT_reopen_device` extension of OpenAL Soft 1.22 or later. The OpenAL SFML 2.5
+//! ships with on Windows and macOS predates it, so [`set_device`] returns
+//! [`DeviceError::Unsupported`] there, unless SFML uses a newer OpenAL Soft instead, like
+//! the one most Linux distributions package.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::audio::{device, Music};
+//!
+//! let mut music = Music::from_file("set.ogg").unwrap();
+//! for name in device::devices() {
+//!     println!("{}", name);
+//! }
+//! device::set_device(Some("USB Audio CODEC Analog Stereo")).unwrap();
+//! music.play();
+//! loop {
+//!     if !device::is_connected() {
+//!         // The headphones were unplugged, fall back to the default device
+//!         let _ = device::set_device(None);
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use crate::audio::csfml_audio_sys::openal::*;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+/// Error that can happen when switching the output device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceError {
+    /// SFML has no device open, because no audio object is alive.
+    NoDevice,
+    /// The OpenAL implementation can't switch devices.
+    Unsupported,
+    /// The device name contains a nul byte.
+    InvalidName,
+    /// The device could not be opened, the previous one is still used.
+    Failed,
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DeviceError::NoDevice => "no audio device is open",
+            DeviceError::Unsupported => "switching audio devices is not supported",
+            DeviceError::InvalidName => "invalid audio device name",
+            DeviceError::Failed => "failed to open the audio device",
+        })
+    }
+}
+
+impl Error for DeviceError {}
+
+fn has_extension(device: *mut ALCdevice, name: &[u8]) -> bool {
+    let name = CStr::from_bytes_with_nul(name).expect("extension names are nul terminated");
+    unsafe { alcIsExtensionPresent(device, name.as_ptr()) != 0 }
+}
+
+/// Parameters naming devices, with the full names if OpenAL can enumerate them.
+fn specifiers() -> (c_int, c_int) {
+    if has_extension(ptr::null_mut(), b"ALC_ENUMERATE_ALL_EXT\0") {
+        (ALC_ALL_DEVICES_SPECIFIER, ALC_DEFAULT_ALL_DEVICES_SPECIFIER)
+    } else {
+        (ALC_DEVICE_SPECIFIER, ALC_DEFAULT_DEVICE_SPECIFIER)
+    }
+}
+
+fn string(device: *mut ALCdevice, param: c_int) -> Option<String> {
+    let string = unsafe { alcGetString(device, param) };
+    if string.is_null() {
+        None
+    } else {
+        Some(
+            unsafe { CStr::from_ptr(string) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+/// Split a list of strings, each one terminated by a nul byte, the list by an empty string.
+unsafe fn parse_list(mut list: *const c_char) -> Vec<String> {
+    let mut names = Vec::new();
+    if list.is_null() {
+        return names;
+    }
+    while *list != 0 {
+        let name = CStr::from_ptr(list);
+        list = list.add(name.to_bytes().len() + 1);
+        names.push(name.to_string_lossy().into_owned());
+    }
+    names
+}
+
+/// Get the device SFML plays through, if it has one open.
+fn open_device() -> Option<*mut ALCdevice> {
+    let device = unsafe {
+        let context = alcGetCurrentContext();
+        if context.is_null() {
+            return None;
+        }
+        alcGetContextsDevice(context)
+    };
+    if device.is_null() {
+        None
+    } else {
+        Some(device)
+    }
+}
+
+/// Get the names of the available output devices.
+pub fn devices() -> Vec<String> {
+    unsafe { parse_list(alcGetString(ptr::null_mut(), specifiers().0)) }
+}
+
+/// Get the name of the default output device of the system.
+pub fn default_device() -> Option<String> {
+    string(ptr::null_mut(), specifiers().1)
+}
+
+/// Get the name of the device SFML plays through.
+///
+/// Returns `None` if SFML has no device open.
+pub fn current_device() -> Option<String> {
+    string(open_device()?, specifiers().0)
+}
+
+/// Move the playback of SFML to another output device.
+///
+/// Sounds and musics keep playing, through the new device. This needs a recent OpenAL
+/// Soft, see the [module documentation](crate::audio::device).
+///
+/// # Arguments
+/// * name - Name of the device, as returned by [`devices`],
+///   or `None` for the default device of the system
+pub fn set_device(name: Option<&str>) -> Result<(), DeviceError> {
+    let device = open_device().ok_or(DeviceError::NoDevice)?;
+    if !has_extension(device, b"ALC_SOFT_reopen_device\0") {
+        return Err(DeviceError::Unsupported);
+    }
+    let name = match name {
+        Some(name) => Some(CString::new(name).map_err(|_| DeviceError::InvalidName)?),
+        None => None,
+    };
+    unsafe {
+        let reopen = alcGetProcAddress(device, b"alcReopenDeviceSOFT\0".as_ptr() as _);
+        if reopen.is_null() {
+            return Err(DeviceError::Unsupported);
+        }
+        let reopen: LPALCREOPENDEVICESOFT = std::mem::transmute(reopen);
+        let name = name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+        if reopen(device, name, ptr::null()) != 0 {
+            Ok(())
+        } else {
+            Err(DeviceError::Failed)
+        }
+    }
+}
+
+/// Tell whether the device SFML plays through is still connected.
+///
+/// A device unplugged while in use stays silent. Check this regularly, and switch to
+/// another device with [`set_device`] when it returns `false`.
+/// If SFML has no device open, or OpenAL can't tell, the device is considered connected.
+pub fn is_connected() -> bool {
+    let device = match open_device() {
+        Some(device) => device,
+        None => return true,
+    };
+    if !has_extension(device, b"ALC_EXT_disconnect\0") {
+        return true;
+    }
+    let mut connected = 1;
+    unsafe { alcGetIntegerv(device, ALC_CONNECTED, 1, &mut connected) };
+    connected != 0
+}
+
+#[test]
+fn test_parse_list() {
+    let list = b"Speakers\0USB Headset\0\0";
+    let names = unsafe { parse_list(list.as_ptr() as *const c_char) };
+    assert_eq!(names, ["Speakers", "USB Headset"]);
+    assert!(unsafe { parse_list(b"\0".as_ptr() as *const c_char) }.is_empty());
+}
@@ -0,0 +1,125 @@
+//! Signal processing helpers for audio samples.
+//!
+//! This module requires the `dsp` feature.
+
+use std::f32::consts::PI;
+
+/// Compute the discrete Fourier transform of a complex signal, in place.
+///
+/// This is a radix-2 fast Fourier transform.
+///
+/// # Arguments
+/// * re - Real parts of the signal, replaced by the real parts of the transform
+/// * im - Imaginary parts of the signal, replaced by the imaginary parts of the transform
+///
+/// # Panics
+///
+/// Panics if `re` and `im` have different lengths, or if their length is not a power of two.
+pub fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    assert_eq!(
+        n,
+        im.len(),
+        "fft: real and imaginary parts differ in length"
+    );
+    assert!(n.is_power_of_two(), "fft: length is not a power of two");
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2. * PI / len as f32;
+        let (w_im, w_re) = angle.sin_cos();
+        for start in (0..n).step_by(len) {
+            let (mut cur_re, mut cur_im) = (1., 0.);
+            for k in start..start + len / 2 {
+                let l = k + len / 2;
+                let t_re = re[l] * cur_re - im[l] * cur_im;
+                let t_im = re[l] * cur_im + im[l] * cur_re;
+                re[l] = re[k] - t_re;
+                im[l] = im[k] - t_im;
+                re[k] += t_re;
+                im[k] += t_im;
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Compute the magnitude spectrum of interleaved 16-bit samples.
+///
+/// The channels are mixed down to mono, and a Hann window is applied before the transform.
+/// The samples are zero-padded up to the next power of two.
+///
+/// # Arguments
+/// * samples - Interleaved samples, such as those kept by a [`SampleTap`]
+/// * channel_count - Number of channels of the samples
+///
+/// Return the magnitudes of the positive frequency bins, normalized so that a full scale
+/// sine wave peaks at about 1. See [`bin_frequency`] for the frequency of each bin.
+///
+/// [`SampleTap`]: crate::audio::SampleTap
+pub fn spectrum(samples: &[i16], channel_count: u32) -> Vec<f32> {
+    let channel_count = channel_count.max(1) as usize;
+    let frames = samples.len() / channel_count;
+    if frames == 0 {
+        return Vec::new();
+    }
+    let n = frames.next_power_of_two();
+    let mut re = vec![0.; n];
+    let mut im = vec![0.; n];
+    for (i, frame) in samples.chunks(channel_count).take(frames).enumerate() {
+        let sum: f32 = frame.iter().map(|&s| f32::from(s)).sum();
+        let window = 0.5 - 0.5 * (2. * PI * i as f32 / frames as f32).cos();
+        re[i] = sum / channel_count as f32 / 32_768. * window;
+    }
+    fft(&mut re, &mut im);
+    // The Hann window halves the amplitude, and the energy is split between the positive
+    // and negative frequencies
+    let scale = 4. / frames as f32;
+    re.iter()
+        .zip(&im)
+        .take(n / 2)
+        .map(|(re, im)| (re * re + im * im).sqrt() * scale)
+        .collect()
+}
+
+/// Get the center frequency, in hertz, of a bin of a spectrum.
+///
+/// # Arguments
+/// * bin - Index of the bin
+/// * fft_size - Size of the transform, which is twice the length of a [`spectrum`]
+/// * sample_rate - Sample rate of the samples
+pub fn bin_frequency(bin: usize, fft_size: usize, sample_rate: u32) -> f32 {
+    bin as f32 * sample_rate as f32 / fft_size as f32
+}
+
+#[test]
+fn test_spectrum() {
+    let samples: Vec<i16> = (0..1024)
+        .map(|i| ((2. * PI * 64. * i as f32 / 1024.).sin() * 32_767.) as i16)
+        .collect();
+    let spectrum = spectrum(&samples, 1);
+    assert_eq!(spectrum.len(), 512);
+    let peak = (0..spectrum.len())
+        .max_by(|&a, &b| spectrum[a].partial_cmp(&spectrum[b]).unwrap())
+        .unwrap();
+    assert_eq!(peak, 64);
+    assert!((spectrum[peak] - 1.).abs() < 0.05);
+}
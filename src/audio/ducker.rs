@@ -0,0 +1,156 @@
+use crate::audio::{MixerBus, SoundSource};
+use crate::system::Time;
+
+/// Lowers the volume of a mixer bus while the sources of another bus are playing.
+///
+/// Ducking is how games keep dialogs intelligible: whenever a voice is playing, the music
+/// dips by a few decibels, and comes back up once the voice is over. The dip is reached
+/// in the attack time, and the volume recovers in the release time; the changes are linear
+/// in decibels, which sounds smooth.
+///
+/// The ducker drives the duck factor of the target bus (see [`MixerBus::set_duck`]),
+/// so it doesn't interfere with the volume of the bus set by the player, or with fades
+/// on its sources. Like a [`Crossfader`], it has to be updated every frame.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Ducker, MixerBus, Music, Sound, SoundBuffer};
+/// use sfml::system::{Clock, Time};
+///
+/// let mut music = MixerBus::new();
+/// music.add(Music::from_file("theme.ogg").unwrap());
+/// let buffer = SoundBuffer::from_file("line.wav").unwrap();
+/// let mut voices = MixerBus::new();
+/// voices.add(Sound::with_buffer(&buffer));
+///
+/// // Dip the music by 12 dB in a quarter of a second, recover in one second
+/// let mut ducker = Ducker::new(12., Time::milliseconds(250), Time::seconds(1.));
+/// let mut clock = Clock::start();
+/// loop {
+///     ducker.update(clock.restart(), &voices, &mut music);
+///     // ...
+/// }
+/// ```
+///
+/// [`MixerBus::set_duck`]: crate::audio::MixerBus::set_duck
+/// [`Crossfader`]: crate::audio::Crossfader
+#[derive(Debug, Clone, Copy)]
+pub struct Ducker {
+    depth: f32,
+    /// Attack and release times, in seconds.
+    attack: f32,
+    release: f32,
+    /// Current attenuation, in decibels, from 0 to `depth`.
+    level: f32,
+}
+
+impl Ducker {
+    /// Create a new ducker.
+    ///
+    /// # Arguments
+    /// * depth - How much the target bus is lowered, in decibels
+    /// * attack - Time it takes to lower the target bus
+    /// * release - Time it takes for the target bus to recover
+    pub fn new(depth: f32, attack: Time, release: Time) -> Self {
+        Ducker {
+            depth: depth.abs(),
+            attack: attack.as_seconds(),
+            release: release.as_seconds(),
+            level: 0.,
+        }
+    }
+    /// Advance the ducker by `dt`, ducking `target` if any source of `trigger` is playing.
+    ///
+    /// Returns the duck factor applied to `target`.
+    pub fn update<A, B>(&mut self, dt: Time, trigger: &MixerBus<A>, target: &mut MixerBus<B>) -> f32
+    where
+        A: SoundSource,
+        B: SoundSource,
+    {
+        let factor = self.advance(dt, trigger.is_playing());
+        target.set_duck(factor);
+        factor
+    }
+    /// Advance the ducker by `dt`, without touching any bus.
+    ///
+    /// This is for ducking triggered by something else than a bus. The returned factor
+    /// can be applied to a bus with [`MixerBus::set_duck`], or to the volume of a source.
+    ///
+    /// # Arguments
+    /// * dt - Time elapsed since the last update
+    /// * active - Whether the target should be ducked
+    ///
+    /// Returns the duck factor, in the range [0, 1].
+    ///
+    /// [`MixerBus::set_duck`]: crate::audio::MixerBus::set_duck
+    pub fn advance(&mut self, dt: Time, active: bool) -> f32 {
+        self.advance_seconds(dt.as_seconds(), active)
+    }
+    fn advance_seconds(&mut self, dt: f32, active: bool) -> f32 {
+        let (goal, duration) = if active {
+            (self.depth, self.attack)
+        } else {
+            (0., self.release)
+        };
+        if duration <= 0. {
+            self.level = goal;
+        } else {
+            let step = self.depth * dt / duration;
+            self.level = if self.level < goal {
+                (self.level + step).min(goal)
+            } else {
+                (self.level - step).max(goal)
+            };
+        }
+        self.factor()
+    }
+    /// Get the current duck factor, in the range [0, 1].
+    pub fn factor(&self) -> f32 {
+        10f32.powf(-self.level / 20.)
+    }
+    /// Tell whether the target is lowered at all.
+    pub fn is_ducking(&self) -> bool {
+        self.level > 0.
+    }
+    /// Set how much the target is lowered, in decibels.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth.abs();
+        self.level = self.level.min(self.depth);
+    }
+    /// Get how much the target is lowered, in decibels.
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+    /// Set the time it takes to lower the target.
+    pub fn set_attack(&mut self, attack: Time) {
+        self.attack = attack.as_seconds();
+    }
+    /// Get the time it takes to lower the target.
+    pub fn attack(&self) -> Time {
+        Time::seconds(self.attack)
+    }
+    /// Set the time it takes for the target to recover.
+    pub fn set_release(&mut self, release: Time) {
+        self.release = release.as_seconds();
+    }
+    /// Get the time it takes for the target to recover.
+    pub fn release(&self) -> Time {
+        Time::seconds(self.release)
+    }
+}
+
+#[test]
+fn test_envelope() {
+    let mut ducker = Ducker {
+        depth: 20.,
+        attack: 1.,
+        release: 2.,
+        level: 0.,
+    };
+    assert!((ducker.advance_seconds(0.5, true) - 10f32.powf(-0.5)).abs() < 1e-6);
+    assert!((ducker.advance_seconds(1., true) - 0.1).abs() < 1e-6);
+    assert!((ducker.advance_seconds(1., false) - 10f32.powf(-0.5)).abs() < 1e-6);
+    assert_eq!(ducker.advance_seconds(1., false), 1.);
+    assert!(!ducker.is_ducking());
+}
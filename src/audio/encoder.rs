@@ -0,0 +1,307 @@
+use crate::audio::flac::FlacEncoder;
+use crate::audio::{SoundFileFormat, SoundRecorder, WavEncoder};
+use crate::system::Time;
+use std::fmt;
+use std::io::{self, Seek, Write};
+#[cfg(feature = "vorbis_rs")]
+use std::num::{NonZeroU32, NonZeroU8};
+#[cfg(feature = "vorbis_rs")]
+use vorbis_rs::{VorbisEncoder, VorbisEncoderBuilder};
+
+/// Writes audio samples to a WAV, FLAC or Ogg Vorbis file as they come.
+///
+/// Like a [`WavEncoder`], an `Encoder` writes the samples to its writer as soon as it has
+/// enough of them, so that long recordings don't have to be held in memory. It is a
+/// [`SoundRecorder`], so it can be driven by a [`SoundRecorderDriver`] directly, and custom
+/// recorders can feed it with [`write_samples`] as well.
+///
+/// The formats are encoded in Rust:
+///
+/// - WAV files hold the samples as they are, see [`WavEncoder`].
+/// - FLAC files are lossless, and written a block of 4096 frames at a time. The encoder is
+///   simpler than libFLAC, so the files are a bit larger than the ones SFML writes.
+/// - Ogg Vorbis files are encoded with libvorbis, through the [vorbis_rs] crate. This
+///   requires the `vorbis_rs` feature.
+///
+/// The headers of the files hold the length of the audio, so they are written again by
+/// [`finish`], or when the encoder is dropped, when the writer can seek (see [`new`]).
+/// Writers that can't seek, such as pipes and sockets, are given headers with an unknown
+/// length instead (see [`new_streaming`]). Ogg Vorbis files don't need their length
+/// upfront.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Encoder, SoundFileFormat, SoundRecorderDriver};
+/// use std::fs::File;
+/// use std::io::BufWriter;
+///
+/// let file = BufWriter::new(File::create("interview.flac").unwrap());
+/// let mut encoder = Encoder::new(file, SoundFileFormat::Flac, 1, 44_100).unwrap();
+/// {
+///     let mut driver = SoundRecorderDriver::new(&mut encoder);
+///     driver.start(44_100);
+///     // ...
+///     driver.stop();
+/// }
+/// encoder.finish().unwrap();
+/// ```
+///
+/// [vorbis_rs]: https://crates.io/crates/vorbis_rs
+/// [`SoundRecorderDriver`]: crate::audio::SoundRecorderDriver
+/// [`write_samples`]: Encoder::write_samples
+/// [`finish`]: Encoder::finish
+/// [`new`]: Encoder::new
+/// [`new_streaming`]: Encoder::new_streaming
+pub struct Encoder<W: Write> {
+    format: Format<W>,
+    channel_count: u32,
+    sample_rate: u32,
+    /// Number of frames written.
+    frames: u64,
+    /// First write error hit while recording, which stopped the recording.
+    error: Option<io::Error>,
+}
+
+enum Format<W: Write> {
+    Wav(WavEncoder<W>),
+    Flac(FlacEncoder<W>),
+    #[cfg(feature = "vorbis_rs")]
+    Ogg {
+        encoder: VorbisEncoder<W>,
+        /// The samples of each channel, as libvorbis takes them.
+        channels: Vec<Vec<f32>>,
+    },
+}
+
+// The Vorbis encoder can't be shown
+impl<W: Write> fmt::Debug for Encoder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Encoder")
+            .field("format", &self.format())
+            .field("channel_count", &self.channel_count)
+            .field("sample_rate", &self.sample_rate)
+            .field("frames", &self.frames)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<W: Write + Seek> Encoder<W> {
+    /// Create a new encoder, and write the header of the file.
+    ///
+    /// The header is written at the current position of `writer`, and written again
+    /// with the length of the audio by [`finish`].
+    ///
+    /// # Arguments
+    /// * writer - Where to write the file
+    /// * format - Format of the file
+    /// * channel_count - Number of channels of the samples (1 = mono, 2 = stereo)
+    /// * sample_rate - Sample rate of the samples, in samples per second
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the format can't hold such samples,
+    /// or if it is Ogg Vorbis without the `vorbis_rs` feature.
+    ///
+    /// [`finish`]: Encoder::finish
+    pub fn new(
+        writer: W,
+        format: SoundFileFormat,
+        channel_count: u32,
+        sample_rate: u32,
+    ) -> io::Result<Self> {
+        let format = match format {
+            SoundFileFormat::Wav => {
+                Format::Wav(WavEncoder::new(writer, channel_count, sample_rate)?)
+            }
+            SoundFileFormat::Flac => {
+                Format::Flac(FlacEncoder::new(writer, channel_count, sample_rate)?)
+            }
+            SoundFileFormat::Ogg => Format::ogg(writer, channel_count, sample_rate)?,
+        };
+        Ok(Self::with_format(format, channel_count, sample_rate))
+    }
+}
+
+impl<W: Write> Encoder<W> {
+    /// Create a new encoder for a writer that can't seek, and write the header of the file.
+    ///
+    /// The header holds an unknown length, and is never written again: the file is
+    /// complete as soon as the samples are written.
+    ///
+    /// # Arguments
+    /// * writer - Where to write the file, a pipe or a socket for example
+    /// * format - Format of the file
+    /// * channel_count - Number of channels of the samples (1 = mono, 2 = stereo)
+    /// * sample_rate - Sample rate of the samples, in samples per second
+    ///
+    /// Fails like [`new`].
+    ///
+    /// [`new`]: Encoder::new
+    pub fn new_streaming(
+        writer: W,
+        format: SoundFileFormat,
+        channel_count: u32,
+        sample_rate: u32,
+    ) -> io::Result<Self> {
+        let format = match format {
+            SoundFileFormat::Wav => Format::Wav(WavEncoder::new_streaming(
+                writer,
+                channel_count,
+                sample_rate,
+            )?),
+            SoundFileFormat::Flac => Format::Flac(FlacEncoder::new_streaming(
+                writer,
+                channel_count,
+                sample_rate,
+            )?),
+            SoundFileFormat::Ogg => Format::ogg(writer, channel_count, sample_rate)?,
+        };
+        Ok(Self::with_format(format, channel_count, sample_rate))
+    }
+    fn with_format(format: Format<W>, channel_count: u32, sample_rate: u32) -> Self {
+        Encoder {
+            format,
+            channel_count,
+            sample_rate,
+            frames: 0,
+            error: None,
+        }
+    }
+    /// Get the format of the file.
+    pub fn format(&self) -> SoundFileFormat {
+        match self.format {
+            Format::Wav(_) => SoundFileFormat::Wav,
+            Format::Flac(_) => SoundFileFormat::Flac,
+            #[cfg(feature = "vorbis_rs")]
+            Format::Ogg { .. } => SoundFileFormat::Ogg,
+        }
+    }
+    /// Append interleaved samples to the file.
+    ///
+    /// FLAC and Ogg Vorbis samples may be buffered until a block of them is complete.
+    pub fn write_samples(&mut self, samples: &[i16]) -> io::Result<()> {
+        match self.format {
+            Format::Wav(ref mut encoder) => encoder.write_samples(samples)?,
+            Format::Flac(ref mut encoder) => encoder.write_samples(samples)?,
+            #[cfg(feature = "vorbis_rs")]
+            Format::Ogg {
+                ref mut encoder,
+                ref mut channels,
+            } => {
+                let channel_count = channels.len();
+                for (c, channel) in channels.iter_mut().enumerate() {
+                    channel.clear();
+                    channel.extend(
+                        samples
+                            .iter()
+                            .skip(c)
+                            .step_by(channel_count)
+                            .map(|&s| f32::from(s) / 32_768.),
+                    );
+                }
+                encoder
+                    .encode_audio_block(&channels[..])
+                    .map_err(vorbis_error)?;
+            }
+        }
+        self.frames += (samples.len() / self.channel_count as usize) as u64;
+        Ok(())
+    }
+    /// Get the duration of the audio written so far.
+    pub fn duration(&self) -> Time {
+        Time::microseconds((self.frames * 1_000_000 / u64::from(self.sample_rate)) as i64)
+    }
+    /// Get the error that stopped the recording, if any.
+    ///
+    /// When driven by a [`SoundRecorderDriver`], a failed write stops the recording,
+    /// and the error is kept here.
+    ///
+    /// [`SoundRecorderDriver`]: crate::audio::SoundRecorderDriver
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+    /// Write the samples left and the final header of the file, and get the writer back.
+    ///
+    /// The writer is flushed, and left at the end of the file.
+    pub fn finish(self) -> io::Result<W> {
+        match self.format {
+            Format::Wav(encoder) => encoder.finish(),
+            Format::Flac(encoder) => encoder.finish(),
+            #[cfg(feature = "vorbis_rs")]
+            Format::Ogg { encoder, .. } => {
+                let mut writer = encoder.finish().map_err(vorbis_error)?;
+                writer.flush()?;
+                Ok(writer)
+            }
+        }
+    }
+}
+
+impl<W: Write> Format<W> {
+    #[cfg(feature = "vorbis_rs")]
+    fn ogg(writer: W, channel_count: u32, sample_rate: u32) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "invalid Vorbis format");
+        let channels = NonZeroU8::new(channel_count as u8)
+            .filter(|_| channel_count <= 255)
+            .ok_or_else(invalid)?;
+        let rate = NonZeroU32::new(sample_rate).ok_or_else(invalid)?;
+        let encoder = VorbisEncoderBuilder::new(rate, channels, writer)
+            .map_err(vorbis_error)?
+            .build()
+            .map_err(vorbis_error)?;
+        Ok(Format::Ogg {
+            encoder,
+            channels: vec![Vec::new(); channel_count as usize],
+        })
+    }
+    #[cfg(not(feature = "vorbis_rs"))]
+    fn ogg(_writer: W, _channel_count: u32, _sample_rate: u32) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "encoding Ogg Vorbis requires the `vorbis_rs` feature",
+        ))
+    }
+}
+
+#[cfg(feature = "vorbis_rs")]
+fn vorbis_error(error: vorbis_rs::VorbisError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+impl<W: Write> SoundRecorder for Encoder<W> {
+    fn on_process_samples(&mut self, samples: &[i16]) -> bool {
+        match self.write_samples(samples) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error = Some(e);
+                false
+            }
+        }
+    }
+}
+
+#[test]
+fn test_encode() {
+    use std::io::Cursor;
+
+    let samples = [1, -1, 2, -2, 3, -3];
+    let mut encoder =
+        Encoder::new(Cursor::new(Vec::new()), SoundFileFormat::Wav, 2, 8_000).unwrap();
+    assert!(encoder.on_process_samples(&samples));
+    assert_eq!(encoder.duration().as_microseconds(), 375);
+    let wav = encoder.finish().unwrap().into_inner();
+    let mut expected = WavEncoder::new(Cursor::new(Vec::new()), 2, 8_000).unwrap();
+    expected.write_samples(&samples).unwrap();
+    assert_eq!(wav, expected.finish().unwrap().into_inner());
+
+    let mut encoder = Encoder::new_streaming(Vec::new(), SoundFileFormat::Flac, 2, 8_000).unwrap();
+    encoder.write_samples(&samples).unwrap();
+    let flac = encoder.finish().unwrap();
+    assert!(flac.starts_with(b"fLaC"));
+
+    #[cfg(not(feature = "vorbis_rs"))]
+    {
+        let error = Encoder::new_streaming(Vec::new(), SoundFileFormat::Ogg, 2, 8_000);
+        assert_eq!(error.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
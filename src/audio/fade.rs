@@ -0,0 +1,77 @@
+use crate::audio::SoundSource;
+use crate::system::Time;
+
+/// A volume ramp applied to a sound source over time.
+///
+/// A fade interpolates the volume of a source linearly, from a starting volume to a target
+/// volume. Fades are usually created through [`SoundSource::fade_in`],
+/// [`SoundSource::fade_out`] and [`SoundSource::fade_to`], which start from the current
+/// volume of the source.
+///
+/// A fade doesn't hold on to its source: it has to be advanced by calling [`update`]
+/// regularly (typically once per frame) with the elapsed time and the source to apply
+/// the volume to.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Music, SoundSource};
+/// use sfml::system::{Clock, Time};
+///
+/// let mut music = Music::from_file("music.ogg").unwrap();
+/// let mut fade = music.fade_in(Time::seconds(3.));
+/// music.play();
+/// let mut clock = Clock::start();
+/// loop {
+///     fade.update(clock.restart(), &mut music);
+///     // ...
+/// }
+/// ```
+///
+/// [`update`]: Fade::update
+#[derive(Debug, Clone, Copy)]
+pub struct Fade {
+    from: f32,
+    to: f32,
+    duration: Time,
+    elapsed: Time,
+}
+
+impl Fade {
+    /// Create a new fade from volume `from` to volume `to`, lasting `duration`.
+    pub fn new(from: f32, to: f32, duration: Time) -> Self {
+        Fade {
+            from,
+            to,
+            duration,
+            elapsed: Time::ZERO,
+        }
+    }
+    /// Advance the fade by `dt`, and apply the new volume to `source`.
+    ///
+    /// Once the fade is finished, this keeps applying the target volume.
+    ///
+    /// Returns `true` once the fade is finished.
+    pub fn update<S: SoundSource + ?Sized>(&mut self, dt: Time, source: &mut S) -> bool {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        source.set_volume(self.volume());
+        self.is_finished()
+    }
+    /// Get the volume at the current point of the fade.
+    pub fn volume(&self) -> f32 {
+        let progress = if self.duration <= Time::ZERO {
+            1.
+        } else {
+            self.elapsed.as_seconds() / self.duration.as_seconds()
+        };
+        self.from + (self.to - self.from) * progress
+    }
+    /// Get the volume the fade ends at.
+    pub fn target(&self) -> f32 {
+        self.to
+    }
+    /// Tell whether the fade is finished.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
@@ -0,0 +1,417 @@
+//! Minimal encoder for 16-bit FLAC files.
+//!
+//! Each channel of each block is encoded with the fixed linear predictor that fits it best,
+//! and its residual with a single Rice partition. This is far simpler than what libFLAC
+//! does, and compresses less, but the files are still lossless and much smaller than WAV.
+
+use crate::byte_order::ByteOrder;
+use std::fmt;
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Number of frames in each block, the usual size for 44.1 kHz audio.
+const BLOCK_SIZE: usize = 4096;
+/// Size of the `fLaC` marker, the metadata block header, and the `STREAMINFO` block.
+const HEADER_LEN: u64 = 4 + 4 + 34;
+/// Largest Rice parameter of the 4-bit coding method, 15 is reserved as an escape code.
+const MAX_RICE_PARAMETER: u32 = 14;
+
+/// Writes the header of a file again, given the length of the file and the header.
+type PatchHeader<W> = fn(&mut W, u64, &[u8]) -> io::Result<()>;
+
+/// Writes 16-bit samples to a FLAC file as they come, one block at a time.
+pub(super) struct FlacEncoder<W: Write> {
+    /// Only `None` once the encoder is finished.
+    writer: Option<W>,
+    /// Writes the final header, when the writer can seek back to it.
+    patch_header: Option<PatchHeader<W>>,
+    channel_count: u32,
+    sample_rate: u32,
+    /// Interleaved samples of the block being filled.
+    block: Vec<i16>,
+    /// Number of blocks written.
+    frame_number: u64,
+    /// Number of frames written, not counting the block being filled.
+    frames: u64,
+    /// Number of bytes written.
+    len: u64,
+}
+
+// Function pointers taking references only implement `Debug` from Rust 1.35
+impl<W: Write + fmt::Debug> fmt::Debug for FlacEncoder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FlacEncoder")
+            .field("writer", &self.writer)
+            .field("seekable", &self.patch_header.is_some())
+            .field("channel_count", &self.channel_count)
+            .field("sample_rate", &self.sample_rate)
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+impl<W: Write + Seek> FlacEncoder<W> {
+    /// Create a new encoder, whose header is written again with the length of the audio.
+    pub(super) fn new(writer: W, channel_count: u32, sample_rate: u32) -> io::Result<Self> {
+        Self::with_header(writer, channel_count, sample_rate, Some(patch_header::<W>))
+    }
+}
+
+impl<W: Write> FlacEncoder<W> {
+    /// Create a new encoder for a writer that can't seek, leaving the length unknown.
+    pub(super) fn new_streaming(
+        writer: W,
+        channel_count: u32,
+        sample_rate: u32,
+    ) -> io::Result<Self> {
+        Self::with_header(writer, channel_count, sample_rate, None)
+    }
+    fn with_header(
+        mut writer: W,
+        channel_count: u32,
+        sample_rate: u32,
+        patch_header: Option<PatchHeader<W>>,
+    ) -> io::Result<Self> {
+        if channel_count == 0 || channel_count > 8 || sample_rate == 0 || sample_rate > 655_350 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "FLAC files hold 1 to 8 channels, at up to 655350 Hz",
+            ));
+        }
+        let header = header(channel_count, sample_rate, 0);
+        writer.write_all(&header)?;
+        Ok(FlacEncoder {
+            writer: Some(writer),
+            patch_header,
+            channel_count,
+            sample_rate,
+            block: Vec::with_capacity(BLOCK_SIZE * channel_count as usize),
+            frame_number: 0,
+            frames: 0,
+            len: header.len() as u64,
+        })
+    }
+    /// Append interleaved samples to the file.
+    ///
+    /// The samples are written once a whole block of them is gathered.
+    pub(super) fn write_samples(&mut self, mut samples: &[i16]) -> io::Result<()> {
+        let block_len = BLOCK_SIZE * self.channel_count as usize;
+        while !samples.is_empty() {
+            let (head, tail) = samples.split_at((block_len - self.block.len()).min(samples.len()));
+            self.block.extend_from_slice(head);
+            samples = tail;
+            if self.block.len() == block_len {
+                self.write_block()?;
+            }
+        }
+        Ok(())
+    }
+    /// Write the samples left and the final header, and get the writer back.
+    pub(super) fn finish(mut self) -> io::Result<W> {
+        self.write_end()?;
+        Ok(self.writer.take().expect("FlacEncoder: already finished"))
+    }
+
+    fn writer(&mut self) -> &mut W {
+        self.writer.as_mut().expect("FlacEncoder: already finished")
+    }
+    fn write_block(&mut self) -> io::Result<()> {
+        let channels = self.channel_count as usize;
+        let frames = self.block.len() / channels;
+        if frames == 0 {
+            return Ok(());
+        }
+        let frame = encode_frame(
+            &self.block[..frames * channels],
+            channels,
+            self.frame_number,
+        );
+        self.writer().write_all(&frame)?;
+        self.block.clear();
+        self.frame_number += 1;
+        self.frames += frames as u64;
+        self.len += frame.len() as u64;
+        Ok(())
+    }
+    fn write_end(&mut self) -> io::Result<()> {
+        self.write_block()?;
+        let header = header(self.channel_count, self.sample_rate, self.frames);
+        let (len, patch_header) = (self.len, self.patch_header);
+        let writer = self.writer();
+        if let Some(patch_header) = patch_header {
+            patch_header(writer, len, &header)?;
+        }
+        writer.flush()
+    }
+}
+
+impl<W: Write> Drop for FlacEncoder<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            // Like a `BufWriter`, errors can't be reported from here, see `finish`
+            let _ = self.write_end();
+        }
+    }
+}
+
+/// Write `header` at the start of the `len` bytes just written, and seek back.
+fn patch_header<W: Write + Seek>(writer: &mut W, len: u64, header: &[u8]) -> io::Result<()> {
+    let end = writer.seek(SeekFrom::Current(0))?;
+    let _ = writer.seek(SeekFrom::Start(end - len))?;
+    writer.write_all(header)?;
+    let _ = writer.seek(SeekFrom::Start(end))?;
+    Ok(())
+}
+
+/// The start of a FLAC file: its marker and its only metadata block, `STREAMINFO`.
+///
+/// A `frames` of 0 means an unknown length.
+fn header(channel_count: u32, sample_rate: u32, frames: u64) -> Vec<u8> {
+    let mut header = BitWriter::new();
+    header.write_bytes(b"fLaC");
+    // Last metadata block, of type STREAMINFO, 34 bytes long
+    header.write(0x80, 8);
+    header.write(34, 24);
+    header.write(BLOCK_SIZE as u64, 16);
+    header.write(BLOCK_SIZE as u64, 16);
+    // Unknown minimum and maximum frame sizes
+    header.write(0, 24);
+    header.write(0, 24);
+    header.write(u64::from(sample_rate), 20);
+    header.write(u64::from(channel_count - 1), 3);
+    header.write(15, 5);
+    header.write((frames >> 32) & 0x0F, 4);
+    header.write(frames & 0xFFFF_FFFF, 32);
+    // No MD5 signature of the samples
+    header.write_bytes(&[0; 16]);
+    debug_assert_eq!(header.bytes.len() as u64, HEADER_LEN);
+    header.bytes
+}
+
+/// Encode a block of interleaved samples as a frame, each channel on its own.
+fn encode_frame(samples: &[i16], channels: usize, frame_number: u64) -> Vec<u8> {
+    let frames = samples.len() / channels;
+    let mut frame = BitWriter::new();
+    // Sync code, fixed block size
+    frame.write(0xFFF8, 16);
+    // Block size written at the end of the header, sample rate of STREAMINFO
+    frame.write(0b0111_0000, 8);
+    // Independent channels, 16 bits per sample
+    frame.write(((channels as u64 - 1) << 4) | 0b1000, 8);
+    frame.write_bytes(&utf8(frame_number));
+    frame.write(frames as u64 - 1, 16);
+    let crc = crc8(&frame.bytes);
+    frame.write(u64::from(crc), 8);
+    let mut channel = Vec::with_capacity(frames);
+    for c in 0..channels {
+        channel.clear();
+        channel.extend(
+            samples
+                .iter()
+                .skip(c)
+                .step_by(channels)
+                .map(|&s| i32::from(s)),
+        );
+        encode_subframe(&mut frame, &channel);
+    }
+    frame.align();
+    let crc = crc16(&frame.bytes);
+    frame.write_bytes(&crc.be_bytes());
+    frame.bytes
+}
+
+/// Encode the samples of one channel, as a constant, or with a fixed predictor.
+fn encode_subframe(frame: &mut BitWriter, samples: &[i32]) {
+    if samples.iter().all(|&s| s == samples[0]) {
+        // CONSTANT
+        frame.write(0, 8);
+        frame.write(u64::from(samples[0] as u16), 16);
+        return;
+    }
+    let (order, residual, parameter, bits) = (0..5)
+        .filter(|&order| order < samples.len())
+        .map(|order| {
+            let residual = fixed_residual(samples, order);
+            let (parameter, bits) = rice_parameter(&residual);
+            (order, residual, parameter, bits + 16 * order as u64)
+        })
+        .min_by_key(|&(_, _, _, bits)| bits)
+        .expect("blocks hold at least one frame");
+    if bits >= 16 * samples.len() as u64 {
+        // VERBATIM
+        frame.write(0b0000_0010, 8);
+        for &sample in samples {
+            frame.write(u64::from(sample as u16), 16);
+        }
+        return;
+    }
+    // FIXED, of the chosen order
+    frame.write((0b1000 | order as u64) << 1, 8);
+    for &sample in &samples[..order] {
+        frame.write(u64::from(sample as u16), 16);
+    }
+    // Rice coding with a 4-bit parameter, in a single partition
+    frame.write(0, 2);
+    frame.write(0, 4);
+    frame.write(u64::from(parameter), 4);
+    for &value in &residual {
+        let folded = fold(value);
+        frame.write_unary(folded >> parameter);
+        frame.write(folded & ((1 << parameter) - 1), parameter);
+    }
+}
+
+/// The error of the fixed predictor of order `order` on the samples after the first ones.
+fn fixed_residual(samples: &[i32], order: usize) -> Vec<i64> {
+    let coefficients: &[i64] = match order {
+        0 => &[],
+        1 => &[1],
+        2 => &[2, -1],
+        3 => &[3, -3, 1],
+        _ => &[4, -6, 4, -1],
+    };
+    (order..samples.len())
+        .map(|i| {
+            let prediction: i64 = coefficients
+                .iter()
+                .enumerate()
+                .map(|(j, &c)| c * i64::from(samples[i - 1 - j]))
+                .sum();
+            i64::from(samples[i]) - prediction
+        })
+        .collect()
+}
+
+/// Map signed values to unsigned ones, the small ones first: 0, -1, 1, -2...
+fn fold(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// The Rice parameter coding `residual` in the fewest bits, and that number of bits.
+fn rice_parameter(residual: &[i64]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAMETER)
+        .map(|parameter| {
+            let bits = residual
+                .iter()
+                .map(|&value| u64::from(parameter) + 1 + (fold(value) >> parameter))
+                .sum();
+            (parameter, bits)
+        })
+        .min_by_key(|&(_, bits)| bits)
+        .expect("there are Rice parameters")
+}
+
+/// Code a frame number the way UTF-8 codes characters, extended to 36 bits.
+fn utf8(value: u64) -> Vec<u8> {
+    if value < 0x80 {
+        return vec![value as u8];
+    }
+    let len = (2..7).find(|&len| value >> (5 * len + 1) == 0).unwrap_or(7);
+    let mut bytes = vec![(0xFF00u16 >> len) as u8 | (value >> (6 * (len - 1))) as u8];
+    for i in (0..len - 1).rev() {
+        bytes.push(0x80 | ((value >> (6 * i)) as u8 & 0x3F));
+    }
+    bytes
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0, |mut crc, &byte| {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// Writes values of any number of bits, most significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    /// Bits not yet written to `bytes`, fewer than 8 of them.
+    pending: u64,
+    pending_bits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            pending: 0,
+            pending_bits: 0,
+        }
+    }
+    /// Write the `bits` lowest bits of `value`, at most 32 of them.
+    fn write(&mut self, value: u64, bits: u32) {
+        debug_assert!(bits <= 32);
+        self.pending = (self.pending << bits) | (value & ((1 << bits) - 1));
+        self.pending_bits += bits;
+        while self.pending_bits >= 8 {
+            self.pending_bits -= 8;
+            self.bytes.push((self.pending >> self.pending_bits) as u8);
+        }
+        self.pending &= (1 << self.pending_bits) - 1;
+    }
+    /// Write `value` zeros, then a one.
+    fn write_unary(&mut self, mut value: u64) {
+        while value >= 32 {
+            self.write(0, 32);
+            value -= 32;
+        }
+        self.write(1, value as u32 + 1);
+    }
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write(u64::from(byte), 8);
+        }
+    }
+    /// Pad the last byte with zeros.
+    fn align(&mut self) {
+        if self.pending_bits > 0 {
+            let bits = 8 - self.pending_bits;
+            self.write(0, bits);
+        }
+    }
+}
+
+#[test]
+fn test_encode() {
+    use std::io::Cursor;
+
+    assert_eq!(utf8(0x7F), [0x7F]);
+    assert_eq!(utf8(0x80), [0xC2, 0x80]);
+    assert_eq!(utf8(0x20AC), [0xE2, 0x82, 0xAC]);
+    assert_eq!(crc8(b"123456789"), 0xF4);
+    assert_eq!(crc16(b"123456789"), 0xFEE8);
+
+    let samples: Vec<i16> = (0..BLOCK_SIZE as i32 + 100)
+        .flat_map(|i| vec![(i * 7 % 1000) as i16, 0])
+        .collect();
+    let mut encoder = FlacEncoder::new(Cursor::new(Vec::new()), 2, 8_000).unwrap();
+    encoder.write_samples(&samples[..5]).unwrap();
+    encoder.write_samples(&samples[5..]).unwrap();
+    let data = encoder.finish().unwrap().into_inner();
+    assert_eq!(&data[..HEADER_LEN as usize], &header(2, 8_000, 4196)[..]);
+    // Two frames, the second one holding the last 100 frames
+    let first = &data[HEADER_LEN as usize..];
+    assert_eq!(&first[..7], &[0xFF, 0xF8, 0x70, 0x18, 0, 0x0F, 0xFF]);
+    let second = [0xFF, 0xF8, 0x70, 0x18, 1, 0, 99];
+    assert!(first.windows(7).any(|header| header == second));
+    assert!(data.len() < samples.len());
+}
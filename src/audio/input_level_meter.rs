@@ -0,0 +1,124 @@
+use crate::audio::SoundRecorder;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Level of an audio signal, as measured by an [`InputLevelMeter`].
+///
+/// Both values are normalized: 1 is the full scale of 16-bit samples.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InputLevel {
+    /// Root mean square of the samples, which follows the perceived loudness.
+    pub rms: f32,
+    /// Highest absolute value of the samples.
+    pub peak: f32,
+}
+
+impl InputLevel {
+    /// Measure the level of `samples`.
+    pub fn measure(samples: &[i16]) -> Self {
+        if samples.is_empty() {
+            return InputLevel::default();
+        }
+        let mut sum = 0.;
+        let mut peak = 0;
+        for &sample in samples {
+            sum += f64::from(sample) * f64::from(sample);
+            peak = peak.max(i32::from(sample).abs());
+        }
+        InputLevel {
+            rms: ((sum / samples.len() as f64).sqrt() / 32_768.) as f32,
+            peak: peak as f32 / 32_768.,
+        }
+    }
+    /// Get the root mean square, in decibels relative to full scale (0 is the loudest).
+    pub fn rms_db(&self) -> f32 {
+        to_db(self.rms)
+    }
+    /// Get the peak, in decibels relative to full scale (0 is the loudest).
+    pub fn peak_db(&self) -> f32 {
+        to_db(self.peak)
+    }
+}
+
+fn to_db(level: f32) -> f32 {
+    20. * level.max(1e-5).log10()
+}
+
+/// A [`SoundRecorder`] measuring the level of the captured audio.
+///
+/// The meter keeps the level of the latest chunk of samples, so how recent it is depends on
+/// the processing interval of the recorder (see
+/// [`SoundRecorderDriver::set_processing_interval`]). Cloning a meter gives another handle
+/// to the same level: the driver borrows one of them while recording, and the level can be
+/// read from the other one, from any thread.
+///
+/// Custom recorders can measure the level of their samples too, by calling
+/// [`InputLevelMeter::process`] from [`SoundRecorder::on_process_samples`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{capture, InputLevelMeter, SoundRecorderDriver};
+/// use sfml::system::Time;
+///
+/// let meter = InputLevelMeter::new();
+/// let mut recorder = meter.clone();
+/// let mut driver = SoundRecorderDriver::new(&mut recorder);
+/// driver.set_processing_interval(Time::milliseconds(20));
+/// driver.start(44_100);
+/// loop {
+///     let level = meter.level();
+///     if level.rms_db() > -30. {
+///         // Someone is speaking
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// [`SoundRecorderDriver::set_processing_interval`]:
+/// crate::audio::SoundRecorderDriver::set_processing_interval
+#[derive(Debug, Clone, Default)]
+pub struct InputLevelMeter {
+    level: Arc<Mutex<InputLevel>>,
+}
+
+impl InputLevelMeter {
+    /// Create a new meter, with a level of 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Get the level of the latest chunk of samples.
+    pub fn level(&self) -> InputLevel {
+        *self.lock()
+    }
+    /// Measure the level of `samples`, which becomes the current level.
+    pub fn process(&self, samples: &[i16]) {
+        *self.lock() = InputLevel::measure(samples);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, InputLevel> {
+        match self.level.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl SoundRecorder for InputLevelMeter {
+    fn on_start(&mut self) -> bool {
+        self.process(&[]);
+        true
+    }
+    fn on_process_samples(&mut self, samples: &[i16]) -> bool {
+        self.process(samples);
+        true
+    }
+}
+
+#[test]
+fn test_measure() {
+    let level = InputLevel::measure(&[16_384, -16_384, 16_384, -16_384]);
+    assert_eq!(level.rms, 0.5);
+    assert_eq!(level.peak, 0.5);
+    assert_eq!(InputLevel::measure(&[-32_768]).peak, 1.);
+    assert_eq!(InputLevel::measure(&[]), InputLevel::default());
+}
@@ -3,12 +3,31 @@
 //! The audio listener defines the global properties of the audio environment,
 //! it defines where and how sounds and musics are heard.
 //!
-//! If [`View`] is the eyes of the user, then `listener` is his ears (by the way, they are often
+//! If `View` is the eyes of the user, then `listener` is his ears (by the way, they are often
 //! linked together – same position, orientation, etc.).
 //!
 //! `listener` is a simple interface, which allows to setup the listener in the 3D audio environment
 //! (position, direction and up vector), and to adjust the global volume.
 //!
+//! # Doppler effect
+//!
+//! SFML doesn't know about the velocity of sounds, so it can't apply the doppler effect by
+//! itself. Instead, this module keeps track of a doppler factor, the speed of sound and the
+//! velocity of the listener, and [`doppler_pitch`] computes the pitch shift of a moving
+//! source from them. Apply it to the source every frame, on top of its own pitch:
+//!
+//! ```no_run
+//! use sfml::audio::{listener, Sound, SoundSource};
+//! use sfml::system::Vector3f;
+//!
+//! # let mut engine = Sound::new();
+//! let position = Vector3f::new(-30., 0., -10.);
+//! let velocity = Vector3f::new(80., 0., 0.);
+//! listener::set_velocity((0., 0., -5.));
+//! engine.set_position(position);
+//! engine.set_pitch(listener::doppler_pitch(position, velocity));
+//! ```
+//!
 //! # Usage example
 //!
 //! ```
@@ -20,15 +39,38 @@
 //! // Make it face the right axis (1, 0, 0)
 //! listener::set_direction((1., 0., 0.));
 //!
+//! // Keep the default up vector (0, 1, 0), but set it explicitly
+//! listener::set_up_vector((0., 1., 0.));
+//!
 //! // Reduce the global volume
 //! listener::set_global_volume(50.);
 //! ```
 //!
-//! [`View`]: ::graphics::View
-//!
 
 use crate::audio::csfml_audio_sys as ffi;
 use crate::system::Vector3f;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Highest doppler pitch shift, reached when a source moves towards the listener as fast
+/// as sound. This is the highest pitch OpenAL Soft plays.
+const MAX_DOPPLER_PITCH: f32 = 255.;
+
+// f32 values, stored as bits
+static DOPPLER_FACTOR: AtomicUsize = AtomicUsize::new(0x3f80_0000); // 1.
+static SPEED_OF_SOUND: AtomicUsize = AtomicUsize::new(0x43ab_a666); // 343.3
+static VELOCITY: [AtomicUsize; 3] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+fn load(value: &AtomicUsize) -> f32 {
+    f32::from_bits(value.load(Ordering::Relaxed) as u32)
+}
+
+fn store(value: &AtomicUsize, new: f32) {
+    value.store(new.to_bits() as usize, Ordering::Relaxed)
+}
 
 /// Change the global volume of all the sounds and musics
 ///
@@ -93,11 +135,155 @@ pub fn direction() -> Vector3f {
 /// Together with the direction, it defines the 3D orientation of the listener in the scene.
 /// The up vector doesn't have to be normalized. The default listener's up vector is (0, 1, 0).
 /// It is usually not necessary to change it, especially in 2D scenarios.
-pub fn set_up_vector(value: &Vector3f) {
-    unsafe { ffi::sfListener_setUpVector(value.raw()) }
+///
+/// # Arguments
+/// * up_vector - New listener's up vector
+pub fn set_up_vector<U: Into<Vector3f>>(up_vector: U) {
+    unsafe { ffi::sfListener_setUpVector(up_vector.into().raw()) }
 }
 
-/// Get the current upward vector of the listener in the scene. (not normalized)
+/// Get the current upward vector of the listener in the scene
+///
+/// Return the listener's up vector (not normalized)
 pub fn up_vector() -> Vector3f {
     unsafe { Vector3f::from_raw(ffi::sfListener_getUpVector()) }
 }
+
+/// Set the doppler factor, used by [`doppler_pitch`]
+///
+/// The doppler factor exaggerates (above 1) or attenuates (below 1) the doppler effect.
+/// 0 disables it. The default doppler factor is 1.
+///
+/// # Arguments
+/// * factor - New doppler factor, must be positive
+pub fn set_doppler_factor(factor: f32) {
+    store(&DOPPLER_FACTOR, factor.max(0.))
+}
+
+/// Get the current doppler factor
+///
+/// Return the doppler factor
+pub fn doppler_factor() -> f32 {
+    load(&DOPPLER_FACTOR)
+}
+
+/// Set the speed of sound, used by [`doppler_pitch`]
+///
+/// The speed of sound is expressed in the same units as positions and velocities
+/// (per second). The default speed of sound is 343.3, the speed of sound in the air in meters
+/// per second.
+///
+/// # Arguments
+/// * speed - New speed of sound, must be strictly positive
+pub fn set_speed_of_sound(speed: f32) {
+    if speed > 0. {
+        store(&SPEED_OF_SOUND, speed)
+    }
+}
+
+/// Get the current speed of sound
+///
+/// Return the speed of sound
+pub fn speed_of_sound() -> f32 {
+    load(&SPEED_OF_SOUND)
+}
+
+/// Set the velocity of the listener, used by [`doppler_pitch`]
+///
+/// The default listener's velocity is (0, 0, 0).
+///
+/// # Arguments
+/// * velocity - New velocity of the listener, in units per second
+pub fn set_velocity<V: Into<Vector3f>>(velocity: V) {
+    let velocity = velocity.into();
+    store(&VELOCITY[0], velocity.x);
+    store(&VELOCITY[1], velocity.y);
+    store(&VELOCITY[2], velocity.z);
+}
+
+/// Get the current velocity of the listener
+///
+/// Return the listener's velocity
+pub fn velocity() -> Vector3f {
+    Vector3f::new(load(&VELOCITY[0]), load(&VELOCITY[1]), load(&VELOCITY[2]))
+}
+
+/// Compute the doppler pitch shift of a moving source
+///
+/// The shift follows the OpenAL doppler model, from the position and velocity of the
+/// listener, the doppler factor and the speed of sound.
+/// It should be multiplied with the pitch of the source.
+///
+/// Sources relative to the listener should be given their position and velocity
+/// relative to it, with the velocity of the listener being (0, 0, 0).
+///
+/// # Arguments
+/// * source_position - Position of the source
+/// * source_velocity - Velocity of the source, in units per second
+///
+/// Return the pitch shift, above 1 when the source and the listener get closer,
+/// below 1 when they move apart. It is at most 255, reached when the source moves
+/// towards the listener as fast as sound, and 0 when the listener outruns the sound.
+pub fn doppler_pitch<P, V>(source_position: P, source_velocity: V) -> f32
+where
+    P: Into<Vector3f>,
+    V: Into<Vector3f>,
+{
+    doppler(
+        position() - source_position.into(),
+        velocity(),
+        source_velocity.into(),
+        doppler_factor(),
+        speed_of_sound(),
+    )
+}
+
+fn doppler(
+    to_listener: Vector3f,
+    listener_velocity: Vector3f,
+    source_velocity: Vector3f,
+    factor: f32,
+    speed: f32,
+) -> f32 {
+    let dot = |a: Vector3f, b: Vector3f| a.x * b.x + a.y * b.y + a.z * b.z;
+    let distance = dot(to_listener, to_listener).sqrt();
+    if factor <= 0. || distance <= 0. {
+        return 1.;
+    }
+    // Velocities along the axis going from the source to the listener
+    let listener = factor * dot(to_listener, listener_velocity) / distance;
+    let source = factor * dot(to_listener, source_velocity) / distance;
+    if listener >= speed {
+        // The listener outruns the sound, which can't reach it
+        0.
+    } else if source >= speed {
+        // The source catches up with its own sound
+        MAX_DOPPLER_PITCH
+    } else {
+        ((speed - listener) / (speed - source)).min(MAX_DOPPLER_PITCH)
+    }
+}
+
+#[test]
+fn test_doppler() {
+    let still = Vector3f::new(0., 0., 0.);
+    // Source at (100, 0, 0), listener at the origin
+    let to_listener = Vector3f::new(-100., 0., 0.);
+    let approaching = Vector3f::new(-50., 0., 0.);
+    assert_eq!(doppler(to_listener, still, still, 1., 343.3), 1.);
+    assert!(doppler(to_listener, still, approaching, 1., 343.3) > 1.);
+    assert!(doppler(to_listener, still, -approaching, 1., 343.3) < 1.);
+    assert_eq!(doppler(to_listener, still, approaching, 0., 343.3), 1.);
+    assert!(doppler(to_listener, -approaching, still, 1., 343.3) > 1.);
+    // At the speed of sound
+    let supersonic = Vector3f::new(-343.3, 0., 0.);
+    assert_eq!(
+        doppler(to_listener, still, supersonic, 1., 343.3),
+        MAX_DOPPLER_PITCH
+    );
+    assert_eq!(doppler(to_listener, supersonic, still, 1., 343.3), 0.);
+    assert_eq!(
+        doppler(to_listener, still, supersonic * 0.5, 2., 343.3),
+        MAX_DOPPLER_PITCH
+    );
+}
@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Error that can happen when loading audio data.
+///
+/// SFML itself only reports success or failure (the details are printed on the standard
+/// error output, and CSFML offers no way of capturing them), so the cause is determined
+/// on the Rust side after a failed load, by inspecting the source of the audio data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadError {
+    /// The file does not exist.
+    NotFound,
+    /// The source could not be read.
+    Io(io::ErrorKind),
+    /// The data is not in one of the supported audio formats (ogg, wav, flac).
+    UnsupportedFormat,
+    /// The data looks like a supported audio format, but it could not be decoded.
+    Decode,
+}
+
+impl LoadError {
+    /// Find out why loading the file at `path` failed.
+    pub(super) fn from_file(path: &Path) -> Self {
+        match File::open(path) {
+            Ok(mut file) => Self::from_stream(&mut file),
+            Err(e) => e.into(),
+        }
+    }
+    /// Find out why loading from `stream` failed, leaving its position untouched.
+    pub(super) fn from_stream<T: Read + Seek>(stream: &mut T) -> Self {
+        let probe = |stream: &mut T| -> io::Result<LoadError> {
+            let start = stream.seek(SeekFrom::Current(0))?;
+            let mut header = Vec::with_capacity(12);
+            let _ = stream.by_ref().take(12).read_to_end(&mut header)?;
+            let _ = stream.seek(SeekFrom::Start(start))?;
+            Ok(Self::from_memory(&header))
+        };
+        probe(stream).unwrap_or_else(Into::into)
+    }
+    /// Find out why loading the in-memory file `data` failed.
+    pub(super) fn from_memory(data: &[u8]) -> Self {
+        let known = data.starts_with(b"OggS")
+            || data.starts_with(b"fLaC")
+            || (data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE"));
+        if known {
+            LoadError::Decode
+        } else {
+            LoadError::UnsupportedFormat
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => LoadError::NotFound,
+            kind => LoadError::Io(kind),
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::NotFound => f.write_str("audio file not found"),
+            LoadError::Io(kind) => write!(f, "failed to read audio data: {:?}", kind),
+            LoadError::UnsupportedFormat => f.write_str("unsupported audio format"),
+            LoadError::Decode => f.write_str("failed to decode audio data"),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+#[test]
+fn test_from_memory() {
+    assert_eq!(LoadError::from_memory(b"OggS\0\0"), LoadError::Decode);
+    assert_eq!(
+        LoadError::from_memory(b"RIFF\0\0\0\0WAVEfmt "),
+        LoadError::Decode
+    );
+    assert_eq!(
+        LoadError::from_memory(b"RIFF\0\0\0\0AVI "),
+        LoadError::UnsupportedFormat
+    );
+    assert_eq!(LoadError::from_memory(b"ID3"), LoadError::UnsupportedFormat);
+}
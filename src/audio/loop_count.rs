@@ -0,0 +1,91 @@
+use crate::audio::{SoundSource, SoundStatus};
+use crate::system::Time;
+
+/// Plays a sound source a given number of times, then stops it.
+///
+/// Loop counts are usually created through [`SoundSource::set_loop_count`], which also sets
+/// up the looping state of the source.
+///
+/// The source loops on its own, so the repetitions are seamless. Like a
+/// [`PlaybackMonitor`], a loop count checks on the source in [`update`] (typically once per
+/// frame): it notices each time the playing position jumps back to the beginning, and turns
+/// looping off when the last repetition starts, so that the source stops at its end.
+///
+/// For this to work, one repetition must last longer than the time between two updates.
+/// Also note that streamed sources ([`Music`] and sound streams) buffer their data ahead
+/// of time: when they are only a few seconds long, the last repetition may already be queued
+/// when looping is turned off, and they play once more.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Sound, SoundBuffer, SoundSource};
+///
+/// let buffer = SoundBuffer::from_file("jingle.wav").unwrap();
+/// let mut jingle = Sound::with_buffer(&buffer);
+/// let mut loops = jingle.set_loop_count(3);
+/// jingle.play();
+/// while !loops.update(&mut jingle) {
+///     // ...
+/// }
+/// ```
+///
+/// [`PlaybackMonitor`]: crate::audio::PlaybackMonitor
+/// [`Music`]: crate::audio::Music
+/// [`update`]: LoopCount::update
+#[derive(Debug, Clone, Copy)]
+pub struct LoopCount {
+    count: u32,
+    done: u32,
+    last_offset: Time,
+    started: bool,
+    finished: bool,
+}
+
+impl LoopCount {
+    /// Create a new loop count, for a source to be played `count` times.
+    ///
+    /// A count of 0 plays the source once, like a count of 1.
+    pub fn new(count: u32) -> Self {
+        LoopCount {
+            count: count.max(1),
+            done: 0,
+            last_offset: Time::ZERO,
+            started: false,
+            finished: false,
+        }
+    }
+    /// Check on `source`, turning its looping off when its last repetition starts.
+    ///
+    /// Returns `true` once the source played all its repetitions and stopped.
+    pub fn update<S: SoundSource + ?Sized>(&mut self, source: &mut S) -> bool {
+        match source.status() {
+            SoundStatus::Playing => {
+                let offset = source.playing_offset();
+                if self.started && offset < self.last_offset && self.remaining() > 0 {
+                    self.done += 1;
+                }
+                self.started = true;
+                self.last_offset = offset;
+                if self.remaining() == 0 && source.is_looping() {
+                    source.set_looping(false);
+                }
+            }
+            SoundStatus::Paused => {}
+            SoundStatus::Stopped => self.finished |= self.started,
+        }
+        self.finished
+    }
+    /// Get the number of times the source is played.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+    /// Get the number of repetitions left after the one being played.
+    pub fn remaining(&self) -> u32 {
+        self.count - self.done - 1
+    }
+    /// Tell whether the source played all its repetitions and stopped.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
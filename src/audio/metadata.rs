@@ -0,0 +1,388 @@
+//! Reading the tags and duration of audio files.
+//!
+//! This requires the `metadata` feature.
+//!
+//! SFML decodes audio files, but ignores their tags. These functions read the title,
+//! artist and album of ogg (Vorbis), flac and wav files, along with their duration, from
+//! the headers alone: nothing is decoded, so a whole music library can be listed quickly,
+//! without loading the files with SFML.
+//!
+//! Ogg and flac files are tagged with Vorbis comments, wav files with the `INFO` list of
+//! the RIFF format. ID3 tags, which some programs add to wav files, are not read.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! # #[cfg(feature = "metadata")] {
+//! use sfml::audio::metadata;
+//!
+//! let metadata = metadata::read("album/01.ogg").unwrap();
+//! println!(
+//!     "{} - {}",
+//!     metadata.artist.as_ref().map_or("Unknown artist", String::as_str),
+//!     metadata.title.as_ref().map_or("Untitled", String::as_str),
+//! );
+//! # }
+//! ```
+
+// Without the feature, only the format of files is read, for `read_format`
+#![cfg_attr(not(feature = "metadata"), allow(dead_code))]
+
+use crate::audio::LoadError;
+use crate::byte_order::ByteOrder;
+use crate::system::Time;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Largest chunk of tags read, so that a corrupt size doesn't exhaust the memory.
+const MAX_TAGS_LEN: u64 = 16 * 1024 * 1024;
+/// Largest size of an ogg page.
+const MAX_OGG_PAGE: u64 = 65_307;
+
+/// The tags and duration of an audio file, see [`read`].
+///
+/// Each field is `None` if the file doesn't tell.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Metadata {
+    /// Title of the track.
+    pub title: Option<String>,
+    /// Artist performing the track.
+    pub artist: Option<String>,
+    /// Album the track is part of.
+    pub album: Option<String>,
+    /// Duration of the track.
+    pub duration: Option<Time>,
+}
+
+/// What the headers tell, with the duration in frames.
+#[derive(Debug, Default, PartialEq)]
+struct Info {
+    title: Option<String>,
+    artist: Option<String>,
+    album: Option<String>,
+    frames: Option<u64>,
+    sample_rate: u32,
+    channel_count: u32,
+}
+
+impl Info {
+    fn into_metadata(self) -> Metadata {
+        let sample_rate = self.sample_rate;
+        Metadata {
+            title: self.title,
+            artist: self.artist,
+            album: self.album,
+            duration: self.frames.filter(|_| sample_rate > 0).map(|frames| {
+                Time::microseconds(
+                    (u128::from(frames) * 1_000_000 / u128::from(sample_rate)) as i64,
+                )
+            }),
+        }
+    }
+    /// Keep a tag, the first one of each kind.
+    fn tag(&mut self, key: &str, value: &[u8]) {
+        let field = if key.eq_ignore_ascii_case("title") || key == "INAM" {
+            &mut self.title
+        } else if key.eq_ignore_ascii_case("artist") || key == "IART" {
+            &mut self.artist
+        } else if key.eq_ignore_ascii_case("album") || key == "IPRD" {
+            &mut self.album
+        } else {
+            return;
+        };
+        if field.is_none() {
+            let value = String::from_utf8_lossy(value);
+            let value = value.trim_end_matches('\0');
+            if !value.is_empty() {
+                *field = Some(value.to_owned());
+            }
+        }
+    }
+    /// Read a Vorbis comment block, as found in ogg and flac files.
+    fn vorbis_comment(&mut self, mut data: &[u8]) {
+        fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+            if data.len() < len {
+                return None;
+            }
+            let (head, tail) = data.split_at(len);
+            *data = tail;
+            Some(head)
+        }
+        fn take_len(data: &mut &[u8]) -> Option<usize> {
+            take(data, 4).map(|bytes| u32_le(bytes) as usize)
+        }
+        let vendor_len = take_len(&mut data);
+        if vendor_len.and_then(|len| take(&mut data, len)).is_none() {
+            return;
+        }
+        let count = take_len(&mut data).unwrap_or(0);
+        for _ in 0..count {
+            let comment = match take_len(&mut data).and_then(|len| take(&mut data, len)) {
+                Some(comment) => comment,
+                None => return,
+            };
+            if let Some(split) = comment.iter().position(|&b| b == b'=') {
+                self.tag(
+                    &String::from_utf8_lossy(&comment[..split]),
+                    &comment[split + 1..],
+                );
+            }
+        }
+    }
+}
+
+fn invalid() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid audio file")
+}
+
+fn read_exact_vec<R: Read>(reader: &mut R, len: u64) -> io::Result<Vec<u8>> {
+    if len > MAX_TAGS_LEN {
+        return Err(invalid());
+    }
+    let mut data = vec![0; len as usize];
+    reader.read_exact(&mut data)?;
+    Ok(data)
+}
+
+fn u32_le(bytes: &[u8]) -> u32 {
+    u32::from_le_array([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn read_wav<R: Read + Seek>(reader: &mut R) -> io::Result<Info> {
+    let mut info = Info::default();
+    let mut block_align = 0;
+    let mut data_len = None;
+    let _ = reader.seek(SeekFrom::Start(12))?;
+    let mut header = [0; 8];
+    while reader.read_exact(&mut header).is_ok() {
+        let size = u64::from(u32_le(&header[4..]));
+        // Chunks are padded to an even size
+        let padded = size + (size & 1);
+        match &header[..4] {
+            b"fmt " if size >= 16 => {
+                let format = read_exact_vec(reader, padded)?;
+                info.channel_count = u32::from(u16::from_le_array([format[2], format[3]]));
+                info.sample_rate = u32_le(&format[4..]);
+                block_align = u16::from_le_array([format[12], format[13]]);
+            }
+            b"LIST" => {
+                let list = read_exact_vec(reader, padded)?;
+                if list.starts_with(b"INFO") {
+                    let mut entries = &list[4..];
+                    while entries.len() >= 8 {
+                        let len = u32_le(&entries[4..]) as usize;
+                        let value = entries.get(8..8 + len).unwrap_or(&entries[8..]);
+                        info.tag(&String::from_utf8_lossy(&entries[..4]), value);
+                        entries = entries.get(8 + len + (len & 1)..).unwrap_or(&[]);
+                    }
+                }
+            }
+            id => {
+                if id == b"data" {
+                    data_len = Some(size);
+                }
+                let _ = reader.seek(SeekFrom::Current(padded as i64))?;
+            }
+        }
+    }
+    if block_align > 0 {
+        info.frames = data_len.map(|len| len / u64::from(block_align));
+    }
+    Ok(info)
+}
+
+fn read_flac<R: Read + Seek>(reader: &mut R) -> io::Result<Info> {
+    let mut info = Info::default();
+    let _ = reader.seek(SeekFrom::Start(4))?;
+    loop {
+        let mut header = [0; 4];
+        reader.read_exact(&mut header)?;
+        let len = u64::from(u32::from_be_array([0, header[1], header[2], header[3]]));
+        match header[0] & 0x7F {
+            // STREAMINFO
+            0 if len >= 34 => {
+                let block = read_exact_vec(reader, len)?;
+                info.sample_rate = (u32::from(block[10]) << 12)
+                    | (u32::from(block[11]) << 4)
+                    | (u32::from(block[12]) >> 4);
+                info.channel_count = u32::from((block[12] >> 1) & 0x07) + 1;
+                let frames = (u64::from(block[13] & 0x0F) << 32)
+                    | u64::from(u32::from_be_array([
+                        block[14], block[15], block[16], block[17],
+                    ]));
+                // 0 means unknown
+                info.frames = Some(frames).filter(|&frames| frames > 0);
+            }
+            // VORBIS_COMMENT
+            4 => info.vorbis_comment(&read_exact_vec(reader, len)?),
+            _ => {
+                let _ = reader.seek(SeekFrom::Current(len as i64))?;
+            }
+        }
+        if header[0] & 0x80 != 0 {
+            return Ok(info);
+        }
+    }
+}
+
+/// Read the packets at the start of an ogg stream, until `count` of them are complete.
+fn read_ogg_packets<R: Read>(reader: &mut R, count: usize) -> io::Result<Vec<Vec<u8>>> {
+    let mut packets = Vec::new();
+    let mut packet = Vec::new();
+    let mut total = 0;
+    while packets.len() < count {
+        let mut header = [0; 27];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != b"OggS" {
+            return Err(invalid());
+        }
+        let mut segments = vec![0; usize::from(header[26])];
+        reader.read_exact(&mut segments)?;
+        for &len in &segments {
+            let start = packet.len();
+            packet.resize(start + usize::from(len), 0);
+            reader.read_exact(&mut packet[start..])?;
+            total += u64::from(len);
+            if total > MAX_TAGS_LEN {
+                return Err(invalid());
+            }
+            // A segment shorter than 255 bytes ends the packet
+            if len < 255 {
+                packets.push(std::mem::replace(&mut packet, Vec::new()));
+            }
+        }
+    }
+    Ok(packets)
+}
+
+/// Find the granule position of the last page of an ogg stream, which counts its frames.
+fn last_granule<R: Read + Seek>(reader: &mut R) -> io::Result<Option<u64>> {
+    let len = reader.seek(SeekFrom::End(0))?;
+    let start = len.saturating_sub(MAX_OGG_PAGE);
+    let _ = reader.seek(SeekFrom::Start(start))?;
+    let mut tail = Vec::new();
+    let _ = reader.read_to_end(&mut tail)?;
+    let granule = (0..tail.len().saturating_sub(14))
+        .rev()
+        .filter(|&i| &tail[i..i + 4] == b"OggS")
+        .map(|i| {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(&tail[i + 6..i + 14]);
+            u64::from_le_array(bytes)
+        })
+        // Pages where no packet ends have no granule position
+        .find(|&granule| granule != u64::max_value());
+    Ok(granule)
+}
+
+fn read_ogg<R: Read + Seek>(reader: &mut R) -> io::Result<Info> {
+    let mut info = Info::default();
+    let _ = reader.seek(SeekFrom::Start(0))?;
+    let packets = read_ogg_packets(reader, 2)?;
+    let (identification, comment) = (&packets[0], &packets[1]);
+    if !identification.starts_with(b"\x01vorbis") || identification.len() < 16 {
+        return Err(invalid());
+    }
+    info.channel_count = u32::from(identification[11]);
+    info.sample_rate = u32_le(&identification[12..]);
+    if comment.starts_with(b"\x03vorbis") {
+        info.vorbis_comment(&comment[7..]);
+    }
+    info.frames = last_granule(reader)?;
+    Ok(info)
+}
+
+fn read_info<R: Read + Seek>(reader: &mut R) -> Result<Info, LoadError> {
+    let mut magic = [0; 12];
+    let _ = reader.seek(SeekFrom::Start(0))?;
+    let len = reader.read(&mut magic)?;
+    let magic = &magic[..len];
+    let info = if magic.starts_with(b"OggS") {
+        read_ogg(reader)
+    } else if magic.starts_with(b"fLaC") {
+        read_flac(reader)
+    } else if magic.starts_with(b"RIFF") && magic.get(8..12) == Some(b"WAVE") {
+        read_wav(reader)
+    } else {
+        return Err(LoadError::UnsupportedFormat);
+    };
+    info.map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof | io::ErrorKind::InvalidData => LoadError::Decode,
+        _ => e.into(),
+    })
+}
+
+/// Read the metadata of the audio file at `path`.
+///
+/// The supported formats are ogg (Vorbis), flac and wav.
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Metadata, LoadError> {
+    let mut file = BufReader::new(File::open(path)?);
+    read_from(&mut file)
+}
+
+/// Read the metadata of an audio file from a reader.
+///
+/// The file must start at the beginning of the reader. The supported formats are
+/// ogg (Vorbis), flac and wav.
+pub fn read_from<R: Read + Seek>(reader: &mut R) -> Result<Metadata, LoadError> {
+    read_info(reader).map(Info::into_metadata)
+}
+
+/// The format of an audio file, as far as a null music needs it, see `set_null_backend`.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Format {
+    pub(super) channel_count: u32,
+    pub(super) sample_rate: u32,
+    /// Zero when the file doesn't tell.
+    pub(super) duration: Time,
+}
+
+/// Read the format of an audio file from a reader, like `read_from` reads its metadata.
+pub(super) fn read_format<R: Read + Seek>(reader: &mut R) -> Result<Format, LoadError> {
+    let info = read_info(reader)?;
+    Ok(Format {
+        channel_count: info.channel_count,
+        sample_rate: info.sample_rate,
+        duration: info.into_metadata().duration.unwrap_or(Time::ZERO),
+    })
+}
+
+#[test]
+fn test_read() {
+    use crate::audio::wav;
+    use std::io::Cursor;
+
+    let mut file = Vec::new();
+    wav::write_header(&mut file, 2, 8_000, 400).unwrap();
+    file.extend_from_slice(&[0; 400]);
+    file.extend_from_slice(b"LIST\x1e\0\0\0INFOINAM\x05\0\0\0Song\0\0IART\x04\0\0\0Band");
+    let info = read_info(&mut Cursor::new(file)).unwrap();
+    assert_eq!(
+        info,
+        Info {
+            title: Some("Song".into()),
+            artist: Some("Band".into()),
+            album: None,
+            frames: Some(100),
+            sample_rate: 8_000,
+            channel_count: 2,
+        }
+    );
+
+    let mut file = b"fLaC\0\0\0\x22".to_vec();
+    let mut stream_info = [0; 34];
+    // 44100 Hz, 2 channels, 16 bits, 88200 frames
+    stream_info[10..18].copy_from_slice(&[0x0A, 0xC4, 0x42, 0xF0, 0, 1, 0x58, 0x88]);
+    file.extend_from_slice(&stream_info);
+    file.extend_from_slice(b"\x84\0\0\x18\x02\0\0\0me\x01\0\0\0\x0a\0\0\0ALBUM=Live");
+    let info = read_info(&mut Cursor::new(file)).unwrap();
+    assert_eq!(info.album, Some("Live".to_string()));
+    assert_eq!((info.frames, info.sample_rate), (Some(88_200), 44_100));
+    assert_eq!(info.channel_count, 2);
+
+    assert_eq!(
+        read_info(&mut Cursor::new(b"ID3\x04".to_vec())),
+        Err(LoadError::UnsupportedFormat)
+    );
+}
@@ -0,0 +1,268 @@
+use crate::audio::{SoundSource, SoundStatus};
+use crate::system::{Time, Vector3f};
+use std::ops::{Deref, DerefMut};
+
+/// Identifier of a sound source added to a [`MixerBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BusSourceId(usize);
+
+#[derive(Debug)]
+struct Member<S> {
+    source: S,
+    volume: f32,
+}
+
+impl<S: SoundSource> Member<S> {
+    fn apply(&mut self, bus_volume: f32) {
+        self.source.set_volume(self.volume * bus_volume / 100.);
+    }
+}
+
+/// A group of sound sources sharing a common volume.
+///
+/// Games usually let players control the volume of broad categories of sounds
+/// ("music", "sound effects", "voices", ...) independently. A mixer bus owns the sources of
+/// such a category, and keeps track of the volume of each of them: the volume actually applied
+/// to a source is its own volume scaled by the volume of the bus.
+///
+/// A bus holds sources of a single type. Sources are added with [`add`], and accessed through
+/// the returned [`BusSourceId`] with [`get_mut`], which hands out a [`BusSource`].
+/// It dereferences to the source, so that it can be played, paused, etc., and implements
+/// [`SoundSource`] itself, with its volume being the own volume of the source.
+/// This means fades work on bus members as expected, unaffected by the volume of the bus.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{MixerBus, Sound, SoundBuffer, SoundSource};
+///
+/// let buffer = SoundBuffer::from_file("explosion.wav").unwrap();
+/// let mut sfx = MixerBus::new();
+/// let explosion = sfx.add(Sound::with_buffer(&buffer));
+///
+/// // From the options menu
+/// sfx.set_volume(50.);
+///
+/// let mut explosion = sfx.get_mut(explosion).unwrap();
+/// explosion.set_volume(80.); // Actually played at 40
+/// explosion.play();
+/// ```
+///
+/// [`add`]: MixerBus::add
+/// [`get_mut`]: MixerBus::get_mut
+#[derive(Debug)]
+pub struct MixerBus<S> {
+    volume: f32,
+    duck: f32,
+    members: Vec<Option<Member<S>>>,
+}
+
+impl<S: SoundSource> MixerBus<S> {
+    /// Create a new empty bus, at full volume.
+    pub fn new() -> Self {
+        MixerBus {
+            volume: 100.,
+            duck: 1.,
+            members: Vec::new(),
+        }
+    }
+    /// Set the volume of the bus, in the range [0, 100].
+    ///
+    /// The volume of every source of the bus is updated accordingly.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+        self.apply();
+    }
+    /// Get the volume of the bus.
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+    /// Scale the volume of the bus by an extra factor, in the range [0, 1].
+    ///
+    /// This is meant for temporary dips, and is usually driven by a [`Ducker`], while the
+    /// volume of the bus is left to the player. The default factor is 1.
+    ///
+    /// [`Ducker`]: crate::audio::Ducker
+    pub fn set_duck(&mut self, factor: f32) {
+        self.duck = factor;
+        self.apply();
+    }
+    /// Get the extra factor the volume of the bus is scaled by.
+    pub fn duck(&self) -> f32 {
+        self.duck
+    }
+    /// Tell whether any source of the bus is playing.
+    pub fn is_playing(&self) -> bool {
+        self.iter()
+            .any(|(_, source)| source.status() == SoundStatus::Playing)
+    }
+    /// Get the volume applied to the sources, including the duck factor.
+    fn bus_volume(&self) -> f32 {
+        self.volume * self.duck
+    }
+    fn apply(&mut self) {
+        let bus_volume = self.bus_volume();
+        for member in self.members.iter_mut().flatten() {
+            member.apply(bus_volume);
+        }
+    }
+    /// Add a source to the bus.
+    ///
+    /// The current volume of the source becomes its own volume,
+    /// which is then scaled by the volume of the bus.
+    pub fn add(&mut self, source: S) -> BusSourceId {
+        let mut member = Member {
+            volume: source.volume(),
+            source,
+        };
+        member.apply(self.bus_volume());
+        match self.members.iter().position(Option::is_none) {
+            Some(index) => {
+                self.members[index] = Some(member);
+                BusSourceId(index)
+            }
+            None => {
+                self.members.push(Some(member));
+                BusSourceId(self.members.len() - 1)
+            }
+        }
+    }
+    /// Remove a source from the bus, giving it back with its own volume restored.
+    pub fn remove(&mut self, id: BusSourceId) -> Option<S> {
+        let mut member = self.members.get_mut(id.0)?.take()?;
+        member.apply(100.);
+        Some(member.source)
+    }
+    /// Access a source of the bus.
+    pub fn get(&self, id: BusSourceId) -> Option<&S> {
+        match self.members.get(id.0) {
+            Some(Some(member)) => Some(&member.source),
+            _ => None,
+        }
+    }
+    /// Mutably access a source of the bus.
+    pub fn get_mut(&mut self, id: BusSourceId) -> Option<BusSource<'_, S>> {
+        let bus_volume = self.bus_volume();
+        match self.members.get_mut(id.0) {
+            Some(Some(member)) => Some(BusSource { member, bus_volume }),
+            _ => None,
+        }
+    }
+    /// Iterate over the sources of the bus.
+    pub fn iter(&self) -> impl Iterator<Item = (BusSourceId, &S)> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| m.as_ref().map(|m| (BusSourceId(i), &m.source)))
+    }
+    /// Mutably iterate over the sources of the bus.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (BusSourceId, BusSource<'_, S>)> {
+        let bus_volume = self.bus_volume();
+        self.members
+            .iter_mut()
+            .enumerate()
+            .filter_map(move |(i, m)| {
+                m.as_mut()
+                    .map(|member| (BusSourceId(i), BusSource { member, bus_volume }))
+            })
+    }
+    /// Return the number of sources in the bus.
+    pub fn len(&self) -> usize {
+        self.members.iter().flatten().count()
+    }
+    /// Tell whether the bus has no sources.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S: SoundSource> Default for MixerBus<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mutable access to a source of a [`MixerBus`].
+///
+/// It dereferences to the source, and implements [`SoundSource`] by forwarding everything
+/// to it, except for the volume: the volume of a `BusSource` is the own volume of the source,
+/// before the volume of the bus is applied.
+#[derive(Debug)]
+pub struct BusSource<'a, S: 'a> {
+    member: &'a mut Member<S>,
+    bus_volume: f32,
+}
+
+impl<'a, S> Deref for BusSource<'a, S> {
+    type Target = S;
+    fn deref(&self) -> &S {
+        &self.member.source
+    }
+}
+
+impl<'a, S> DerefMut for BusSource<'a, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.member.source
+    }
+}
+
+impl<'a, S: SoundSource> SoundSource for BusSource<'a, S> {
+    fn play(&mut self) {
+        self.member.source.play()
+    }
+    fn pause(&mut self) {
+        self.member.source.pause()
+    }
+    fn stop(&mut self) {
+        self.member.source.stop()
+    }
+    fn set_pitch(&mut self, pitch: f32) {
+        self.member.source.set_pitch(pitch)
+    }
+    fn set_volume(&mut self, volume: f32) {
+        self.member.volume = volume;
+        self.member.apply(self.bus_volume);
+    }
+    fn set_position_vector(&mut self, position: Vector3f) {
+        self.member.source.set_position_vector(position)
+    }
+    fn set_relative_to_listener(&mut self, relative: bool) {
+        self.member.source.set_relative_to_listener(relative)
+    }
+    fn set_min_distance(&mut self, distance: f32) {
+        self.member.source.set_min_distance(distance)
+    }
+    fn set_attenuation(&mut self, attenuation: f32) {
+        self.member.source.set_attenuation(attenuation)
+    }
+    fn pitch(&self) -> f32 {
+        self.member.source.pitch()
+    }
+    fn volume(&self) -> f32 {
+        self.member.volume
+    }
+    fn position(&self) -> Vector3f {
+        self.member.source.position()
+    }
+    fn is_relative_to_listener(&self) -> bool {
+        self.member.source.is_relative_to_listener()
+    }
+    fn min_distance(&self) -> f32 {
+        self.member.source.min_distance()
+    }
+    fn attenuation(&self) -> f32 {
+        self.member.source.attenuation()
+    }
+    fn status(&self) -> SoundStatus {
+        self.member.source.status()
+    }
+    fn playing_offset(&self) -> Time {
+        self.member.source.playing_offset()
+    }
+    fn is_looping(&self) -> bool {
+        self.member.source.is_looping()
+    }
+    fn set_looping(&mut self, looping: bool) {
+        self.member.source.set_looping(looping)
+    }
+}
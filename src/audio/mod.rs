@@ -3,20 +3,98 @@
 
 extern crate csfml_audio_sys;
 
+pub use self::asset_loader::{AssetLoader, LoadHandle};
+pub use self::availability::{is_available, is_null_backend, set_null_backend};
 pub use self::capture::{SoundBufferRecorder, SoundRecorder, SoundRecorderDriver};
+pub use self::crossfader::Crossfader;
+#[cfg(feature = "symphonia")]
+pub use self::decoder::{DecodedMusic, DecoderStream};
+pub use self::ducker::Ducker;
+pub use self::encoder::Encoder;
+pub use self::fade::Fade;
+pub use self::input_level_meter::{InputLevel, InputLevelMeter};
+pub use self::load_error::LoadError;
+pub use self::loop_count::LoopCount;
+pub use self::mixer_bus::{BusSource, BusSourceId, MixerBus};
 pub use self::music::Music;
+pub use self::null_source::NullSource;
+#[cfg(feature = "dsp")]
+pub use self::onset::{Onset, OnsetDetector};
+pub use self::playback_monitor::{PlaybackEvent, PlaybackMonitor};
+pub use self::playlist::{Playlist, Repeat};
+pub use self::processed_music::ProcessedMusic;
+pub use self::rechunked::Rechunked;
+#[cfg(feature = "rodio")]
+pub use self::rodio_interop::RodioStream;
+pub use self::sample_processor::{Processed, SampleProcessor};
+pub use self::sample_tap::SampleTap;
+pub use self::scheduler::{ScheduleId, Scheduler};
+pub use self::seekable_reader::SeekableReader;
 pub use self::sound::Sound;
 pub use self::sound_buffer::SoundBuffer;
+pub use self::sound_file_format::SoundFileFormat;
 pub use self::sound_source::SoundSource;
 pub use self::sound_status::SoundStatus;
 pub use self::sound_stream::{SoundStream, SoundStreamPlayer};
+pub use self::source_limiter::{LimitedSourceId, SourceLimiter};
+pub use self::spatial_attachment::{Falloff, SpatialAttachment};
+pub use self::status_watcher::{StatusChange, StatusWatcher, WatchId};
+pub use self::stream_music::StreamMusic;
+#[cfg(feature = "dsp")]
+pub use self::time_stretch::{SpeedControl, TimeStretch};
+pub use self::voice::{VoiceCapture, VoiceCodec, VoicePacket, VoicePlayback};
+pub use self::wav_encoder::WavEncoder;
 
+mod asset_loader;
+mod availability;
 /// Types and helper functions dealing with audio capture.
 pub mod capture;
+mod crossfader;
+#[cfg(feature = "symphonia")]
+mod decoder;
+#[cfg(feature = "openal")]
+pub mod device;
+#[cfg(feature = "dsp")]
+pub mod dsp;
+mod ducker;
+mod encoder;
+mod fade;
+mod flac;
+mod input_level_meter;
 pub mod listener;
+mod load_error;
+mod loop_count;
+#[cfg(feature = "metadata")]
+pub mod metadata;
+#[cfg(not(feature = "metadata"))]
+mod metadata;
+mod mixer_bus;
 mod music;
+mod null_source;
+#[cfg(feature = "dsp")]
+mod onset;
+mod playback_monitor;
+mod playlist;
+mod processed_music;
+mod rechunked;
+#[cfg(feature = "rodio")]
+mod rodio_interop;
+mod sample_processor;
+mod sample_tap;
+mod scheduler;
+mod seekable_reader;
 mod sound;
 mod sound_buffer;
+mod sound_file_format;
 mod sound_source;
 mod sound_status;
 mod sound_stream;
+mod source_limiter;
+mod spatial_attachment;
+mod status_watcher;
+mod stream_music;
+#[cfg(feature = "dsp")]
+mod time_stretch;
+mod voice;
+mod wav;
+mod wav_encoder;
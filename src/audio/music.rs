@@ -1,13 +1,29 @@
 use crate::audio::csfml_audio_sys as ffi;
-use crate::audio::{SoundSource, SoundStatus};
+use crate::audio::metadata::{self, Format};
+use crate::audio::scheduler::Plans;
+use crate::audio::{
+    is_null_backend, LoadError, LoadHandle, NullSource, ProcessedMusic, SampleProcessor,
+    ScheduleId, Scheduler, SeekableReader, SoundSource, SoundStatus,
+};
+#[cfg(feature = "symphonia")]
+use crate::audio::{DecoderStream, SoundBuffer, SoundStream};
 use crate::inputstream::InputStream;
+use crate::path_conv::path_to_cstring;
 use crate::sf_bool_ext::SfBoolExt;
+#[cfg(feature = "symphonia")]
+use crate::system::SfBox;
 use crate::system::Time;
 use crate::system::Vector3f;
 use csfml_system_sys::sfBool;
-use std::ffi::CString;
-use std::io::{Read, Seek};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::marker::PhantomData;
 use std::mem;
+use std::path::{Path, PathBuf};
+use std::slice;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
 
 /// Streamed music played from an audio file.
 ///
@@ -16,7 +32,9 @@ use std::mem;
 /// This is especially useful for compressed musics that usually take hundreds of MB when they are
 /// uncompressed: by streaming it instead of loading it entirely, you avoid saturating the memory
 /// and have almost no loading delay. This implies that the underlying resource
-/// (file, stream or memory buffer) must remain valid for the lifetime of the `Music` object.
+/// (file, stream or memory buffer) must remain valid for the lifetime of the `Music` object,
+/// which is what the `'src` lifetime parameter enforces for borrowed streams and memory buffers.
+/// Musics opened from a file, or from data they own (see [`from_owned_memory`]), are `'static`.
 ///
 /// Apart from that, a `Music` has almost the same features as the
 /// [`SoundBuffer`] / [`Sound`] pair: you can play/pause/stop it, request its parameters
@@ -26,6 +44,27 @@ use std::mem;
 /// program. This means that you can leave the music alone after calling [`play`],
 /// it will manage itself very well.
 ///
+/// SFML streams musics in chunks of one second, three of them being queued at any time.
+/// This can't be changed; a custom [`SoundStream`] controls the size of its chunks, see
+/// [`Rechunked`]. The samples SFML streams never leave it either: to get a part of a
+/// music in memory, decode it again with `decode_range` (with the `symphonia` feature), and
+/// to apply effects to it or tap it for visualizers, open it again as a [`ProcessedMusic`]
+/// with [`processed`].
+///
+/// # Systems without audio
+///
+/// Musics opened while [`set_null_backend`] is on are null ones: they play nothing and
+/// don't call SFML, but behave like a [`NullSource`] lasting as long as the music. Only ogg
+/// (Vorbis), flac and wav files can be opened then, whose format is read in Rust.
+///
+/// # Thread safety
+///
+/// A music can be moved to another thread, for example a thread dedicated to audio, and
+/// controlled from there: OpenAL is thread safe, and SFML synchronizes the music with its
+/// streaming thread. Since the data source of the music is read from the streaming thread,
+/// streams must be `Send`. A music isn't `Sync`, it can only be controlled from one thread
+/// at a time.
+///
 /// # Usage example
 ///
 /// ```no_run
@@ -45,16 +84,127 @@ use std::mem;
 /// ```
 ///
 /// [`play`]: Music::play
+/// [`from_owned_memory`]: Music::from_owned_memory
+/// [`SoundStream`]: crate::audio::SoundStream
+/// [`processed`]: Music::processed
+/// [`ProcessedMusic`]: crate::audio::ProcessedMusic
+/// [`Rechunked`]: crate::audio::Rechunked
 /// [`SoundBuffer`]: crate::audio::SoundBuffer
 /// [`Sound`]: crate::audio::Sound
+/// [`set_null_backend`]: crate::audio::set_null_backend
+/// [`NullSource`]: crate::audio::NullSource
+
+///
+pub struct Music<'src> {
+    backend: Backend,
+    /// Data source the music streams from, when it owns it.
+    /// It must not be dropped before `music` is destroyed.
+    _owned_source: Option<Box<dyn Send + 'src>>,
+    /// Where the music was opened from, to decode parts of it again.
+    decode_source: DecodeSource<'src>,
+    /// The starts planned with `play_at`.
+    plans: Plans,
+    source: PhantomData<&'src mut ()>,
+}
+
+/// What plays a music, see `set_null_backend`.
+enum Backend {
+    Sfml(*mut ffi::sfMusic),
+    /// A null music, with the format of its file.
+    Null {
+        source: Box<NullSource>,
+        channel_count: u32,
+        sample_rate: u32,
+    },
+}
+
+impl Backend {
+    fn null(format: Format) -> Self {
+        Backend::Null {
+            source: Box::new(NullSource::new(format.duration)),
+            channel_count: format.channel_count,
+            sample_rate: format.sample_rate,
+        }
+    }
+    /// Open file data in memory, which must outlive the backend.
+    fn from_memory(data: &[u8]) -> Result<Self, LoadError> {
+        if is_null_backend() {
+            return metadata::read_format(&mut Cursor::new(data)).map(Backend::null);
+        }
+        let music_tmp =
+            unsafe { ffi::sfMusic_createFromMemory(data.as_ptr() as *const _, data.len()) };
+        if music_tmp.is_null() {
+            Err(LoadError::from_memory(data))
+        } else {
+            Ok(Backend::Sfml(music_tmp))
+        }
+    }
+}
+
+/// The data a music can be decoded from a second time, see `Music::decode_range`.
+enum DecodeSource<'src> {
+    File(PathBuf),
+    /// File data borrowed for `'src`, or owned by the music.
+    Memory(*const u8, usize),
+    /// The stream of the music, also read by its streaming thread.
+    #[cfg_attr(not(feature = "symphonia"), allow(dead_code))]
+    Stream(SharedStream<'src>),
+}
+
+// The borrowed or owned source of the music is `Send`, see `from_stream`.
+unsafe impl Send for Music<'_> {}
+
+/// A stream that several readers take turns reading, see `StreamReader`.
+type SharedStream<'src> = Arc<Mutex<dyn ReadSeek + Send + 'src>>;
 
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// A reader of a shared stream, with a position of its own.
 ///
-#[derive(Debug)]
-pub struct Music {
-    music: *mut ffi::sfMusic,
+/// The streaming thread of a music and the decoders of `Music::decode_range` read the same
+/// stream through readers of their own, without getting in the way of each other.
+struct StreamReader<'src> {
+    stream: SharedStream<'src>,
+    position: u64,
 }
 
-impl Music {
+impl<'src> StreamReader<'src> {
+    fn new(stream: SharedStream<'src>) -> Self {
+        StreamReader {
+            stream,
+            position: 0,
+        }
+    }
+    /// Lock the stream, positioned where this reader left it.
+    fn lock(&self) -> io::Result<MutexGuard<'_, dyn ReadSeek + Send + 'src>> {
+        let mut stream = match self.stream.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let _ = stream.seek(SeekFrom::Start(self.position))?;
+        Ok(stream)
+    }
+}
+
+impl Read for StreamReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.lock()?.read(buf)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for StreamReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = self.lock()?.seek(pos)?;
+        self.position = position;
+        Ok(position)
+    }
+}
+
+impl<'src> Music<'src> {
     /// Create a new music and load it from a file
     ///
     /// This function doesn't start playing the music (call [`play`] to do so).
@@ -65,17 +215,50 @@ impl Music {
     /// # Arguments
     /// * filename - Path of the music file to open
     ///
-    /// Return Some(Music) or None
+    /// Return the music, or the reason why it could not be opened
     ///
     /// [`play`]: Music::play
-    pub fn from_file(filename: &str) -> Option<Music> {
-        let c_str = CString::new(filename.as_bytes()).unwrap();
-        let music_tmp: *mut ffi::sfMusic = unsafe { ffi::sfMusic_createFromFile(c_str.as_ptr()) };
-        if music_tmp.is_null() {
-            None
+    pub fn from_file<P: AsRef<Path>>(filename: P) -> Result<Music<'static>, LoadError> {
+        let filename = filename.as_ref();
+        let backend = if is_null_backend() {
+            Backend::null(metadata::read_format(&mut BufReader::new(File::open(
+                filename,
+            )?))?)
         } else {
-            Some(Music { music: music_tmp })
-        }
+            let c_str =
+                path_to_cstring(filename).ok_or(LoadError::Io(io::ErrorKind::InvalidInput))?;
+            let music_tmp: *mut ffi::sfMusic =
+                unsafe { ffi::sfMusic_createFromFile(c_str.as_ptr()) };
+            if music_tmp.is_null() {
+                return Err(LoadError::from_file(filename));
+            }
+            Backend::Sfml(music_tmp)
+        };
+        Ok(Music::from_raw(
+            backend,
+            None,
+            DecodeSource::File(filename.to_path_buf()),
+        ))
+    }
+
+    /// Create a new music and open a file, on a thread of its own
+    ///
+    /// Opening a music only reads the header of the file, but that can still be slow
+    /// on a busy disk or a network drive. See [`from_file`], and [`AssetLoader`] to
+    /// open many files.
+    ///
+    /// # Arguments
+    /// * filename - Path of the music file to open
+    ///
+    /// Return a handle to poll until the music is open
+    ///
+    /// [`from_file`]: Music::from_file
+    /// [`AssetLoader`]: crate::audio::AssetLoader
+    pub fn load_async<P: AsRef<Path>>(filename: P) -> LoadHandle<Music<'static>> {
+        let filename = filename.as_ref().to_path_buf();
+        let (handle, sender) = LoadHandle::new();
+        let _ = thread::spawn(move || sender.send(Music::from_file(filename)));
+        handle
     }
 
     /// Create a new music and load it from a stream (a struct implementing Read and Seek)
@@ -86,20 +269,80 @@ impl Music {
     /// w64, mat4, mat5 pvf, htk, sds, avr, sd2, caf, wve, mpc2k, rf64.
     ///
     /// # Arguments
+    /// * stream - Your struct, implementing Read and Seek.
+    ///   It stays borrowed for as long as the music is alive, and is read from the
+    ///   streaming thread of the music.
+    ///   See [`from_owned_stream`] for a music that owns its stream.
+    ///
+    /// Return the music, or the reason why it could not be opened
+    ///
+    /// [`play`]: Music::play
+    /// [`from_owned_stream`]: Music::from_owned_stream
+    pub fn from_stream<T: Read + Seek + Send>(
+        stream: &'src mut T,
+    ) -> Result<Music<'src>, LoadError> {
+        Music::from_shared_stream(Arc::new(Mutex::new(stream)))
+    }
+
+    /// Create a new music and load it from a stream it takes ownership of
+    ///
+    /// Unlike [`from_stream`], the stream is moved into the music (along with the
+    /// bookkeeping CSFML needs to read from it), so the music is self-contained and
+    /// can be freely moved around and stored.
+    ///
+    /// This function doesn't start playing the music (call [`play`] to do so).
+    ///
+    /// # Arguments
     /// * stream - Your struct, implementing Read and Seek
     ///
-    /// Return Some(Music) or None
+    /// Return the music, or the reason why it could not be opened
     ///
+    /// [`from_stream`]: Music::from_stream
     /// [`play`]: Music::play
-    pub fn from_stream<T: Read + Seek>(stream: &mut T) -> Option<Music> {
-        let mut input_stream = InputStream::new(stream);
-        let music_tmp: *mut ffi::sfMusic =
-            unsafe { ffi::sfMusic_createFromStream(&mut input_stream.0) };
-        if music_tmp.is_null() {
-            None
+    pub fn from_owned_stream<T: Read + Seek + Send + 'static>(
+        stream: T,
+    ) -> Result<Music<'static>, LoadError> {
+        Music::from_shared_stream(Arc::new(Mutex::new(stream)))
+    }
+
+    fn from_shared_stream(stream: SharedStream<'src>) -> Result<Music<'src>, LoadError> {
+        // The reader lives on the heap, so moving its box into the music
+        // doesn't invalidate the pointer given to CSFML.
+        let mut reader = Box::new(StreamReader::new(stream.clone()));
+        let backend = if is_null_backend() {
+            Backend::null(metadata::read_format(&mut *reader)?)
         } else {
-            Some(Music { music: music_tmp })
-        }
+            let music_tmp =
+                unsafe { ffi::sfMusic_createFromStream(&mut InputStream::new(&mut *reader).0) };
+            if music_tmp.is_null() {
+                return Err(LoadError::from_stream(&mut *reader));
+            }
+            Backend::Sfml(music_tmp)
+        };
+        Ok(Music::from_raw(
+            backend,
+            Some(reader),
+            DecodeSource::Stream(stream),
+        ))
+    }
+
+    /// Create a new music and load it from a reader that can't seek
+    ///
+    /// The data read is kept in memory to answer the seek requests of SFML,
+    /// see [`SeekableReader`]. The reader must reach an end, since SFML finds out
+    /// the size of the data when opening it.
+    ///
+    /// This function doesn't start playing the music (call [`play`] to do so).
+    ///
+    /// # Arguments
+    /// * reader - The source of the data, a pipe for example
+    ///
+    /// Return the music, or the reason why it could not be opened
+    ///
+    /// [`SeekableReader`]: crate::audio::SeekableReader
+    /// [`play`]: Music::play
+    pub fn from_reader<R: Read + Send + 'static>(reader: R) -> Result<Music<'static>, LoadError> {
+        Music::from_owned_stream(SeekableReader::new(reader))
     }
 
     /// Create a new music and load it from memory
@@ -110,18 +353,123 @@ impl Music {
     /// w64, mat4, mat5 pvf, htk, sds, avr, sd2, caf, wve, mpc2k, rf64.
     ///
     /// # Arguments
-    /// * mem - Pointer to the file data in memory
+    /// * mem - The file data in memory.
+    ///   It stays borrowed for as long as the music is alive.
     ///
-    /// Return Some(Music) or None
+    /// Return the music, or the reason why it could not be opened
     ///
     /// [`play`]: Music::play
-    pub fn from_memory(mem: &[u8]) -> Option<Music> {
-        let music_tmp =
-            unsafe { ffi::sfMusic_createFromMemory(mem.as_ptr() as *const _, mem.len()) };
-        if music_tmp.is_null() {
-            None
-        } else {
-            Some(Music { music: music_tmp })
+    pub fn from_memory(mem: &'src [u8]) -> Result<Music<'src>, LoadError> {
+        let backend = Backend::from_memory(mem)?;
+        Ok(Music::from_raw(
+            backend,
+            None,
+            DecodeSource::Memory(mem.as_ptr(), mem.len()),
+        ))
+    }
+
+    /// Create a new music and load it from file data it takes ownership of
+    ///
+    /// Unlike [`from_memory`], the data is stored inside the music,
+    /// so it doesn't have to be kept alive separately.
+    ///
+    /// This function doesn't start playing the music (call [`play`] to do so).
+    ///
+    /// # Arguments
+    /// * data - The file data
+    ///
+    /// Return the music, or the reason why it could not be opened
+    ///
+    /// [`from_memory`]: Music::from_memory
+    /// [`play`]: Music::play
+    pub fn from_owned_memory(data: Vec<u8>) -> Result<Music<'static>, LoadError> {
+        // Moving the vector into the music doesn't move its heap buffer,
+        // so the pointer handed to CSFML stays valid.
+        let backend = Backend::from_memory(&data)?;
+        let decode_source = DecodeSource::Memory(data.as_ptr(), data.len());
+        Ok(Music::from_raw(
+            backend,
+            Some(Box::new(data)),
+            decode_source,
+        ))
+    }
+
+    fn from_raw(
+        backend: Backend,
+        owned_source: Option<Box<dyn Send + 'src>>,
+        decode_source: DecodeSource<'src>,
+    ) -> Self {
+        Music {
+            backend,
+            _owned_source: owned_source,
+            decode_source,
+            plans: Plans::default(),
+            source: PhantomData,
+        }
+    }
+
+    /// Decode a part of the music into a new sound buffer
+    ///
+    /// The source of the music is opened a second time, so the music keeps playing
+    /// undisturbed. This pulls a clip out of a long music, to preview it or to play it
+    /// as a sound, for example.
+    ///
+    /// The part is decoded in Rust, with a `DecoderStream`, which seeks to `offset` and
+    /// decodes only up to `offset + length`. Musics opened from a stream share it with the
+    /// decoder, each reading at its own position.
+    ///
+    /// Only the formats symphonia can decode are supported, see `DecoderStream`.
+    ///
+    /// # Arguments
+    /// * offset - Start of the part to decode
+    /// * length - Duration of the part to decode
+    ///
+    /// Returns `None` on failure.
+    #[cfg(feature = "symphonia")]
+    pub fn decode_range(&self, offset: Time, length: Time) -> Option<SfBox<SoundBuffer>> {
+        let mut stream = match self.decode_source {
+            DecodeSource::File(ref path) => DecoderStream::from_file(path),
+            DecodeSource::Memory(data, len) => {
+                // The data outlives the music, see `from_memory` and `from_owned_memory`.
+                let data = unsafe { slice::from_raw_parts(data, len) };
+                DecoderStream::from_memory(data.to_vec())
+            }
+            DecodeSource::Stream(ref stream) => {
+                // The decoder is dropped before this function returns, well within `'src`.
+                let stream: SharedStream<'static> = unsafe { mem::transmute(stream.clone()) };
+                DecoderStream::from_reader(StreamReader::new(stream))
+            }
+        }
+        .ok()?;
+        stream.decode_range(offset, length)
+    }
+
+    /// Open the music again, with its samples going through a processor
+    ///
+    /// The samples of a music never leave SFML, so effects and [`SampleTap`]s can't be
+    /// applied to it directly. The returned [`ProcessedMusic`] decodes the same file or
+    /// memory in Rust instead, and is played in place of this music. It starts stopped,
+    /// with the default settings.
+    ///
+    /// # Arguments
+    /// * processor - The processor the samples go through
+    ///
+    /// Returns `None` for musics opened from a stream, which is already being read by the
+    /// music, or on failure.
+    ///
+    /// [`SampleTap`]: crate::audio::SampleTap
+    pub fn processed<P: SampleProcessor + 'static>(
+        &self,
+        processor: P,
+    ) -> Option<ProcessedMusic<P>> {
+        match self.decode_source {
+            DecodeSource::File(ref path) => ProcessedMusic::from_file(path, processor).ok(),
+            DecodeSource::Memory(data, len) => {
+                // The data outlives the music, see `from_memory` and `from_owned_memory`.
+                let data = unsafe { slice::from_raw_parts(data, len) };
+                ProcessedMusic::from_memory(data, processor).ok()
+            }
+            DecodeSource::Stream(_) => None,
         }
     }
 
@@ -133,21 +481,32 @@ impl Music {
     ///
     /// By default, the music will *not* loop.
     pub fn set_looping(&mut self, looping: bool) {
-        unsafe { ffi::sfMusic_setLoop(self.music, sfBool::from_bool(looping)) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe {
+                ffi::sfMusic_setLoop(music, sfBool::from_bool(looping))
+            },
+            Backend::Null { ref mut source, .. } => source.set_looping(looping),
+        }
     }
 
     /// Tell whether or not a music is in loop mode
     ///
     /// Return true if the music is looping, false otherwise
     pub fn is_looping(&self) -> bool {
-        unsafe { ffi::sfMusic_getLoop(self.music) }.to_bool()
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getLoop(music) }.to_bool(),
+            Backend::Null { ref source, .. } => source.is_looping(),
+        }
     }
 
     /// Get the total duration of a music
     ///
     /// Return Music duration
     pub fn duration(&self) -> Time {
-        unsafe { Time::from_raw(ffi::sfMusic_getDuration(self.music)) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { Time::from_raw(ffi::sfMusic_getDuration(music)) },
+            Backend::Null { ref source, .. } => source.duration(),
+        }
     }
 
     /// Start or resume playing a music
@@ -158,7 +517,11 @@ impl Music {
     /// This function uses its own thread so that it doesn't block
     /// the rest of the program while the music is played.
     pub fn play(&mut self) {
-        unsafe { ffi::sfMusic_play(self.music) }
+        self.plans.cancel();
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_play(music) },
+            Backend::Null { ref mut source, .. } => source.play(),
+        }
     }
 
     /// Pause a music
@@ -166,7 +529,11 @@ impl Music {
     /// This function pauses the music if it was playing,
     /// otherwise (music already paused or stopped) it has no effect.
     pub fn pause(&mut self) {
-        unsafe { ffi::sfMusic_pause(self.music) }
+        self.plans.cancel();
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_pause(music) },
+            Backend::Null { ref mut source, .. } => source.pause(),
+        }
     }
 
     /// Stop playing a music
@@ -175,7 +542,42 @@ impl Music {
     /// and does nothing if it was already stopped.
     /// It also resets the playing position (unlike pause).
     pub fn stop(&mut self) {
-        unsafe { ffi::sfMusic_stop(self.music) }
+        self.plans.cancel();
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_stop(music) },
+            Backend::Null { ref mut source, .. } => source.stop(),
+        }
+    }
+
+    /// Start or resume playing a music at the time `at` of `scheduler`
+    ///
+    /// The music is started from the scheduler thread, within a fraction of a millisecond
+    /// of `at`, as [`play`] would start it then. A time in the past plays the music right
+    /// away. Unlike [`Scheduler::play_at`], the music doesn't have to be shared with the
+    /// scheduler: only its start happens on the scheduler thread.
+    ///
+    /// Several starts can be planned. Calling [`play`], [`pause`] or [`stop`], or dropping
+    /// the music cancels them all.
+    ///
+    /// Return the planned start, to cancel it alone with [`Scheduler::cancel`]. Null musics
+    /// (see [`set_null_backend`]) follow the clock of the scheduler without using its
+    /// thread, keep only the last start planned, and return `None`.
+    ///
+    /// [`play`]: Music::play
+    /// [`pause`]: Music::pause
+    /// [`stop`]: Music::stop
+    /// [`set_null_backend`]: crate::audio::set_null_backend
+    pub fn play_at(&mut self, scheduler: &mut Scheduler, at: Time) -> Option<ScheduleId> {
+        match self.backend {
+            // The music is destroyed only after the plans are cancelled
+            Backend::Sfml(music) => {
+                Some(unsafe { self.plans.play_at(scheduler, at, music, ffi::sfMusic_play) })
+            }
+            Backend::Null { ref mut source, .. } => {
+                source.play_at(scheduler.instant(at));
+                None
+            }
+        }
     }
 
     /// Return the number of channels of a music
@@ -184,7 +586,10 @@ impl Music {
     ///
     /// Return the number of channels
     pub fn channel_count(&self) -> u32 {
-        unsafe { ffi::sfMusic_getChannelCount(self.music) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getChannelCount(music) },
+            Backend::Null { channel_count, .. } => channel_count,
+        }
     }
 
     /// Get the sample rate of a music
@@ -194,21 +599,32 @@ impl Music {
     ///
     /// Return the sample rate, in number of samples per second
     pub fn sample_rate(&self) -> u32 {
-        unsafe { ffi::sfMusic_getSampleRate(self.music) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getSampleRate(music) },
+            Backend::Null { sample_rate, .. } => sample_rate,
+        }
     }
 
     /// Get the current status of a music (stopped, paused, playing)
     ///
     /// Return current status
     pub fn status(&self) -> SoundStatus {
-        unsafe { mem::transmute(ffi::sfMusic_getStatus(self.music)) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe {
+                mem::transmute::<ffi::sfSoundStatus, SoundStatus>(ffi::sfMusic_getStatus(music))
+            },
+            Backend::Null { ref source, .. } => source.status(),
+        }
     }
 
     /// Get the current playing position of a music
     ///
     /// Return the current playing position
     pub fn playing_offset(&self) -> Time {
-        unsafe { Time::from_raw(ffi::sfMusic_getPlayingOffset(self.music)) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { Time::from_raw(ffi::sfMusic_getPlayingOffset(music)) },
+            Backend::Null { ref source, .. } => source.playing_offset(),
+        }
     }
 
     /// Change the current playing position of a music
@@ -219,53 +635,162 @@ impl Music {
     /// # Arguments
     /// * timeOffset - New playing position
     pub fn set_playing_offset(&mut self, time_offset: Time) {
-        unsafe { ffi::sfMusic_setPlayingOffset(self.music, time_offset.raw()) }
+        match self.backend {
+            // Seeking restarts the streaming thread, which a planned start must not race
+            Backend::Sfml(music) => unsafe {
+                let _hold = self.plans.hold();
+                ffi::sfMusic_setPlayingOffset(music, time_offset.raw())
+            },
+            Backend::Null { ref mut source, .. } => source.set_playing_offset(time_offset),
+        }
     }
 }
 
-impl SoundSource for Music {
+impl<'src> SoundSource for Music<'src> {
+    fn play(&mut self) {
+        Music::play(self)
+    }
+    fn pause(&mut self) {
+        Music::pause(self)
+    }
+    fn stop(&mut self) {
+        Music::stop(self)
+    }
     fn set_pitch(&mut self, pitch: f32) {
-        unsafe { ffi::sfMusic_setPitch(self.music, pitch) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_setPitch(music, pitch) },
+            Backend::Null { ref mut source, .. } => source.set_pitch(pitch),
+        }
     }
     fn set_volume(&mut self, volume: f32) {
-        unsafe { ffi::sfMusic_setVolume(self.music, volume) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_setVolume(music, volume) },
+            Backend::Null { ref mut source, .. } => source.set_volume(volume),
+        }
     }
-    fn set_position<P: Into<Vector3f>>(&mut self, position: P) {
-        unsafe { ffi::sfMusic_setPosition(self.music, position.into().raw()) }
+    fn set_position_vector(&mut self, position: Vector3f) {
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_setPosition(music, position.raw()) },
+            Backend::Null { ref mut source, .. } => source.set_position_vector(position),
+        }
     }
     fn set_relative_to_listener(&mut self, relative: bool) {
-        unsafe { ffi::sfMusic_setRelativeToListener(self.music, sfBool::from_bool(relative)) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe {
+                ffi::sfMusic_setRelativeToListener(music, sfBool::from_bool(relative))
+            },
+            Backend::Null { ref mut source, .. } => source.set_relative_to_listener(relative),
+        }
     }
     fn set_min_distance(&mut self, distance: f32) {
-        unsafe { ffi::sfMusic_setMinDistance(self.music, distance) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_setMinDistance(music, distance) },
+            Backend::Null { ref mut source, .. } => source.set_min_distance(distance),
+        }
     }
     fn set_attenuation(&mut self, attenuation: f32) {
-        unsafe { ffi::sfMusic_setAttenuation(self.music, attenuation) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_setAttenuation(music, attenuation) },
+            Backend::Null { ref mut source, .. } => source.set_attenuation(attenuation),
+        }
     }
     fn pitch(&self) -> f32 {
-        unsafe { ffi::sfMusic_getPitch(self.music) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getPitch(music) },
+            Backend::Null { ref source, .. } => source.pitch(),
+        }
     }
     fn volume(&self) -> f32 {
-        unsafe { ffi::sfMusic_getVolume(self.music) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getVolume(music) },
+            Backend::Null { ref source, .. } => source.volume(),
+        }
     }
     fn position(&self) -> Vector3f {
-        unsafe { Vector3f::from_raw(ffi::sfMusic_getPosition(self.music)) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { Vector3f::from_raw(ffi::sfMusic_getPosition(music)) },
+            Backend::Null { ref source, .. } => source.position(),
+        }
     }
     fn is_relative_to_listener(&self) -> bool {
-        unsafe { ffi::sfMusic_isRelativeToListener(self.music).to_bool() }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_isRelativeToListener(music).to_bool() },
+            Backend::Null { ref source, .. } => source.is_relative_to_listener(),
+        }
     }
     fn min_distance(&self) -> f32 {
-        unsafe { ffi::sfMusic_getMinDistance(self.music) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getMinDistance(music) },
+            Backend::Null { ref source, .. } => source.min_distance(),
+        }
     }
     fn attenuation(&self) -> f32 {
-        unsafe { ffi::sfMusic_getAttenuation(self.music) }
+        match self.backend {
+            Backend::Sfml(music) => unsafe { ffi::sfMusic_getAttenuation(music) },
+            Backend::Null { ref source, .. } => source.attenuation(),
+        }
+    }
+    fn status(&self) -> SoundStatus {
+        Music::status(self)
+    }
+    fn playing_offset(&self) -> Time {
+        Music::playing_offset(self)
+    }
+    fn is_looping(&self) -> bool {
+        Music::is_looping(self)
+    }
+    fn set_looping(&mut self, looping: bool) {
+        Music::set_looping(self, looping)
     }
 }
 
-impl Drop for Music {
+// The data source is read by the streaming thread, it can't be shown
+impl fmt::Debug for Music<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Music")
+            .field("status", &self.status())
+            .field("channel_count", &self.channel_count())
+            .field("sample_rate", &self.sample_rate())
+            .field("duration", &self.duration())
+            .finish()
+    }
+}
+
+impl<'src> Drop for Music<'src> {
     fn drop(&mut self) {
-        unsafe {
-            ffi::sfMusic_destroy(self.music);
+        self.plans.cancel();
+        if let Backend::Sfml(music) = self.backend {
+            unsafe { ffi::sfMusic_destroy(music) }
         }
     }
 }
+
+#[test]
+fn test_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<Music<'_>>();
+    assert_send::<crate::system::SfBox<crate::audio::SoundBuffer>>();
+}
+
+#[test]
+fn test_null_backend() {
+    use crate::audio::{set_null_backend, wav};
+
+    set_null_backend(true);
+    let mut file = Vec::new();
+    wav::write_header(&mut file, 2, 8_000, 32_000).unwrap();
+    file.resize(file.len() + 32_000, 0);
+    let mut music = Music::from_owned_memory(file).unwrap();
+    assert_eq!((music.channel_count(), music.sample_rate()), (2, 8_000));
+    assert_eq!(music.duration().as_microseconds(), 1_000_000);
+    music.play();
+    assert_eq!(music.status(), SoundStatus::Playing);
+    music.stop();
+    assert_eq!(music.status(), SoundStatus::Stopped);
+    let mut scheduler = Scheduler::new();
+    let at = scheduler.now() + Time::microseconds(20_000);
+    assert!(music.play_at(&mut scheduler, at).is_none());
+    assert_eq!(music.status(), SoundStatus::Stopped);
+    thread::sleep(std::time::Duration::from_millis(40));
+    assert_eq!(music.status(), SoundStatus::Playing);
+}
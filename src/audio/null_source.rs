@@ -0,0 +1,256 @@
+use crate::audio::{SoundSource, SoundStatus};
+use crate::system::{Time, Vector3f};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Stopped,
+    Paused(Duration),
+    /// Playing since `Instant`, which was at the offset `Duration`.
+    Playing(Instant, Duration),
+    /// Starting to play at `Instant` from the offset `Duration`, see `Sound::play_at`.
+    Planned(Instant, Duration),
+}
+
+/// A sound source that plays nothing.
+///
+/// A null source keeps all the properties of a source, and goes through the motions of
+/// playing: its status and playing offset follow the clock as if it was playing a sound
+/// of a given duration. It doesn't touch SFML at all, so it can stand for real sources
+/// on systems without audio (see [`is_available`]), and in tests
+/// running without a sound device.
+///
+/// Since [`SoundSource`] is object safe, a game can hold its sources as trait objects,
+/// and pick null ones when there is no audio.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{NullSource, Sound, SoundBuffer, SoundSource};
+/// use sfml::system::Time;
+///
+/// let audio_available = sfml::audio::is_available();
+/// let buffer = SoundBuffer::from_file("speech.wav").unwrap();
+/// let mut speech: Box<dyn SoundSource> = if audio_available {
+///     Box::new(Sound::with_buffer(&buffer))
+/// } else {
+///     Box::new(NullSource::new(buffer.duration()))
+/// };
+/// speech.play();
+/// ```
+///
+/// [`SoundSource`]: crate::audio::SoundSource
+/// [`is_available`]: crate::audio::is_available
+#[derive(Debug, Clone, Copy)]
+pub struct NullSource {
+    duration: Duration,
+    state: State,
+    looping: bool,
+    pitch: f32,
+    volume: f32,
+    position: Vector3f,
+    relative: bool,
+    min_distance: f32,
+    attenuation: f32,
+}
+
+impl NullSource {
+    /// Create a new null source, standing for a sound lasting `duration`.
+    pub fn new(duration: Time) -> Self {
+        Self::with_duration(Duration::from_micros(
+            duration.as_microseconds().max(0) as u64
+        ))
+    }
+    pub(super) fn with_duration(duration: Duration) -> Self {
+        NullSource {
+            duration,
+            state: State::Stopped,
+            looping: false,
+            pitch: 1.,
+            volume: 100.,
+            position: Vector3f::new(0., 0., 0.),
+            relative: false,
+            min_distance: 1.,
+            attenuation: 1.,
+        }
+    }
+    /// Change the duration of the sound the source stands for, stopping it.
+    pub(super) fn set_duration(&mut self, duration: Time) {
+        self.duration = Duration::from_micros(duration.as_microseconds().max(0) as u64);
+        self.state = State::Stopped;
+    }
+    /// Get the duration of the sound the source stands for.
+    pub fn duration(&self) -> Time {
+        Time::microseconds(micros(self.duration))
+    }
+    /// Change the current playing position of the source.
+    pub fn set_playing_offset(&mut self, offset: Time) {
+        let offset = Duration::from_micros(offset.as_microseconds().max(0) as u64);
+        self.seek(offset.min(self.duration), Instant::now());
+    }
+
+    /// Start playing at `at`, like `play` would then.
+    pub(super) fn play_at(&mut self, at: Instant) {
+        let now = Instant::now();
+        let offset = match self.state {
+            State::Paused(offset) | State::Planned(_, offset) => offset,
+            _ => Duration::from_secs(0),
+        };
+        self.state = State::Planned(at.max(now), offset);
+    }
+
+    fn seek(&mut self, offset: Duration, now: Instant) {
+        self.state = match self.state {
+            State::Stopped | State::Paused(_) => State::Paused(offset),
+            State::Playing(..) => State::Playing(now, offset),
+            State::Planned(at, _) => State::Planned(at, offset),
+        };
+    }
+    /// Get the playing offset at `now`, stopping the source if it reached its end.
+    fn offset_at(&mut self, now: Instant) -> Duration {
+        if let State::Planned(at, offset) = self.state {
+            if now >= at {
+                self.state = State::Playing(at, offset);
+            }
+        }
+        match self.state {
+            State::Stopped => Duration::from_secs(0),
+            State::Paused(offset) | State::Planned(_, offset) => offset,
+            State::Playing(since, offset) => {
+                let elapsed =
+                    nanos(now.duration_since(since)) as f64 * f64::from(self.pitch.max(0.));
+                let elapsed = Duration::from_nanos(elapsed as u64);
+                let offset = offset + elapsed;
+                if offset < self.duration {
+                    offset
+                } else if self.looping && self.duration > Duration::from_secs(0) {
+                    Duration::from_nanos(nanos(offset) % nanos(self.duration))
+                } else {
+                    self.state = State::Stopped;
+                    Duration::from_secs(0)
+                }
+            }
+        }
+    }
+    fn status_at(&mut self, now: Instant) -> SoundStatus {
+        let _ = self.offset_at(now);
+        match self.state {
+            State::Stopped => SoundStatus::Stopped,
+            State::Paused(_) => SoundStatus::Paused,
+            State::Playing(..) => SoundStatus::Playing,
+            // Not started yet
+            State::Planned(_, offset) if offset == Duration::from_secs(0) => SoundStatus::Stopped,
+            State::Planned(..) => SoundStatus::Paused,
+        }
+    }
+}
+
+impl SoundSource for NullSource {
+    fn play(&mut self) {
+        let now = Instant::now();
+        self.state = match self.state {
+            State::Paused(offset) | State::Planned(_, offset) => State::Playing(now, offset),
+            _ => State::Playing(now, Duration::from_secs(0)),
+        };
+    }
+    fn pause(&mut self) {
+        let now = Instant::now();
+        let offset = self.offset_at(now);
+        self.state = match self.state {
+            State::Playing(..) => State::Paused(offset),
+            // Cancelled before it started
+            State::Planned(..) if offset == Duration::from_secs(0) => State::Stopped,
+            State::Planned(..) => State::Paused(offset),
+            state => state,
+        };
+    }
+    fn stop(&mut self) {
+        self.state = State::Stopped;
+    }
+    fn set_pitch(&mut self, pitch: f32) {
+        // The offset reached so far was played at the previous pitch
+        let now = Instant::now();
+        let offset = self.offset_at(now);
+        if let State::Playing(..) = self.state {
+            self.state = State::Playing(now, offset);
+        }
+        self.pitch = pitch;
+    }
+    fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+    }
+    fn set_position_vector(&mut self, position: Vector3f) {
+        self.position = position;
+    }
+    fn set_relative_to_listener(&mut self, relative: bool) {
+        self.relative = relative;
+    }
+    fn set_min_distance(&mut self, distance: f32) {
+        self.min_distance = distance;
+    }
+    fn set_attenuation(&mut self, attenuation: f32) {
+        self.attenuation = attenuation;
+    }
+    fn pitch(&self) -> f32 {
+        self.pitch
+    }
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+    fn position(&self) -> Vector3f {
+        self.position
+    }
+    fn is_relative_to_listener(&self) -> bool {
+        self.relative
+    }
+    fn min_distance(&self) -> f32 {
+        self.min_distance
+    }
+    fn attenuation(&self) -> f32 {
+        self.attenuation
+    }
+    fn status(&self) -> SoundStatus {
+        let mut source = *self;
+        source.status_at(Instant::now())
+    }
+    fn playing_offset(&self) -> Time {
+        let mut source = *self;
+        let offset = source.offset_at(Instant::now());
+        Time::microseconds(micros(offset))
+    }
+    fn is_looping(&self) -> bool {
+        self.looping
+    }
+    fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+}
+
+// `Duration::as_micros` and `Duration::as_nanos` are only available from Rust 1.33
+fn micros(duration: Duration) -> i64 {
+    duration.as_secs() as i64 * 1_000_000 + i64::from(duration.subsec_micros())
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_secs() * 1_000_000_000 + u64::from(duration.subsec_nanos())
+}
+
+#[test]
+fn test_playback() {
+    let second = Duration::from_secs(1);
+    let mut source = NullSource::with_duration(2 * second);
+    let start = Instant::now();
+    source.state = State::Playing(start, Duration::from_secs(0));
+    assert_eq!(source.offset_at(start + second), second);
+    assert_eq!(source.status_at(start + second), SoundStatus::Playing);
+    source.looping = true;
+    assert_eq!(source.offset_at(start + 3 * second), second);
+    source.looping = false;
+    assert_eq!(source.status_at(start + 3 * second), SoundStatus::Stopped);
+    source.seek(second, start);
+    assert_eq!(source.status_at(start), SoundStatus::Paused);
+    source.state = State::Planned(start + second, Duration::from_secs(0));
+    assert_eq!(source.status_at(start), SoundStatus::Stopped);
+    assert_eq!(source.offset_at(start + 2 * second), second);
+    assert_eq!(source.status_at(start + 2 * second), SoundStatus::Playing);
+}
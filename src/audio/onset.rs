@@ -0,0 +1,345 @@
+use crate::audio::dsp::fft;
+use crate::audio::{SampleProcessor, SoundBuffer};
+use crate::system::Time;
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Number of frames analyzed at once.
+const WINDOW: usize = 1024;
+/// Number of frames between two analyses.
+const HOP: usize = 512;
+/// Number of past analyses the threshold is computed from, about 0.2 s at 44.1 kHz.
+const HISTORY: usize = 16;
+/// Spectral flux under which nothing is detected, so that noise in silent parts is ignored.
+const MIN_FLUX: f32 = 0.1;
+/// Number of onsets kept by a detector that nobody polls.
+const MAX_PENDING: usize = 256;
+
+/// A beat, or more generally the start of a note, detected in audio samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Onset {
+    /// Position of the onset in the samples.
+    pub time: Time,
+    /// How sudden the onset is, 1 being the detection threshold. Strong beats are well
+    /// above 1, which can drive the intensity of an effect.
+    pub strength: f32,
+}
+
+/// Spectral flux analysis, counting positions in frames.
+#[derive(Debug, Clone)]
+struct Analyzer {
+    sensitivity: f32,
+    /// Minimum time between two onsets, in seconds.
+    min_interval: f32,
+    /// Mono samples not analyzed yet, preceded by those of the previous window.
+    pending: Vec<f32>,
+    magnitudes: Vec<f32>,
+    history: VecDeque<f32>,
+    /// Position of the first sample of `pending` not analyzed yet.
+    position: u64,
+    last_onset: Option<u64>,
+}
+
+impl Analyzer {
+    fn new(sensitivity: f32, min_interval: f32) -> Self {
+        let mut analyzer = Analyzer {
+            sensitivity,
+            min_interval,
+            pending: Vec::new(),
+            magnitudes: vec![0.; WINDOW / 2],
+            history: VecDeque::with_capacity(HISTORY),
+            position: 0,
+            last_onset: None,
+        };
+        analyzer.reset();
+        analyzer
+    }
+    fn reset(&mut self) {
+        // The first analysis happens once a hop worth of samples is available
+        self.pending.clear();
+        self.pending.resize(WINDOW - HOP, 0.);
+        self.magnitudes.iter_mut().for_each(|m| *m = 0.);
+        self.history.clear();
+        self.position = 0;
+        self.last_onset = None;
+    }
+    /// Analyze interleaved samples, and call `on_onset` with the position in frames and
+    /// the strength of each onset found.
+    fn feed<F>(&mut self, samples: &[i16], channel_count: u32, sample_rate: u32, mut on_onset: F)
+    where
+        F: FnMut(u64, f32),
+    {
+        let channel_count = channel_count.max(1) as usize;
+        self.pending
+            .extend(samples.chunks(channel_count).map(|frame| {
+                frame.iter().map(|&s| f32::from(s)).sum::<f32>() / channel_count as f32 / 32_768.
+            }));
+        let min_interval = (self.min_interval.max(0.) * sample_rate as f32) as u64;
+        let mut start = 0;
+        while start + WINDOW <= self.pending.len() {
+            let flux = self.flux(start);
+            let threshold = if self.history.is_empty() {
+                0.
+            } else {
+                self.history.iter().sum::<f32>() / self.history.len() as f32 * self.sensitivity
+            };
+            let threshold = threshold.max(MIN_FLUX);
+            let previous = self.history.back().cloned().unwrap_or(0.);
+            let spaced = self
+                .last_onset
+                .map_or(true, |last| self.position - last >= min_interval);
+            if flux > threshold && flux > previous && spaced {
+                on_onset(self.position, flux / threshold);
+                self.last_onset = Some(self.position);
+            }
+            if self.history.len() == HISTORY {
+                let _ = self.history.pop_front();
+            }
+            self.history.push_back(flux);
+            self.position += HOP as u64;
+            start += HOP;
+        }
+        drop(self.pending.drain(..start));
+    }
+    /// Compute the increase of the spectrum of the window at `start` over the previous one.
+    fn flux(&mut self, start: usize) -> f32 {
+        let mut re: Vec<f32> = self.pending[start..start + WINDOW]
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s * (0.5 - 0.5 * (2. * PI * i as f32 / WINDOW as f32).cos()))
+            .collect();
+        let mut im = vec![0.; WINDOW];
+        fft(&mut re, &mut im);
+        let scale = 4. / WINDOW as f32;
+        let mut flux = 0.;
+        for (previous, (re, im)) in self.magnitudes.iter_mut().zip(re.iter().zip(&im)) {
+            let magnitude = (re * re + im * im).sqrt() * scale;
+            flux += (magnitude - *previous).max(0.);
+            *previous = magnitude;
+        }
+        flux
+    }
+}
+
+fn frames_to_time(frames: u64, sample_rate: u32) -> Time {
+    Time::microseconds((frames * 1_000_000 / u64::from(sample_rate.max(1))) as i64)
+}
+
+#[derive(Debug)]
+struct Shared {
+    analyzer: Analyzer,
+    onsets: VecDeque<Onset>,
+}
+
+/// Detects beats and note onsets in audio, for music-reactive visuals.
+///
+/// This requires the `dsp` feature.
+///
+/// The detector looks for sudden increases of energy across the spectrum (the spectral
+/// flux), compared to the average over the last fraction of a second. This catches drum
+/// hits and the attacks of most instruments, and reports each one as an [`Onset`].
+///
+/// Whole sounds can be analyzed at once with [`detect`], to prepare the effects of a level
+/// in advance for example.
+///
+/// A detector is also a [`SampleProcessor`], which analyzes the samples of a stream as they
+/// are played. Like a [`SampleTap`], cloning a detector gives another handle to the same
+/// analysis: attach one clone to a stream with [`SoundStream::with_processor`], and
+/// [`poll`] the other one from the main thread. The times of the onsets are then counted
+/// from the start of the stream, and start over from zero when the stream seeks.
+///
+/// Only custom sound streams can be analyzed while playing: the samples of a [`Music`]
+/// never leave SFML. Music files can be played through a custom stream instead (see
+/// `DecoderStream`, with the `symphonia` feature), or analyzed offline from a
+/// [`SoundBuffer`] of the same file.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::audio::{SoundStream, SoundStreamPlayer};
+/// # use sfml::system::Time;
+/// # struct Song(Vec<i16>);
+/// # impl SoundStream for Song {
+/// #     fn get_data(&mut self) -> (&mut [i16], bool) { (&mut self.0[..], true) }
+/// #     fn seek(&mut self, _: Time) {}
+/// #     fn channel_count(&self) -> u32 { 2 }
+/// #     fn sample_rate(&self) -> u32 { 44_100 }
+/// # }
+/// # let song = Song(vec![0; 4096]);
+/// use sfml::audio::OnsetDetector;
+///
+/// let detector = OnsetDetector::new();
+/// let mut stream = song.with_processor(detector.clone());
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+///
+/// loop {
+///     while let Some(onset) = detector.poll() {
+///         // Flash the screen, with `onset.strength` as intensity...
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// [`detect`]: OnsetDetector::detect
+/// [`poll`]: OnsetDetector::poll
+/// [`SampleTap`]: crate::audio::SampleTap
+/// [`SoundStream::with_processor`]: crate::audio::SoundStream::with_processor
+/// [`Music`]: crate::audio::Music
+#[derive(Debug, Clone)]
+pub struct OnsetDetector {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl OnsetDetector {
+    /// Create a new detector, with a sensitivity of 1.5 and a minimum interval of 100 ms.
+    pub fn new() -> Self {
+        OnsetDetector {
+            shared: Arc::new(Mutex::new(Shared {
+                analyzer: Analyzer::new(1.5, 0.1),
+                onsets: VecDeque::new(),
+            })),
+        }
+    }
+    /// Change how much the spectral flux must exceed its recent average to be an onset.
+    ///
+    /// Lower values detect more onsets, higher values only the most marked ones.
+    /// The default is 1.5.
+    pub fn set_sensitivity(&mut self, sensitivity: f32) {
+        self.lock().analyzer.sensitivity = sensitivity;
+    }
+    /// Get the sensitivity of the detector.
+    pub fn sensitivity(&self) -> f32 {
+        self.lock().analyzer.sensitivity
+    }
+    /// Change the minimum time between two onsets.
+    ///
+    /// The attack of a note often spans several analyses, this keeps it from being reported
+    /// several times. The default is 100 ms.
+    pub fn set_min_interval(&mut self, interval: Time) {
+        self.lock().analyzer.min_interval = interval.as_seconds();
+    }
+    /// Get the minimum time between two onsets.
+    pub fn min_interval(&self) -> Time {
+        Time::seconds(self.lock().analyzer.min_interval)
+    }
+    /// Find all the onsets of a sound buffer.
+    ///
+    /// This doesn't change the state of the streaming analysis.
+    pub fn detect(&self, buffer: &SoundBuffer) -> Vec<Onset> {
+        self.detect_samples(
+            buffer.samples(),
+            buffer.channel_count(),
+            buffer.sample_rate(),
+        )
+    }
+    /// Find all the onsets of interleaved samples.
+    ///
+    /// This doesn't change the state of the streaming analysis.
+    ///
+    /// # Arguments
+    /// * samples - Interleaved samples
+    /// * channel_count - Number of channels of the samples
+    /// * sample_rate - Sample rate of the samples
+    pub fn detect_samples(
+        &self,
+        samples: &[i16],
+        channel_count: u32,
+        sample_rate: u32,
+    ) -> Vec<Onset> {
+        let (sensitivity, min_interval) = {
+            let shared = self.lock();
+            (shared.analyzer.sensitivity, shared.analyzer.min_interval)
+        };
+        let mut onsets = Vec::new();
+        Analyzer::new(sensitivity, min_interval).feed(
+            samples,
+            channel_count,
+            sample_rate,
+            |frames, strength| {
+                onsets.push(Onset {
+                    time: frames_to_time(frames, sample_rate),
+                    strength,
+                })
+            },
+        );
+        onsets
+    }
+    /// Take the oldest onset detected in the streamed samples, if any.
+    ///
+    /// Onsets are detected when the samples are handed over to SFML, which is a little
+    /// before they are heard. At most 256 onsets are kept, the oldest ones are discarded
+    /// if the detector isn't polled.
+    pub fn poll(&self) -> Option<Onset> {
+        self.lock().onsets.pop_front()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Shared> {
+        // A panic while the lock is held can at worst lose some onsets
+        match self.shared.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl Default for OnsetDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SampleProcessor for OnsetDetector {
+    fn process(&mut self, samples: &mut [i16], channel_count: u32, sample_rate: u32) {
+        let mut shared = self.lock();
+        let Shared {
+            ref mut analyzer,
+            ref mut onsets,
+        } = *shared;
+        analyzer.feed(samples, channel_count, sample_rate, |frames, strength| {
+            if onsets.len() == MAX_PENDING {
+                let _ = onsets.pop_front();
+            }
+            onsets.push_back(Onset {
+                time: frames_to_time(frames, sample_rate),
+                strength,
+            });
+        });
+    }
+    fn reset(&mut self) {
+        let mut shared = self.lock();
+        shared.analyzer.reset();
+        shared.onsets.clear();
+    }
+}
+
+#[test]
+fn test_detect() {
+    // Bursts of a decaying tone at 0.5, 1 and 1.5 seconds
+    let rate = 8_000;
+    let samples: Vec<i16> = (0..2 * rate)
+        .map(|i| {
+            let since = (i % (rate / 2)) as f32 / rate as f32;
+            if i < rate / 2 || since > 0.2 {
+                0
+            } else {
+                let tone = (2. * PI * 440. * since).sin();
+                (tone * (-since * 20.).exp() * 16_000.) as i16
+            }
+        })
+        .collect();
+    let mut onsets = Vec::new();
+    let mut analyzer = Analyzer::new(1.5, 0.1);
+    // Streamed in uneven chunks, to go through the buffering
+    for chunk in samples.chunks(700) {
+        analyzer.feed(chunk, 1, rate as u32, |frames, strength| {
+            onsets.push((frames, strength))
+        });
+    }
+    assert_eq!(onsets.len(), 3);
+    for (&(frames, strength), expected) in onsets.iter().zip(&[4_000, 8_000, 12_000]) {
+        assert!((frames as i64 - expected).abs() <= HOP as i64);
+        assert!(strength > 1.);
+    }
+}
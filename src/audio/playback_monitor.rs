@@ -0,0 +1,102 @@
+use crate::audio::{SoundSource, SoundStatus};
+use crate::system::Time;
+
+/// Notable change in the playback of a sound source, reported by a [`PlaybackMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackEvent {
+    /// The source stopped playing, after reaching its end.
+    Finished,
+    /// The looping source reached its end, and started over from the beginning.
+    Looped,
+}
+
+/// Detects when a sound source finishes playing, or loops.
+///
+/// SFML doesn't notify the end of a sound, the only way to know about it is to check its
+/// status. A monitor does this bookkeeping: feed it the source it watches with [`update`]
+/// (typically once per frame), and it reports a [`PlaybackEvent::Finished`] event
+/// once each time the source goes from playing to stopped.
+///
+/// Note that SFML can't tell apart a source that reached its end from a source that was
+/// stopped while playing, so stopping a playing source explicitly is reported as well.
+///
+/// Looping sources never finish on their own. Instead, a [`PlaybackEvent::Looped`] event
+/// is reported each time their playing position jumps back to the beginning, which is the
+/// place to count repetitions or to start layers in sync with the loop. For this to work,
+/// the loop must last longer than the time between two updates; and since the position
+/// is all the monitor has to go by, seeking backwards in a looping source is reported
+/// as a loop too.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{PlaybackEvent, PlaybackMonitor, Sound, SoundBuffer};
+///
+/// let intro = SoundBuffer::from_file("intro.wav").unwrap();
+/// let main = SoundBuffer::from_file("main.wav").unwrap();
+/// let mut sound = Sound::with_buffer(&intro);
+/// let mut monitor = PlaybackMonitor::new();
+/// sound.play();
+/// loop {
+///     if monitor.update(&sound) == Some(PlaybackEvent::Finished) {
+///         // Chain the next sound
+///         sound.set_buffer(&main);
+///         sound.play();
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// [`update`]: PlaybackMonitor::update
+#[derive(Debug, Clone, Copy)]
+pub struct PlaybackMonitor {
+    status: SoundStatus,
+    offset: Time,
+    loops: u32,
+}
+
+impl PlaybackMonitor {
+    /// Create a new monitor, for a source that is not playing yet.
+    pub fn new() -> Self {
+        PlaybackMonitor {
+            status: SoundStatus::Stopped,
+            offset: Time::ZERO,
+            loops: 0,
+        }
+    }
+    /// Check the status of `source`, and report what happened since the last check.
+    pub fn update<S: SoundSource + ?Sized>(&mut self, source: &S) -> Option<PlaybackEvent> {
+        let status = source.status();
+        let previous = self.status;
+        self.status = status;
+        match (previous, status) {
+            (SoundStatus::Playing, SoundStatus::Stopped) => Some(PlaybackEvent::Finished),
+            (SoundStatus::Playing, SoundStatus::Playing) => {
+                let offset = source.playing_offset();
+                let previous_offset = self.offset;
+                self.offset = offset;
+                if offset < previous_offset && source.is_looping() {
+                    self.loops += 1;
+                    Some(PlaybackEvent::Looped)
+                } else {
+                    None
+                }
+            }
+            (_, SoundStatus::Playing) => {
+                self.offset = source.playing_offset();
+                None
+            }
+            _ => None,
+        }
+    }
+    /// Get the number of [`PlaybackEvent::Looped`] events reported so far.
+    pub fn loop_count(&self) -> u32 {
+        self.loops
+    }
+}
+
+impl Default for PlaybackMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,517 @@
+use crate::audio::processed_music::MusicSamples;
+use crate::audio::{SoundSource, SoundStatus, SoundStream, StreamMusic};
+use crate::system::Time;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How a [`Playlist`] behaves when the current track ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// Stop after the last track.
+    Off,
+    /// Loop the current track.
+    One,
+    /// Start over from the first track after the last one.
+    All,
+}
+
+/// A queue of music tracks, played one after the other without gaps.
+///
+/// The tracks are decoded in Rust, and streamed one after the other through a single
+/// sound stream: the first samples of a track directly follow the last samples of the
+/// previous one, so that tracks meant to flow into each other (like the movements of a
+/// live album) play without a gap or a click. The following track is opened ahead of time
+/// by [`update`], so that reaching it never waits for its file to open.
+///
+/// With the `symphonia` feature, the tracks are decoded as they are played
+/// (see `DecoderStream`). Otherwise, and for the formats symphonia can't decode, each track
+/// is loaded in a [`SoundBuffer`] first, which takes about 10 MB per minute of stereo
+/// audio for the current and the following tracks.
+///
+/// A stream has a single channel count and sample rate: the transition to a track that
+/// differs from the previous one in either is not gapless. The current stream ends
+/// instead, and [`update`] starts a new one for the track. The same goes for the tracks
+/// switched to with [`next_track`], [`previous_track`] and [`play_track`].
+///
+/// [`update`] must be called regularly (typically once per frame), for the tracks to be
+/// opened in advance, and for the playlist to go on after such a transition.
+///
+/// Tracks that fail to open are skipped.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Playlist, Repeat};
+///
+/// let mut playlist = Playlist::new();
+/// playlist.push("intro.ogg");
+/// playlist.push("theme.ogg");
+/// playlist.push("credits.ogg");
+/// playlist.set_repeat(Repeat::All);
+/// playlist.play();
+///
+/// loop {
+///     playlist.update();
+///     // ...
+/// }
+/// ```
+///
+/// [`update`]: Playlist::update
+/// [`next_track`]: Playlist::next_track
+/// [`previous_track`]: Playlist::previous_track
+/// [`play_track`]: Playlist::play_track
+/// [`SoundBuffer`]: crate::audio::SoundBuffer
+#[derive(Debug)]
+pub struct Playlist {
+    queue: Arc<Mutex<Queue>>,
+    music: Option<StreamMusic<PlaylistStream>>,
+    shuffle: bool,
+    volume: f32,
+    playing: bool,
+    rng: u64,
+}
+
+impl Playlist {
+    /// Create a new empty playlist.
+    pub fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() ^ u64::from(d.subsec_nanos()))
+            .unwrap_or(0);
+        let queue = Queue {
+            tracks: Vec::new(),
+            order: Vec::new(),
+            cursor: 0,
+            repeat: Repeat::Off,
+            next: None,
+        };
+        Playlist {
+            queue: Arc::new(Mutex::new(queue)),
+            music: None,
+            shuffle: false,
+            volume: 100.,
+            playing: false,
+            rng: seed | 1,
+        }
+    }
+    /// Add a track at the end of the playlist.
+    ///
+    /// When shuffling, the track is inserted at a random position among the tracks
+    /// that haven't been played yet.
+    pub fn push<P: Into<PathBuf>>(&mut self, path: P) {
+        let (len, cursor) = {
+            let queue = lock(&self.queue);
+            (queue.order.len(), queue.cursor)
+        };
+        let at = if self.shuffle && len > cursor {
+            Some(cursor + 1 + self.random_below(len - cursor))
+        } else {
+            None
+        };
+        let mut queue = lock(&self.queue);
+        let index = queue.tracks.len();
+        queue.tracks.push(path.into());
+        match at {
+            Some(at) => queue.order.insert(at, index),
+            None => queue.order.push(index),
+        }
+        queue.next = None;
+    }
+    /// Return the number of tracks in the playlist.
+    pub fn len(&self) -> usize {
+        lock(&self.queue).tracks.len()
+    }
+    /// Tell whether the playlist has no tracks.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.queue).tracks.is_empty()
+    }
+    /// Return the path of the track at `index` (in insertion order).
+    pub fn track(&self, index: usize) -> Option<PathBuf> {
+        lock(&self.queue).tracks.get(index).cloned()
+    }
+    /// Return the index (in insertion order) of the current track,
+    /// if one has been opened.
+    ///
+    /// The playlist moves on to the following track as soon as it starts streaming it,
+    /// which is slightly ahead of it being heard.
+    pub fn current_track(&self) -> Option<usize> {
+        let queue = lock(&self.queue);
+        self.music.as_ref().map(|_| queue.order[queue.cursor])
+    }
+    /// Start or resume playing.
+    ///
+    /// If no track has been opened yet, this starts from the first one.
+    pub fn play(&mut self) {
+        self.playing = true;
+        match self.music.as_mut() {
+            Some(music) => music.play(),
+            None => {
+                let cursor = lock(&self.queue).cursor;
+                if !self.open_from(cursor) {
+                    self.playing = false;
+                }
+            }
+        }
+    }
+    /// Pause the current track.
+    pub fn pause(&mut self) {
+        self.playing = false;
+        if let Some(music) = self.music.as_mut() {
+            music.pause();
+        }
+    }
+    /// Stop the current track, rewinding it to its beginning.
+    pub fn stop(&mut self) {
+        self.playing = false;
+        if let Some(music) = self.music.as_mut() {
+            music.stop();
+        }
+    }
+    /// Get the current status of the playlist (stopped, paused, playing).
+    pub fn status(&self) -> SoundStatus {
+        self.music
+            .as_ref()
+            .map_or(SoundStatus::Stopped, StreamMusic::status)
+    }
+    /// Switch to the next track.
+    ///
+    /// After the last track, this wraps around to the first one if the repeat mode is
+    /// [`Repeat::All`], and does nothing otherwise.
+    ///
+    /// Returns whether the track changed.
+    pub fn next_track(&mut self) -> bool {
+        let following = {
+            let queue = lock(&self.queue);
+            queue.following(queue.cursor)
+        };
+        match following {
+            Some(cursor) => self.open_from(cursor),
+            None => false,
+        }
+    }
+    /// Switch to the previous track.
+    ///
+    /// Before the first track, this wraps around to the last one if the repeat mode is
+    /// [`Repeat::All`], and restarts the first track otherwise.
+    ///
+    /// Returns whether the track changed.
+    pub fn previous_track(&mut self) -> bool {
+        let previous = {
+            let queue = lock(&self.queue);
+            if queue.cursor > 0 {
+                Some(queue.cursor - 1)
+            } else if queue.repeat == Repeat::All && !queue.order.is_empty() {
+                Some(queue.order.len() - 1)
+            } else {
+                None
+            }
+        };
+        match previous {
+            Some(cursor) => self.open(cursor),
+            None => {
+                if let Some(music) = self.music.as_mut() {
+                    music.set_playing_offset(Time::ZERO);
+                }
+                false
+            }
+        }
+    }
+    /// Switch to the track at `index` (in insertion order).
+    ///
+    /// Returns whether the track could be opened.
+    pub fn play_track(&mut self, index: usize) -> bool {
+        let cursor = lock(&self.queue).order.iter().position(|&i| i == index);
+        match cursor {
+            Some(cursor) => self.open(cursor),
+            None => false,
+        }
+    }
+    /// Set the repeat mode. The default is [`Repeat::Off`].
+    pub fn set_repeat(&mut self, repeat: Repeat) {
+        let mut queue = lock(&self.queue);
+        queue.repeat = repeat;
+        queue.next = None;
+    }
+    /// Get the repeat mode.
+    pub fn repeat(&self) -> Repeat {
+        lock(&self.queue).repeat
+    }
+    /// Enable or disable shuffling. Shuffling is disabled by default.
+    ///
+    /// Enabling shuffling randomizes the order of the tracks, the current one staying first.
+    /// Disabling it restores the insertion order.
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+        let (len, current) = {
+            let queue = lock(&self.queue);
+            (queue.tracks.len(), queue.order.get(queue.cursor).cloned())
+        };
+        let mut order: Vec<usize> = (0..len).collect();
+        let cursor = if shuffle {
+            for i in (1..order.len()).rev() {
+                let j = self.random_below(i + 1);
+                order.swap(i, j);
+            }
+            if let Some(current) = current {
+                let at = order.iter().position(|&i| i == current).unwrap_or(0);
+                order.swap(0, at);
+            }
+            0
+        } else {
+            current.unwrap_or(0)
+        };
+        let mut queue = lock(&self.queue);
+        queue.order = order;
+        queue.cursor = cursor;
+        queue.next = None;
+    }
+    /// Tell whether shuffling is enabled.
+    pub fn is_shuffled(&self) -> bool {
+        self.shuffle
+    }
+    /// Set the volume of the playlist, in the range [0, 100]. The default is 100.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+        if let Some(music) = self.music.as_mut() {
+            music.set_volume(volume);
+        }
+    }
+    /// Get the volume of the playlist.
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+    /// Make sure the following track is opened in advance, and move on to it
+    /// if the stream had to end before it.
+    ///
+    /// This should be called regularly, for example once per frame.
+    pub fn update(&mut self) {
+        if self.playing && self.status() == SoundStatus::Stopped {
+            let following = {
+                let queue = lock(&self.queue);
+                queue.following(queue.cursor)
+            };
+            let advanced = match following {
+                Some(cursor) => self.open_from(cursor),
+                None => false,
+            };
+            if !advanced {
+                self.playing = false;
+            }
+        }
+        let following = {
+            let queue = lock(&self.queue);
+            match queue.following(queue.cursor) {
+                Some(cursor) if !queue.has_next(cursor) => queue.path(cursor),
+                _ => None,
+            }
+        };
+        // The queue isn't locked while the track opens, the stream may need it meanwhile
+        if let Some((cursor, path)) = following {
+            let samples = match MusicSamples::from_file(&path) {
+                Ok(samples) => samples,
+                Err(_) => return,
+            };
+            let mut queue = lock(&self.queue);
+            if queue.following(queue.cursor) == Some(cursor) {
+                queue.next = Some((cursor, samples));
+            }
+        }
+    }
+
+    /// Open the track at `cursor`, or the first one after it that can be opened.
+    fn open_from(&mut self, cursor: usize) -> bool {
+        let mut cursor = cursor;
+        let len = lock(&self.queue).order.len();
+        for _ in 0..len {
+            if self.open(cursor) {
+                return true;
+            }
+            match lock(&self.queue).following(cursor) {
+                Some(following) => cursor = following,
+                None => break,
+            }
+        }
+        false
+    }
+    /// Make the track at `cursor` the current one, in a new stream.
+    fn open(&mut self, cursor: usize) -> bool {
+        let samples = match Queue::take(&self.queue, cursor) {
+            Some(samples) => samples,
+            None => return false,
+        };
+        // The stream of the previous track must be done with the queue before it changes
+        self.music = None;
+        lock(&self.queue).cursor = cursor;
+        let mut music = StreamMusic::new(PlaylistStream::new(self.queue.clone(), samples));
+        music.set_volume(self.volume);
+        if self.playing {
+            music.play();
+        }
+        self.music = Some(music);
+        true
+    }
+    /// A pseudo-random number in `0..n` (xorshift), good enough for shuffling.
+    fn random_below(&mut self, n: usize) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng % n as u64) as usize
+    }
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lock(queue: &Mutex<Queue>) -> MutexGuard<'_, Queue> {
+    // A panic while the lock is held can at worst skip a track
+    match queue.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// The tracks of a playlist, shared with the stream playing them.
+#[derive(Debug)]
+struct Queue {
+    tracks: Vec<PathBuf>,
+    /// Play order, as indices into `tracks`.
+    order: Vec<usize>,
+    /// Position of the current track in `order`.
+    cursor: usize,
+    repeat: Repeat,
+    /// The pre-opened track at the given position in `order`.
+    next: Option<(usize, MusicSamples)>,
+}
+
+impl Queue {
+    /// The position in `order` that comes after `cursor`, if any.
+    fn following(&self, cursor: usize) -> Option<usize> {
+        if cursor + 1 < self.order.len() {
+            Some(cursor + 1)
+        } else if self.repeat == Repeat::All && !self.order.is_empty() {
+            Some(0)
+        } else {
+            None
+        }
+    }
+    fn has_next(&self, cursor: usize) -> bool {
+        self.next
+            .as_ref()
+            .map_or(false, |&(next, _)| next == cursor)
+    }
+    fn path(&self, cursor: usize) -> Option<(usize, PathBuf)> {
+        let index = *self.order.get(cursor)?;
+        Some((cursor, self.tracks[index].clone()))
+    }
+    /// Take the track at `cursor` if it was opened in advance, or open it.
+    ///
+    /// The queue isn't locked while the track opens.
+    fn take(queue: &Mutex<Queue>, cursor: usize) -> Option<MusicSamples> {
+        let path = {
+            let mut queue = lock(queue);
+            if queue.has_next(cursor) {
+                return queue.next.take().map(|(_, samples)| samples);
+            }
+            queue.path(cursor)?.1
+        };
+        MusicSamples::from_file(&path).ok()
+    }
+}
+
+/// The stream of a playlist, chaining its tracks as long as they share their format.
+#[derive(Debug)]
+struct PlaylistStream {
+    queue: Arc<Mutex<Queue>>,
+    current: MusicSamples,
+    channel_count: u32,
+    sample_rate: u32,
+    chunk: Vec<i16>,
+}
+
+impl PlaylistStream {
+    fn new(queue: Arc<Mutex<Queue>>, current: MusicSamples) -> Self {
+        PlaylistStream {
+            queue,
+            channel_count: current.channel_count(),
+            sample_rate: current.sample_rate(),
+            current,
+            chunk: Vec::new(),
+        }
+    }
+    /// Move on to the track after the current one, if this stream can play it.
+    fn advance(&mut self) -> bool {
+        let (repeat, len, mut cursor) = {
+            let queue = lock(&self.queue);
+            (queue.repeat, queue.order.len(), queue.cursor)
+        };
+        if repeat == Repeat::One {
+            self.current.seek(Time::ZERO);
+            return true;
+        }
+        // Tracks that fail to open are skipped
+        for _ in 0..len {
+            cursor = match lock(&self.queue).following(cursor) {
+                Some(following) => following,
+                None => return false,
+            };
+            let samples = match Queue::take(&self.queue, cursor) {
+                Some(samples) => samples,
+                None => continue,
+            };
+            let mut queue = lock(&self.queue);
+            if samples.channel_count() != self.channel_count
+                || samples.sample_rate() != self.sample_rate
+            {
+                // The playlist plays it in another stream, once this one ended
+                queue.next = Some((cursor, samples));
+                return false;
+            }
+            queue.cursor = cursor;
+            self.current = samples;
+            return true;
+        }
+        false
+    }
+}
+
+impl SoundStream for PlaylistStream {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        self.chunk.clear();
+        // A track with no samples at all can't loop forever
+        let mut advances = 0;
+        loop {
+            let keep_playing = {
+                let (samples, keep_playing) = self.current.get_data();
+                self.chunk.extend_from_slice(samples);
+                keep_playing
+            };
+            if keep_playing && !self.chunk.is_empty() {
+                return (&mut self.chunk[..], true);
+            }
+            if !keep_playing {
+                advances += 1;
+                let len = lock(&self.queue).order.len();
+                if advances > len.max(1) || !self.advance() {
+                    return (&mut self.chunk[..], false);
+                }
+                if !self.chunk.is_empty() {
+                    return (&mut self.chunk[..], true);
+                }
+            }
+        }
+    }
+    /// Seek in the current track.
+    fn seek(&mut self, offset: Time) {
+        self.current.seek(offset)
+    }
+    fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
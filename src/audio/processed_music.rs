@@ -0,0 +1,181 @@
+#[cfg(feature = "symphonia")]
+use crate::audio::DecoderStream;
+use crate::audio::{LoadError, Processed, SampleProcessor, SoundBuffer, SoundStream, StreamMusic};
+use crate::system::{SfBox, Time};
+use std::path::Path;
+
+/// Duration of the chunks streamed from a sound buffer, short enough for visualizers.
+const BUFFER_CHUNK_MS: u32 = 50;
+
+/// A music whose samples go through a [`SampleProcessor`] before they are played.
+///
+/// SFML decodes and plays the samples of a [`Music`] on its own, out of reach of Rust code.
+/// A processed music decodes the audio file in Rust instead, and plays the samples through
+/// [`SoundStream::with_processor`] with a [`StreamMusic`]. This applies
+/// effects to a music, or taps it for visualizers with a [`SampleTap`].
+///
+/// With the `symphonia` feature, the file is decoded as it is played, like a [`Music`]
+/// (see `DecoderStream`). Otherwise, and for the formats symphonia can't decode, the
+/// whole file is loaded in a [`SoundBuffer`] first, which takes about 10 MB per minute of
+/// stereo audio.
+///
+/// Like a [`Music`], it can be moved to a thread dedicated to audio.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{ProcessedMusic, SampleTap};
+///
+/// let tap = SampleTap::new(2048);
+/// let mut music = ProcessedMusic::from_file("music.ogg", tap.clone()).unwrap();
+/// music.play();
+///
+/// let mut waveform = [0; 2048];
+/// loop {
+///     let len = tap.copy_latest(&mut waveform);
+///     // Draw `waveform[..len]`...
+/// }
+/// ```
+///
+/// [`Music`]: crate::audio::Music
+/// [`SampleTap`]: crate::audio::SampleTap
+pub type ProcessedMusic<P> = StreamMusic<Processed<Box<dyn SoundStream + Send>, P>>;
+
+impl<P: SampleProcessor + 'static> StreamMusic<Processed<Box<dyn SoundStream + Send>, P>> {
+    /// Create a new music and load it from a file.
+    ///
+    /// # Arguments
+    /// * path - Path of the music file to open
+    /// * processor - The processor the samples go through
+    ///
+    /// Return the music, or the reason why it could not be opened
+    pub fn from_file<Q: AsRef<Path>>(path: Q, processor: P) -> Result<Self, LoadError> {
+        MusicSamples::from_file(path.as_ref()).map(|samples| Self::processed(samples, processor))
+    }
+    /// Create a new music and load it from file data in memory.
+    ///
+    /// The data is copied, it doesn't have to be kept alive.
+    ///
+    /// # Arguments
+    /// * data - The file data
+    /// * processor - The processor the samples go through
+    ///
+    /// Return the music, or the reason why it could not be opened
+    pub fn from_memory(data: &[u8], processor: P) -> Result<Self, LoadError> {
+        MusicSamples::from_memory(data).map(|samples| Self::processed(samples, processor))
+    }
+    fn processed(samples: MusicSamples, processor: P) -> Self {
+        let samples: Box<dyn SoundStream + Send> = Box::new(samples);
+        Self::new(samples.with_processor(processor))
+    }
+}
+
+/// The samples of an audio file, decoded in Rust.
+///
+/// With the `symphonia` feature, they are decoded as they are streamed. Otherwise, and for
+/// the formats symphonia can't decode, the whole file is loaded in a sound buffer first.
+#[derive(Debug)]
+pub(super) enum MusicSamples {
+    #[cfg(feature = "symphonia")]
+    Decoder(DecoderStream),
+    Buffer {
+        buffer: SfBox<SoundBuffer>,
+        /// Index of the next sample to stream.
+        position: usize,
+        chunk_len: usize,
+        chunk: Vec<i16>,
+    },
+}
+
+impl MusicSamples {
+    pub(super) fn from_file(path: &Path) -> Result<Self, LoadError> {
+        #[cfg(feature = "symphonia")]
+        {
+            if let Ok(stream) = DecoderStream::from_file(path) {
+                return Ok(MusicSamples::Decoder(stream));
+            }
+        }
+        let buffer = SoundBuffer::from_file(path).ok_or_else(|| LoadError::from_file(path))?;
+        Ok(Self::buffer(buffer))
+    }
+    fn from_memory(data: &[u8]) -> Result<Self, LoadError> {
+        #[cfg(feature = "symphonia")]
+        {
+            if let Ok(stream) = DecoderStream::from_memory(data.to_vec()) {
+                return Ok(MusicSamples::Decoder(stream));
+            }
+        }
+        let buffer = SoundBuffer::from_memory(data).ok_or_else(|| LoadError::from_memory(data))?;
+        Ok(Self::buffer(buffer))
+    }
+    fn buffer(buffer: SfBox<SoundBuffer>) -> Self {
+        let frames = buffer.sample_rate() * BUFFER_CHUNK_MS / 1000;
+        let chunk_len = (frames * buffer.channel_count()).max(1) as usize;
+        MusicSamples::Buffer {
+            buffer,
+            position: 0,
+            chunk_len,
+            chunk: Vec::with_capacity(chunk_len),
+        }
+    }
+}
+
+impl SoundStream for MusicSamples {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        match *self {
+            #[cfg(feature = "symphonia")]
+            MusicSamples::Decoder(ref mut stream) => stream.get_data(),
+            MusicSamples::Buffer {
+                ref buffer,
+                ref mut position,
+                chunk_len,
+                ref mut chunk,
+            } => {
+                let samples = buffer.samples();
+                let end = (*position + chunk_len).min(samples.len());
+                chunk.clear();
+                chunk.extend_from_slice(&samples[*position..end]);
+                *position = end;
+                (&mut chunk[..], end < samples.len())
+            }
+        }
+    }
+    fn seek(&mut self, offset: Time) {
+        match *self {
+            #[cfg(feature = "symphonia")]
+            MusicSamples::Decoder(ref mut stream) => stream.seek(offset),
+            MusicSamples::Buffer {
+                ref buffer,
+                ref mut position,
+                ..
+            } => {
+                let channels = buffer.channel_count() as usize;
+                let frame = offset.as_microseconds().max(0) as u64
+                    * u64::from(buffer.sample_rate())
+                    / 1_000_000;
+                *position = (frame as usize * channels).min(buffer.samples().len());
+            }
+        }
+    }
+    fn channel_count(&self) -> u32 {
+        match *self {
+            #[cfg(feature = "symphonia")]
+            MusicSamples::Decoder(ref stream) => stream.channel_count(),
+            MusicSamples::Buffer { ref buffer, .. } => buffer.channel_count(),
+        }
+    }
+    fn sample_rate(&self) -> u32 {
+        match *self {
+            #[cfg(feature = "symphonia")]
+            MusicSamples::Decoder(ref stream) => stream.sample_rate(),
+            MusicSamples::Buffer { ref buffer, .. } => buffer.sample_rate(),
+        }
+    }
+    fn duration(&self) -> Option<Time> {
+        match *self {
+            #[cfg(feature = "symphonia")]
+            MusicSamples::Decoder(ref stream) => stream.duration(),
+            MusicSamples::Buffer { ref buffer, .. } => Some(buffer.duration()),
+        }
+    }
+}
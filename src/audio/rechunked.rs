@@ -0,0 +1,159 @@
+use crate::audio::SoundStream;
+use crate::system::Time;
+
+/// Number of chunks SFML keeps queued while playing a stream.
+const BUFFER_COUNT: u32 = 3;
+
+/// A sound stream handing out its samples in chunks of a fixed size.
+///
+/// SFML keeps three chunks of a stream queued for playback, and asks for a new one each
+/// time one has been played. The size of the chunks therefore sets the latency of the stream
+/// (the time between the moment samples are produced and the moment they are heard), as well
+/// as how often the stream is called:
+///
+/// * Small chunks (a few milliseconds) give a low latency, for sounds that react to
+///   the user, at the cost of frequent calls and a higher risk of underruns
+///   if the streaming thread doesn't get to run in time.
+/// * Big chunks (a second or more) make it easy to keep up with slow sources,
+///   like a file on a busy disk, but changes take a while to be heard.
+///
+/// The size of the chunks of a custom stream is whatever its [`get_data`] returns.
+/// A `Rechunked` stream collects the samples of another stream, and hands them out in
+/// chunks of the requested size instead. See [`SoundStream::with_chunk_size`].
+///
+/// The chunk size of a [`Music`] is fixed by SFML to one second.
+///
+/// # Example
+///
+/// ```no_run
+/// # use sfml::audio::{SoundStream, SoundStreamPlayer};
+/// # use sfml::system::Time;
+/// # struct Synth(Vec<i16>);
+/// # impl SoundStream for Synth {
+/// #     fn get_data(&mut self) -> (&mut [i16], bool) { (&mut self.0[..], true) }
+/// #     fn seek(&mut self, _: Time) {}
+/// #     fn channel_count(&self) -> u32 { 1 }
+/// #     fn sample_rate(&self) -> u32 { 44_100 }
+/// # }
+/// # let synth = Synth(vec![0; 44_100]);
+/// // 256 frames at 44.1 kHz is about 5.8 ms per chunk
+/// let mut stream = synth.with_chunk_size(256);
+/// println!("latency: {} ms", stream.latency().as_milliseconds());
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+/// ```
+///
+/// [`get_data`]: SoundStream::get_data
+/// [`Music`]: crate::audio::Music
+#[derive(Debug)]
+pub struct Rechunked<S> {
+    stream: S,
+    chunk_len: usize,
+    buffer: Vec<i16>,
+    /// Number of samples at the start of `buffer` handed out by the last call.
+    consumed: usize,
+    ended: bool,
+}
+
+impl<S: SoundStream> Rechunked<S> {
+    /// Hand out the samples of `stream` in chunks of `frames` frames.
+    ///
+    /// A frame holds one sample per channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is 0.
+    pub fn new(stream: S, frames: usize) -> Self {
+        assert!(frames > 0, "Rechunked: chunks must hold at least one frame");
+        let chunk_len = frames * stream.channel_count() as usize;
+        Rechunked {
+            stream,
+            chunk_len,
+            buffer: Vec::with_capacity(chunk_len * 2),
+            consumed: 0,
+            ended: false,
+        }
+    }
+    /// Get the duration of one chunk.
+    pub fn chunk_duration(&self) -> Time {
+        let frames = self.chunk_len as f32 / self.stream.channel_count() as f32;
+        Time::seconds(frames / self.stream.sample_rate() as f32)
+    }
+    /// Get the latency of the stream, which is the duration of the chunks queued by SFML.
+    pub fn latency(&self) -> Time {
+        Time::seconds(self.chunk_duration().as_seconds() * BUFFER_COUNT as f32)
+    }
+}
+
+impl<S> Rechunked<S> {
+    /// Access the underlying stream.
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+    /// Mutably access the underlying stream.
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+    /// Unwrap the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: SoundStream> SoundStream for Rechunked<S> {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        drop(self.buffer.drain(..self.consumed));
+        while self.buffer.len() < self.chunk_len && !self.ended {
+            let (data, keep_playing) = self.stream.get_data();
+            self.buffer.extend_from_slice(data);
+            self.ended = !keep_playing;
+        }
+        self.consumed = self.buffer.len().min(self.chunk_len);
+        let keep_playing = !self.ended || self.buffer.len() > self.consumed;
+        (&mut self.buffer[..self.consumed], keep_playing)
+    }
+    fn seek(&mut self, offset: Time) {
+        self.buffer.clear();
+        self.consumed = 0;
+        self.ended = false;
+        self.stream.seek(offset);
+    }
+    fn channel_count(&self) -> u32 {
+        self.stream.channel_count()
+    }
+    fn sample_rate(&self) -> u32 {
+        self.stream.sample_rate()
+    }
+    fn duration(&self) -> Option<Time> {
+        self.stream.duration()
+    }
+}
+
+#[test]
+fn test_rechunk() {
+    struct Counter(Vec<i16>, i16);
+    impl SoundStream for Counter {
+        fn get_data(&mut self) -> (&mut [i16], bool) {
+            for sample in &mut self.0 {
+                *sample = self.1;
+                self.1 += 1;
+            }
+            (&mut self.0[..], self.1 < 10)
+        }
+        fn seek(&mut self, _: Time) {
+            self.1 = 0;
+        }
+        fn channel_count(&self) -> u32 {
+            1
+        }
+        fn sample_rate(&self) -> u32 {
+            44_100
+        }
+    }
+    let mut stream = Counter(vec![0; 3], 0).with_chunk_size(4);
+    assert_eq!(stream.get_data(), (&mut [0, 1, 2, 3][..], true));
+    assert_eq!(stream.get_data(), (&mut [4, 5, 6, 7][..], true));
+    assert_eq!(stream.get_data(), (&mut [8, 9, 10, 11][..], false));
+    stream.seek(Time::ZERO);
+    assert_eq!(stream.get_data(), (&mut [0, 1, 2, 3][..], true));
+}
@@ -0,0 +1,137 @@
+use crate::audio::{SoundBuffer, SoundStream};
+use crate::system::{SfBox, Time};
+use rodio::buffer::SamplesBuffer;
+use rodio::Source;
+
+impl SoundBuffer {
+    /// Copy the samples of the sound buffer into a rodio buffer
+    ///
+    /// This requires the `rodio` feature. The conversion is also available as a `From` impl.
+    pub fn to_rodio(&self) -> SamplesBuffer<i16> {
+        SamplesBuffer::new(
+            self.channel_count() as u16,
+            self.sample_rate(),
+            self.samples(),
+        )
+    }
+    /// Create a new sound buffer from all the samples of a rodio source
+    ///
+    /// This requires the `rodio` feature.
+    ///
+    /// The channel count and sample rate are read at the start of the source, which must
+    /// end, and keep the same format all along. Sources of other sample types can be
+    /// converted with `convert_samples::<i16>()`.
+    ///
+    /// Returns `None` on failure.
+    pub fn from_rodio<S: Source<Item = i16>>(source: S) -> Option<SfBox<Self>> {
+        let channel_count = u32::from(source.channels());
+        let sample_rate = source.sample_rate();
+        let samples: Vec<i16> = source.collect();
+        Self::from_samples(&samples, channel_count, sample_rate)
+    }
+}
+
+impl<'a> From<&'a SoundBuffer> for SamplesBuffer<i16> {
+    fn from(buffer: &'a SoundBuffer) -> Self {
+        buffer.to_rodio()
+    }
+}
+
+/// A rodio source played as an SFML sound stream.
+///
+/// This requires the `rodio` feature.
+///
+/// rodio sources are iterators over samples, which the stream pulls in chunks of 100 ms.
+/// Seeking restarts a copy of the source made at creation, and skips samples up to the
+/// requested position; this is why the source must be `Clone`. Most sources can be made
+/// clonable with `buffered()`.
+///
+/// The channel count and sample rate are read at creation, the source must keep the same
+/// format all along. Sources of other sample types can be converted with
+/// `convert_samples::<i16>()`.
+///
+/// # Example
+///
+/// ```no_run
+/// use sfml::audio::{RodioStream, SoundBuffer, SoundStreamPlayer};
+///
+/// let buffer = SoundBuffer::from_file("loop.wav").unwrap();
+/// let mut stream = RodioStream::new(buffer.to_rodio());
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+/// ```
+#[derive(Debug)]
+pub struct RodioStream<S> {
+    start: S,
+    source: S,
+    channel_count: u32,
+    sample_rate: u32,
+    buffer: Vec<i16>,
+}
+
+impl<S: Source<Item = i16> + Clone> RodioStream<S> {
+    /// Play `source` as a sound stream.
+    pub fn new(source: S) -> Self {
+        RodioStream {
+            channel_count: u32::from(source.channels()),
+            sample_rate: source.sample_rate(),
+            start: source.clone(),
+            source,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl<S> RodioStream<S> {
+    /// Access the source, at its current position.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+    /// Unwrap the source, at its current position.
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source<Item = i16> + Clone> SoundStream for RodioStream<S> {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        let chunk_len = (self.sample_rate as usize / 10 * self.channel_count as usize).max(1);
+        self.buffer.clear();
+        self.buffer.extend(self.source.by_ref().take(chunk_len));
+        let keep_playing = self.buffer.len() == chunk_len;
+        (&mut self.buffer[..], keep_playing)
+    }
+    fn seek(&mut self, offset: Time) {
+        self.source = self.start.clone();
+        let frames =
+            offset.as_microseconds().max(0) as u64 * u64::from(self.sample_rate) / 1_000_000;
+        let skip = frames * u64::from(self.channel_count);
+        if skip > 0 {
+            let _ = self.source.nth(skip as usize - 1);
+        }
+    }
+    fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+#[test]
+fn test_stream() {
+    let samples: Vec<i16> = (0..25).collect();
+    let mut stream = RodioStream::new(SamplesBuffer::new(1, 100, samples));
+    assert_eq!(
+        stream.get_data(),
+        (&mut (0..10).collect::<Vec<_>>()[..], true)
+    );
+    assert_eq!(
+        stream.get_data(),
+        (&mut (10..20).collect::<Vec<_>>()[..], true)
+    );
+    assert_eq!(
+        stream.get_data(),
+        (&mut (20..25).collect::<Vec<_>>()[..], false)
+    );
+}
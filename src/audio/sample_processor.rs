@@ -0,0 +1,121 @@
+use crate::audio::SoundStream;
+use crate::system::Time;
+
+/// Processing applied to audio samples, one chunk at a time.
+///
+/// Processors are attached to sound streams with [`SoundStream::with_processor`], or to
+/// musics with [`Music::processed`], and see every chunk of samples right before it is
+/// handed over to SFML for playback. This is the
+/// place to implement effects such as filters, echo or dynamic range compression.
+///
+/// The chunks contain interleaved 16-bit samples, and are processed in place.
+///
+/// Any closure taking the samples, the channel count and the sample rate is a processor.
+///
+/// Note that processing runs on the streaming thread of SFML, so it had better be fast.
+///
+/// [`Music::processed`]: crate::audio::Music::processed
+pub trait SampleProcessor {
+    /// Process a chunk of samples in place.
+    fn process(&mut self, samples: &mut [i16], channel_count: u32, sample_rate: u32);
+    /// Reset the internal state of the processor.
+    ///
+    /// This is called when the stream seeks, so that effects with memory (an echo for
+    /// example) don't carry over samples from another position in the stream.
+    /// The default implementation does nothing.
+    fn reset(&mut self) {}
+}
+
+impl<F: FnMut(&mut [i16], u32, u32)> SampleProcessor for F {
+    fn process(&mut self, samples: &mut [i16], channel_count: u32, sample_rate: u32) {
+        self(samples, channel_count, sample_rate)
+    }
+}
+
+/// A sound stream whose samples go through a [`SampleProcessor`].
+///
+/// See [`SoundStream::with_processor`].
+///
+/// The samples of a [`Music`](crate::audio::Music) are decoded and played entirely inside
+/// SFML, out of reach of Rust code: to process a music, open it as a
+/// [`ProcessedMusic`](crate::audio::ProcessedMusic) instead.
+///
+/// # Example
+///
+/// ```no_run
+/// # use sfml::audio::{SoundStream, SoundStreamPlayer};
+/// # use sfml::system::Time;
+/// # struct Noise(Vec<i16>);
+/// # impl SoundStream for Noise {
+/// #     fn get_data(&mut self) -> (&mut [i16], bool) { (&mut self.0[..], true) }
+/// #     fn seek(&mut self, _: Time) {}
+/// #     fn channel_count(&self) -> u32 { 1 }
+/// #     fn sample_rate(&self) -> u32 { 44_100 }
+/// # }
+/// # let noise = Noise(vec![0; 4096]);
+/// // A crude low-pass filter, for a mono stream
+/// let mut previous = 0.;
+/// let mut stream = noise.with_processor(move |samples: &mut [i16], _: u32, _: u32| {
+///     for sample in samples {
+///         previous += (f32::from(*sample) - previous) * 0.1;
+///         *sample = previous as i16;
+///     }
+/// });
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+/// ```
+#[derive(Debug)]
+pub struct Processed<S, P> {
+    stream: S,
+    processor: P,
+}
+
+impl<S, P> Processed<S, P> {
+    /// Pass the samples of `stream` through `processor`.
+    pub fn new(stream: S, processor: P) -> Self {
+        Processed { stream, processor }
+    }
+    /// Access the underlying stream.
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+    /// Mutably access the underlying stream.
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+    /// Access the processor.
+    pub fn processor(&self) -> &P {
+        &self.processor
+    }
+    /// Mutably access the processor.
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Split into the underlying stream and the processor.
+    pub fn into_inner(self) -> (S, P) {
+        (self.stream, self.processor)
+    }
+}
+
+impl<S: SoundStream, P: SampleProcessor> SoundStream for Processed<S, P> {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        let channel_count = self.stream.channel_count();
+        let sample_rate = self.stream.sample_rate();
+        let (samples, keep_playing) = self.stream.get_data();
+        self.processor.process(samples, channel_count, sample_rate);
+        (samples, keep_playing)
+    }
+    fn seek(&mut self, offset: Time) {
+        self.processor.reset();
+        self.stream.seek(offset)
+    }
+    fn channel_count(&self) -> u32 {
+        self.stream.channel_count()
+    }
+    fn sample_rate(&self) -> u32 {
+        self.stream.sample_rate()
+    }
+    fn duration(&self) -> Option<Time> {
+        self.stream.duration()
+    }
+}
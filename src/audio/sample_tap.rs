@@ -0,0 +1,157 @@
+use crate::audio::SampleProcessor;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Debug)]
+struct Ring {
+    samples: VecDeque<i16>,
+    capacity: usize,
+    channel_count: u32,
+    sample_rate: u32,
+}
+
+/// Keeps the most recently streamed samples around, for visualizers.
+///
+/// A tap is a [`SampleProcessor`] that leaves the samples untouched, but copies them into a
+/// ring buffer that can be read from other threads. Cloning a tap gives another handle to the
+/// same buffer: attach one clone to a stream with [`SoundStream::with_processor`], and keep
+/// the other one to draw waveforms or spectrums (see the `dsp` feature) from the main thread.
+///
+/// The samples are those most recently handed over to SFML, which buffers a few chunks ahead
+/// of what is actually heard. Keep the chunks of the stream small for the display to be in
+/// sync with the sound.
+///
+/// The samples of a [`Music`] never leave SFML: to tap a music, open it as a
+/// [`ProcessedMusic`], see [`Music::processed`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::audio::{SoundStream, SoundStreamPlayer};
+/// # use sfml::system::Time;
+/// # struct Noise(Vec<i16>);
+/// # impl SoundStream for Noise {
+/// #     fn get_data(&mut self) -> (&mut [i16], bool) { (&mut self.0[..], true) }
+/// #     fn seek(&mut self, _: Time) {}
+/// #     fn channel_count(&self) -> u32 { 1 }
+/// #     fn sample_rate(&self) -> u32 { 44_100 }
+/// # }
+/// # let noise = Noise(vec![0; 4096]);
+/// use sfml::audio::SampleTap;
+///
+/// let tap = SampleTap::new(1024);
+/// let mut stream = noise.with_processor(tap.clone());
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+///
+/// let mut waveform = [0; 1024];
+/// loop {
+///     let len = tap.copy_latest(&mut waveform);
+///     // Draw `waveform[..len]`...
+/// }
+/// ```
+///
+/// [`SoundStream::with_processor`]: crate::audio::SoundStream::with_processor
+/// [`Music`]: crate::audio::Music
+/// [`ProcessedMusic`]: crate::audio::ProcessedMusic
+/// [`Music::processed`]: crate::audio::Music::processed
+#[derive(Debug, Clone)]
+pub struct SampleTap {
+    ring: Arc<Mutex<Ring>>,
+}
+
+impl SampleTap {
+    /// Create a new tap, keeping at most `capacity` samples.
+    ///
+    /// The samples are interleaved, so for a stereo stream `capacity` should be twice
+    /// the number of frames needed.
+    pub fn new(capacity: usize) -> Self {
+        SampleTap {
+            ring: Arc::new(Mutex::new(Ring {
+                samples: VecDeque::with_capacity(capacity),
+                capacity,
+                channel_count: 0,
+                sample_rate: 0,
+            })),
+        }
+    }
+    /// Get the maximum number of samples kept by the tap.
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+    /// Get the number of samples currently available.
+    pub fn len(&self) -> usize {
+        self.lock().samples.len()
+    }
+    /// Tell whether no samples went through the tap yet (or since it was cleared).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get the number of channels of the tapped stream, or 0 if nothing was streamed yet.
+    pub fn channel_count(&self) -> u32 {
+        self.lock().channel_count
+    }
+    /// Get the sample rate of the tapped stream, or 0 if nothing was streamed yet.
+    pub fn sample_rate(&self) -> u32 {
+        self.lock().sample_rate
+    }
+    /// Copy the most recent samples into `out`, oldest first.
+    ///
+    /// Returns the number of samples copied, which is less than the length of `out`
+    /// if not enough samples are available.
+    pub fn copy_latest(&self, out: &mut [i16]) -> usize {
+        let ring = self.lock();
+        let len = out.len().min(ring.samples.len());
+        let skip = ring.samples.len() - len;
+        for (dst, src) in out.iter_mut().zip(ring.samples.iter().skip(skip)) {
+            *dst = *src;
+        }
+        len
+    }
+    /// Return a copy of all the samples currently available, oldest first.
+    pub fn latest(&self) -> Vec<i16> {
+        self.lock().samples.iter().cloned().collect()
+    }
+    /// Discard the samples currently available.
+    pub fn clear(&self) {
+        self.lock().samples.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Ring> {
+        // A panic while the lock is held can't leave the ring in an inconsistent state
+        match self.ring.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl SampleProcessor for SampleTap {
+    fn process(&mut self, samples: &mut [i16], channel_count: u32, sample_rate: u32) {
+        let mut ring = self.lock();
+        ring.channel_count = channel_count;
+        ring.sample_rate = sample_rate;
+        let samples = &samples[samples.len().saturating_sub(ring.capacity)..];
+        let overflow = (ring.samples.len() + samples.len()).saturating_sub(ring.capacity);
+        drop(ring.samples.drain(..overflow));
+        ring.samples.extend(samples);
+    }
+    fn reset(&mut self) {
+        self.clear();
+    }
+}
+
+#[test]
+fn test_ring() {
+    let tap = SampleTap::new(4);
+    let mut processor = tap.clone();
+    processor.process(&mut [1, 2, 3], 1, 44_100);
+    assert_eq!(tap.latest(), [1, 2, 3]);
+    processor.process(&mut [4, 5], 1, 44_100);
+    assert_eq!(tap.latest(), [2, 3, 4, 5]);
+    processor.process(&mut [6, 7, 8, 9, 10], 1, 44_100);
+    assert_eq!(tap.latest(), [7, 8, 9, 10]);
+    let mut out = [0; 2];
+    assert_eq!(tap.copy_latest(&mut out), 2);
+    assert_eq!(out, [9, 10]);
+}
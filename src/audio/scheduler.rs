@@ -0,0 +1,325 @@
+use crate::audio::asset_loader::Task;
+use crate::audio::SoundSource;
+use crate::system::Time;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long before an action the scheduler stops sleeping, and waits actively.
+///
+/// Sleeps can overshoot by a millisecond or more, depending on the system.
+const SPIN: Duration = Duration::from_millis(2);
+
+/// Identifier of an action planned with a [`Scheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScheduleId(u64);
+
+struct Entry {
+    at: Instant,
+    id: u64,
+    action: Box<dyn Task>,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    // Reversed, so that the heap gives the earliest entry first
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.at, other.id).cmp(&(self.at, self.id))
+    }
+}
+
+enum Message {
+    Add(Entry),
+    Cancel(u64),
+}
+
+/// Starts sound sources at precise moments, from a thread of its own.
+///
+/// Calling [`play`] from the game loop starts a source whenever the next frame comes,
+/// which is up to 16 ms late at 60 frames per second, and inconsistently so. A scheduler
+/// waits for the planned moment on a dedicated thread instead, sleeping most of the time
+/// and then waiting actively for the last couple of milliseconds, so that actions happen
+/// within a fraction of a millisecond of their time.
+///
+/// Times are measured from the creation of the scheduler, see [`now`].
+///
+/// Sounds and musics are planned with [`Sound::play_at`] and [`Music::play_at`], and stay
+/// on their own thread: only their start happens on the scheduler thread. Other
+/// sources are shared with the scheduler thread through an `Arc<Mutex<_>>` with
+/// [`play_at`], so they must be `Send`. Any action can be planned with [`schedule`] too.
+///
+/// Dropping the scheduler cancels the actions not done yet.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Music, Scheduler, Sound, SoundBuffer};
+/// use sfml::system::Time;
+///
+/// let mut scheduler = Scheduler::new();
+/// let mut drums = Music::from_file("drums.ogg").unwrap();
+/// let mut bass = Music::from_file("bass.ogg").unwrap();
+/// let buffer = SoundBuffer::from_file("tick.wav").unwrap();
+/// let mut tick = Sound::with_buffer(&buffer);
+/// // Start both tracks together, on the next bar, with a tick
+/// let bar = scheduler.now() + Time::milliseconds(500);
+/// drums.play_at(&mut scheduler, bar);
+/// bass.play_at(&mut scheduler, bar);
+/// tick.play_at(&mut scheduler, bar);
+/// ```
+///
+/// [`play`]: SoundSource::play
+/// [`now`]: Scheduler::now
+/// [`Sound::play_at`]: crate::audio::Sound::play_at
+/// [`Music::play_at`]: crate::audio::Music::play_at
+/// [`play_at`]: Scheduler::play_at
+/// [`schedule`]: Scheduler::schedule
+#[derive(Debug)]
+pub struct Scheduler {
+    start: Instant,
+    next_id: u64,
+    messages: Option<Sender<Message>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Scheduler {
+    /// Create a new scheduler, which starts its thread.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("sfml-scheduler".into())
+            .spawn(move || {
+                let mut entries = BinaryHeap::<Entry>::new();
+                let mut cancelled = HashSet::new();
+                loop {
+                    let now = Instant::now();
+                    while entries.peek().map_or(false, |entry| entry.at <= now) {
+                        let entry = entries.pop().expect("the heap has an entry");
+                        if !cancelled.remove(&entry.id) {
+                            let action = entry.action;
+                            drop(panic::catch_unwind(AssertUnwindSafe(|| action.run())));
+                        }
+                    }
+                    let message = match entries.peek() {
+                        None => match receiver.recv() {
+                            Ok(message) => message,
+                            Err(_) => return,
+                        },
+                        Some(next) if next.at - now > SPIN => {
+                            match receiver.recv_timeout(next.at - now - SPIN) {
+                                Ok(message) => message,
+                                Err(RecvTimeoutError::Timeout) => continue,
+                                Err(RecvTimeoutError::Disconnected) => return,
+                            }
+                        }
+                        Some(_) => {
+                            thread::yield_now();
+                            match receiver.try_recv() {
+                                Ok(message) => message,
+                                Err(TryRecvError::Empty) => continue,
+                                Err(TryRecvError::Disconnected) => return,
+                            }
+                        }
+                    };
+                    match message {
+                        Message::Add(entry) => entries.push(entry),
+                        Message::Cancel(id) => {
+                            if entries.iter().any(|entry| entry.id == id) {
+                                let _ = cancelled.insert(id);
+                            }
+                        }
+                    }
+                }
+            })
+            .expect("Failed to spawn the scheduler thread");
+        Scheduler {
+            start: Instant::now(),
+            next_id: 0,
+            messages: Some(sender),
+            thread: Some(thread),
+        }
+    }
+    /// Get the current time of the scheduler, measured from its creation.
+    pub fn now(&self) -> Time {
+        let elapsed = self.start.elapsed();
+        Time::microseconds(
+            elapsed.as_secs() as i64 * 1_000_000 + i64::from(elapsed.subsec_micros()),
+        )
+    }
+    /// Start playing `source` at the time `at`.
+    ///
+    /// A time in the past plays the source right away.
+    pub fn play_at<S>(&mut self, at: Time, source: &Arc<Mutex<S>>) -> ScheduleId
+    where
+        S: SoundSource + Send + 'static,
+    {
+        let source = Arc::clone(source);
+        self.schedule(at, move || match source.lock() {
+            Ok(mut source) => source.play(),
+            Err(poisoned) => poisoned.into_inner().play(),
+        })
+    }
+    /// Run `action` on the scheduler thread at the time `at`.
+    ///
+    /// The action should be quick, the next ones wait for it to be done.
+    pub fn schedule<F: FnOnce() + Send + 'static>(&mut self, at: Time, action: F) -> ScheduleId {
+        self.schedule_instant(self.instant(at), Box::new(action))
+    }
+    /// Cancel an action, if it isn't done yet.
+    pub fn cancel(&mut self, id: ScheduleId) {
+        self.send(Message::Cancel(id.0));
+    }
+
+    /// Get the instant of a time of the scheduler.
+    pub(super) fn instant(&self, at: Time) -> Instant {
+        self.start + Duration::from_micros(at.as_microseconds().max(0) as u64)
+    }
+    fn schedule_instant(&mut self, at: Instant, action: Box<dyn Task>) -> ScheduleId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(Message::Add(Entry { at, id, action }));
+        ScheduleId(id)
+    }
+    fn send(&self, message: Message) {
+        if let Some(ref messages) = self.messages {
+            // The thread only stops when the scheduler is dropped
+            messages
+                .send(message)
+                .expect("The scheduler thread stopped");
+        }
+    }
+}
+
+/// The starts of an SFML source planned with a [`Scheduler`], see `Sound::play_at`.
+///
+/// Sounds can't be moved to another thread, but starting one is a single OpenAL call, which
+/// is thread safe. So the scheduler thread is given the SFML object of the source, and
+/// these plans keep it from using the object once the source no longer wants to be
+/// started, or is destroyed.
+#[derive(Debug, Default)]
+pub(super) struct Plans {
+    /// Whether the planned starts can still happen, locked while one happens.
+    armed: Option<Arc<Mutex<bool>>>,
+}
+
+/// The SFML object of a source, and the function starting it.
+struct Start<T>(*mut T, unsafe extern "C" fn(*mut T));
+
+// Only sent to the scheduler thread, which only calls the function, see `Plans`
+unsafe impl<T> Send for Start<T> {}
+
+impl Plans {
+    /// Start `source` with `play` at the time `at` of `scheduler`.
+    ///
+    /// # Safety
+    /// `source` must stay valid until the plans are cancelled.
+    pub(super) unsafe fn play_at<T: 'static>(
+        &mut self,
+        scheduler: &mut Scheduler,
+        at: Time,
+        source: *mut T,
+        play: unsafe extern "C" fn(*mut T),
+    ) -> ScheduleId {
+        let armed = Arc::clone(self.armed.get_or_insert_with(|| Arc::new(Mutex::new(true))));
+        let start = Start(source, play);
+        scheduler.schedule(at, move || {
+            if *lock(&armed) {
+                unsafe { (start.1)(start.0) }
+            }
+        })
+    }
+    /// Keep the planned starts from happening while the guard is held.
+    pub(super) fn hold(&self) -> Option<MutexGuard<'_, bool>> {
+        self.armed.as_ref().map(|armed| lock(armed))
+    }
+    /// Cancel the planned starts, waiting for one happening right now.
+    pub(super) fn cancel(&mut self) {
+        if let Some(armed) = self.armed.take() {
+            *lock(&armed) = false;
+        }
+    }
+}
+
+fn lock(armed: &Mutex<bool>) -> MutexGuard<'_, bool> {
+    match armed.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        drop(self.messages.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[test]
+fn test_order() {
+    let (sender, receiver) = mpsc::channel();
+    let mut scheduler = Scheduler::new();
+    let start = Instant::now();
+    for &(ms, tag) in &[(30, 'c'), (10, 'a'), (20, 'b'), (25, 'x')] {
+        let sender = sender.clone();
+        let at = start + Duration::from_millis(ms);
+        let id = scheduler.schedule_instant(
+            at,
+            Box::new(move || sender.send((tag, Instant::now() >= at)).unwrap()),
+        );
+        if tag == 'x' {
+            scheduler.cancel(id);
+        }
+    }
+    let fired: Vec<_> = (0..3).map(|_| receiver.recv().unwrap()).collect();
+    assert_eq!(fired, [('a', true), ('b', true), ('c', true)]);
+    drop(scheduler);
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test]
+fn test_plans() {
+    use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+    unsafe extern "C" fn start(starts: *mut AtomicUsize) {
+        let _ = (*starts).fetch_add(1, SeqCst);
+    }
+    let starts = Box::into_raw(Box::new(AtomicUsize::new(0)));
+    let mut scheduler = Scheduler::new();
+    let mut plans = Plans::default();
+    let _ = unsafe { plans.play_at(&mut scheduler, Time::ZERO, starts, start) };
+    while unsafe { (*starts).load(SeqCst) } == 0 {
+        thread::yield_now();
+    }
+    let at = scheduler.now() + Time::microseconds(20_000);
+    let _ = unsafe { plans.play_at(&mut scheduler, at, starts, start) };
+    plans.cancel();
+    thread::sleep(Duration::from_millis(40));
+    drop(scheduler);
+    let starts = unsafe { Box::from_raw(starts) };
+    assert_eq!(starts.load(SeqCst), 1);
+}
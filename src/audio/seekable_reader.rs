@@ -0,0 +1,171 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Make a reader seekable, by keeping the data read from it in memory.
+///
+/// SFML needs to seek in the audio data it streams, which rules out sources such as pipes
+/// or network sockets. A `SeekableReader` wraps such a source, and answers seek requests
+/// from the data it has already buffered, reading ahead from the source when seeking past it.
+///
+/// It comes in two flavors:
+///
+/// * [`new`] keeps all the data. The size of the stream is found by reading the source
+///   until its end, so the source has to end, but any format can be played.
+/// * [`live`] only keeps the most recent data, for sources with no end, like an internet
+///   radio. Seeking back past the kept data, or relative to the end, fails.
+///   Whether SFML copes with that depends on the format: its vorbis decoder for instance
+///   insists on finding the end of the data. The `symphonia` feature adds
+///   `DecoderStream::from_live_reader`, which is meant for such sources.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Music, SeekableReader};
+/// use std::io;
+///
+/// // Play a song piped into the program
+/// let mut music = Music::from_owned_stream(SeekableReader::new(io::stdin())).unwrap();
+/// // Which is what `Music::from_reader` does
+/// let mut music = Music::from_reader(io::stdin()).unwrap();
+/// music.play();
+/// ```
+///
+/// [`new`]: SeekableReader::new
+/// [`live`]: SeekableReader::live
+#[derive(Debug)]
+pub struct SeekableReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// Position in the stream of the first byte of `buffer`.
+    start: u64,
+    pos: u64,
+    window: Option<usize>,
+    eof: bool,
+}
+
+impl<R: Read> SeekableReader<R> {
+    /// Wrap `reader`, keeping all the data read from it.
+    pub fn new(reader: R) -> Self {
+        Self::with_window(reader, None)
+    }
+    /// Wrap `reader`, keeping at least the last `window` bytes read from it.
+    pub fn live(reader: R, window: usize) -> Self {
+        Self::with_window(reader, Some(window))
+    }
+    /// Unwrap the underlying reader, discarding the buffered data.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn with_window(reader: R, window: Option<usize>) -> Self {
+        SeekableReader {
+            reader,
+            buffer: Vec::new(),
+            start: 0,
+            pos: 0,
+            window,
+            eof: false,
+        }
+    }
+    fn end(&self) -> u64 {
+        self.start + self.buffer.len() as u64
+    }
+    /// Read from the source until `pos` is buffered, or the source ends.
+    fn fill_to(&mut self, pos: u64) -> io::Result<()> {
+        let mut chunk = [0; 4096];
+        while self.end() < pos && !self.eof {
+            match self.reader.read(&mut chunk) {
+                Ok(0) => self.eof = true,
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+            self.trim();
+        }
+        Ok(())
+    }
+    /// Drop the data that went out of the window.
+    fn trim(&mut self) {
+        if let Some(window) = self.window {
+            // Trimming once the buffer doubled keeps the cost of the copies linear
+            if self.buffer.len() > window.saturating_mul(2) {
+                // Never drop what is about to be read
+                let unread = self.pos.saturating_sub(self.start) as usize;
+                let excess = (self.buffer.len() - window).min(unread);
+                drop(self.buffer.drain(..excess));
+                self.start += excess as u64;
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for SeekableReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill_to(self.pos + buf.len() as u64)?;
+        if self.pos < self.start {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the data at this position was dropped",
+            ));
+        }
+        let offset = (self.pos - self.start) as usize;
+        let available = self.buffer.get(offset..).unwrap_or(&[]);
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+impl<R: Read> Seek for SeekableReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => offset_by(self.pos, offset),
+            SeekFrom::End(offset) => {
+                if self.window.is_some() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "a live stream has no end",
+                    ));
+                }
+                self.fill_to(u64::max_value())?;
+                offset_by(self.end(), offset)
+            }
+        };
+        match target {
+            Some(target) if target >= self.start => {
+                self.pos = target;
+                Ok(target)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a dropped or negative position",
+            )),
+        }
+    }
+}
+
+fn offset_by(pos: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        pos.checked_add(offset as u64)
+    } else {
+        // `wrapping_abs` keeps `i64::min_value()`, which is right once unsigned
+        pos.checked_sub(offset.wrapping_abs() as u64)
+    }
+}
+
+#[test]
+fn test_seek() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = SeekableReader::new(&data[..]);
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [0, 1, 2, 3]);
+    assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 254);
+    reader.read_exact(&mut buf[..2]).unwrap();
+    assert_eq!(buf[..2], [254, 255]);
+    assert_eq!(reader.seek(SeekFrom::Start(1)).unwrap(), 1);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [1, 2, 3, 4]);
+    assert!(reader.seek(SeekFrom::Current(-10)).is_err());
+}
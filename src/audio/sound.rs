@@ -1,11 +1,16 @@
 use crate::audio::csfml_audio_sys as ffi;
-use crate::audio::{SoundBuffer, SoundSource, SoundStatus};
+use crate::audio::scheduler::Plans;
+use crate::audio::{
+    is_null_backend, NullSource, ScheduleId, Scheduler, SoundBuffer, SoundSource, SoundStatus,
+};
 use crate::sf_bool_ext::SfBoolExt;
-use crate::system::Time;
 use crate::system::Vector3f;
+use crate::system::{SfBox, Time};
 use csfml_system_sys::sfBool;
 use std::marker::PhantomData;
 use std::mem;
+use std::ptr;
+use std::sync::Arc;
 
 /// Regular sound that can be played in the audio environment.
 ///
@@ -24,9 +29,27 @@ use std::mem;
 /// In order to work, a sound must be given a buffer of audio data to play.
 /// Audio data (samples) is stored in [`SoundBuffer`], and attached to a sound with the
 /// [`set_buffer`] function. The buffer object attached to a sound must remain alive as long as
-/// the sound uses it. Note that multiple sounds can use the same sound buffer at the same time.
+/// the sound uses it, which the `'s` lifetime parameter enforces. Alternatively, a sound can
+/// share the ownership of its buffer, with [`set_shared_buffer`]; the buffer then lives at least
+/// as long as the sound, whatever the scope it was loaded in.
+/// Note that multiple sounds can use the same sound buffer at the same time.
 ///
 /// [`set_buffer`]: Sound::set_buffer
+/// [`set_shared_buffer`]: Sound::set_shared_buffer
+///
+/// # Thread safety
+///
+/// Sounds can't be moved to another thread: SFML updates the buffer of a sound whenever the
+/// sound is attached or detached, without synchronization, so a sound must stay on the
+/// thread of the other sounds using its buffer. To play sounds from a thread dedicated
+/// to audio, move the buffers to that thread (see [`SoundBuffer`]), and create the sounds
+/// there. To start a sound at a precise moment, [`play_at`] starts it from the thread of a
+/// [`Scheduler`] without moving it.
+///
+/// # Systems without audio
+///
+/// Sounds created while [`set_null_backend`] is on are null ones: they play nothing and
+/// don't call SFML, but behave like a [`NullSource`] lasting as long as their buffer.
 ///
 /// # Usage example
 ///
@@ -39,20 +62,43 @@ use std::mem;
 /// ```
 ///
 /// [`Music`]: crate::audio::Music
+/// [`play_at`]: Sound::play_at
+/// [`Scheduler`]: crate::audio::Scheduler
+/// [`set_null_backend`]: crate::audio::set_null_backend
+/// [`NullSource`]: crate::audio::NullSource
 #[derive(Debug)]
 pub struct Sound<'s> {
-    sound: *mut ffi::sfSound,
+    backend: Backend,
     buffer: PhantomData<&'s SoundBuffer>,
+    /// Keeps the buffer set with `set_shared_buffer` alive while the sound uses it.
+    shared_buffer: Option<Arc<SfBox<SoundBuffer>>>,
+    /// The starts planned with `play_at`.
+    plans: Plans,
+}
+
+/// What plays a sound, see `set_null_backend`.
+#[derive(Debug, Clone, Copy)]
+enum Backend {
+    Sfml(*mut ffi::sfSound),
+    /// A null sound, with the buffer it was given.
+    Null(NullSource, *const SoundBuffer),
 }
 
 impl<'s> Sound<'s> {
     /// Create a new `Sound`
     pub fn new() -> Sound<'s> {
-        let s = unsafe { ffi::sfSound_create() };
-        assert!(!s.is_null(), "Failed to create Sound");
+        let backend = if is_null_backend() {
+            Backend::Null(NullSource::new(Time::ZERO), ptr::null())
+        } else {
+            let s = unsafe { ffi::sfSound_create() };
+            assert!(!s.is_null(), "Failed to create Sound");
+            Backend::Sfml(s)
+        };
         Sound {
-            sound: s,
+            backend,
             buffer: PhantomData,
+            shared_buffer: None,
+            plans: Plans::default(),
         }
     }
 
@@ -63,16 +109,33 @@ impl<'s> Sound<'s> {
         s
     }
 
+    /// Create a new `Sound` sharing the ownership of a buffer
+    ///
+    /// See [`set_shared_buffer`](Sound::set_shared_buffer).
+    pub fn with_shared_buffer(buffer: Arc<SfBox<SoundBuffer>>) -> Sound<'s> {
+        let mut s = Sound::new();
+        s.set_shared_buffer(buffer);
+        s
+    }
+
     /// Sets whether this sound should loop or not.
     pub fn set_looping(&mut self, looping: bool) {
-        unsafe { ffi::sfSound_setLoop(self.sound, sfBool::from_bool(looping)) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe {
+                ffi::sfSound_setLoop(sound, sfBool::from_bool(looping))
+            },
+            Backend::Null(ref mut source, _) => source.set_looping(looping),
+        }
     }
 
     /// Tell whether or not a sound is in loop mode
     ///
     /// Return true if the sound is looping, false otherwise
     pub fn is_looping(&self) -> bool {
-        unsafe { ffi::sfSound_getLoop(self.sound) }.to_bool()
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_getLoop(sound) }.to_bool(),
+            Backend::Null(ref source, _) => source.is_looping(),
+        }
     }
 
     /// Start or resume playing a sound
@@ -83,7 +146,11 @@ impl<'s> Sound<'s> {
     /// This function uses its own thread so that it doesn't block
     /// the rest of the program while the sound is played.
     pub fn play(&mut self) {
-        unsafe { ffi::sfSound_play(self.sound) }
+        self.plans.cancel();
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_play(sound) },
+            Backend::Null(ref mut source, _) => source.play(),
+        }
     }
 
     /// Pause a sound
@@ -91,7 +158,11 @@ impl<'s> Sound<'s> {
     /// This function pauses the sound if it was playing,
     /// otherwise (sound already paused or stopped) it has no effect.
     pub fn pause(&mut self) {
-        unsafe { ffi::sfSound_pause(self.sound) }
+        self.plans.cancel();
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_pause(sound) },
+            Backend::Null(ref mut source, _) => source.pause(),
+        }
     }
 
     /// Stop playing a sound
@@ -100,21 +171,80 @@ impl<'s> Sound<'s> {
     /// and does nothing if it was already stopped.
     /// It also resets the playing position (unlike pause).
     pub fn stop(&mut self) {
-        unsafe { ffi::sfSound_stop(self.sound) }
+        self.plans.cancel();
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_stop(sound) },
+            Backend::Null(ref mut source, _) => source.stop(),
+        }
+    }
+
+    /// Start or resume playing a sound at the time `at` of `scheduler`
+    ///
+    /// The sound is started from the scheduler thread, within a fraction of a millisecond
+    /// of `at`, as [`play`] would start it then. A time in the past plays the sound right
+    /// away. The sound stays on its own thread, only its start happens on the scheduler
+    /// thread.
+    ///
+    /// Several starts can be planned. Calling [`play`], [`pause`] or [`stop`], setting the
+    /// buffer of the sound, or dropping it cancels them all.
+    ///
+    /// Return the planned start, to cancel it alone with [`Scheduler::cancel`]. Null sounds
+    /// (see [`set_null_backend`]) follow the clock of the scheduler without using its
+    /// thread, keep only the last start planned, and return `None`.
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// use sfml::audio::{Scheduler, Sound, SoundBuffer};
+    /// use sfml::system::Time;
+    ///
+    /// let buffer = SoundBuffer::from_file("tick.wav").unwrap();
+    /// let mut tick = Sound::with_buffer(&buffer);
+    /// let mut scheduler = Scheduler::new();
+    /// // A metronome at 120 beats per minute
+    /// let start = scheduler.now();
+    /// for beat in 0..8 {
+    ///     tick.play_at(&mut scheduler, start + Time::milliseconds(500 * beat));
+    /// }
+    /// ```
+    ///
+    /// [`play`]: Sound::play
+    /// [`pause`]: Sound::pause
+    /// [`stop`]: Sound::stop
+    /// [`set_null_backend`]: crate::audio::set_null_backend
+    pub fn play_at(&mut self, scheduler: &mut Scheduler, at: Time) -> Option<ScheduleId> {
+        match self.backend {
+            // The sound is destroyed only after the plans are cancelled
+            Backend::Sfml(sound) => {
+                Some(unsafe { self.plans.play_at(scheduler, at, sound, ffi::sfSound_play) })
+            }
+            Backend::Null(ref mut source, _) => {
+                source.play_at(scheduler.instant(at));
+                None
+            }
+        }
     }
 
     /// Get the current status of a sound (stopped, paused, playing)
     ///
     /// Return current status
     pub fn status(&self) -> SoundStatus {
-        unsafe { mem::transmute(ffi::sfSound_getStatus(self.sound)) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe {
+                mem::transmute::<ffi::sfSoundStatus, SoundStatus>(ffi::sfSound_getStatus(sound))
+            },
+            Backend::Null(ref source, _) => source.status(),
+        }
     }
 
     /// Get the current playing position of a sound
     ///
     /// Return the current playing position
     pub fn playing_offset(&self) -> Time {
-        unsafe { Time::from_raw(ffi::sfSound_getPlayingOffset(self.sound)) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { Time::from_raw(ffi::sfSound_getPlayingOffset(sound)) },
+            Backend::Null(ref source, _) => source.playing_offset(),
+        }
     }
 
     /// Change the current playing position of a sound
@@ -125,7 +255,12 @@ impl<'s> Sound<'s> {
     /// # Arguments
     /// * timeOffset - New playing position
     pub fn set_playing_offset(&mut self, time_offset: Time) {
-        unsafe { ffi::sfSound_setPlayingOffset(self.sound, time_offset.raw()) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe {
+                ffi::sfSound_setPlayingOffset(sound, time_offset.raw())
+            },
+            Backend::Null(ref mut source, _) => source.set_playing_offset(time_offset),
+        }
     }
 
     /// Set the source buffer containing the audio data to play
@@ -133,8 +268,32 @@ impl<'s> Sound<'s> {
     /// # Arguments
     /// * buffer - Sound buffer to attach to the sound
     pub fn set_buffer(&mut self, buffer: &'s SoundBuffer) {
+        self.attach(buffer);
+        self.shared_buffer = None;
+    }
+
+    /// Set the source buffer containing the audio data to play, sharing its ownership
+    ///
+    /// The sound keeps a reference to the buffer, so that it can't be destroyed
+    /// while the sound uses it.
+    ///
+    /// # Arguments
+    /// * buffer - Sound buffer to attach to the sound
+    pub fn set_shared_buffer(&mut self, buffer: Arc<SfBox<SoundBuffer>>) {
+        self.attach(&buffer);
+        self.shared_buffer = Some(buffer);
+    }
+
+    fn attach(&mut self, buffer: &SoundBuffer) {
+        self.plans.cancel();
         let ptr: *const SoundBuffer = buffer;
-        unsafe { ffi::sfSound_setBuffer(self.sound, ptr as _) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_setBuffer(sound, ptr as _) },
+            Backend::Null(ref mut source, ref mut null_buffer) => {
+                source.set_duration(buffer.duration());
+                *null_buffer = ptr;
+            }
+        }
     }
 
     /// Get the audio buffer attached to a sound
@@ -142,11 +301,14 @@ impl<'s> Sound<'s> {
     /// Return an option to Sound buffer attached to the sound or None
     pub fn buffer(&self) -> Option<&SoundBuffer> {
         unsafe {
-            let ptr = ffi::sfSound_getBuffer(self.sound);
+            let ptr = match self.backend {
+                Backend::Sfml(sound) => ffi::sfSound_getBuffer(sound) as *const SoundBuffer,
+                Backend::Null(_, buffer) => buffer,
+            };
             if ptr.is_null() {
                 None
             } else {
-                Some(&*(ptr as *const SoundBuffer))
+                Some(&*ptr)
             }
         }
     }
@@ -160,58 +322,126 @@ impl<'a> Default for Sound<'a> {
 
 impl<'s> Clone for Sound<'s> {
     fn clone(&self) -> Self {
-        let s = unsafe { ffi::sfSound_copy(self.sound) };
-        assert!(!s.is_null(), "Failed to copy Sound");
+        let backend = match self.backend {
+            Backend::Sfml(sound) => {
+                let s = unsafe { ffi::sfSound_copy(sound) };
+                assert!(!s.is_null(), "Failed to copy Sound");
+                Backend::Sfml(s)
+            }
+            null => null,
+        };
         Sound {
-            sound: s,
+            backend,
             buffer: self.buffer,
+            shared_buffer: self.shared_buffer.clone(),
+            plans: Plans::default(),
         }
     }
 }
 
 impl<'s> SoundSource for Sound<'s> {
+    fn play(&mut self) {
+        Sound::play(self)
+    }
+    fn pause(&mut self) {
+        Sound::pause(self)
+    }
+    fn stop(&mut self) {
+        Sound::stop(self)
+    }
     fn set_pitch(&mut self, pitch: f32) {
-        unsafe { ffi::sfSound_setPitch(self.sound, pitch) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_setPitch(sound, pitch) },
+            Backend::Null(ref mut source, _) => source.set_pitch(pitch),
+        }
     }
     fn set_volume(&mut self, volume: f32) {
-        unsafe { ffi::sfSound_setVolume(self.sound, volume) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_setVolume(sound, volume) },
+            Backend::Null(ref mut source, _) => source.set_volume(volume),
+        }
     }
-    fn set_position<P: Into<Vector3f>>(&mut self, position: P) {
-        unsafe { ffi::sfSound_setPosition(self.sound, position.into().raw()) }
+    fn set_position_vector(&mut self, position: Vector3f) {
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_setPosition(sound, position.raw()) },
+            Backend::Null(ref mut source, _) => source.set_position_vector(position),
+        }
     }
     fn set_relative_to_listener(&mut self, relative: bool) {
-        unsafe { ffi::sfSound_setRelativeToListener(self.sound, sfBool::from_bool(relative)) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe {
+                ffi::sfSound_setRelativeToListener(sound, sfBool::from_bool(relative))
+            },
+            Backend::Null(ref mut source, _) => source.set_relative_to_listener(relative),
+        }
     }
     fn set_min_distance(&mut self, distance: f32) {
-        unsafe { ffi::sfSound_setMinDistance(self.sound, distance) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_setMinDistance(sound, distance) },
+            Backend::Null(ref mut source, _) => source.set_min_distance(distance),
+        }
     }
     fn set_attenuation(&mut self, attenuation: f32) {
-        unsafe { ffi::sfSound_setAttenuation(self.sound, attenuation) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_setAttenuation(sound, attenuation) },
+            Backend::Null(ref mut source, _) => source.set_attenuation(attenuation),
+        }
     }
     fn pitch(&self) -> f32 {
-        unsafe { ffi::sfSound_getPitch(self.sound) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_getPitch(sound) },
+            Backend::Null(ref source, _) => source.pitch(),
+        }
     }
     fn volume(&self) -> f32 {
-        unsafe { ffi::sfSound_getVolume(self.sound) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_getVolume(sound) },
+            Backend::Null(ref source, _) => source.volume(),
+        }
     }
     fn position(&self) -> Vector3f {
-        unsafe { Vector3f::from_raw(ffi::sfSound_getPosition(self.sound)) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { Vector3f::from_raw(ffi::sfSound_getPosition(sound)) },
+            Backend::Null(ref source, _) => source.position(),
+        }
     }
     fn is_relative_to_listener(&self) -> bool {
-        unsafe { ffi::sfSound_isRelativeToListener(self.sound).to_bool() }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_isRelativeToListener(sound).to_bool() },
+            Backend::Null(ref source, _) => source.is_relative_to_listener(),
+        }
     }
     fn min_distance(&self) -> f32 {
-        unsafe { ffi::sfSound_getMinDistance(self.sound) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_getMinDistance(sound) },
+            Backend::Null(ref source, _) => source.min_distance(),
+        }
     }
     fn attenuation(&self) -> f32 {
-        unsafe { ffi::sfSound_getAttenuation(self.sound) }
+        match self.backend {
+            Backend::Sfml(sound) => unsafe { ffi::sfSound_getAttenuation(sound) },
+            Backend::Null(ref source, _) => source.attenuation(),
+        }
+    }
+    fn status(&self) -> SoundStatus {
+        Sound::status(self)
+    }
+    fn playing_offset(&self) -> Time {
+        Sound::playing_offset(self)
+    }
+    fn is_looping(&self) -> bool {
+        Sound::is_looping(self)
+    }
+    fn set_looping(&mut self, looping: bool) {
+        Sound::set_looping(self, looping)
     }
 }
 
 impl<'s> Drop for Sound<'s> {
     fn drop(&mut self) {
-        unsafe {
-            ffi::sfSound_destroy(self.sound);
+        self.plans.cancel();
+        if let Backend::Sfml(sound) = self.backend {
+            unsafe { ffi::sfSound_destroy(sound) }
         }
     }
 }
@@ -1,11 +1,19 @@
 use crate::audio::csfml_audio_sys as ffi;
+use crate::audio::{asset_loader, wav, LoadHandle, SoundFileFormat};
 use crate::inputstream::InputStream;
+use crate::path_conv::path_to_cstring;
 use crate::sf_bool_ext::SfBoolExt;
 use crate::system::{Dispose, SfBox, Time};
+use crate::temp_dir::TempDir;
 use std::borrow::ToOwned;
-use std::ffi::CString;
-use std::io::{Read, Seek};
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{self, Read, Seek, Write};
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
 use std::slice;
+use std::thread;
 
 /// Storage for audio samples defining a sound.
 ///
@@ -58,10 +66,24 @@ use std::slice;
 /// sound_2.play();
 /// ```
 ///
+/// # Thread safety
+///
+/// An owned buffer (`SfBox<SoundBuffer>`) can be moved to another thread, to play it from
+/// a thread dedicated to audio for example. It can't be shared between threads though:
+/// SFML keeps track of the sounds using a buffer without any synchronization, so sounds
+/// using the same buffer must all live on the same thread.
+///
 /// [`Sound`]: crate::audio::Sound
 #[derive(Debug)]
 #[allow(missing_copy_implementations)]
-pub enum SoundBuffer {}
+pub struct SoundBuffer {
+    _opaque: [u8; 0],
+    /// Attaching a sound to a buffer modifies it, even through a shared reference.
+    _not_sync: PhantomData<Cell<u8>>,
+}
+
+// A buffer that can be moved has no sound attached to it.
+unsafe impl Send for SfBox<SoundBuffer> {}
 
 impl SoundBuffer {
     /// Save a sound buffer to an audio file
@@ -74,11 +96,73 @@ impl SoundBuffer {
     /// * filename - Path of the sound file to write
     ///
     /// Return true if saving succeeded, false if it faileds
-    pub fn save_to_file(&self, filename: &str) -> bool {
-        let c_str = CString::new(filename.as_bytes()).unwrap();
+    pub fn save_to_file<P: AsRef<Path>>(&self, filename: P) -> bool {
+        let c_str = match path_to_cstring(filename.as_ref()) {
+            Some(c_str) => c_str,
+            None => return false,
+        };
         unsafe { ffi::sfSoundBuffer_saveToFile(self.raw(), c_str.as_ptr()) }.to_bool()
     }
 
+    /// Save a sound buffer to an audio file in memory
+    ///
+    /// See [`save_to_writer`](SoundBuffer::save_to_writer).
+    ///
+    /// # Arguments
+    /// * format - Format of the audio file
+    ///
+    /// Return the file data, or the error that prevented saving it
+    pub fn save_to_memory(&self, format: SoundFileFormat) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.save_to_writer(&mut data, format)?;
+        Ok(data)
+    }
+
+    /// Save a sound buffer as an audio file, to a writer
+    ///
+    /// WAV data is encoded directly. SFML is only able to write the other formats to files,
+    /// so they are encoded to a file of a new private temporary directory, which is removed
+    /// afterwards.
+    ///
+    /// # Arguments
+    /// * writer - Where to write the file data
+    /// * format - Format of the audio file
+    ///
+    /// Return the error that prevented saving, if any
+    pub fn save_to_writer<W: Write>(
+        &self,
+        mut writer: W,
+        format: SoundFileFormat,
+    ) -> io::Result<()> {
+        if format == SoundFileFormat::Wav {
+            let samples = self.samples();
+            let data_len = samples.len() as u64 * 2;
+            if data_len > u64::from(u32::max_value() - wav::HEADER_LEN) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "sound buffer too big for a WAV file",
+                ));
+            }
+            wav::write_header(
+                &mut writer,
+                self.channel_count(),
+                self.sample_rate(),
+                data_len as u32,
+            )?;
+            return wav::write_samples(&mut writer, samples);
+        }
+        let dir = TempDir::new()?;
+        let path = dir.path().join(format!("sound.{}", format.extension()));
+        if !self.save_to_file(&path) {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "failed to encode the sound buffer",
+            ));
+        }
+        let result = File::open(&path).and_then(|mut file| io::copy(&mut file, &mut writer));
+        result.map(|_| ())
+    }
+
     /// Get the number of samples stored in a sound buffer
     ///
     /// The array of samples can be accessed with [`samples`](SoundBuffer::samples).
@@ -90,9 +174,15 @@ impl SoundBuffer {
 
     /// Get the samples stored in the buffer
     ///
+    /// The samples are 16 bits signed integers, interleaved when the buffer has
+    /// several channels (left, right, left, right, ... for stereo).
+    ///
     /// Panic if the sample count exceeds usize range
     pub fn samples(&self) -> &[i16] {
         let len = self.sample_count();
+        if len == 0 {
+            return &[];
+        }
         // TODO: Replace with TryFrom, or a similar standard library API, once available
         #[cfg(target_pointer_width = "32")]
         {
@@ -144,12 +234,29 @@ impl SoundBuffer {
     /// * filename - Path of the sound file to load
     ///
     /// Returns `None` on failure.
-    pub fn from_file(filename: &str) -> Option<SfBox<Self>> {
-        let c_str = CString::new(filename.as_bytes()).unwrap();
+    pub fn from_file<P: AsRef<Path>>(filename: P) -> Option<SfBox<Self>> {
+        let c_str = path_to_cstring(filename.as_ref())?;
         let sound_buffer: *mut ffi::sfSoundBuffer =
             unsafe { ffi::sfSoundBuffer_createFromFile(c_str.as_ptr()) };
         SfBox::new(sound_buffer as *mut Self)
     }
+    /// Create a new sound buffer and load it from a file, on a thread of its own
+    ///
+    /// Loading a big file can take a while, this keeps the calling thread going meanwhile.
+    /// To load many files, an [`AssetLoader`] reuses a few threads instead.
+    ///
+    /// # Arguments
+    /// * filename - Path of the sound file to load
+    ///
+    /// Return a handle to poll until the buffer is loaded
+    ///
+    /// [`AssetLoader`]: crate::audio::AssetLoader
+    pub fn load_async<P: AsRef<Path>>(filename: P) -> LoadHandle<SfBox<Self>> {
+        let filename = filename.as_ref().to_path_buf();
+        let (handle, sender) = LoadHandle::new();
+        let _ = thread::spawn(move || sender.send(asset_loader::load_sound_buffer(&filename)));
+        handle
+    }
     /// Load the sound buffer from a file in memory.
     pub fn from_memory(data: &[u8]) -> Option<SfBox<Self>> {
         let sound_buffer =
@@ -164,7 +271,32 @@ impl SoundBuffer {
     }
     /// Load the sound buffer from a slice of audio samples.
     ///
-    /// The assumed format of the audio samples is 16 bits signed integer.
+    /// The assumed format of the audio samples is 16 bits signed integer,
+    /// interleaved when there are several channels. The samples are copied into the buffer.
+    ///
+    /// # Arguments
+    /// * samples - The audio samples
+    /// * channel_count - Number of channels (1 = mono, 2 = stereo, ...)
+    /// * sample_rate - Sample rate (number of samples to play per second)
+    ///
+    /// Returns `None` on failure.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use sfml::audio::{Sound, SoundBuffer};
+    ///
+    /// // One second of a 440 Hz sine wave
+    /// let samples: Vec<i16> = (0..44_100)
+    ///     .map(|i| {
+    ///         let t = i as f32 / 44_100.;
+    ///         ((t * 440. * 2. * std::f32::consts::PI).sin() * 10_000.) as i16
+    ///     })
+    ///     .collect();
+    /// let buffer = SoundBuffer::from_samples(&samples, 1, 44_100).unwrap();
+    /// let mut sound = Sound::with_buffer(&buffer);
+    /// sound.play();
+    /// ```
     pub fn from_samples(
         samples: &[i16],
         channel_count: u32,
@@ -180,6 +312,151 @@ impl SoundBuffer {
         };
         SfBox::new(buffer as *mut Self)
     }
+    /// Create a copy of the sound buffer, at another sample rate
+    ///
+    /// The samples are interpolated linearly, which is fine for effects and voices.
+    /// Lowering the sample rate a lot can make high frequencies fold back as noise.
+    ///
+    /// # Arguments
+    /// * sample_rate - Sample rate of the new buffer, in samples per second
+    ///
+    /// Returns `None` on failure.
+    pub fn resampled(&self, sample_rate: u32) -> Option<SfBox<Self>> {
+        let channel_count = self.channel_count();
+        let samples = resample(
+            self.samples(),
+            channel_count as usize,
+            self.sample_rate(),
+            sample_rate,
+        );
+        Self::from_samples(&samples, channel_count, sample_rate)
+    }
+    /// Create a copy of the sound buffer, with its channels mixed into one
+    ///
+    /// Only mono sounds can be spatialized, see [`SoundSource::set_position`].
+    ///
+    /// Returns `None` on failure.
+    ///
+    /// [`SoundSource::set_position`]: crate::audio::SoundSource::set_position
+    pub fn to_mono(&self) -> Option<SfBox<Self>> {
+        let samples = to_mono(self.samples(), self.channel_count() as usize);
+        Self::from_samples(&samples, 1, self.sample_rate())
+    }
+    /// Create a copy of the sound buffer, with two channels
+    ///
+    /// A mono sound is played on both channels. A sound with more than two channels
+    /// keeps its first two (front left and front right), the others are dropped.
+    ///
+    /// Returns `None` on failure.
+    pub fn to_stereo(&self) -> Option<SfBox<Self>> {
+        let samples = to_stereo(self.samples(), self.channel_count() as usize);
+        Self::from_samples(&samples, 2, self.sample_rate())
+    }
+    /// Create a new sound buffer from a part of this one
+    ///
+    /// The bounds are rounded down to the closest sample, and clamped to the duration
+    /// of the buffer.
+    ///
+    /// # Arguments
+    /// * range - Part of the buffer to copy, for example `Time::seconds(1.)..Time::seconds(2.)`
+    ///
+    /// Returns `None` on failure.
+    pub fn slice<R: RangeBounds<Time>>(&self, range: R) -> Option<SfBox<Self>> {
+        let channels = self.channel_count().max(1) as usize;
+        let frames = self.samples().len() / channels;
+        let rate = i64::from(self.sample_rate());
+        let frame = |time: &Time| {
+            let frame = time.as_microseconds().max(0) * rate / 1_000_000;
+            (frame as usize).min(frames)
+        };
+        let start = match range.start_bound() {
+            Bound::Included(time) | Bound::Excluded(time) => frame(time),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(time) | Bound::Excluded(time) => frame(time),
+            Bound::Unbounded => frames,
+        }
+        .max(start);
+        let samples = &self.samples()[start * channels..end * channels];
+        Self::from_samples(samples, self.channel_count(), self.sample_rate())
+    }
+    /// Create a new sound buffer by putting several ones end to end
+    ///
+    /// The buffers must all have the same channel count and sample rate,
+    /// see [`resampled`], [`to_mono`] and [`to_stereo`] to convert them.
+    ///
+    /// # Arguments
+    /// * buffers - The buffers to concatenate, in order
+    ///
+    /// Returns `None` if `buffers` is empty, if their formats differ, or on failure.
+    ///
+    /// [`resampled`]: SoundBuffer::resampled
+    /// [`to_mono`]: SoundBuffer::to_mono
+    /// [`to_stereo`]: SoundBuffer::to_stereo
+    pub fn concat(buffers: &[&SoundBuffer]) -> Option<SfBox<Self>> {
+        let first = buffers.first()?;
+        let (channel_count, sample_rate) = (first.channel_count(), first.sample_rate());
+        let same_format = buffers
+            .iter()
+            .all(|b| b.channel_count() == channel_count && b.sample_rate() == sample_rate);
+        if !same_format {
+            return None;
+        }
+        let samples: Vec<i16> = buffers
+            .iter()
+            .flat_map(|b| b.samples().iter().cloned())
+            .collect();
+        Self::from_samples(&samples, channel_count, sample_rate)
+    }
+}
+
+/// Resample interleaved `samples` from the rate `from` to the rate `to`.
+fn resample(samples: &[i16], channels: usize, from: u32, to: u32) -> Vec<i16> {
+    let channels = channels.max(1);
+    let frames = samples.len() / channels;
+    if from == to || from == 0 || frames == 0 {
+        return samples.to_vec();
+    }
+    let out_frames = (frames as u64 * u64::from(to) / u64::from(from)) as usize;
+    let step = f64::from(from) / f64::from(to);
+    let mut out = Vec::with_capacity(out_frames * channels);
+    for i in 0..out_frames {
+        let position = i as f64 * step;
+        let index = position as usize;
+        let next = (index + 1).min(frames - 1);
+        let t = position - index as f64;
+        for c in 0..channels {
+            let a = f64::from(samples[index * channels + c]);
+            let b = f64::from(samples[next * channels + c]);
+            out.push((a + (b - a) * t).round() as i16);
+        }
+    }
+    out
+}
+
+/// Mix interleaved `samples` down to one channel.
+fn to_mono(samples: &[i16], channels: usize) -> Vec<i16> {
+    let channels = channels.max(1);
+    samples
+        .chunks_exact(channels)
+        .map(|frame| {
+            let sum: i32 = frame.iter().map(|&s| i32::from(s)).sum();
+            (sum / channels as i32) as i16
+        })
+        .collect()
+}
+
+/// Convert interleaved `samples` to two channels.
+fn to_stereo(samples: &[i16], channels: usize) -> Vec<i16> {
+    match channels {
+        0 | 1 => samples.iter().flat_map(|&s| vec![s, s]).collect(),
+        2 => samples.to_vec(),
+        _ => samples
+            .chunks_exact(channels)
+            .flat_map(|frame| frame[..2].to_vec())
+            .collect(),
+    }
 }
 
 impl ToOwned for SoundBuffer {
@@ -197,3 +474,18 @@ impl Dispose for SoundBuffer {
         ffi::sfSoundBuffer_destroy(ptr as _);
     }
 }
+
+#[test]
+fn test_convert() {
+    assert_eq!(
+        resample(&[0, 100, 200, 300], 1, 4, 8),
+        [0, 50, 100, 150, 200, 250, 300, 300]
+    );
+    assert_eq!(
+        resample(&[0, 1, 100, 101, 200, 201, 300, 301], 2, 2, 1),
+        [0, 1, 200, 201]
+    );
+    assert_eq!(to_mono(&[100, 200, -100, -300], 2), [150, -200]);
+    assert_eq!(to_stereo(&[1, 2], 1), [1, 1, 2, 2]);
+    assert_eq!(to_stereo(&[1, 2, 3, 4, 5, 6], 3), [1, 2, 4, 5]);
+}
@@ -0,0 +1,30 @@
+/// Audio file format, for saving sound data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundFileFormat {
+    /// Uncompressed 16-bit PCM WAV.
+    Wav,
+    /// Ogg Vorbis.
+    Ogg,
+    /// FLAC (lossless).
+    Flac,
+}
+
+impl SoundFileFormat {
+    /// Get the format matching a file extension (case insensitive), if it is supported.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "wav" => Some(SoundFileFormat::Wav),
+            "ogg" | "oga" => Some(SoundFileFormat::Ogg),
+            "flac" => Some(SoundFileFormat::Flac),
+            _ => None,
+        }
+    }
+    /// Get the usual file extension of the format, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            SoundFileFormat::Wav => "wav",
+            SoundFileFormat::Ogg => "ogg",
+            SoundFileFormat::Flac => "flac",
+        }
+    }
+}
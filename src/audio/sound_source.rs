@@ -1,7 +1,49 @@
-use crate::system::Vector3f;
+use crate::audio::{Fade, LoopCount, SoundStatus};
+use crate::system::{Time, Vector3f};
 
 /// Base trait defining a sound's properties.
+///
+/// This trait is object safe, so sources of different types can be managed together,
+/// as trait objects:
+///
+/// ```no_run
+/// use sfml::audio::{Music, Sound, SoundBuffer, SoundSource};
+///
+/// let buffer = SoundBuffer::from_file("rain.wav").unwrap();
+/// let mut sources: Vec<Box<dyn SoundSource>> = vec![
+///     Box::new(Music::from_file("storm.ogg").unwrap()),
+///     Box::new(Sound::with_buffer(&buffer)),
+/// ];
+/// for source in &mut sources {
+///     source.set_volume(50.);
+///     source.play();
+/// }
+/// ```
+///
+/// The only method not available on trait objects is the generic [`set_position`],
+/// [`set_position_vector`] is used instead.
+///
+/// [`set_position`]: SoundSource::set_position
+/// [`set_position_vector`]: SoundSource::set_position_vector
 pub trait SoundSource {
+    /// Start or resume playing the sound.
+    ///
+    /// This function starts the sound if it was stopped, resumes it if it was paused,
+    /// and restarts it from the beginning if it was already playing.
+    fn play(&mut self);
+
+    /// Pause the sound.
+    ///
+    /// This function pauses the sound if it was playing, otherwise (sound already paused
+    /// or stopped) it has no effect.
+    fn pause(&mut self);
+
+    /// Stop playing the sound.
+    ///
+    /// This function stops the sound if it was playing or paused, and does nothing if it
+    /// was already stopped. It also resets the playing position.
+    fn stop(&mut self);
+
     /// Set the pitch of the sound.
     ///
     /// The pitch represents the perceived fundamental frequency of a sound;
@@ -29,7 +71,22 @@ pub trait SoundSource {
     ///
     /// # Parameters
     /// position - Position of the sound in the scene
-    fn set_position<P: Into<Vector3f>>(&mut self, position: P);
+    fn set_position<P: Into<Vector3f>>(&mut self, position: P)
+    where
+        Self: Sized,
+    {
+        self.set_position_vector(position.into())
+    }
+
+    /// Set the 3D position of the sound in the audio scene.
+    ///
+    /// This is [`set_position`] taking a vector only, which can be called on trait objects.
+    ///
+    /// # Parameters
+    /// position - Position of the sound in the scene
+    ///
+    /// [`set_position`]: SoundSource::set_position
+    fn set_position_vector(&mut self, position: Vector3f);
 
     /// Make the sound's position relative to the listener or absolute.
     ///
@@ -85,4 +142,93 @@ pub trait SoundSource {
 
     /// Get the attenuation factor of the sound.
     fn attenuation(&self) -> f32;
+
+    /// Get the current status of the sound (stopped, paused, playing).
+    fn status(&self) -> SoundStatus;
+
+    /// Get the current playing position of the sound.
+    fn playing_offset(&self) -> Time;
+
+    /// Tell whether or not the sound is in loop mode.
+    fn is_looping(&self) -> bool;
+
+    /// Set whether or not the sound should loop after reaching the end.
+    ///
+    /// If set, the sound will restart from beginning after reaching the end and so on,
+    /// until it is stopped or `set_looping(false)` is called.
+    /// The default looping state for sounds is false.
+    ///
+    /// # Parameters
+    /// looping - True to play in loop, false to play once
+    fn set_looping(&mut self, looping: bool);
+
+    /// Pan the sound between the left and the right speakers.
+    ///
+    /// This is a shortcut for 2D games, built on the 3D spatialization: the sound is made
+    /// relative to the listener, and placed on a circle around it, in front of it. A pan
+    /// of -1 plays the sound on the left only, 1 on the right only, and 0 in the middle.
+    /// The pan is clamped to that range.
+    ///
+    /// The sound stays at its full volume, as long as its minimum distance is at least 1.
+    /// Like any spatialization, only works for sounds with one channel (mono sounds).
+    ///
+    /// # Parameters
+    /// pan - Position of the sound between the speakers, in the range [-1, 1]
+    fn set_pan(&mut self, pan: f32) {
+        let pan = pan.max(-1.).min(1.);
+        self.set_relative_to_listener(true);
+        self.set_position_vector(Vector3f::new(pan, 0., -(1. - pan * pan).sqrt()));
+    }
+
+    /// Get the pan of the sound, in the range [-1, 1].
+    ///
+    /// This is the horizontal position of the sound relative to the listener, see
+    /// [`set_pan`]. A sound whose position is absolute has a pan of 0.
+    ///
+    /// [`set_pan`]: SoundSource::set_pan
+    fn pan(&self) -> f32 {
+        if self.is_relative_to_listener() {
+            let position = self.position();
+            let distance = (position.x * position.x + position.z * position.z).sqrt();
+            if distance > 0. {
+                return position.x / distance;
+            }
+        }
+        0.
+    }
+
+    /// Start fading the sound in, from silence up to its current volume.
+    ///
+    /// The volume is set to 0 right away. The returned [`Fade`] then brings it back up
+    /// over `duration`, as it is advanced with [`Fade::update`].
+    fn fade_in(&mut self, duration: Time) -> Fade {
+        let volume = self.volume();
+        self.set_volume(0.);
+        Fade::new(0., volume, duration)
+    }
+
+    /// Start fading the sound out, from its current volume down to silence.
+    ///
+    /// The returned [`Fade`] has to be advanced with [`Fade::update`].
+    fn fade_out(&mut self, duration: Time) -> Fade {
+        self.fade_to(0., duration)
+    }
+
+    /// Play the sound `count` times in a row, then stop.
+    ///
+    /// This sets the looping state of the sound according to `count`. The returned
+    /// [`LoopCount`] then counts the repetitions, and turns looping off for the last one,
+    /// as it is advanced with [`LoopCount::update`].
+    fn set_loop_count(&mut self, count: u32) -> LoopCount {
+        let counter = LoopCount::new(count);
+        self.set_looping(counter.remaining() > 0);
+        counter
+    }
+
+    /// Start fading the sound from its current volume to `volume`.
+    ///
+    /// The returned [`Fade`] has to be advanced with [`Fade::update`].
+    fn fade_to(&mut self, volume: f32, duration: Time) -> Fade {
+        Fade::new(self.volume(), volume, duration)
+    }
 }
@@ -1,13 +1,87 @@
 use crate::audio::csfml_audio_sys::*;
 use crate::audio::SoundSource;
 use crate::audio::SoundStatus;
+use crate::audio::{Processed, Rechunked, SampleProcessor, SoundBuffer};
 use crate::sf_bool_ext::SfBoolExt;
-use crate::system::{Time, Vector3f};
+use crate::system::{SfBox, Time, Vector3f};
 use csfml_system_sys::*;
 use std::os::raw::c_void;
 use std::panic;
 
 /// Trait for streamed audio sources.
+///
+/// Unlike audio buffers (see [`SoundBuffer`]), audio streams are never completely loaded
+/// in memory. Instead, the audio data is acquired continuously while the stream is playing.
+/// This behavior allows to play a sound with no loading delay, and keeps the memory
+/// consumption very low.
+///
+/// Sound sources that need to be streamed are usually big files (compressed audio musics
+/// that would eat hundreds of MB in memory) or files that would take a lot of time to be
+/// received (sounds played over the network), but procedurally generated audio
+/// (synthesizers, emulators, ...) is a good fit as well.
+///
+/// `SoundStream` only cares about providing the audio samples, the playback itself is
+/// handled by a [`SoundStreamPlayer`], which borrows the stream for as long as it plays it.
+///
+/// Two methods are responsible for the data:
+///
+/// [`get_data`] is called by the streaming thread whenever it needs a new chunk of audio
+/// samples. The samples are interleaved 16-bit integers, and the chunk can be of any
+/// size; smaller chunks mean lower latency, bigger chunks mean less frequent calls.
+/// See [`with_chunk_size`] to pick the size of the chunks independently of the stream.
+///
+/// [`seek`] is called when the playing position is changed with
+/// [`SoundStreamPlayer::set_playing_offset`], or when the stream is restarted.
+///
+/// [`channel_count`] and [`sample_rate`] are read once, when the player is created.
+///
+/// It is important to note that [`get_data`] and [`seek`] are called from a separate
+/// thread, so you may have to take care of synchronization issues if you share data
+/// between threads. Panics inside them are caught, and stop the playback.
+///
+/// # Example
+///
+/// ```no_run
+/// use sfml::audio::{SoundStream, SoundStreamPlayer};
+/// use sfml::system::Time;
+///
+/// // A 440 Hz sine wave, generated on the fly.
+/// struct Tone {
+///     buf: Vec<i16>,
+///     sample: u64,
+/// }
+///
+/// impl SoundStream for Tone {
+///     fn get_data(&mut self) -> (&mut [i16], bool) {
+///         for s in &mut self.buf {
+///             let t = self.sample as f32 / 44_100.;
+///             *s = ((t * 440. * 2. * std::f32::consts::PI).sin() * 8_000.) as i16;
+///             self.sample += 1;
+///         }
+///         (&mut self.buf[..], true)
+///     }
+///     fn seek(&mut self, offset: Time) {
+///         self.sample = (offset.as_seconds() * 44_100.) as u64;
+///     }
+///     fn channel_count(&self) -> u32 {
+///         1
+///     }
+///     fn sample_rate(&self) -> u32 {
+///         44_100
+///     }
+/// }
+///
+/// let mut tone = Tone { buf: vec![0; 4096], sample: 0 };
+/// let mut player = SoundStreamPlayer::new(&mut tone);
+/// player.play();
+/// ```
+///
+/// [`SoundBuffer`]: crate::audio::SoundBuffer
+/// [`get_data`]: SoundStream::get_data
+/// [`seek`]: SoundStream::seek
+/// [`channel_count`]: SoundStream::channel_count
+/// [`sample_rate`]: SoundStream::sample_rate
+/// [`with_chunk_size`]: SoundStream::with_chunk_size
 pub trait SoundStream {
     /// Request a new chunk of audio samples from the stream source.
     ///
@@ -20,15 +94,108 @@ pub trait SoundStream {
     fn channel_count(&self) -> u32;
     /// Get the stream sample rate of the stream.
     fn sample_rate(&self) -> u32;
+    /// Get the total duration of the stream, if it is known.
+    ///
+    /// Streams that never end, or don't know when they do, return `None`, the default.
+    fn duration(&self) -> Option<Time> {
+        None
+    }
+    /// Pass the samples of the stream through `processor` before they are played.
+    ///
+    /// See [`SampleProcessor`].
+    fn with_processor<P: SampleProcessor>(self, processor: P) -> Processed<Self, P>
+    where
+        Self: Sized,
+    {
+        Processed::new(self, processor)
+    }
+    /// Hand out the samples of the stream in chunks of `frames` frames,
+    /// to control its latency.
+    ///
+    /// See [`Rechunked`].
+    fn with_chunk_size(self, frames: usize) -> Rechunked<Self>
+    where
+        Self: Sized,
+    {
+        Rechunked::new(self, frames)
+    }
+    /// Decode a part of the stream into a new sound buffer.
+    ///
+    /// The stream seeks to `offset`, and its chunks are gathered until `length` is reached
+    /// or the stream ends. This pulls a clip out of a long file without decoding all of it,
+    /// to preview it or to play it as a sound, for example.
+    ///
+    /// The stream is left after the decoded part, it should be seeked back before it is
+    /// played. A stream that is currently played by a [`SoundStreamPlayer`] is borrowed by
+    /// it, and can't be decoded from.
+    ///
+    /// A [`Music`] isn't a `SoundStream`, see `Music::decode_range` (with the `symphonia`
+    /// feature) to decode it.
+    ///
+    /// # Arguments
+    /// * offset - Start of the part to decode
+    /// * length - Duration of the part to decode
+    ///
+    /// Returns `None` on failure.
+    ///
+    /// [`Music`]: crate::audio::Music
+    fn decode_range(&mut self, offset: Time, length: Time) -> Option<SfBox<SoundBuffer>>
+    where
+        Self: Sized,
+    {
+        let frames =
+            length.as_microseconds().max(0) as u64 * u64::from(self.sample_rate()) / 1_000_000;
+        self.seek(offset);
+        let samples = read_samples(self, frames as usize * self.channel_count() as usize);
+        SoundBuffer::from_samples(&samples, self.channel_count(), self.sample_rate())
+    }
+}
+
+impl<S: SoundStream + ?Sized> SoundStream for Box<S> {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        (**self).get_data()
+    }
+    fn seek(&mut self, offset: Time) {
+        (**self).seek(offset)
+    }
+    fn channel_count(&self) -> u32 {
+        (**self).channel_count()
+    }
+    fn sample_rate(&self) -> u32 {
+        (**self).sample_rate()
+    }
+    fn duration(&self) -> Option<Time> {
+        (**self).duration()
+    }
+}
+
+/// Gather the chunks of `stream` until `len` samples are read, or the stream ends.
+fn read_samples<S: SoundStream>(stream: &mut S, len: usize) -> Vec<i16> {
+    let mut samples = Vec::with_capacity(len);
+    while samples.len() < len {
+        let (chunk, keep_playing) = stream.get_data();
+        let take = chunk.len().min(len - samples.len());
+        samples.extend_from_slice(&chunk[..take]);
+        // An empty chunk would never fill the samples
+        if !keep_playing || chunk.is_empty() {
+            break;
+        }
+    }
+    samples
 }
 
 /// Player for custom streamed audio sources. See [`SoundStream`].
+///
+/// A player can be moved to another thread when its stream is `Send`,
+/// SFML synchronizes it with its streaming thread.
 #[derive(Debug)]
 pub struct SoundStreamPlayer<'a, S: SoundStream + 'a> {
     sf_sound_stream: *mut sfSoundStream,
     stream: &'a mut S,
 }
 
+unsafe impl<'a, S: SoundStream + Send> Send for SoundStreamPlayer<'a, S> {}
+
 unsafe extern "C" fn get_data_callback<S: SoundStream>(
     chunk: *mut sfSoundStreamChunk,
     user_data: *mut c_void,
@@ -62,16 +229,18 @@ impl<'a, S: SoundStream> SoundStreamPlayer<'a, S> {
     /// Create a new `SoundStreamPlayer` with the specified [`SoundStream`].
     pub fn new(sound_stream: &'a mut S) -> Self {
         let ptr: *mut S = sound_stream;
+        let sf_sound_stream = unsafe {
+            sfSoundStream_create(
+                Some(get_data_callback::<S>),
+                Some(seek_callback::<S>),
+                sound_stream.channel_count(),
+                sound_stream.sample_rate(),
+                ptr as *mut _,
+            )
+        };
+        assert!(!sf_sound_stream.is_null(), "Failed to create SoundStream");
         SoundStreamPlayer {
-            sf_sound_stream: unsafe {
-                sfSoundStream_create(
-                    Some(get_data_callback::<S>),
-                    Some(seek_callback::<S>),
-                    sound_stream.channel_count(),
-                    sound_stream.sample_rate(),
-                    ptr as *mut _,
-                )
-            },
+            sf_sound_stream,
             stream: sound_stream,
         }
     }
@@ -167,14 +336,23 @@ impl<'a, S: SoundStream> SoundStreamPlayer<'a, S> {
 }
 
 impl<'a, S: SoundStream> SoundSource for SoundStreamPlayer<'a, S> {
+    fn play(&mut self) {
+        SoundStreamPlayer::play(self)
+    }
+    fn pause(&mut self) {
+        SoundStreamPlayer::pause(self)
+    }
+    fn stop(&mut self) {
+        let _ = SoundStreamPlayer::stop(self);
+    }
     fn set_pitch(&mut self, pitch: f32) {
         unsafe { sfSoundStream_setPitch(self.sf_sound_stream, pitch) }
     }
     fn set_volume(&mut self, volume: f32) {
         unsafe { sfSoundStream_setVolume(self.sf_sound_stream, volume) }
     }
-    fn set_position<P: Into<Vector3f>>(&mut self, position: P) {
-        unsafe { sfSoundStream_setPosition(self.sf_sound_stream, position.into().raw()) }
+    fn set_position_vector(&mut self, position: Vector3f) {
+        unsafe { sfSoundStream_setPosition(self.sf_sound_stream, position.raw()) }
     }
     fn set_relative_to_listener(&mut self, relative: bool) {
         unsafe {
@@ -208,6 +386,18 @@ impl<'a, S: SoundStream> SoundSource for SoundStreamPlayer<'a, S> {
     fn attenuation(&self) -> f32 {
         unsafe { sfSoundStream_getAttenuation(self.sf_sound_stream) }
     }
+    fn status(&self) -> SoundStatus {
+        SoundStreamPlayer::status(self)
+    }
+    fn playing_offset(&self) -> Time {
+        SoundStreamPlayer::playing_offset(self)
+    }
+    fn is_looping(&self) -> bool {
+        SoundStreamPlayer::is_looping(self)
+    }
+    fn set_looping(&mut self, looping: bool) {
+        SoundStreamPlayer::set_looping(self, looping)
+    }
 }
 
 impl<'a, S: SoundStream> Drop for SoundStreamPlayer<'a, S> {
@@ -220,3 +410,30 @@ impl<'a, S: SoundStream> Drop for SoundStreamPlayer<'a, S> {
         }
     }
 }
+
+#[test]
+fn test_read_samples() {
+    struct Counter(Vec<i16>, i16);
+    impl SoundStream for Counter {
+        fn get_data(&mut self) -> (&mut [i16], bool) {
+            for sample in &mut self.0 {
+                *sample = self.1;
+                self.1 += 1;
+            }
+            (&mut self.0[..], self.1 < 10)
+        }
+        fn seek(&mut self, _: Time) {}
+        fn channel_count(&self) -> u32 {
+            1
+        }
+        fn sample_rate(&self) -> u32 {
+            44_100
+        }
+    }
+    let mut stream = Counter(vec![0; 4], 0);
+    assert_eq!(read_samples(&mut stream, 6), [0, 1, 2, 3, 4, 5]);
+    // The stream ends after its third chunk
+    assert_eq!(read_samples(&mut stream, 100), [8, 9, 10, 11]);
+    // A stream that keeps playing without data doesn't block
+    assert!(read_samples(&mut Counter(Vec::new(), 0), 4).is_empty());
+}
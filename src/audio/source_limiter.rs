@@ -0,0 +1,305 @@
+use crate::audio::{listener, Falloff, SoundSource, SoundStatus};
+use crate::system::Vector3f;
+use std::cmp::Ordering;
+
+/// Identifier of a sound source added to a [`SourceLimiter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LimitedSourceId(usize);
+
+#[derive(Debug)]
+struct Member<S> {
+    source: S,
+    priority: i32,
+    /// Whether the source was asked to play, and hasn't finished.
+    wanted: bool,
+    /// Whether the limiter started the source, as opposed to keeping it virtual.
+    started: bool,
+}
+
+impl<S: SoundSource> Member<S> {
+    /// Volume the source is heard at, accounting for its distance to the listener.
+    fn audibility(&self, listener: Vector3f) -> f32 {
+        let source = &self.source;
+        let offset = if source.is_relative_to_listener() {
+            source.position()
+        } else {
+            source.position() - listener
+        };
+        let distance = (offset.x * offset.x + offset.y * offset.y + offset.z * offset.z).sqrt();
+        let falloff = Falloff {
+            min_distance: source.min_distance(),
+            attenuation: source.attenuation(),
+        };
+        source.volume() * falloff.gain(distance)
+    }
+}
+
+/// Caps the number of sound sources playing at the same time.
+///
+/// OpenAL can only play a limited number of sources at once, 256 with the implementation
+/// SFML ships with, and less on some systems; past that, sources silently fail to play.
+/// Mixing dozens of sounds also muddies the result. A limiter owns a set of sources, and
+/// lets at most a given number of them play: when more are asked to play, the least
+/// important ones are made *virtual*. A virtual source is paused, and resumes where it left
+/// off as soon as a slot is free, or as soon as it becomes more important than a playing one.
+///
+/// Sources are ranked by their priority first, then by how loud they are heard: their volume,
+/// attenuated by their distance to the listener. Virtualization can be disabled with
+/// [`set_virtualization`], in which case the sources losing their slot are stopped for good,
+/// which suits short sound effects that would make no sense played late.
+///
+/// Sources are played, paused and stopped through the limiter, with [`play`], [`pause`] and
+/// [`stop`], and [`update`] must be called regularly, typically once per frame, to notice
+/// the sources that finished and hand their slots over. Playing a source directly, through
+/// [`get_mut`], bypasses the limit until the next update.
+///
+/// A limiter holds sources of a single type. Since [`SoundSource`] is object safe, sources
+/// of different types can be limited together as `Box<dyn SoundSource>`.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Sound, SoundBuffer, SourceLimiter};
+///
+/// let buffer = SoundBuffer::from_file("footstep.wav").unwrap();
+/// let mut limiter = SourceLimiter::new(32);
+/// let ids: Vec<_> = (0..100)
+///     .map(|_| limiter.add(Sound::with_buffer(&buffer), 0))
+///     .collect();
+/// for &id in &ids {
+///     // Only the 32 loudest footsteps are heard
+///     limiter.play(id);
+/// }
+/// loop {
+///     limiter.update();
+///     // ...
+/// }
+/// ```
+///
+/// [`set_virtualization`]: SourceLimiter::set_virtualization
+/// [`play`]: SourceLimiter::play
+/// [`pause`]: SourceLimiter::pause
+/// [`stop`]: SourceLimiter::stop
+/// [`update`]: SourceLimiter::update
+/// [`get_mut`]: SourceLimiter::get_mut
+#[derive(Debug)]
+pub struct SourceLimiter<S> {
+    max_playing: usize,
+    virtualization: bool,
+    members: Vec<Option<Member<S>>>,
+}
+
+impl<S: SoundSource> SourceLimiter<S> {
+    /// Create a new empty limiter, letting at most `max_playing` sources play at once.
+    pub fn new(max_playing: usize) -> Self {
+        SourceLimiter {
+            max_playing,
+            virtualization: true,
+            members: Vec::new(),
+        }
+    }
+    /// Change the maximum number of sources playing at once.
+    ///
+    /// This takes effect on the next update.
+    pub fn set_max_playing(&mut self, max_playing: usize) {
+        self.max_playing = max_playing;
+    }
+    /// Get the maximum number of sources playing at once.
+    pub fn max_playing(&self) -> usize {
+        self.max_playing
+    }
+    /// Choose whether sources losing their slot are paused until they get one again (the
+    /// default), or stopped.
+    pub fn set_virtualization(&mut self, virtualization: bool) {
+        self.virtualization = virtualization;
+    }
+    /// Tell whether sources losing their slot are paused until they get one again.
+    pub fn is_virtualization_enabled(&self) -> bool {
+        self.virtualization
+    }
+    /// Add a source to the limiter.
+    ///
+    /// Sources of higher priority always take precedence over sources of lower priority.
+    pub fn add(&mut self, source: S, priority: i32) -> LimitedSourceId {
+        let member = Member {
+            source,
+            priority,
+            wanted: false,
+            started: false,
+        };
+        match self.members.iter().position(Option::is_none) {
+            Some(index) => {
+                self.members[index] = Some(member);
+                LimitedSourceId(index)
+            }
+            None => {
+                self.members.push(Some(member));
+                LimitedSourceId(self.members.len() - 1)
+            }
+        }
+    }
+    /// Remove a source from the limiter, giving it back.
+    ///
+    /// The source keeps playing if it was, and its slot is handed over on the next update.
+    pub fn remove(&mut self, id: LimitedSourceId) -> Option<S> {
+        Some(self.members.get_mut(id.0)?.take()?.source)
+    }
+    /// Access a source of the limiter.
+    pub fn get(&self, id: LimitedSourceId) -> Option<&S> {
+        self.member(id).map(|member| &member.source)
+    }
+    /// Mutably access a source of the limiter.
+    pub fn get_mut(&mut self, id: LimitedSourceId) -> Option<&mut S> {
+        self.member_mut(id).map(|member| &mut member.source)
+    }
+    /// Change the priority of a source.
+    ///
+    /// This takes effect on the next update.
+    pub fn set_priority(&mut self, id: LimitedSourceId, priority: i32) {
+        if let Some(member) = self.member_mut(id) {
+            member.priority = priority;
+        }
+    }
+    /// Get the priority of a source.
+    pub fn priority(&self, id: LimitedSourceId) -> Option<i32> {
+        self.member(id).map(|member| member.priority)
+    }
+    /// Play a source, or make it virtual if it isn't important enough to get a slot.
+    ///
+    /// Like [`SoundSource::play`], this restarts a stopped source, and resumes a paused one.
+    pub fn play(&mut self, id: LimitedSourceId) {
+        if let Some(member) = self.member_mut(id) {
+            if member.source.status() == SoundStatus::Stopped {
+                member.started = false;
+            }
+            member.wanted = true;
+            self.update();
+        }
+    }
+    /// Pause a source, which frees its slot.
+    pub fn pause(&mut self, id: LimitedSourceId) {
+        if let Some(member) = self.member_mut(id) {
+            member.source.pause();
+            member.wanted = false;
+            member.started = false;
+            self.update();
+        }
+    }
+    /// Stop a source, which frees its slot.
+    pub fn stop(&mut self, id: LimitedSourceId) {
+        if let Some(member) = self.member_mut(id) {
+            member.source.stop();
+            member.wanted = false;
+            member.started = false;
+            self.update();
+        }
+    }
+    /// Tell whether a source is virtual: asked to play, but paused for lack of a slot.
+    pub fn is_virtual(&self, id: LimitedSourceId) -> bool {
+        self.member(id)
+            .map_or(false, |member| member.wanted && !member.started)
+    }
+    /// Get the number of sources currently played by the limiter.
+    pub fn playing_count(&self) -> usize {
+        self.members.iter().flatten().filter(|m| m.started).count()
+    }
+    /// Hand the slots of the finished sources over to the virtual ones, and give the slots
+    /// to the most important sources.
+    pub fn update(&mut self) {
+        self.rebalance(listener::position());
+    }
+    /// Return the number of sources in the limiter.
+    pub fn len(&self) -> usize {
+        self.members.iter().flatten().count()
+    }
+    /// Tell whether the limiter has no sources.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn member(&self, id: LimitedSourceId) -> Option<&Member<S>> {
+        self.members.get(id.0)?.as_ref()
+    }
+    fn member_mut(&mut self, id: LimitedSourceId) -> Option<&mut Member<S>> {
+        self.members.get_mut(id.0)?.as_mut()
+    }
+    fn rebalance(&mut self, listener: Vector3f) {
+        let mut wanted: Vec<(usize, i32, f32)> = Vec::new();
+        for (index, member) in self.members.iter_mut().enumerate() {
+            let member = match *member {
+                Some(ref mut member) if member.wanted => member,
+                _ => continue,
+            };
+            if member.started && member.source.status() == SoundStatus::Stopped {
+                // The source reached its end
+                member.wanted = false;
+                member.started = false;
+                continue;
+            }
+            wanted.push((index, member.priority, member.audibility(listener)));
+        }
+        // Most important first
+        wanted.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then(b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal))
+        });
+        let (playing, virtual_) = wanted.split_at(wanted.len().min(self.max_playing));
+        // Free the slots before taking them, so that the limit is never exceeded
+        for &(index, ..) in virtual_ {
+            let member = self.members[index].as_mut().expect("wanted members exist");
+            if self.virtualization {
+                if member.started {
+                    member.source.pause();
+                    member.started = false;
+                }
+            } else {
+                member.source.stop();
+                member.wanted = false;
+                member.started = false;
+            }
+        }
+        for &(index, ..) in playing {
+            let member = self.members[index].as_mut().expect("wanted members exist");
+            if !member.started {
+                member.source.play();
+                member.started = true;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_limit() {
+    use crate::audio::NullSource;
+    use std::time::Duration;
+
+    let listener = Vector3f::new(0., 0., 0.);
+    let mut limiter = SourceLimiter::new(2);
+    let source = |volume| {
+        let mut source = NullSource::with_duration(Duration::from_secs(60));
+        source.set_volume(volume);
+        source
+    };
+    let quiet = limiter.add(source(10.), 0);
+    let loud = limiter.add(source(90.), 0);
+    let mid = limiter.add(source(50.), 0);
+    let important = limiter.add(source(1.), 1);
+    for &id in &[quiet, loud, mid] {
+        limiter.members[id.0].as_mut().unwrap().wanted = true;
+    }
+    limiter.rebalance(listener);
+    assert!(limiter.is_virtual(quiet));
+    assert_eq!(limiter.playing_count(), 2);
+
+    limiter.members[important.0].as_mut().unwrap().wanted = true;
+    limiter.rebalance(listener);
+    assert!(!limiter.is_virtual(important) && !limiter.is_virtual(loud));
+    assert_eq!(limiter.get(mid).unwrap().status(), SoundStatus::Paused);
+
+    // The important source finishes, the virtual one resumes
+    limiter.get_mut(important).unwrap().stop();
+    limiter.rebalance(listener);
+    assert!(!limiter.is_virtual(mid));
+    assert_eq!(limiter.get(mid).unwrap().status(), SoundStatus::Playing);
+    assert_eq!(limiter.playing_count(), 2);
+}
@@ -0,0 +1,204 @@
+use crate::audio::{listener, SoundSource};
+use crate::system::{Time, Vector3f};
+
+/// How the volume of a spatialized source decreases with its distance to the listener.
+///
+/// SFML attenuates sources with the inverse distance model: a source is at full volume up
+/// to its minimum distance, then its volume decreases according to its attenuation factor.
+/// The factor is not very intuitive, so besides the presets, a falloff can be built from the
+/// distance at which the source is heard at half its volume with [`half_volume_at`].
+///
+/// [`half_volume_at`]: Falloff::half_volume_at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Falloff {
+    /// Distance up to which the source is heard at full volume.
+    pub min_distance: f32,
+    /// Attenuation factor beyond the minimum distance.
+    pub attenuation: f32,
+}
+
+impl Falloff {
+    /// No attenuation at all, the source is heard at full volume from anywhere.
+    pub const NONE: Falloff = Falloff {
+        min_distance: 1.,
+        attenuation: 0.,
+    };
+    /// A slow falloff, for loud sources that carry far (explosions, sirens).
+    pub const GENTLE: Falloff = Falloff {
+        min_distance: 1.,
+        attenuation: 0.25,
+    };
+    /// The default falloff of SFML.
+    pub const DEFAULT: Falloff = Falloff {
+        min_distance: 1.,
+        attenuation: 1.,
+    };
+    /// A fast falloff, for quiet sources only heard up close (footsteps, whispers).
+    pub const STEEP: Falloff = Falloff {
+        min_distance: 1.,
+        attenuation: 4.,
+    };
+
+    /// Create a falloff where the source is at full volume up to `min_distance`,
+    /// and at half its volume at `distance`.
+    ///
+    /// If `distance` is not beyond `min_distance`, there is no attenuation.
+    pub fn half_volume_at(min_distance: f32, distance: f32) -> Self {
+        let attenuation = if distance > min_distance {
+            min_distance / (distance - min_distance)
+        } else {
+            0.
+        };
+        Falloff {
+            min_distance,
+            attenuation,
+        }
+    }
+    /// Apply the falloff to `source`.
+    pub fn apply<S: SoundSource + ?Sized>(&self, source: &mut S) {
+        source.set_min_distance(self.min_distance);
+        source.set_attenuation(self.attenuation);
+    }
+    /// Compute the volume factor, from 0 to 1, of a source at `distance` from the listener.
+    pub fn gain(&self, distance: f32) -> f32 {
+        let distance = distance.max(self.min_distance);
+        self.min_distance / (self.min_distance + self.attenuation * (distance - self.min_distance))
+    }
+}
+
+impl Default for Falloff {
+    fn default() -> Self {
+        Falloff::DEFAULT
+    }
+}
+
+/// Keeps a sound source at the position of something moving in the scene.
+///
+/// An attachment is given the position of the emitter of a sound every frame, typically the
+/// position of a game entity, and moves the source there. It also derives the velocity of the
+/// emitter from its successive positions, to apply the doppler effect to the source (see
+/// [`listener::doppler_pitch`]), and applies a [`Falloff`] to the source on the first update.
+///
+/// The attachment doesn't own the source, so any number of emitters can be managed by storing
+/// an attachment next to each source, and updating them together.
+///
+/// Remember that only mono sources can be spatialized.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Falloff, Sound, SoundBuffer, SpatialAttachment};
+/// use sfml::system::{Clock, Vector3f};
+///
+/// let buffer = SoundBuffer::from_file("engine.wav").unwrap();
+/// let mut engine = Sound::with_buffer(&buffer);
+/// engine.set_looping(true);
+/// engine.play();
+///
+/// let mut car = Vector3f::new(-50., 0., -5.);
+/// let mut attachment = SpatialAttachment::new(Falloff::half_volume_at(5., 20.));
+/// let mut clock = Clock::start();
+/// loop {
+///     let dt = clock.restart();
+///     car.x += 30. * dt.as_seconds();
+///     attachment.update(dt, car, &mut engine);
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SpatialAttachment {
+    falloff: Falloff,
+    /// Whether the falloff still has to be applied to the source.
+    apply_falloff: bool,
+    position: Option<Vector3f>,
+    velocity: Vector3f,
+    pitch: f32,
+    doppler: bool,
+}
+
+impl SpatialAttachment {
+    /// Create a new attachment, applying `falloff` to its source.
+    pub fn new(falloff: Falloff) -> Self {
+        SpatialAttachment {
+            falloff,
+            apply_falloff: true,
+            position: None,
+            velocity: Vector3f::new(0., 0., 0.),
+            pitch: 1.,
+            doppler: true,
+        }
+    }
+    /// Move the source to `position`, the position of the emitter after `dt`.
+    ///
+    /// This also updates the velocity of the emitter, and the pitch of the source
+    /// if the doppler effect is enabled.
+    pub fn update<P, S>(&mut self, dt: Time, position: P, source: &mut S)
+    where
+        P: Into<Vector3f>,
+        S: SoundSource + ?Sized,
+    {
+        let position = position.into();
+        if let Some(previous) = self.position {
+            if dt > Time::ZERO {
+                self.velocity = (position - previous) / dt.as_seconds();
+            }
+        }
+        if self.apply_falloff {
+            source.set_relative_to_listener(false);
+            self.falloff.apply(source);
+            self.apply_falloff = false;
+        }
+        self.position = Some(position);
+        source.set_position_vector(position);
+        if self.doppler {
+            source.set_pitch(self.pitch * listener::doppler_pitch(position, self.velocity));
+        }
+    }
+    /// Forget the last position of the emitter, for example after it was teleported.
+    ///
+    /// The velocity is reset to zero, and the falloff is applied again on the next update.
+    pub fn reset(&mut self) {
+        self.apply_falloff = true;
+        self.position = None;
+        self.velocity = Vector3f::new(0., 0., 0.);
+    }
+    /// Get the velocity of the emitter, in units per second.
+    pub fn velocity(&self) -> Vector3f {
+        self.velocity
+    }
+    /// Get the falloff applied to the source.
+    pub fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+    /// Change the falloff applied to the source, which happens on the next update.
+    pub fn set_falloff(&mut self, falloff: Falloff) {
+        self.falloff = falloff;
+        self.apply_falloff = true;
+    }
+    /// Set the pitch of the source, before the doppler effect is applied. The default is 1.
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch;
+    }
+    /// Get the pitch of the source, before the doppler effect is applied.
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+    /// Enable or disable the doppler effect. It is enabled by default.
+    ///
+    /// When disabled, the attachment leaves the pitch of the source alone.
+    pub fn set_doppler(&mut self, doppler: bool) {
+        self.doppler = doppler;
+    }
+    /// Tell whether the doppler effect is enabled.
+    pub fn is_doppler_enabled(&self) -> bool {
+        self.doppler
+    }
+}
+
+#[test]
+fn test_falloff() {
+    let falloff = Falloff::half_volume_at(5., 20.);
+    assert_eq!(falloff.gain(1.), 1.);
+    assert!((falloff.gain(20.) - 0.5).abs() < 1e-6);
+    assert_eq!(Falloff::NONE.gain(1000.), 1.);
+}
@@ -0,0 +1,242 @@
+use crate::audio::{SoundSource, SoundStatus};
+use crate::system::Time;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Identifier of a source watched by a [`StatusWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchId(u64);
+
+/// Change of the status of a source, reported by a [`StatusWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusChange {
+    /// The source whose status changed.
+    pub id: WatchId,
+    /// The status the source had before.
+    pub previous: SoundStatus,
+    /// The new status of the source.
+    pub status: SoundStatus,
+}
+
+type SharedSource = Weak<Mutex<dyn SoundSource + Send>>;
+
+struct Watched {
+    id: u64,
+    source: SharedSource,
+    status: SoundStatus,
+}
+
+enum Message {
+    Watch(Watched),
+    Unwatch(u64),
+}
+
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Reports the status changes of sound sources through a channel.
+///
+/// A [`PlaybackMonitor`] has to be updated with its source every frame. A watcher checks
+/// the status of all its sources from a thread of its own instead, and sends a
+/// [`StatusChange`] each time one of them changes, for example from
+/// [`Playing`] to [`Stopped`] when a sound ends. The game only has to look at the events
+/// when it sees fit, with [`poll`], or to wait for them on the [`receiver`].
+///
+/// The sources are shared with the watcher thread through an `Arc<Mutex<_>>`, so they must
+/// be `Send`: musics are, sounds are not (see the thread safety notes of [`Sound`]). The
+/// watcher only keeps weak references to them, a dropped source is no longer watched.
+///
+/// Like a [`PlaybackMonitor`], a watcher only sees the status of its sources at each check,
+/// so a source stopped and played again between two checks goes unnoticed.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{Music, SoundStatus, StatusWatcher};
+/// use std::sync::{Arc, Mutex};
+///
+/// let mut watcher = StatusWatcher::new();
+/// let intro = Arc::new(Mutex::new(Music::from_file("intro.ogg").unwrap()));
+/// let intro_id = watcher.watch(&intro);
+/// intro.lock().unwrap().play();
+/// loop {
+///     while let Some(change) = watcher.poll() {
+///         if change.id == intro_id && change.status == SoundStatus::Stopped {
+///             // Start the game
+///         }
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// [`PlaybackMonitor`]: crate::audio::PlaybackMonitor
+/// [`Playing`]: SoundStatus::Playing
+/// [`Stopped`]: SoundStatus::Stopped
+/// [`poll`]: StatusWatcher::poll
+/// [`receiver`]: StatusWatcher::receiver
+/// [`Sound`]: crate::audio::Sound
+#[derive(Debug)]
+pub struct StatusWatcher {
+    next_id: u64,
+    messages: Option<Sender<Message>>,
+    changes: Receiver<StatusChange>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StatusWatcher {
+    /// Create a new watcher, checking its sources every 10 milliseconds.
+    pub fn new() -> Self {
+        Self::with_duration(Duration::from_millis(10))
+    }
+    /// Create a new watcher, checking its sources every `interval`.
+    pub fn with_interval(interval: Time) -> Self {
+        Self::with_duration(Duration::from_micros(
+            interval.as_microseconds().max(1) as u64
+        ))
+    }
+    fn with_duration(interval: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let (changes_sender, changes) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("sfml-status-watcher".into())
+            .spawn(move || {
+                let mut watched = Vec::<Watched>::new();
+                let mut next_check = Instant::now();
+                loop {
+                    let now = Instant::now();
+                    if now >= next_check {
+                        watched.retain(|watched| watched.source.upgrade().is_some());
+                        for watched in watched.iter_mut() {
+                            let source = match watched.source.upgrade() {
+                                Some(source) => source,
+                                None => continue,
+                            };
+                            let status = lock(&source).status();
+                            if status != watched.status {
+                                // The receiver lives as long as the watcher
+                                let _ = changes_sender.send(StatusChange {
+                                    id: WatchId(watched.id),
+                                    previous: watched.status,
+                                    status,
+                                });
+                                watched.status = status;
+                            }
+                        }
+                        next_check = now + interval;
+                    }
+                    let timeout = if next_check > now {
+                        next_check - now
+                    } else {
+                        Duration::from_secs(0)
+                    };
+                    match receiver.recv_timeout(timeout) {
+                        Ok(Message::Watch(entry)) => watched.push(entry),
+                        Ok(Message::Unwatch(id)) => watched.retain(|entry| entry.id != id),
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+            })
+            .expect("Failed to spawn the status watcher thread");
+        StatusWatcher {
+            next_id: 0,
+            messages: Some(sender),
+            changes,
+            thread: Some(thread),
+        }
+    }
+    /// Start watching `source`.
+    ///
+    /// The changes are reported from the current status of the source.
+    pub fn watch<S>(&mut self, source: &Arc<Mutex<S>>) -> WatchId
+    where
+        S: SoundSource + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        let status = lock(source).status();
+        let source: Weak<Mutex<S>> = Arc::downgrade(source);
+        let source: SharedSource = source;
+        self.send(Message::Watch(Watched { id, source, status }));
+        WatchId(id)
+    }
+    /// Stop watching a source.
+    ///
+    /// Changes detected before may still be pending.
+    pub fn unwatch(&mut self, id: WatchId) {
+        self.send(Message::Unwatch(id.0));
+    }
+    /// Take the oldest status change not retrieved yet, if any.
+    pub fn poll(&self) -> Option<StatusChange> {
+        self.changes.try_recv().ok()
+    }
+    /// Get the channel the status changes are sent to.
+    ///
+    /// This allows waiting for changes, with a timeout for example.
+    pub fn receiver(&self) -> &Receiver<StatusChange> {
+        &self.changes
+    }
+
+    fn send(&self, message: Message) {
+        if let Some(ref messages) = self.messages {
+            // The thread only stops when the watcher is dropped
+            messages
+                .send(message)
+                .expect("The status watcher thread stopped");
+        }
+    }
+}
+
+impl Default for StatusWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for StatusWatcher {
+    fn drop(&mut self) {
+        drop(self.messages.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[test]
+fn test_watch() {
+    use crate::audio::NullSource;
+
+    let mut watcher = StatusWatcher::with_duration(Duration::from_millis(1));
+    let source = Arc::new(Mutex::new(NullSource::with_duration(
+        Duration::from_millis(20),
+    )));
+    let other = Arc::new(Mutex::new(NullSource::with_duration(Duration::from_secs(
+        1,
+    ))));
+    let id = watcher.watch(&source);
+    let other_id = watcher.watch(&other);
+    watcher.unwatch(other_id);
+    lock(&source).play();
+    lock(&other).play();
+    let timeout = Duration::from_secs(1);
+    let change = |previous, status| StatusChange {
+        id,
+        previous,
+        status,
+    };
+    assert_eq!(
+        watcher.receiver().recv_timeout(timeout),
+        Ok(change(SoundStatus::Stopped, SoundStatus::Playing))
+    );
+    assert_eq!(
+        watcher.receiver().recv_timeout(timeout),
+        Ok(change(SoundStatus::Playing, SoundStatus::Stopped))
+    );
+    assert_eq!(watcher.poll(), None);
+}
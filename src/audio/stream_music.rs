@@ -0,0 +1,199 @@
+use crate::audio::{SoundSource, SoundStatus, SoundStream, SoundStreamPlayer};
+use crate::system::{Time, Vector3f};
+use std::fmt;
+use std::mem::ManuallyDrop;
+
+/// A sound stream along with the [`SoundStreamPlayer`] playing it.
+///
+/// A [`SoundStreamPlayer`] borrows its stream, so both have to be kept around together.
+/// A `StreamMusic` owns the stream instead, and offers the same interface as [`Music`]
+/// for it. It is the base of [`ProcessedMusic`], and of `DecodedMusic` with the
+/// `symphonia` feature.
+///
+/// Like a [`Music`], it can be moved to a thread dedicated to audio when the stream is `Send`.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{SoundStream, StreamMusic};
+/// use sfml::system::Time;
+/// use std::{thread, time::Duration};
+///
+/// struct Silence(Vec<i16>);
+///
+/// impl SoundStream for Silence {
+///     fn get_data(&mut self) -> (&mut [i16], bool) {
+///         (&mut self.0[..], true)
+///     }
+///     fn seek(&mut self, _offset: Time) {}
+///     fn channel_count(&self) -> u32 {
+///         1
+///     }
+///     fn sample_rate(&self) -> u32 {
+///         44_100
+///     }
+/// }
+///
+/// let mut music = StreamMusic::new(Silence(vec![0; 4096]));
+/// music.play();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(1));
+///     music.stop();
+/// });
+/// ```
+///
+/// [`Music`]: crate::audio::Music
+/// [`ProcessedMusic`]: crate::audio::ProcessedMusic
+pub struct StreamMusic<S: SoundStream + 'static> {
+    player: ManuallyDrop<SoundStreamPlayer<'static, S>>,
+    stream: *mut S,
+    duration: Option<Time>,
+}
+
+// The player and the stream it points to are both `Send` when the stream is.
+unsafe impl<S: SoundStream + Send + 'static> Send for StreamMusic<S> {}
+
+impl<S: SoundStream + 'static> StreamMusic<S> {
+    /// Create a new music playing `stream`.
+    pub fn new(stream: S) -> Self {
+        let duration = stream.duration();
+        // The stream lives on the heap until the player is dropped, see `Drop`
+        let stream = Box::into_raw(Box::new(stream));
+        let player = SoundStreamPlayer::new(unsafe { &mut *stream });
+        StreamMusic {
+            player: ManuallyDrop::new(player),
+            stream,
+            duration,
+        }
+    }
+    /// Start or resume playing the music.
+    pub fn play(&mut self) {
+        self.player.play()
+    }
+    /// Pause the music.
+    pub fn pause(&mut self) {
+        self.player.pause()
+    }
+    /// Stop playing the music, and rewind it to its beginning.
+    pub fn stop(&mut self) {
+        let _ = self.player.stop();
+    }
+    /// Get the current status of the music (stopped, paused, playing).
+    pub fn status(&self) -> SoundStatus {
+        self.player.status()
+    }
+    /// Get the total duration of the music, if it is known.
+    ///
+    /// See [`SoundStream::duration`].
+    pub fn duration(&self) -> Option<Time> {
+        self.duration
+    }
+    /// Get the current playing position of the music.
+    pub fn playing_offset(&self) -> Time {
+        self.player.playing_offset()
+    }
+    /// Change the current playing position of the music.
+    ///
+    /// The playing position can be changed when the music is either paused or playing.
+    pub fn set_playing_offset(&mut self, offset: Time) {
+        self.player.set_playing_offset(offset)
+    }
+    /// Return the number of channels of the music.
+    pub fn channel_count(&self) -> u32 {
+        self.player.channel_count()
+    }
+    /// Get the sample rate of the music.
+    pub fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+    /// Tell whether or not the music is in loop mode.
+    pub fn is_looping(&self) -> bool {
+        self.player.is_looping()
+    }
+    /// Set whether or not the music should loop after reaching the end.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.player.set_looping(looping)
+    }
+}
+
+impl<S: SoundStream + 'static> SoundSource for StreamMusic<S> {
+    fn play(&mut self) {
+        StreamMusic::play(self)
+    }
+    fn pause(&mut self) {
+        StreamMusic::pause(self)
+    }
+    fn stop(&mut self) {
+        StreamMusic::stop(self)
+    }
+    fn set_pitch(&mut self, pitch: f32) {
+        self.player.set_pitch(pitch)
+    }
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume)
+    }
+    fn set_position_vector(&mut self, position: Vector3f) {
+        self.player.set_position_vector(position)
+    }
+    fn set_relative_to_listener(&mut self, relative: bool) {
+        self.player.set_relative_to_listener(relative)
+    }
+    fn set_min_distance(&mut self, distance: f32) {
+        self.player.set_min_distance(distance)
+    }
+    fn set_attenuation(&mut self, attenuation: f32) {
+        self.player.set_attenuation(attenuation)
+    }
+    fn pitch(&self) -> f32 {
+        self.player.pitch()
+    }
+    fn volume(&self) -> f32 {
+        self.player.volume()
+    }
+    fn position(&self) -> Vector3f {
+        self.player.position()
+    }
+    fn is_relative_to_listener(&self) -> bool {
+        self.player.is_relative_to_listener()
+    }
+    fn min_distance(&self) -> f32 {
+        self.player.min_distance()
+    }
+    fn attenuation(&self) -> f32 {
+        self.player.attenuation()
+    }
+    fn status(&self) -> SoundStatus {
+        self.player.status()
+    }
+    fn playing_offset(&self) -> Time {
+        self.player.playing_offset()
+    }
+    fn is_looping(&self) -> bool {
+        self.player.is_looping()
+    }
+    fn set_looping(&mut self, looping: bool) {
+        self.player.set_looping(looping)
+    }
+}
+
+// The stream is used by the streaming thread, it can't be shown
+impl<S: SoundStream + 'static> fmt::Debug for StreamMusic<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StreamMusic")
+            .field("status", &self.status())
+            .field("channel_count", &self.channel_count())
+            .field("sample_rate", &self.sample_rate())
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+impl<S: SoundStream + 'static> Drop for StreamMusic<S> {
+    fn drop(&mut self) {
+        unsafe {
+            // The player stops the streaming thread, it must go before the stream
+            ManuallyDrop::drop(&mut self.player);
+            drop(Box::from_raw(self.stream));
+        }
+    }
+}
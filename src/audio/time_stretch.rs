@@ -0,0 +1,310 @@
+use crate::audio::SoundStream;
+use crate::system::Time;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Handle to change the speed of a [`TimeStretch`] while it plays.
+///
+/// Cloning a control gives another handle to the same speed.
+#[derive(Debug, Clone)]
+pub struct SpeedControl {
+    /// Bits of the speed, as `AtomicU32` needs Rust 1.34.
+    speed: Arc<AtomicUsize>,
+}
+
+impl SpeedControl {
+    /// Set the playback speed, clamped to the range [0.25, 4].
+    ///
+    /// 1 is the normal speed, 2 twice as fast, 0.5 half as fast.
+    pub fn set(&self, speed: f32) {
+        let speed = speed.max(0.25).min(4.);
+        self.speed.store(speed.to_bits() as usize, Ordering::Relaxed)
+    }
+    /// Get the playback speed.
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.speed.load(Ordering::Relaxed) as u32)
+    }
+}
+
+/// A sound stream played faster or slower, without changing its pitch.
+///
+/// This requires the `dsp` feature.
+///
+/// Changing the pitch of a source (see [`SoundSource::set_pitch`]) changes its speed
+/// as well, like a tape played too fast. A `TimeStretch` changes the speed only, by cutting
+/// the stream into short overlapping segments and laying them out with more or less overlap
+/// (this is the WSOLA algorithm). Each segment is aligned on the previous one, so the result
+/// stays smooth. It works best with speeds between 0.5 and 2.
+///
+/// The speed is changed while the stream plays through a [`SpeedControl`].
+///
+/// Only custom sound streams can be stretched: the samples of a [`Music`] never leave SFML.
+/// The `symphonia` feature provides a stream decoding audio files in Rust, for musics.
+///
+/// Note that the playing offset of the player counts the time actually played, while
+/// seeking goes to a position of the underlying stream.
+///
+/// # Example
+///
+/// ```no_run
+/// # use sfml::audio::{SoundStream, SoundStreamPlayer};
+/// # use sfml::system::Time;
+/// # struct Replay(Vec<i16>);
+/// # impl SoundStream for Replay {
+/// #     fn get_data(&mut self) -> (&mut [i16], bool) { (&mut self.0[..], true) }
+/// #     fn seek(&mut self, _: Time) {}
+/// #     fn channel_count(&self) -> u32 { 2 }
+/// #     fn sample_rate(&self) -> u32 { 44_100 }
+/// # }
+/// # let replay = Replay(vec![0; 4096]);
+/// use sfml::audio::TimeStretch;
+///
+/// let mut stream = TimeStretch::new(replay);
+/// let speed = stream.speed_control();
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+/// // Slow motion
+/// speed.set(0.5);
+/// ```
+///
+/// [`SoundSource::set_pitch`]: crate::audio::SoundSource::set_pitch
+/// [`Music`]: crate::audio::Music
+#[derive(Debug)]
+pub struct TimeStretch<S> {
+    stream: S,
+    speed: SpeedControl,
+    channels: usize,
+    /// Length of the overlaps between segments, which is also the output hop, in frames.
+    overlap: usize,
+    /// How far from its nominal position a segment may be moved to align it, in frames.
+    search: usize,
+    /// Fade-in curve of the overlaps, the fade-out being its complement.
+    fade: Vec<f32>,
+    /// Interleaved samples of the underlying stream not consumed yet.
+    input: Vec<f32>,
+    /// Nominal position of the next segment in `input`, in frames.
+    position: f64,
+    /// Second half of the previous segment, which the next one is overlapped with.
+    tail: Vec<f32>,
+    started: bool,
+    ended: bool,
+    output: Vec<i16>,
+}
+
+impl<S: SoundStream> TimeStretch<S> {
+    /// Play `stream` through a time stretch, at normal speed.
+    pub fn new(stream: S) -> Self {
+        let channels = stream.channel_count().max(1) as usize;
+        let rate = stream.sample_rate() as usize;
+        // 40 ms segments, aligned within 10 ms
+        let overlap = (rate / 50).max(1);
+        let fade = (0..overlap)
+            .map(|i| {
+                let x = std::f32::consts::PI * i as f32 / overlap as f32;
+                0.5 - 0.5 * x.cos()
+            })
+            .collect();
+        TimeStretch {
+            stream,
+            speed: SpeedControl {
+                speed: Arc::new(AtomicUsize::new(1f32.to_bits() as usize)),
+            },
+            channels,
+            overlap,
+            search: rate / 100,
+            fade,
+            input: Vec::new(),
+            position: 0.,
+            tail: vec![0.; overlap * channels],
+            started: false,
+            ended: false,
+            output: Vec::new(),
+        }
+    }
+    /// Get a handle to control the speed of the stream.
+    pub fn speed_control(&self) -> SpeedControl {
+        self.speed.clone()
+    }
+    /// Get the current playback speed.
+    pub fn speed(&self) -> f32 {
+        self.speed.get()
+    }
+    /// Set the playback speed. See [`SpeedControl::set`].
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed.set(speed)
+    }
+
+    fn frames(&self) -> usize {
+        self.input.len() / self.channels
+    }
+    /// Pull data from the underlying stream until `frames` frames are available.
+    fn fill(&mut self, frames: usize) {
+        while self.frames() < frames && !self.ended {
+            let (data, keep_playing) = self.stream.get_data();
+            self.input.extend(data.iter().map(|&s| f32::from(s)));
+            self.ended = !keep_playing;
+        }
+    }
+    /// Find the segment start, around `nominal`, best matching the tail of the last segment.
+    fn align(&self, nominal: usize, max_start: usize) -> usize {
+        let channels = self.channels;
+        let low = nominal.saturating_sub(self.search);
+        let high = (nominal + self.search).min(max_start);
+        let mono = |frame: &[f32]| frame.iter().sum::<f32>();
+        let mut best = (nominal.min(high), std::f32::MIN);
+        for start in low..=high {
+            let mut correlation = 0.;
+            let mut energy = 1e-9;
+            // Every fourth frame is plenty to compare segments
+            for i in (0..self.overlap).step_by(4) {
+                let at = (start + i) * channels;
+                let candidate = mono(&self.input[at..at + channels]);
+                let tail = mono(&self.tail[i * channels..(i + 1) * channels]);
+                correlation += candidate * tail;
+                energy += candidate * candidate;
+            }
+            let score = correlation / energy.sqrt();
+            if score > best.1 {
+                best = (start, score);
+            }
+        }
+        best.0
+    }
+    /// Lay out the next segment, pushing one hop of output.
+    ///
+    /// Returns `false` once the underlying stream is exhausted.
+    fn step(&mut self) -> bool {
+        let channels = self.channels;
+        let segment = 2 * self.overlap;
+        let nominal = self.position as usize;
+        self.fill(nominal + self.search + segment);
+        if self.frames() < nominal + segment {
+            return false;
+        }
+        let start = if self.started {
+            self.align(nominal, self.frames() - segment)
+        } else {
+            nominal
+        };
+        for i in 0..self.overlap {
+            let fade_in = if self.started { self.fade[i] } else { 1. };
+            for c in 0..channels {
+                let new = self.input[(start + i) * channels + c];
+                let old = self.tail[i * channels + c];
+                let sample = new * fade_in + old * (1. - fade_in);
+                self.output.push(sample.max(-32_768.).min(32_767.) as i16);
+            }
+        }
+        let tail_start = (start + self.overlap) * channels;
+        self.tail
+            .copy_from_slice(&self.input[tail_start..tail_start + self.overlap * channels]);
+        self.started = true;
+        self.position += self.overlap as f64 * f64::from(self.speed());
+        // Drop the input that can't be used anymore, once there is enough of it
+        let unused = (self.position as usize).saturating_sub(self.search);
+        if unused > 4 * segment {
+            drop(self.input.drain(..unused * channels));
+            self.position -= unused as f64;
+        }
+        true
+    }
+}
+
+impl<S> TimeStretch<S> {
+    /// Access the underlying stream.
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+    /// Mutably access the underlying stream.
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+    /// Unwrap the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: SoundStream> SoundStream for TimeStretch<S> {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        self.output.clear();
+        // About 50 ms per chunk
+        let target = self.stream.sample_rate() as usize / 20 * self.channels;
+        let mut keep_playing = true;
+        while self.output.len() < target {
+            if !self.step() {
+                // Nothing follows the last segment, play the rest of it as is
+                if self.started {
+                    self.output.extend(
+                        self.tail
+                            .iter()
+                            .map(|&s| s.max(-32_768.).min(32_767.) as i16),
+                    );
+                }
+                keep_playing = false;
+                break;
+            }
+        }
+        (&mut self.output[..], keep_playing)
+    }
+    fn seek(&mut self, offset: Time) {
+        self.input.clear();
+        self.position = 0.;
+        for sample in &mut self.tail {
+            *sample = 0.;
+        }
+        self.started = false;
+        self.ended = false;
+        self.stream.seek(offset);
+    }
+    fn channel_count(&self) -> u32 {
+        self.stream.channel_count()
+    }
+    fn sample_rate(&self) -> u32 {
+        self.stream.sample_rate()
+    }
+}
+
+#[test]
+fn test_length() {
+    struct Tone(Vec<i16>, usize);
+    impl SoundStream for Tone {
+        fn get_data(&mut self) -> (&mut [i16], bool) {
+            for (i, sample) in self.0.iter_mut().enumerate() {
+                let t = (self.1 + i) as f32 / 8_000.;
+                *sample = ((t * 440. * 2. * std::f32::consts::PI).sin() * 10_000.) as i16;
+            }
+            self.1 += self.0.len();
+            (&mut self.0[..], self.1 < 8_000)
+        }
+        fn seek(&mut self, _: Time) {
+            self.1 = 0;
+        }
+        fn channel_count(&self) -> u32 {
+            1
+        }
+        fn sample_rate(&self) -> u32 {
+            8_000
+        }
+    }
+    for &speed in &[0.5, 1., 2.] {
+        let mut stream = TimeStretch::new(Tone(vec![0; 500], 0));
+        stream.set_speed(speed);
+        let mut len = 0;
+        loop {
+            let (data, keep_playing) = stream.get_data();
+            len += data.len();
+            if !keep_playing {
+                break;
+            }
+        }
+        // One second of input
+        let expected = 8_000. / speed;
+        assert!(
+            (len as f32 - expected).abs() < expected * 0.05,
+            "{} {}",
+            speed,
+            len
+        );
+    }
+}
@@ -0,0 +1,628 @@
+use crate::audio::{SoundRecorder, SoundStream};
+use crate::byte_order::ByteOrder;
+#[cfg(feature = "network")]
+use crate::network::Packet;
+use crate::system::Time;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Number of voice packets per second, each one holding 20 ms of audio.
+const PACKETS_PER_SECOND: u32 = 50;
+/// Codec and sequence number.
+const HEADER_LEN: usize = 3;
+/// Number of packets kept by a capture that nobody polls, or by a playback ahead of time.
+const MAX_QUEUED: usize = 50;
+/// Largest Opus packet, as recommended by the Opus documentation.
+#[cfg(feature = "opus")]
+const MAX_OPUS_PACKET: usize = 4_000;
+
+/// Compression applied to the samples of a [`VoicePacket`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceCodec {
+    /// Uncompressed 16-bit samples.
+    Pcm,
+    /// G.711 μ-law, which stores each sample in 8 bits.
+    ///
+    /// This halves the size of the packets for little processing and a slight loss of
+    /// quality, which is fine for speech.
+    MuLaw,
+    /// Opus, which compresses speech about ten times better than μ-law, and hides lost
+    /// packets well.
+    ///
+    /// This requires the `opus` feature, which links to libopus.
+    /// Opus only supports sample rates of 8, 12, 16, 24 and 48 kHz, and one or two channels.
+    #[cfg(feature = "opus")]
+    Opus,
+}
+
+impl VoiceCodec {
+    fn id(self) -> u8 {
+        match self {
+            VoiceCodec::Pcm => 0,
+            VoiceCodec::MuLaw => 1,
+            #[cfg(feature = "opus")]
+            VoiceCodec::Opus => 2,
+        }
+    }
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(VoiceCodec::Pcm),
+            1 => Some(VoiceCodec::MuLaw),
+            #[cfg(feature = "opus")]
+            2 => Some(VoiceCodec::Opus),
+            _ => None,
+        }
+    }
+}
+
+const MU_LAW_BIAS: i32 = 0x84;
+const MU_LAW_CLIP: i32 = 32_635;
+
+fn mu_law_encode(sample: i16) -> u8 {
+    let sample = i32::from(sample);
+    let sign = if sample < 0 { 0x80 } else { 0 };
+    let magnitude = sample.abs().min(MU_LAW_CLIP) + MU_LAW_BIAS;
+    let exponent = 24 - magnitude.leading_zeros() as i32;
+    let mantissa = (magnitude >> (exponent + 3)) & 0x0F;
+    !(sign | (exponent << 4) | mantissa) as u8
+}
+
+fn mu_law_decode(byte: u8) -> i16 {
+    let byte = !byte;
+    let exponent = (byte >> 4) & 0x07;
+    let mantissa = i32::from(byte & 0x0F);
+    let magnitude = (((mantissa << 3) + MU_LAW_BIAS) << exponent) - MU_LAW_BIAS;
+    if byte & 0x80 != 0 {
+        -magnitude as i16
+    } else {
+        magnitude as i16
+    }
+}
+
+#[cfg(feature = "opus")]
+fn opus_channels(channel_count: u32) -> Option<opus::Channels> {
+    match channel_count {
+        1 => Some(opus::Channels::Mono),
+        2 => Some(opus::Channels::Stereo),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+enum Encoder {
+    Pcm,
+    MuLaw,
+    #[cfg(feature = "opus")]
+    Opus(opus::Encoder),
+}
+
+impl Encoder {
+    fn new(codec: VoiceCodec, _channel_count: u32, _sample_rate: u32) -> Option<Self> {
+        match codec {
+            VoiceCodec::Pcm => Some(Encoder::Pcm),
+            VoiceCodec::MuLaw => Some(Encoder::MuLaw),
+            #[cfg(feature = "opus")]
+            VoiceCodec::Opus => opus::Encoder::new(
+                _sample_rate,
+                opus_channels(_channel_count)?,
+                opus::Application::Voip,
+            )
+            .ok()
+            .map(Encoder::Opus),
+        }
+    }
+    fn encode(&mut self, samples: &[i16], out: &mut Vec<u8>) {
+        match *self {
+            Encoder::Pcm => {
+                for sample in samples {
+                    out.extend_from_slice(&sample.le_bytes());
+                }
+            }
+            Encoder::MuLaw => out.extend(samples.iter().map(|&s| mu_law_encode(s))),
+            #[cfg(feature = "opus")]
+            Encoder::Opus(ref mut encoder) => {
+                let start = out.len();
+                out.resize(start + MAX_OPUS_PACKET, 0);
+                // Frames of 20 ms are always valid, encoding can't fail
+                let len = encoder.encode(samples, &mut out[start..]).unwrap_or(0);
+                out.truncate(start + len);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Decoder {
+    Pcm,
+    MuLaw,
+    #[cfg(feature = "opus")]
+    Opus(opus::Decoder),
+}
+
+impl Decoder {
+    fn new(codec: VoiceCodec, _channel_count: u32, _sample_rate: u32) -> Option<Self> {
+        match codec {
+            VoiceCodec::Pcm => Some(Decoder::Pcm),
+            VoiceCodec::MuLaw => Some(Decoder::MuLaw),
+            #[cfg(feature = "opus")]
+            VoiceCodec::Opus => opus::Decoder::new(_sample_rate, opus_channels(_channel_count)?)
+                .ok()
+                .map(Decoder::Opus),
+        }
+    }
+    /// Decode the payload of a packet into `out`, which holds a frame.
+    ///
+    /// A missing payload stands for a lost packet.
+    fn decode(&mut self, payload: Option<&[u8]>, out: &mut [i16]) {
+        match (self, payload) {
+            (&mut Decoder::Pcm, Some(payload)) => {
+                let samples = payload
+                    .chunks_exact(2)
+                    .map(|bytes| i16::from_le_array([bytes[0], bytes[1]]));
+                fill_frame(out, samples);
+            }
+            (&mut Decoder::MuLaw, Some(payload)) => {
+                fill_frame(out, payload.iter().map(|&byte| mu_law_decode(byte)));
+            }
+            #[cfg(feature = "opus")]
+            (&mut Decoder::Opus(ref mut decoder), payload) => {
+                // An empty packet asks the decoder to make up for the lost one
+                if decoder.decode(payload.unwrap_or(&[]), out, false).is_err() {
+                    out.iter_mut().for_each(|s| *s = 0);
+                }
+            }
+            (_, None) => out.iter_mut().for_each(|s| *s = 0),
+        }
+    }
+}
+
+/// Write decoded samples to a frame, and silence the rest of it when a truncated payload
+/// doesn't fill it, so that nothing of the previous frame is played again.
+fn fill_frame<I: Iterator<Item = i16>>(out: &mut [i16], samples: I) {
+    let mut decoded = 0;
+    for (sample, value) in out.iter_mut().zip(samples) {
+        *sample = value;
+        decoded += 1;
+    }
+    out[decoded..].iter_mut().for_each(|s| *s = 0);
+}
+
+/// Audio ready to be sent over the network, as made by a [`VoiceCapture`].
+///
+/// A packet holds 20 ms of audio, compressed with a [`VoiceCodec`], and a sequence number
+/// which lets a [`VoicePlayback`] put the packets back in order, and notice lost ones.
+/// Packets are meant to be sent as they are made, over UDP for example: a voice chat
+/// is better off skipping a late packet than waiting for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoicePacket {
+    bytes: Vec<u8>,
+}
+
+impl VoicePacket {
+    /// Read a packet from the bytes received from the network.
+    ///
+    /// Returns `None` if the bytes are not a voice packet.
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        if bytes.len() < HEADER_LEN || VoiceCodec::from_id(bytes[0]).is_none() {
+            None
+        } else {
+            Some(VoicePacket { bytes })
+        }
+    }
+    /// Get the bytes to send over the network.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    /// Unwrap the bytes to send over the network.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+    /// Get the codec the audio of the packet is compressed with.
+    pub fn codec(&self) -> VoiceCodec {
+        VoiceCodec::from_id(self.bytes[0]).expect("voice packets have a valid codec")
+    }
+    /// Get the sequence number of the packet.
+    ///
+    /// Each packet of a capture has the number of the previous one plus one,
+    /// wrapping around after 65535.
+    pub fn sequence(&self) -> u16 {
+        u16::from_be_array([self.bytes[1], self.bytes[2]])
+    }
+    /// Copy the packet into a network packet.
+    ///
+    /// This requires the `network` feature.
+    #[cfg(feature = "network")]
+    pub fn to_packet(&self) -> Packet {
+        let mut packet = Packet::new();
+        packet.append(&self.bytes);
+        packet
+    }
+    /// Read a packet from a network packet.
+    ///
+    /// This requires the `network` feature.
+    ///
+    /// Returns `None` if the network packet doesn't hold a voice packet.
+    #[cfg(feature = "network")]
+    pub fn from_packet(packet: &Packet) -> Option<Self> {
+        Self::from_bytes(packet.data().to_vec())
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.bytes[HEADER_LEN..]
+    }
+}
+
+fn frame_len(channel_count: u32, sample_rate: u32) -> usize {
+    (sample_rate / PACKETS_PER_SECOND * channel_count) as usize
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A panic while the lock is held can at worst lose some audio
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+#[derive(Debug)]
+struct CaptureState {
+    encoder: Encoder,
+    /// Samples not making a full frame yet.
+    pending: Vec<i16>,
+    sequence: u16,
+    packets: VecDeque<VoicePacket>,
+}
+
+/// A [`SoundRecorder`] turning the captured audio into packets for a voice chat.
+///
+/// The captured samples are cut into frames of 20 ms, compressed with a [`VoiceCodec`],
+/// and queued as [`VoicePacket`]s. Cloning a capture gives another handle to the same
+/// queue: the driver borrows one of them while recording, and the packets can be taken
+/// from the other one with [`poll`], from any thread, to send them. At the other end,
+/// a [`VoicePlayback`] plays them.
+///
+/// At most 50 packets (one second) are kept, the oldest ones are discarded if the capture
+/// isn't polled.
+///
+/// With the `network` feature, packets convert to and from network `Packet`s
+/// (see `VoicePacket::to_packet`), otherwise their bytes can be sent with any socket.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{SoundRecorderDriver, VoiceCapture, VoiceCodec};
+/// use sfml::system::Time;
+/// use std::net::UdpSocket;
+///
+/// let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
+/// socket.connect("192.168.1.12:4242").unwrap();
+/// let capture = VoiceCapture::new(VoiceCodec::MuLaw, 1, 16_000).unwrap();
+/// let mut recorder = capture.clone();
+/// let mut driver = SoundRecorderDriver::new(&mut recorder);
+/// driver.set_processing_interval(Time::milliseconds(20));
+/// driver.start(16_000);
+/// loop {
+///     while let Some(packet) = capture.poll() {
+///         let _ = socket.send(packet.as_bytes());
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// [`poll`]: VoiceCapture::poll
+#[derive(Debug, Clone)]
+pub struct VoiceCapture {
+    state: Arc<Mutex<CaptureState>>,
+    codec: VoiceCodec,
+    channel_count: u32,
+    sample_rate: u32,
+}
+
+impl VoiceCapture {
+    /// Create a new voice capture.
+    ///
+    /// The channel count and sample rate must be those the recorder is started with.
+    ///
+    /// # Arguments
+    /// * codec - Compression of the packets
+    /// * channel_count - Number of channels of the captured samples (1 = mono, 2 = stereo)
+    /// * sample_rate - Sample rate of the captured samples, in samples per second
+    ///
+    /// Returns `None` if the codec doesn't support these channel count and sample rate.
+    pub fn new(codec: VoiceCodec, channel_count: u32, sample_rate: u32) -> Option<Self> {
+        if frame_len(channel_count, sample_rate) == 0 {
+            return None;
+        }
+        Some(VoiceCapture {
+            state: Arc::new(Mutex::new(CaptureState {
+                encoder: Encoder::new(codec, channel_count, sample_rate)?,
+                pending: Vec::new(),
+                sequence: 0,
+                packets: VecDeque::new(),
+            })),
+            codec,
+            channel_count,
+            sample_rate,
+        })
+    }
+    /// Take the oldest packet not sent yet, if any.
+    pub fn poll(&self) -> Option<VoicePacket> {
+        lock(&self.state).packets.pop_front()
+    }
+    /// Get the codec of the packets.
+    pub fn codec(&self) -> VoiceCodec {
+        self.codec
+    }
+    /// Get the number of channels of the captured samples.
+    pub fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+    /// Get the sample rate of the captured samples.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+impl CaptureState {
+    fn push_frame(&mut self, frame: &[i16], codec: VoiceCodec) {
+        let sequence = self.sequence;
+        self.sequence = sequence.wrapping_add(1);
+        let mut bytes = vec![codec.id()];
+        bytes.extend_from_slice(&sequence.be_bytes());
+        self.encoder.encode(frame, &mut bytes);
+        if self.packets.len() == MAX_QUEUED {
+            let _ = self.packets.pop_front();
+        }
+        self.packets.push_back(VoicePacket { bytes });
+    }
+}
+
+impl SoundRecorder for VoiceCapture {
+    fn on_process_samples(&mut self, samples: &[i16]) -> bool {
+        let frame_len = frame_len(self.channel_count, self.sample_rate);
+        let mut state = lock(&self.state);
+        let mut pending = std::mem::replace(&mut state.pending, Vec::new());
+        pending.extend_from_slice(samples);
+        let mut frames = pending.chunks_exact(frame_len);
+        for frame in &mut frames {
+            state.push_frame(frame, self.codec);
+        }
+        state.pending = frames.remainder().to_vec();
+        true
+    }
+    fn on_stop(&mut self) {
+        // Send the end of the recording, completed with silence
+        let frame_len = frame_len(self.channel_count, self.sample_rate);
+        let mut state = lock(&self.state);
+        if !state.pending.is_empty() {
+            let mut frame = std::mem::replace(&mut state.pending, Vec::new());
+            frame.resize(frame_len, 0);
+            state.push_frame(&frame, self.codec);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PlaybackState {
+    decoder: Decoder,
+    latency: usize,
+    packets: HashMap<u16, VoicePacket>,
+    /// Sequence number of the next packet to play, `None` while buffering.
+    next: Option<u16>,
+    /// Lowest sequence number received while buffering.
+    first: Option<u16>,
+}
+
+/// Tell whether the sequence number `a` comes before `b`, accounting for wrapping.
+fn is_before(a: u16, b: u16) -> bool {
+    (a.wrapping_sub(b) as i16) < 0
+}
+
+impl PlaybackState {
+    fn push(&mut self, packet: VoicePacket) {
+        let sequence = packet.sequence();
+        match self.next {
+            // Too late, the silence or the guess is already played
+            Some(next) if is_before(sequence, next) => return,
+            Some(next) if usize::from(sequence.wrapping_sub(next)) >= MAX_QUEUED => {
+                // The sender restarted, or a long outage happened
+                self.reset();
+            }
+            _ => {}
+        }
+        if self.packets.len() >= MAX_QUEUED {
+            return;
+        }
+        if self.next.is_none() && self.first.map_or(true, |first| is_before(sequence, first)) {
+            self.first = Some(sequence);
+        }
+        let _ = self.packets.insert(sequence, packet);
+    }
+    fn next_frame(&mut self, out: &mut [i16]) {
+        if self.next.is_none() && self.packets.len() >= self.latency {
+            self.next = self.first;
+        }
+        match self.next {
+            None => out.iter_mut().for_each(|s| *s = 0),
+            Some(next) => {
+                let packet = self.packets.remove(&next);
+                self.decoder
+                    .decode(packet.as_ref().map(|p| p.payload()), out);
+                self.next = Some(next.wrapping_add(1));
+                if packet.is_none() && self.packets.is_empty() {
+                    // The sender stopped, buffer again when it comes back
+                    self.reset();
+                }
+            }
+        }
+    }
+    fn reset(&mut self) {
+        self.packets.clear();
+        self.next = None;
+        self.first = None;
+    }
+}
+
+/// A sound stream playing the [`VoicePacket`]s of a remote [`VoiceCapture`].
+///
+/// Packets arrive late, out of order, or not at all. The playback keeps them in a small
+/// buffer, and only starts playing once a few are there (3 by default, that is 60 ms), so
+/// that it has something to play while the next ones are on their way. Missing packets
+/// are played as silence, or guessed from the previous ones with Opus.
+///
+/// Cloning a playback gives another handle to the same buffer: play one clone with a
+/// [`SoundStreamPlayer`], and [`push`] the received packets into the other one, from any
+/// thread. The stream never ends, it plays silence when nobody speaks.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{SoundStreamPlayer, VoiceCodec, VoicePacket, VoicePlayback};
+/// use std::net::UdpSocket;
+///
+/// let socket = UdpSocket::bind("0.0.0.0:4242").unwrap();
+/// let playback = VoicePlayback::new(VoiceCodec::MuLaw, 1, 16_000).unwrap();
+/// let mut stream = playback.clone();
+/// let mut player = SoundStreamPlayer::new(&mut stream);
+/// player.play();
+/// let mut bytes = [0; 1500];
+/// loop {
+///     let len = socket.recv(&mut bytes).unwrap();
+///     if let Some(packet) = VoicePacket::from_bytes(bytes[..len].to_vec()) {
+///         playback.push(packet);
+///     }
+/// }
+/// ```
+///
+/// [`SoundStreamPlayer`]: crate::audio::SoundStreamPlayer
+/// [`push`]: VoicePlayback::push
+#[derive(Debug, Clone)]
+pub struct VoicePlayback {
+    state: Arc<Mutex<PlaybackState>>,
+    codec: VoiceCodec,
+    channel_count: u32,
+    sample_rate: u32,
+    buffer: Vec<i16>,
+}
+
+impl VoicePlayback {
+    /// Create a new voice playback.
+    ///
+    /// The codec, channel count and sample rate must be those of the remote capture.
+    ///
+    /// # Arguments
+    /// * codec - Compression of the packets
+    /// * channel_count - Number of channels of the audio (1 = mono, 2 = stereo)
+    /// * sample_rate - Sample rate of the audio, in samples per second
+    ///
+    /// Returns `None` if the codec doesn't support these channel count and sample rate.
+    pub fn new(codec: VoiceCodec, channel_count: u32, sample_rate: u32) -> Option<Self> {
+        if frame_len(channel_count, sample_rate) == 0 {
+            return None;
+        }
+        Some(VoicePlayback {
+            state: Arc::new(Mutex::new(PlaybackState {
+                decoder: Decoder::new(codec, channel_count, sample_rate)?,
+                latency: 3,
+                packets: HashMap::new(),
+                next: None,
+                first: None,
+            })),
+            codec,
+            channel_count,
+            sample_rate,
+            buffer: Vec::new(),
+        })
+    }
+    /// Add a received packet to the buffer.
+    ///
+    /// Packets of another codec, and packets arriving after their turn, are ignored.
+    pub fn push(&self, packet: VoicePacket) {
+        if packet.codec() == self.codec {
+            lock(&self.state).push(packet);
+        }
+    }
+    /// Change the number of packets buffered before playing starts.
+    ///
+    /// A larger buffer copes better with irregular networks, at the cost of latency:
+    /// each packet holds 20 ms of audio. The default is 3.
+    pub fn set_latency(&self, packets: usize) {
+        lock(&self.state).latency = packets.max(1).min(MAX_QUEUED);
+    }
+    /// Get the number of packets buffered before playing starts.
+    pub fn latency(&self) -> usize {
+        lock(&self.state).latency
+    }
+}
+
+impl SoundStream for VoicePlayback {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        self.buffer
+            .resize(frame_len(self.channel_count, self.sample_rate), 0);
+        lock(&self.state).next_frame(&mut self.buffer);
+        (&mut self.buffer[..], true)
+    }
+    fn seek(&mut self, _offset: Time) {
+        // A live stream can't seek, start over with the next packets
+        lock(&self.state).reset();
+    }
+    fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+#[test]
+fn test_pipeline() {
+    for &sample in &[
+        0,
+        1,
+        -1,
+        100,
+        -1_000,
+        12_345,
+        i16::max_value(),
+        i16::min_value(),
+    ] {
+        let error = (i32::from(mu_law_decode(mu_law_encode(sample))) - i32::from(sample)).abs();
+        assert!(error <= 4 + i32::from(sample).abs() / 16, "{}", sample);
+    }
+
+    // 1000 samples per second make frames of 20 samples
+    let mut capture = VoiceCapture::new(VoiceCodec::Pcm, 1, 1_000).unwrap();
+    let samples: Vec<i16> = (0..90).collect();
+    assert!(capture.on_process_samples(&samples[..50]));
+    assert!(capture.on_process_samples(&samples[50..]));
+    capture.on_stop();
+    let mut packets = Vec::new();
+    while let Some(packet) = capture.poll() {
+        packets.push(packet);
+    }
+    assert_eq!(packets.len(), 5);
+    assert_eq!(packets[4].sequence(), 4);
+
+    // A truncated payload doesn't leave samples of the previous frame behind
+    let mut decoder = Decoder::new(VoiceCodec::Pcm, 1, 1_000).unwrap();
+    let mut frame = [7; 4];
+    decoder.decode(Some(&[1, 0, 2, 0, 3]), &mut frame);
+    assert_eq!(frame, [1, 2, 0, 0]);
+
+    let mut playback = VoicePlayback::new(VoiceCodec::Pcm, 1, 1_000).unwrap();
+    // Out of order, and the third packet is lost
+    packets.swap(0, 1);
+    let _ = packets.remove(2);
+    for packet in packets.drain(..2) {
+        playback.push(VoicePacket::from_bytes(packet.into_bytes()).unwrap());
+    }
+    assert_eq!(playback.get_data().0, &[0; 20][..]);
+    for packet in packets {
+        playback.push(packet);
+    }
+    let frames: Vec<Vec<i16>> = (0..4).map(|_| playback.get_data().0.to_vec()).collect();
+    let mut expected: Vec<i16> = (0..90).collect();
+    expected.resize(100, 0);
+    expected[40..60].iter_mut().for_each(|s| *s = 0);
+    assert_eq!(frames.concat(), &expected[..80]);
+}
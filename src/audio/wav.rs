@@ -0,0 +1,58 @@
+//! Minimal writer for 16-bit PCM WAV files.
+
+use crate::byte_order::ByteOrder;
+use std::io::{self, Write};
+
+/// Size of the header written by [`write_header`], in bytes.
+pub(super) const HEADER_LEN: u32 = 44;
+/// Data size written in the header when it isn't known yet, as most readers expect.
+pub(super) const UNKNOWN_LEN: u32 = u32::max_value();
+
+/// Write the header of a WAV file holding `data_len` bytes of 16-bit samples.
+///
+/// The size of the file saturates, so that [`UNKNOWN_LEN`] stays unknown.
+pub(super) fn write_header<W: Write>(
+    writer: &mut W,
+    channel_count: u32,
+    sample_rate: u32,
+    data_len: u32,
+) -> io::Result<()> {
+    let block_align = channel_count * 2;
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(HEADER_LEN - 8).saturating_add(data_len).le_bytes())?;
+    writer.write_all(b"WAVEfmt ")?;
+    writer.write_all(&16u32.le_bytes())?;
+    // PCM
+    writer.write_all(&1u16.le_bytes())?;
+    writer.write_all(&(channel_count as u16).le_bytes())?;
+    writer.write_all(&sample_rate.le_bytes())?;
+    writer.write_all(&(sample_rate * block_align).le_bytes())?;
+    writer.write_all(&(block_align as u16).le_bytes())?;
+    writer.write_all(&16u16.le_bytes())?;
+    writer.write_all(b"data")?;
+    writer.write_all(&data_len.le_bytes())
+}
+
+/// Write `samples` as little endian 16-bit integers.
+pub(super) fn write_samples<W: Write>(writer: &mut W, samples: &[i16]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(samples.len() * 2);
+    for sample in samples {
+        bytes.extend_from_slice(&sample.le_bytes());
+    }
+    writer.write_all(&bytes)
+}
+
+#[test]
+fn test_header() {
+    let mut header = Vec::new();
+    write_header(&mut header, 2, 44_100, 400).unwrap();
+    assert_eq!(header.len(), HEADER_LEN as usize);
+    assert_eq!(&header[0..4], b"RIFF");
+    assert_eq!(&header[4..8], &436u32.le_bytes());
+    assert_eq!(&header[28..32], &176_400u32.le_bytes());
+    assert_eq!(&header[40..44], &400u32.le_bytes());
+    header.clear();
+    write_header(&mut header, 2, 44_100, UNKNOWN_LEN).unwrap();
+    assert_eq!(&header[4..8], &u32::max_value().le_bytes());
+    assert_eq!(&header[40..44], &u32::max_value().le_bytes());
+}
@@ -0,0 +1,244 @@
+use crate::audio::{wav, SoundRecorder};
+use crate::system::Time;
+use std::fmt;
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Writes audio samples to a WAV file as they come.
+///
+/// A [`SoundBufferRecorder`] keeps the whole recording in memory, which gets big quickly:
+/// an hour of stereo audio at 44.1 kHz takes more than 600 MB. A `WavEncoder` writes the
+/// samples to its writer as soon as they are given to it instead, so that only the current
+/// chunk is held in memory. It is a [`SoundRecorder`], so it can be driven by a
+/// [`SoundRecorderDriver`] directly, and custom recorders can feed it with
+/// [`write_samples`] as well.
+///
+/// The header of the file needs the size of the data, so it is written again by [`finish`],
+/// or when the encoder is dropped, when the writer can seek (see [`new`]). Writers that
+/// can't seek, such as pipes and sockets, are given a header with an unknown size instead
+/// (see [`new_streaming`]), which most readers take as "until the end of the file".
+///
+/// SFML can only encode the other formats (ogg, flac) from a complete [`SoundBuffer`].
+/// To record them incrementally as well, see [`Encoder`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::audio::{SoundRecorderDriver, WavEncoder};
+/// use std::fs::File;
+/// use std::io::BufWriter;
+///
+/// let file = BufWriter::new(File::create("interview.wav").unwrap());
+/// let mut encoder = WavEncoder::new(file, 1, 44_100).unwrap();
+/// {
+///     let mut driver = SoundRecorderDriver::new(&mut encoder);
+///     driver.start(44_100);
+///     // ...
+///     driver.stop();
+/// }
+/// encoder.finish().unwrap();
+/// ```
+///
+/// [`SoundBufferRecorder`]: crate::audio::SoundBufferRecorder
+/// [`SoundRecorderDriver`]: crate::audio::SoundRecorderDriver
+/// [`SoundBuffer`]: crate::audio::SoundBuffer
+/// [`Encoder`]: crate::audio::Encoder
+/// [`write_samples`]: WavEncoder::write_samples
+/// [`finish`]: WavEncoder::finish
+/// [`new`]: WavEncoder::new
+/// [`new_streaming`]: WavEncoder::new_streaming
+pub struct WavEncoder<W: Write> {
+    /// Only `None` once the encoder is finished.
+    writer: Option<W>,
+    /// Writes the final header, when the writer can seek back to it.
+    patch_header: Option<PatchHeader<W>>,
+    channel_count: u32,
+    sample_rate: u32,
+    data_len: u32,
+    /// First write error hit while recording, which stopped the recording.
+    error: Option<io::Error>,
+}
+
+/// Writes the header of a file again, given its channel count, sample rate and data size.
+type PatchHeader<W> = fn(&mut W, u32, u32, u32) -> io::Result<()>;
+
+// Function pointers taking references only implement `Debug` from Rust 1.35
+impl<W: Write + fmt::Debug> fmt::Debug for WavEncoder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WavEncoder")
+            .field("writer", &self.writer)
+            .field("seekable", &self.patch_header.is_some())
+            .field("channel_count", &self.channel_count)
+            .field("sample_rate", &self.sample_rate)
+            .field("data_len", &self.data_len)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<W: Write + Seek> WavEncoder<W> {
+    /// Create a new encoder, and write the header of the file.
+    ///
+    /// The header is written at the current position of `writer`, and written again
+    /// with the size of the data by [`finish`].
+    ///
+    /// # Arguments
+    /// * writer - Where to write the file
+    /// * channel_count - Number of channels of the samples (1 = mono, 2 = stereo)
+    /// * sample_rate - Sample rate of the samples, in samples per second
+    ///
+    /// [`finish`]: WavEncoder::finish
+    pub fn new(writer: W, channel_count: u32, sample_rate: u32) -> io::Result<Self> {
+        Self::with_header(
+            writer,
+            channel_count,
+            sample_rate,
+            0,
+            Some(patch_header::<W>),
+        )
+    }
+}
+
+impl<W: Write> WavEncoder<W> {
+    /// Create a new encoder for a writer that can't seek, and write the header of the file.
+    ///
+    /// The header holds an unknown size, and is never written again: the file is
+    /// complete as soon as the samples are written.
+    ///
+    /// # Arguments
+    /// * writer - Where to write the file, a pipe or a socket for example
+    /// * channel_count - Number of channels of the samples (1 = mono, 2 = stereo)
+    /// * sample_rate - Sample rate of the samples, in samples per second
+    pub fn new_streaming(writer: W, channel_count: u32, sample_rate: u32) -> io::Result<Self> {
+        Self::with_header(writer, channel_count, sample_rate, wav::UNKNOWN_LEN, None)
+    }
+    fn with_header(
+        mut writer: W,
+        channel_count: u32,
+        sample_rate: u32,
+        header_data_len: u32,
+        patch_header: Option<PatchHeader<W>>,
+    ) -> io::Result<Self> {
+        wav::write_header(&mut writer, channel_count, sample_rate, header_data_len)?;
+        Ok(WavEncoder {
+            writer: Some(writer),
+            patch_header,
+            channel_count,
+            sample_rate,
+            data_len: 0,
+            error: None,
+        })
+    }
+    /// Append interleaved samples to the file.
+    ///
+    /// WAV files can't hold more than 4 GB of data, writing past that fails.
+    pub fn write_samples(&mut self, samples: &[i16]) -> io::Result<()> {
+        let len = (samples.len() as u64 * 2)
+            .checked_add(u64::from(self.data_len))
+            .filter(|&len| len <= u64::from(u32::max_value() - wav::HEADER_LEN))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    "WAV files can't hold more than 4 GB of data",
+                )
+            })?;
+        wav::write_samples(self.writer(), samples)?;
+        self.data_len = len as u32;
+        Ok(())
+    }
+    /// Get the duration of the audio written so far.
+    pub fn duration(&self) -> Time {
+        let frames = self.data_len / 2 / self.channel_count.max(1);
+        Time::seconds(frames as f32 / self.sample_rate as f32)
+    }
+    /// Get the error that stopped the recording, if any.
+    ///
+    /// When driven by a [`SoundRecorderDriver`], a failed write stops the recording,
+    /// and the error is kept here.
+    ///
+    /// [`SoundRecorderDriver`]: crate::audio::SoundRecorderDriver
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+    /// Write the final header of the file if the writer can seek, and get the writer back.
+    ///
+    /// The writer is flushed, and left at the end of the file.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_header()?;
+        Ok(self.writer.take().expect("WavEncoder: already finished"))
+    }
+
+    fn writer(&mut self) -> &mut W {
+        self.writer.as_mut().expect("WavEncoder: already finished")
+    }
+    fn write_header(&mut self) -> io::Result<()> {
+        let (channel_count, sample_rate, data_len) =
+            (self.channel_count, self.sample_rate, self.data_len);
+        let patch_header = self.patch_header;
+        let writer = self.writer();
+        if let Some(patch_header) = patch_header {
+            patch_header(writer, channel_count, sample_rate, data_len)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Write the header before the `data_len` bytes of data just written, and seek back.
+fn patch_header<W: Write + Seek>(
+    writer: &mut W,
+    channel_count: u32,
+    sample_rate: u32,
+    data_len: u32,
+) -> io::Result<()> {
+    let end = writer.seek(SeekFrom::Current(0))?;
+    let _ = writer.seek(SeekFrom::Start(end - u64::from(wav::HEADER_LEN + data_len)))?;
+    wav::write_header(writer, channel_count, sample_rate, data_len)?;
+    let _ = writer.seek(SeekFrom::Start(end))?;
+    Ok(())
+}
+
+impl<W: Write> SoundRecorder for WavEncoder<W> {
+    fn on_process_samples(&mut self, samples: &[i16]) -> bool {
+        match self.write_samples(samples) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error = Some(e);
+                false
+            }
+        }
+    }
+}
+
+impl<W: Write> Drop for WavEncoder<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            // Like a `BufWriter`, errors can't be reported from here, see `finish`
+            let _ = self.write_header();
+        }
+    }
+}
+
+#[test]
+fn test_encode() {
+    use std::io::Cursor;
+
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_all(b"prefix").unwrap();
+    let mut encoder = WavEncoder::new(cursor, 2, 8_000).unwrap();
+    encoder.write_samples(&[1, -1, 2, -2]).unwrap();
+    assert!(encoder.on_process_samples(&[3, -3]));
+    let data = encoder.finish().unwrap().into_inner();
+    let data = &data[6..];
+    let mut expected = Vec::new();
+    wav::write_header(&mut expected, 2, 8_000, 12).unwrap();
+    wav::write_samples(&mut expected, &[1, -1, 2, -2, 3, -3]).unwrap();
+    assert_eq!(data, &expected[..]);
+
+    // Not seekable
+    let mut encoder = WavEncoder::new_streaming(Vec::new(), 1, 8_000).unwrap();
+    encoder.write_samples(&[4, 5]).unwrap();
+    let data = encoder.finish().unwrap();
+    let mut expected = Vec::new();
+    wav::write_header(&mut expected, 1, 8_000, wav::UNKNOWN_LEN).unwrap();
+    wav::write_samples(&mut expected, &[4, 5]).unwrap();
+    assert_eq!(data, expected);
+}
@@ -0,0 +1,63 @@
+//! Conversions of integers to and from bytes, in a given byte order.
+//!
+//! The `to_le_bytes` family of the standard library needs Rust 1.32.
+
+// Images only need to write little-endian bytes
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+pub trait ByteOrder: Sized {
+    type Bytes;
+    fn le_bytes(self) -> Self::Bytes;
+    fn be_bytes(self) -> Self::Bytes;
+    fn from_le_array(bytes: Self::Bytes) -> Self;
+    fn from_be_array(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_byte_order {
+    ($($ty:ty, $len:expr;)*) => {
+        $(
+            impl ByteOrder for $ty {
+                type Bytes = [u8; $len];
+                fn le_bytes(self) -> [u8; $len] {
+                    let mut bytes = [0; $len];
+                    for (i, byte) in bytes.iter_mut().enumerate() {
+                        *byte = (self >> (8 * i)) as u8;
+                    }
+                    bytes
+                }
+                fn be_bytes(self) -> [u8; $len] {
+                    let mut bytes = self.le_bytes();
+                    bytes.reverse();
+                    bytes
+                }
+                fn from_le_array(bytes: [u8; $len]) -> Self {
+                    let mut value = 0;
+                    for (i, &byte) in bytes.iter().enumerate() {
+                        value |= (byte as $ty) << (8 * i);
+                    }
+                    value
+                }
+                fn from_be_array(mut bytes: [u8; $len]) -> Self {
+                    bytes.reverse();
+                    Self::from_le_array(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_order! {
+    u16, 2;
+    i16, 2;
+    u32, 4;
+    u64, 8;
+}
+
+#[test]
+fn test_byte_order() {
+    assert_eq!(0x1234u16.le_bytes(), [0x34, 0x12]);
+    assert_eq!((-2i16).le_bytes(), [0xFE, 0xFF]);
+    assert_eq!(0x0102_0304u32.be_bytes(), [1, 2, 3, 4]);
+    assert_eq!(i16::from_le_array([0xFE, 0xFF]), -2);
+    assert_eq!(u32::from_be_array([1, 2, 3, 4]), 0x0102_0304);
+    assert_eq!(u64::from_le_array(7u64.le_bytes()), 7);
+}
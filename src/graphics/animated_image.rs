@@ -0,0 +1,305 @@
+use crate::graphics::{
+    BasicTransformable, Drawable, Image, RenderStates, RenderTarget, Sprite, Texture, Transformable,
+};
+use crate::system::{SfBox, Time, Vector2u};
+
+/// A frame of an [`AnimatedImage`].
+#[derive(Debug, Clone)]
+pub struct AnimationFrame {
+    /// The whole image displayed by the frame.
+    pub image: Image,
+    /// How long the frame is displayed.
+    pub delay: Time,
+}
+
+/// A sequence of images displayed one after the other, like an animated GIF.
+///
+/// SFML only loads the first frame of animated images. An animated image holds all the
+/// frames, each one as a complete [`Image`] along with how long it is displayed, and can be
+/// played by an [`AnimatedImageSprite`].
+///
+/// With the `gif` feature enabled, animated images can be loaded from GIF files.
+#[derive(Debug, Clone)]
+pub struct AnimatedImage {
+    frames: Vec<AnimationFrame>,
+}
+
+impl AnimatedImage {
+    /// Create an animated image from its frames
+    ///
+    /// Return Some(AnimatedImage) or None if there are no frames
+    pub fn from_frames(frames: Vec<AnimationFrame>) -> Option<AnimatedImage> {
+        if frames.is_empty() {
+            return None;
+        }
+        Some(AnimatedImage { frames })
+    }
+
+    /// Load an animated image from a GIF file
+    ///
+    /// The frames of the file are combined the way browsers display them, so that each
+    /// frame of the animated image is complete. Delays shorter than 20 milliseconds are
+    /// played as 100 milliseconds, like browsers do.
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// use sfml::graphics::{
+    ///     AnimatedImage, AnimatedImageSprite, RenderTarget, RenderWindow, Transformable,
+    /// };
+    /// use sfml::system::Clock;
+    ///
+    /// # let mut window: RenderWindow = unimplemented!();
+    /// let image = AnimatedImage::from_gif("loading.gif").unwrap();
+    /// let mut spinner = AnimatedImageSprite::new(&image).unwrap();
+    /// spinner.set_position((400., 300.));
+    /// let mut clock = Clock::start();
+    /// loop {
+    ///     spinner.update(clock.restart());
+    ///     window.draw(&spinner);
+    ///     window.display();
+    /// }
+    /// ```
+    ///
+    /// # Arguments
+    /// * filename - Path of the GIF file to load
+    ///
+    /// Return Some(AnimatedImage) or None if the file can't be read or decoded
+    #[cfg(feature = "gif")]
+    pub fn from_gif(filename: &str) -> Option<AnimatedImage> {
+        let file = std::fs::File::open(filename).ok()?;
+        Self::from_gif_stream(std::io::BufReader::new(file))
+    }
+
+    /// Load an animated image from a GIF file in memory
+    ///
+    /// See [`from_gif`].
+    ///
+    /// Return Some(AnimatedImage) or None if the data can't be decoded
+    ///
+    /// [`from_gif`]: AnimatedImage::from_gif
+    #[cfg(feature = "gif")]
+    pub fn from_gif_memory(data: &[u8]) -> Option<AnimatedImage> {
+        Self::from_gif_stream(data)
+    }
+
+    #[cfg(feature = "gif")]
+    fn from_gif_stream<R: std::io::Read>(stream: R) -> Option<AnimatedImage> {
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(stream).ok()?;
+        let size = Vector2u::new(u32::from(decoder.width()), u32::from(decoder.height()));
+        let mut canvas = vec![0; size.x as usize * size.y as usize * 4];
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().ok()? {
+            let rect = FrameRect {
+                left: u32::from(frame.left),
+                top: u32::from(frame.top),
+                width: u32::from(frame.width),
+                height: u32::from(frame.height),
+            };
+            let previous = match frame.dispose {
+                gif::DisposalMethod::Previous => Some(canvas.clone()),
+                _ => None,
+            };
+            compose(&mut canvas, size.x, rect, &frame.buffer);
+            let image = Image::create_from_pixels(size.x, size.y, &canvas)?;
+            let delay = if frame.delay < 2 { 10 } else { frame.delay };
+            frames.push(AnimationFrame {
+                image,
+                delay: Time::microseconds(i64::from(delay) * 10_000),
+            });
+            match frame.dispose {
+                gif::DisposalMethod::Background => clear(&mut canvas, size.x, rect),
+                gif::DisposalMethod::Previous => canvas = previous.unwrap_or(canvas),
+                _ => {}
+            }
+        }
+        Self::from_frames(frames)
+    }
+
+    /// Get the frames of the animated image
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    /// Get the size of the animated image, the size of its first frame
+    pub fn size(&self) -> Vector2u {
+        self.frames[0].image.size()
+    }
+
+    /// Get the time it takes to play all the frames once
+    pub fn duration(&self) -> Time {
+        self.frames
+            .iter()
+            .fold(Time::ZERO, |duration, frame| duration + frame.delay)
+    }
+
+    /// Get the index of the frame displayed at some time after the animation started
+    ///
+    /// The animation loops: past its duration, it starts over.
+    pub fn frame_index_at(&self, time: Time) -> usize {
+        let delays: Vec<Time> = self.frames.iter().map(|frame| frame.delay).collect();
+        frame_at(&delays, time)
+    }
+}
+
+/// Drawable playing an [`AnimatedImage`] in a loop.
+///
+/// The frames are uploaded to textures once, when the sprite is created. The sprite
+/// then moves to the next frames as time passes, by being told how much time passed with
+/// [`update`].
+///
+/// [`update`]: AnimatedImageSprite::update
+#[derive(Debug)]
+pub struct AnimatedImageSprite {
+    textures: Vec<SfBox<Texture>>,
+    delays: Vec<Time>,
+    elapsed: Time,
+    transformable: BasicTransformable,
+}
+
+impl AnimatedImageSprite {
+    /// Create a new sprite playing an animated image from its first frame
+    ///
+    /// Return Some(AnimatedImageSprite) or None if a frame can't be uploaded to a texture
+    pub fn new(image: &AnimatedImage) -> Option<AnimatedImageSprite> {
+        let textures = image
+            .frames
+            .iter()
+            .map(|frame| Texture::from_image(&frame.image))
+            .collect::<Option<_>>()?;
+        Some(AnimatedImageSprite {
+            textures,
+            delays: image.frames.iter().map(|frame| frame.delay).collect(),
+            elapsed: Time::ZERO,
+            transformable: BasicTransformable::new(),
+        })
+    }
+    /// Advance the animation by the time elapsed since the last update.
+    pub fn update(&mut self, elapsed: Time) {
+        let duration = self
+            .delays
+            .iter()
+            .fold(Time::ZERO, |duration, &delay| duration + delay);
+        self.elapsed += elapsed;
+        if duration > Time::ZERO {
+            self.elapsed %= duration;
+        }
+    }
+    /// Go back to the first frame.
+    pub fn restart(&mut self) {
+        self.elapsed = Time::ZERO;
+    }
+    /// Get the index of the frame currently displayed.
+    pub fn current_frame(&self) -> usize {
+        frame_at(&self.delays, self.elapsed)
+    }
+}
+
+impl Drawable for AnimatedImageSprite {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let sprite = Sprite::with_texture(&self.textures[self.current_frame()]);
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        target.draw_with_renderstates(&sprite, states);
+    }
+}
+
+delegate_transformable!(impl for AnimatedImageSprite => transformable);
+
+/// Get the index of the frame displayed at `time`, given the delays of the frames.
+fn frame_at(delays: &[Time], time: Time) -> usize {
+    let duration = delays.iter().fold(Time::ZERO, |duration, &d| duration + d);
+    if duration <= Time::ZERO {
+        return 0;
+    }
+    let mut time = time % duration;
+    for (i, &delay) in delays.iter().enumerate() {
+        if time < delay {
+            return i;
+        }
+        time -= delay;
+    }
+    delays.len() - 1
+}
+
+/// Area of a GIF frame on the canvas.
+#[cfg(any(feature = "gif", test))]
+#[derive(Debug, Clone, Copy)]
+struct FrameRect {
+    left: u32,
+    top: u32,
+    width: u32,
+    height: u32,
+}
+
+#[cfg(any(feature = "gif", test))]
+impl FrameRect {
+    /// Iterate over the rows of the rectangle, as ranges of bytes in a canvas `width`
+    /// pixels wide, clipped to the canvas.
+    fn rows(self, canvas_len: usize, width: u32) -> impl Iterator<Item = std::ops::Range<usize>> {
+        let right = (self.left + self.width).min(width) as usize;
+        let left = (self.left as usize).min(right);
+        (self.top..self.top + self.height)
+            .map(move |y| y as usize * width as usize)
+            .map(move |start| (start + left) * 4..(start + right) * 4)
+            .take_while(move |range| range.end <= canvas_len)
+    }
+}
+
+/// Draw the RGBA `pixels` of a frame over `canvas`, skipping its transparent pixels.
+#[cfg(any(feature = "gif", test))]
+fn compose(canvas: &mut [u8], width: u32, rect: FrameRect, pixels: &[u8]) {
+    let stride = rect.width as usize * 4;
+    for (row, range) in rect.rows(canvas.len(), width).enumerate() {
+        let source = &pixels[row * stride..row * stride + range.len()];
+        for (dest, source) in canvas[range].chunks_mut(4).zip(source.chunks(4)) {
+            if source[3] != 0 {
+                dest.copy_from_slice(source);
+            }
+        }
+    }
+}
+
+/// Clear the area of a frame on `canvas` to transparent.
+#[cfg(any(feature = "gif", test))]
+fn clear(canvas: &mut [u8], width: u32, rect: FrameRect) {
+    for range in rect.rows(canvas.len(), width) {
+        for byte in &mut canvas[range] {
+            *byte = 0;
+        }
+    }
+}
+
+#[test]
+fn test_frame_at() {
+    let delays = [Time::microseconds(100), Time::microseconds(50)];
+    assert_eq!(frame_at(&delays, Time::microseconds(0)), 0);
+    assert_eq!(frame_at(&delays, Time::microseconds(120)), 1);
+    assert_eq!(frame_at(&delays, Time::microseconds(160)), 0);
+    assert_eq!(frame_at(&[Time::ZERO], Time::microseconds(10)), 0);
+}
+
+#[test]
+fn test_compose() {
+    // 3x2 canvas, frame covering the 2x2 pixels on the right, first one transparent
+    let mut canvas = vec![1; 3 * 2 * 4];
+    let rect = FrameRect {
+        left: 1,
+        top: 0,
+        width: 2,
+        height: 2,
+    };
+    let frame: Vec<u8> = [0, 9, 9, 9].iter().flat_map(|&v| vec![v; 4]).collect();
+    compose(&mut canvas, 3, rect, &frame);
+    let ids: Vec<u8> = canvas.chunks(4).map(|p| p[0]).collect();
+    assert_eq!(ids, [1, 1, 9, 1, 9, 9]);
+    clear(&mut canvas, 3, rect);
+    let ids: Vec<u8> = canvas.chunks(4).map(|p| p[3]).collect();
+    assert_eq!(ids, [1, 0, 0, 1, 0, 0]);
+}
@@ -0,0 +1,286 @@
+use crate::graphics::{Drawable, IntRect, RenderStates, RenderTarget, Sprite, Texture};
+use crate::system::Time;
+
+/// How an [`Animation`] goes on once it has played all its frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationMode {
+    /// Start over from the first frame.
+    Loop,
+    /// Stop on the last frame.
+    Once,
+    /// Play the frames backwards, then forwards again, and so on.
+    PingPong,
+}
+
+/// A sequence of areas of a sprite sheet, each one displayed for some time.
+///
+/// An animation only describes which part of a texture to display, and when. It is played
+/// by an [`AnimatedSprite`], and can be shared by any number of them.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{AnimatedSprite, Animation, AnimationMode, IntRect, Texture};
+/// use sfml::system::Time;
+///
+/// let texture = Texture::from_file("hero.png").unwrap();
+/// // 6 frames of 32x48 pixels, side by side on the second row of the sheet
+/// let walk = Animation::from_strip(
+///     IntRect::new(0, 48, 32, 48),
+///     6,
+///     Time::milliseconds(80),
+///     AnimationMode::Loop,
+/// );
+/// let mut hero = AnimatedSprite::new(&texture, walk);
+/// // In the game loop
+/// # let frame_time = Time::ZERO;
+/// hero.update(frame_time);
+/// // window.draw(&hero);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    frames: Vec<(IntRect, Time)>,
+    mode: AnimationMode,
+}
+
+impl Animation {
+    /// Create a new animation without frames.
+    pub fn new(mode: AnimationMode) -> Self {
+        Animation {
+            frames: Vec::new(),
+            mode,
+        }
+    }
+    /// Create an animation out of frames of the same size, laid out side by side from left
+    /// to right in a sprite sheet.
+    ///
+    /// # Arguments
+    /// * first - Area of the first frame of the sheet
+    /// * count - Number of frames
+    /// * duration - How long each frame is displayed
+    /// * mode - How the animation goes on once it has played all its frames
+    pub fn from_strip(first: IntRect, count: u32, duration: Time, mode: AnimationMode) -> Self {
+        let mut animation = Animation::new(mode);
+        for i in 0..count as i32 {
+            let rect = IntRect::new(
+                first.left + i * first.width,
+                first.top,
+                first.width,
+                first.height,
+            );
+            animation.push_frame(rect, duration);
+        }
+        animation
+    }
+    /// Append a frame at the end of the animation.
+    ///
+    /// # Arguments
+    /// * rect - Area of the texture displayed by the frame
+    /// * duration - How long the frame is displayed
+    pub fn push_frame(&mut self, rect: IntRect, duration: Time) {
+        self.frames.push((rect, duration));
+    }
+    /// Get the frames of the animation, along with how long each one is displayed.
+    pub fn frames(&self) -> &[(IntRect, Time)] {
+        &self.frames
+    }
+    /// Set how the animation goes on once it has played all its frames.
+    pub fn set_mode(&mut self, mode: AnimationMode) {
+        self.mode = mode;
+    }
+    /// Get how the animation goes on once it has played all its frames.
+    pub fn mode(&self) -> AnimationMode {
+        self.mode
+    }
+    /// Get the time it takes to play all the frames once.
+    pub fn duration(&self) -> Time {
+        self.frames
+            .iter()
+            .fold(Time::ZERO, |duration, frame| duration + frame.1)
+    }
+    /// Get the index of the frame displayed at some time after the animation started.
+    ///
+    /// An animation without frames always returns 0.
+    pub fn frame_at(&self, time: Time) -> usize {
+        let duration = self.duration();
+        if self.frames.is_empty() || duration <= Time::ZERO {
+            return 0;
+        }
+        let last = self.frames.len() - 1;
+        let (mut time, backwards) = match self.mode {
+            AnimationMode::Loop => (time % duration, false),
+            AnimationMode::Once if time >= duration => return last,
+            AnimationMode::Once => (time, false),
+            AnimationMode::PingPong => {
+                let time = time % self.cycle();
+                if time < duration {
+                    (time, false)
+                } else {
+                    (time - duration, true)
+                }
+            }
+        };
+        if backwards {
+            for i in (1..last).rev() {
+                if time < self.frames[i].1 {
+                    return i;
+                }
+                time -= self.frames[i].1;
+            }
+            return 0;
+        }
+        for (i, frame) in self.frames.iter().enumerate() {
+            if time < frame.1 {
+                return i;
+            }
+            time -= frame.1;
+        }
+        last
+    }
+    /// Tell whether the animation is over at some time after it started.
+    ///
+    /// Only animations played once are ever over.
+    pub fn is_finished_at(&self, time: Time) -> bool {
+        self.mode == AnimationMode::Once && time >= self.duration()
+    }
+
+    /// Get the time after which the animation displays the same frames again, or zero if
+    /// it never does.
+    fn cycle(&self) -> Time {
+        match self.mode {
+            AnimationMode::Loop => self.duration(),
+            AnimationMode::Once => Time::ZERO,
+            AnimationMode::PingPong => match (self.frames.first(), self.frames.last()) {
+                // Going back skips both ends, they aren't displayed twice in a row
+                (Some(first), Some(last)) if self.frames.len() > 1 => {
+                    let duration = self.duration();
+                    duration + duration - first.1 - last.1
+                }
+                _ => self.duration(),
+            },
+        }
+    }
+}
+
+/// A sprite displaying the frames of an [`Animation`] as time passes.
+///
+/// The sprite moves to the next frame by being told how much time passed with [`update`],
+/// usually once per frame of the game loop. The underlying [`Sprite`] is positioned,
+/// rotated, scaled and colored like any other sprite.
+///
+/// [`update`]: AnimatedSprite::update
+#[derive(Debug)]
+pub struct AnimatedSprite<'t> {
+    sprite: Sprite<'t>,
+    animation: Animation,
+    elapsed: Time,
+    playing: bool,
+}
+
+impl<'t> AnimatedSprite<'t> {
+    /// Create a new sprite playing an animation from its first frame.
+    ///
+    /// # Arguments
+    /// * texture - The sprite sheet the frames of the animation are in
+    /// * animation - The animation to play
+    pub fn new(texture: &'t Texture, animation: Animation) -> Self {
+        let mut sprite = AnimatedSprite {
+            sprite: Sprite::with_texture(texture),
+            animation,
+            elapsed: Time::ZERO,
+            playing: true,
+        };
+        sprite.update_rect();
+        sprite
+    }
+    /// Advance the animation by the time elapsed since the last update.
+    ///
+    /// Does nothing while the animation is paused.
+    pub fn update(&mut self, elapsed: Time) {
+        if !self.playing {
+            return;
+        }
+        self.elapsed += elapsed;
+        // Keep the elapsed time small, it only matters within a cycle
+        let cycle = self.animation.cycle();
+        if cycle > Time::ZERO {
+            self.elapsed %= cycle;
+        }
+        self.update_rect();
+    }
+    /// Resume playing the animation.
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+    /// Pause the animation on the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+    /// Stop the animation, and go back to its first frame.
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.elapsed = Time::ZERO;
+        self.update_rect();
+    }
+    /// Tell whether the animation is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+    /// Tell whether the animation, played once, is over.
+    pub fn is_finished(&self) -> bool {
+        self.animation.is_finished_at(self.elapsed)
+    }
+    /// Change the animation to play, and play it from its first frame.
+    pub fn set_animation(&mut self, animation: Animation) {
+        self.animation = animation;
+        self.elapsed = Time::ZERO;
+        self.update_rect();
+    }
+    /// Get the animation played.
+    pub fn animation(&self) -> &Animation {
+        &self.animation
+    }
+    /// Get the index of the frame currently displayed.
+    pub fn current_frame(&self) -> usize {
+        self.animation.frame_at(self.elapsed)
+    }
+    /// Get the sprite displaying the frames.
+    pub fn sprite(&self) -> &Sprite<'t> {
+        &self.sprite
+    }
+    /// Get the sprite displaying the frames, to move or color it.
+    ///
+    /// The texture rect of the sprite is overwritten on the next update.
+    pub fn sprite_mut(&mut self) -> &mut Sprite<'t> {
+        &mut self.sprite
+    }
+
+    fn update_rect(&mut self) {
+        if let Some(&(rect, _)) = self.animation.frames().get(self.current_frame()) {
+            self.sprite.set_texture_rect(&rect);
+        }
+    }
+}
+
+impl<'t> Drawable for AnimatedSprite<'t> {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        target.draw_with_renderstates(&self.sprite, states);
+    }
+}
+
+#[test]
+fn test_frame_at() {
+    let us = Time::microseconds;
+    let mut animation =
+        Animation::from_strip(IntRect::new(0, 0, 16, 16), 3, us(10), AnimationMode::Loop);
+    assert_eq!(animation.frames()[2].0, IntRect::new(32, 0, 16, 16));
+    let frames = |animation: &Animation| -> Vec<usize> {
+        (0..9).map(|i| animation.frame_at(us(i * 10))).collect()
+    };
+    assert_eq!(frames(&animation), [0, 1, 2, 0, 1, 2, 0, 1, 2]);
+    animation.set_mode(AnimationMode::Once);
+    assert_eq!(frames(&animation), [0, 1, 2, 2, 2, 2, 2, 2, 2]);
+    assert!(animation.is_finished_at(us(30)));
+    animation.set_mode(AnimationMode::PingPong);
+    assert_eq!(frames(&animation), [0, 1, 2, 1, 0, 1, 2, 1, 0]);
+}
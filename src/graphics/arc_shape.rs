@@ -0,0 +1,251 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, FloatRect, PrimitiveType, RenderStates, RenderTarget,
+    Transformable, Vertex,
+};
+
+/// A part of a ring, between two angles and two radii.
+///
+/// With an inner radius of zero the arc is a pie slice, and going all the way around it is
+/// a full ring, so arcs make radial progress bars, cooldown indicators and minimap frames.
+///
+/// Angles are in degrees, clockwise from the x axis, like the rotations of SFML. The arc
+/// goes from its start angle to its end angle, counter-clockwise if the end angle is the
+/// smaller one. Unlike a [`CircleShape`], the center of the arc is at its origin.
+///
+/// Rings aren't convex, so arcs aren't [`Shape`]s: they are made of triangles, filled with
+/// a single color.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{ArcShape, Color, Transformable};
+///
+/// // A cooldown indicator, filling up clockwise from the top
+/// let mut cooldown = ArcShape::new(20., 24., -90., -90., 60);
+/// cooldown.set_fill_color(Color::rgb(250, 200, 40));
+/// cooldown.set_position((400., 300.));
+/// // In the game loop
+/// # let progress = 0.5;
+/// cooldown.set_end_angle(-90. + 360. * progress);
+/// // window.draw(&cooldown);
+/// ```
+///
+/// [`CircleShape`]: crate::graphics::CircleShape
+/// [`Shape`]: crate::graphics::Shape
+#[derive(Debug, Clone)]
+pub struct ArcShape {
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    segment_count: u32,
+    fill_color: Color,
+    transformable: BasicTransformable,
+    vertices: Vec<Vertex>,
+}
+
+impl ArcShape {
+    /// Create a new white arc
+    ///
+    /// # Arguments
+    /// * inner_radius - The radius of the inside of the arc, zero for a pie slice
+    /// * outer_radius - The radius of the outside of the arc
+    /// * start_angle - The angle the arc starts at, in degrees
+    /// * end_angle - The angle the arc ends at, in degrees
+    /// * segment_count - The number of segments a full circle would be cut into
+    pub fn new(
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        segment_count: u32,
+    ) -> Self {
+        let mut arc = ArcShape {
+            inner_radius,
+            outer_radius,
+            start_angle,
+            end_angle,
+            segment_count: segment_count.max(3),
+            fill_color: Color::WHITE,
+            transformable: BasicTransformable::new(),
+            vertices: Vec::new(),
+        };
+        arc.update();
+        arc
+    }
+    /// Create a new white ring, going all the way around
+    ///
+    /// # Arguments
+    /// * inner_radius - The radius of the inside of the ring
+    /// * outer_radius - The radius of the outside of the ring
+    /// * segment_count - The number of segments the ring is cut into
+    pub fn ring(inner_radius: f32, outer_radius: f32, segment_count: u32) -> Self {
+        Self::new(inner_radius, outer_radius, 0., 360., segment_count)
+    }
+    /// Create a new white pie slice
+    ///
+    /// # Arguments
+    /// * radius - The radius of the slice
+    /// * start_angle - The angle the slice starts at, in degrees
+    /// * end_angle - The angle the slice ends at, in degrees
+    /// * segment_count - The number of segments a full circle would be cut into
+    pub fn pie_slice(radius: f32, start_angle: f32, end_angle: f32, segment_count: u32) -> Self {
+        Self::new(0., radius, start_angle, end_angle, segment_count)
+    }
+    /// Set the radius of the inside of the arc, zero for a pie slice.
+    pub fn set_inner_radius(&mut self, radius: f32) {
+        self.inner_radius = radius;
+        self.update();
+    }
+    /// Get the radius of the inside of the arc.
+    pub fn inner_radius(&self) -> f32 {
+        self.inner_radius
+    }
+    /// Set the radius of the outside of the arc.
+    pub fn set_outer_radius(&mut self, radius: f32) {
+        self.outer_radius = radius;
+        self.update();
+    }
+    /// Get the radius of the outside of the arc.
+    pub fn outer_radius(&self) -> f32 {
+        self.outer_radius
+    }
+    /// Set the angle the arc starts at, in degrees.
+    pub fn set_start_angle(&mut self, angle: f32) {
+        self.start_angle = angle;
+        self.update();
+    }
+    /// Get the angle the arc starts at, in degrees.
+    pub fn start_angle(&self) -> f32 {
+        self.start_angle
+    }
+    /// Set the angle the arc ends at, in degrees.
+    ///
+    /// Arcs are at most a full turn long: the end angle is clamped to one turn away from
+    /// the start angle.
+    pub fn set_end_angle(&mut self, angle: f32) {
+        self.end_angle = angle;
+        self.update();
+    }
+    /// Get the angle the arc ends at, in degrees.
+    pub fn end_angle(&self) -> f32 {
+        self.end_angle
+    }
+    /// Set the number of segments a full circle would be cut into, at least 3.
+    ///
+    /// Shorter arcs use a part of the segments, so that an arc keeps the same smoothness
+    /// as its angles change.
+    pub fn set_segment_count(&mut self, count: u32) {
+        self.segment_count = count.max(3);
+        self.update();
+    }
+    /// Get the number of segments a full circle would be cut into.
+    pub fn segment_count(&self) -> u32 {
+        self.segment_count
+    }
+    /// Set the color of the arc.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        for vertex in &mut self.vertices {
+            vertex.color = color;
+        }
+    }
+    /// Get the color of the arc.
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+    /// Get the local bounding rectangle of the whole circle the arc is a part of.
+    ///
+    /// The returned rectangle is in local coordinates, which means
+    /// that it ignores the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn local_bounds(&self) -> FloatRect {
+        let radius = self.outer_radius.abs();
+        FloatRect::new(-radius, -radius, radius * 2., radius * 2.)
+    }
+    /// Get the global bounding rectangle of the whole circle the arc is a part of.
+    ///
+    /// The returned rectangle is in global coordinates, which means
+    /// that it takes into account the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(&self.local_bounds())
+    }
+
+    fn update(&mut self) {
+        self.vertices = arc_vertices(
+            self.inner_radius,
+            self.outer_radius,
+            self.start_angle,
+            self.end_angle,
+            self.segment_count,
+            self.fill_color,
+        );
+    }
+}
+
+impl Drawable for ArcShape {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::Triangles, states);
+    }
+}
+
+delegate_transformable!(impl for ArcShape => transformable);
+
+/// Build the triangles of an arc centered on the origin. Pie slices, with an inner radius
+/// of zero, are one triangle per segment, other arcs are two.
+fn arc_vertices(
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    segment_count: u32,
+    color: Color,
+) -> Vec<Vertex> {
+    let sweep = (end_angle - start_angle).max(-360.).min(360.);
+    let segments = (segment_count as f32 * sweep.abs() / 360.).ceil() as u32;
+    let point = |i: u32, radius: f32| {
+        let angle = (start_angle + sweep * i as f32 / segments as f32).to_radians();
+        Vertex::with_pos_color((angle.cos() * radius, angle.sin() * radius), color)
+    };
+    let mut vertices = Vec::new();
+    for i in 0..segments {
+        let (outer0, outer1) = (point(i, outer_radius), point(i + 1, outer_radius));
+        if inner_radius == 0. {
+            let center = Vertex::with_pos_color((0., 0.), color);
+            vertices.extend_from_slice(&[center, outer0, outer1]);
+        } else {
+            let (inner0, inner1) = (point(i, inner_radius), point(i + 1, inner_radius));
+            vertices.extend_from_slice(&[inner0, outer0, outer1, inner0, outer1, inner1]);
+        }
+    }
+    vertices
+}
+
+#[test]
+fn test_arc_vertices() {
+    use crate::system::Vector2f;
+    let close = |a: Vector2f, b: Vector2f| (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4;
+    // A quarter of a ring, out of 16 segments for a full circle
+    let quarter = arc_vertices(5., 10., 0., 90., 16, Color::RED);
+    assert_eq!(quarter.len(), 4 * 6);
+    assert!(close(quarter[0].position, Vector2f::new(5., 0.)));
+    assert!(close(quarter[23].position, Vector2f::new(0., 5.)));
+    assert_eq!(quarter[0].color, Color::RED);
+    // A pie slice going counter-clockwise
+    let slice = arc_vertices(0., 10., 0., -45., 8, Color::WHITE);
+    assert_eq!(slice.len(), 3);
+    assert!(close(slice[2].position, Vector2f::new(7.071068, -7.071068)));
+    // Arcs are at most a full turn
+    assert_eq!(
+        arc_vertices(5., 10., 0., 720., 8, Color::WHITE).len(),
+        8 * 6
+    );
+    assert!(arc_vertices(5., 10., 30., 30., 8, Color::WHITE).is_empty());
+}
@@ -135,6 +135,15 @@ impl BlendMode {
             alpha_equation: alpha_equ,
         }
     }
+    /// Create a new BlendMode treating the color and alpha channels the same way
+    ///
+    /// # Arguments
+    /// * src - Source blending factor, for all the channels
+    /// * dst - Destination blending factor, for all the channels
+    /// * equation - Blending equation, for all the channels
+    pub fn uniform(src: Factor, dst: Factor, equation: Equation) -> Self {
+        Self::new(src, dst, equation, src, dst, equation)
+    }
     pub(super) fn raw(&self) -> ffi::sfBlendMode {
         unsafe { ::std::mem::transmute(*self) }
     }
@@ -168,6 +177,32 @@ impl BlendMode {
         alpha_equation: Equation::Add,
     };
 
+    /// "Premultiplied alpha" blend mode
+    ///
+    /// Alpha blending for colors already multiplied by their alpha, like the
+    /// contents of render textures drawn with alpha blending.
+    pub const PREMULTIPLIED_ALPHA: BlendMode = BlendMode {
+        color_src_factor: Factor::One,
+        color_dst_factor: Factor::OneMinusSrcAlpha,
+        color_equation: Equation::Add,
+        alpha_src_factor: Factor::One,
+        alpha_dst_factor: Factor::OneMinusSrcAlpha,
+        alpha_equation: Equation::Add,
+    };
+
+    /// "Subtract" blend mode
+    ///
+    /// Removes the colors drawn, weighted by their alpha, from the buffer,
+    /// which keeps its own alpha. The opposite of [`ADD`](BlendMode::ADD).
+    pub const SUBTRACT: BlendMode = BlendMode {
+        color_src_factor: Factor::SrcAlpha,
+        color_dst_factor: Factor::One,
+        color_equation: Equation::ReverseSubtract,
+        alpha_src_factor: Factor::Zero,
+        alpha_dst_factor: Factor::One,
+        alpha_equation: Equation::Add,
+    };
+
     /// "None" blend mode
     pub const NONE: BlendMode = BlendMode {
         color_src_factor: Factor::One,
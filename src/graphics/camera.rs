@@ -0,0 +1,288 @@
+use crate::graphics::{FloatRect, RenderTarget, View};
+use crate::system::{SfBox, Time, Vector2f, Vector2u};
+
+/// A [`View`] that follows the game around.
+///
+/// A camera shows an area of the world of a given size, magnified by its zoom. It can
+/// follow a target smoothly, stay within the bounds of the level, and shake. When the
+/// window is resized, bars are added on its sides or at its top and bottom, so that the
+/// world keeps its proportions.
+///
+/// The camera is updated once per frame of the game loop, then applied to the render
+/// target before drawing the world.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Camera, FloatRect, RenderTarget, RenderWindow};
+/// use sfml::system::{Time, Vector2f};
+/// use sfml::window::Event;
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut camera = Camera::new(Vector2f::new(320., 180.));
+/// camera.resize(window.size());
+/// camera.set_bounds(Some(FloatRect::new(0., 0., 2000., 600.)));
+/// camera.set_follow_speed(5.);
+/// # let (player, frame_time) = (Vector2f::new(0., 0.), Time::ZERO);
+/// // In the game loop
+/// while let Some(event) = window.poll_event() {
+///     if let Event::Resized { width, height } = event {
+///         camera.resize((width, height).into());
+///     }
+/// }
+/// camera.follow(player);
+/// camera.update(frame_time);
+/// camera.apply(&mut window);
+/// // window.draw(&world);
+/// ```
+#[derive(Debug)]
+pub struct Camera {
+    view: SfBox<View>,
+    size: Vector2f,
+    center: Vector2f,
+    zoom: f32,
+    letterbox: bool,
+    window_size: Vector2u,
+    bounds: Option<FloatRect>,
+    target: Option<Vector2f>,
+    follow_speed: f32,
+    shake_intensity: f32,
+    shake_duration: Time,
+    shake_left: Time,
+    shake_offset: Vector2f,
+    rng: u64,
+}
+
+impl Camera {
+    /// Create a new camera showing an area of the world, centered on its origin.
+    ///
+    /// # Arguments
+    /// * size - The size of the area shown, with a zoom of 1
+    pub fn new(size: Vector2f) -> Self {
+        let mut camera = Camera {
+            view: View::new(Vector2f::new(0., 0.), size),
+            size,
+            center: Vector2f::new(0., 0.),
+            zoom: 1.,
+            letterbox: true,
+            window_size: Vector2u::new(0, 0),
+            bounds: None,
+            target: None,
+            follow_speed: 0.,
+            shake_intensity: 0.,
+            shake_duration: Time::ZERO,
+            shake_left: Time::ZERO,
+            shake_offset: Vector2f::new(0., 0.),
+            rng: 0x9E37_79B9_7F4A_7C15,
+        };
+        camera.update_view();
+        camera
+    }
+    /// Set the size of the area shown, with a zoom of 1.
+    pub fn set_size(&mut self, size: Vector2f) {
+        self.size = size;
+        self.update_view();
+    }
+    /// Get the size of the area shown, with a zoom of 1.
+    pub fn size(&self) -> Vector2f {
+        self.size
+    }
+    /// Get the size of the area shown, with the current zoom.
+    pub fn visible_size(&self) -> Vector2f {
+        self.size * (1. / self.zoom)
+    }
+    /// Move the camera to a point at once.
+    ///
+    /// The camera keeps following its target, if it has one.
+    pub fn set_center(&mut self, center: Vector2f) {
+        self.center = center;
+        self.update_view();
+    }
+    /// Get the point the camera is centered on, without the shake.
+    pub fn center(&self) -> Vector2f {
+        self.center
+    }
+    /// Set how much the world is magnified: 2 shows an area half as big, 0.5 twice as big.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(std::f32::EPSILON);
+        self.update_view();
+    }
+    /// Get how much the world is magnified.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+    /// Center the camera on an area, and zoom so that the whole area is shown.
+    pub fn zoom_to_fit(&mut self, area: &FloatRect) {
+        self.center = Vector2f::new(area.left + area.width / 2., area.top + area.height / 2.);
+        let ratio = (area.width.abs() / self.size.x).max(area.height.abs() / self.size.y);
+        self.set_zoom(if ratio > 0. { 1. / ratio } else { 1. });
+    }
+    /// Set whether bars are added around the world to keep its proportions, when the
+    /// window doesn't have the same proportions. Done by default.
+    ///
+    /// Without bars, the world is stretched to fill the window.
+    pub fn set_letterbox(&mut self, letterbox: bool) {
+        self.letterbox = letterbox;
+        self.update_view();
+    }
+    /// Tell whether bars are added around the world to keep its proportions.
+    pub fn is_letterbox(&self) -> bool {
+        self.letterbox
+    }
+    /// Tell the camera the new size of the window, after it was resized.
+    pub fn resize(&mut self, window_size: Vector2u) {
+        self.window_size = window_size;
+        self.update_view();
+    }
+    /// Set the area of the world the camera is kept in, or `None` to let it go anywhere.
+    ///
+    /// When the area shown is bigger than the bounds, the camera is centered on them.
+    pub fn set_bounds(&mut self, bounds: Option<FloatRect>) {
+        self.bounds = bounds;
+        self.update_view();
+    }
+    /// Get the area of the world the camera is kept in.
+    pub fn bounds(&self) -> Option<FloatRect> {
+        self.bounds
+    }
+    /// Set the point the camera moves towards on each update.
+    pub fn follow(&mut self, target: Vector2f) {
+        self.target = Some(target);
+    }
+    /// Stop following the target, the camera stays where it is.
+    pub fn unfollow(&mut self) {
+        self.target = None;
+    }
+    /// Set how fast the camera catches up with its target, 0 by default.
+    ///
+    /// The camera slows down as it gets closer to its target: at a speed of 1 it covers
+    /// about two thirds of the distance in a second, at a speed of 5 nearly all of it.
+    /// 0 keeps the camera right on its target.
+    pub fn set_follow_speed(&mut self, speed: f32) {
+        self.follow_speed = speed.max(0.);
+    }
+    /// Get how fast the camera catches up with its target.
+    pub fn follow_speed(&self) -> f32 {
+        self.follow_speed
+    }
+    /// Shake the camera, for explosions and hits.
+    ///
+    /// # Arguments
+    /// * intensity - How far the camera moves from its center, at most, in world units
+    /// * duration - How long the shake lasts, fading out
+    pub fn shake(&mut self, intensity: f32, duration: Time) {
+        self.shake_intensity = intensity;
+        self.shake_duration = duration;
+        self.shake_left = duration;
+    }
+    /// Move the camera towards its target, and shake it, by the time elapsed since the
+    /// last update.
+    pub fn update(&mut self, elapsed: Time) {
+        let seconds = elapsed.as_seconds();
+        if let Some(target) = self.target {
+            self.center = if self.follow_speed > 0. {
+                let t = 1. - (-self.follow_speed * seconds).exp();
+                self.center + (target - self.center) * t
+            } else {
+                target
+            };
+        }
+        self.shake_offset = Vector2f::new(0., 0.);
+        if self.shake_left > Time::ZERO {
+            self.shake_left = if elapsed < self.shake_left {
+                self.shake_left - elapsed
+            } else {
+                Time::ZERO
+            };
+            let fade = self.shake_left.as_seconds() / self.shake_duration.as_seconds();
+            let amount = self.shake_intensity * fade;
+            self.shake_offset = Vector2f::new(self.random() * amount, self.random() * amount);
+        }
+        self.update_view();
+    }
+    /// Get the view the camera shows the world through.
+    pub fn view(&self) -> &View {
+        &self.view
+    }
+    /// Make a render target show the world through the camera.
+    pub fn apply(&self, target: &mut dyn RenderTarget) {
+        target.set_view(&self.view);
+    }
+
+    fn update_view(&mut self) {
+        let size = self.visible_size();
+        if let Some(bounds) = self.bounds {
+            self.center = clamp_center(self.center, size, &bounds);
+        }
+        self.view.set_center(self.center + self.shake_offset);
+        self.view.set_size(size);
+        let viewport = if self.letterbox {
+            letterbox_viewport(size, self.window_size)
+        } else {
+            FloatRect::new(0., 0., 1., 1.)
+        };
+        self.view.set_viewport(&viewport);
+    }
+    /// A pseudo-random number in `-1..1` (xorshift), good enough for shaking.
+    fn random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 23) as f32 - 1.
+    }
+}
+
+/// Compute the viewport showing an area of `size` with its proportions in a window, with
+/// bars on the sides or at the top and bottom.
+fn letterbox_viewport(size: Vector2f, window_size: Vector2u) -> FloatRect {
+    if window_size.x == 0 || window_size.y == 0 || size.x <= 0. || size.y <= 0. {
+        return FloatRect::new(0., 0., 1., 1.);
+    }
+    let window_ratio = window_size.x as f32 / window_size.y as f32;
+    let ratio = size.x / size.y;
+    if window_ratio > ratio {
+        // Window wider than the area, bars on the sides
+        let width = ratio / window_ratio;
+        FloatRect::new((1. - width) / 2., 0., width, 1.)
+    } else {
+        let height = window_ratio / ratio;
+        FloatRect::new(0., (1. - height) / 2., 1., height)
+    }
+}
+
+/// Move `center` so that an area of `size` around it stays within `bounds`, or center it
+/// on the bounds when it is bigger.
+fn clamp_center(center: Vector2f, size: Vector2f, bounds: &FloatRect) -> Vector2f {
+    let clamp = |center: f32, size: f32, start: f32, len: f32| {
+        if size >= len {
+            start + len / 2.
+        } else {
+            center.max(start + size / 2.).min(start + len - size / 2.)
+        }
+    };
+    Vector2f::new(
+        clamp(center.x, size.x, bounds.left, bounds.width),
+        clamp(center.y, size.y, bounds.top, bounds.height),
+    )
+}
+
+#[test]
+fn test_camera_geometry() {
+    let size = Vector2f::new(320., 180.);
+    // 4:3 window, bars at the top and bottom
+    let viewport = letterbox_viewport(size, Vector2u::new(800, 600));
+    assert_eq!(viewport, FloatRect::new(0., 0.125, 1., 0.75));
+    // Ultrawide window, bars on the sides
+    let viewport = letterbox_viewport(size, Vector2u::new(3200, 900));
+    assert_eq!(viewport, FloatRect::new(0.25, 0., 0.5, 1.));
+
+    let bounds = FloatRect::new(0., 0., 1000., 100.);
+    assert_eq!(
+        clamp_center(Vector2f::new(50., 50.), size, &bounds),
+        Vector2f::new(160., 50.)
+    );
+    assert_eq!(
+        clamp_center(Vector2f::new(500., 50.), size, &bounds),
+        Vector2f::new(500., 50.)
+    );
+}
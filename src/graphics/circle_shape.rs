@@ -82,11 +82,7 @@ impl<'s> Default for CircleShape<'s> {
 }
 
 impl<'s> Drawable for CircleShape<'s> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_circle_shape(self, states)
     }
 }
@@ -1,5 +1,8 @@
 use crate::graphics::csfml_graphics_sys as ffi;
+use std::error::Error;
+use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
 
 /// Utility type for manpulating RGBA colors
 ///
@@ -26,7 +29,7 @@ impl Color {
     /// * blue - Blue component  (0 .. 255)
     ///
     /// Return Color object constructed from the components
-    pub fn rgb(red: u8, green: u8, blue: u8) -> Color {
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Color {
         Color {
             r: red,
             g: green,
@@ -44,7 +47,7 @@ impl Color {
     /// * alpha - Alpha component  (0 .. 255)
     ///
     /// Return Color object constructed from the components
-    pub fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
+    pub const fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
         Color {
             r: red,
             g: green,
@@ -53,6 +56,122 @@ impl Color {
         }
     }
 
+    /// Construct a color from a hexadecimal string, like in CSS
+    ///
+    /// The string is written `#rrggbbaa`, `#rrggbb`, `#rgba` or `#rgb`, the `#` being
+    /// optional and the digits being lowercase or uppercase. Colors
+    /// without an alpha component are opaque, and `#rgb` is the same as `#rrggbb`.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// use sfml::graphics::Color;
+    ///
+    /// assert_eq!(Color::from_hex("#ff8000"), Ok(Color::rgb(255, 128, 0)));
+    /// assert_eq!(Color::from_hex("F808"), Ok(Color::rgba(255, 136, 0, 136)));
+    /// assert!(Color::from_hex("#ff80").is_ok());
+    /// assert!(Color::from_hex("orange").is_err());
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Color, ParseColorError> {
+        let hex = if hex.starts_with('#') { &hex[1..] } else { hex };
+        let mut digits = [0u8; 8];
+        let mut len = 0;
+        for c in hex.chars() {
+            let digit = c.to_digit(16).ok_or(ParseColorError::InvalidDigit(c))?;
+            if len < digits.len() {
+                digits[len] = digit as u8;
+            }
+            len += 1;
+        }
+        let d = digits;
+        let (r, g, b, a) = match len {
+            3 => (d[0] * 17, d[1] * 17, d[2] * 17, 255),
+            4 => (d[0] * 17, d[1] * 17, d[2] * 17, d[3] * 17),
+            6 => (d[0] << 4 | d[1], d[2] << 4 | d[3], d[4] << 4 | d[5], 255),
+            8 => (
+                d[0] << 4 | d[1],
+                d[2] << 4 | d[3],
+                d[4] << 4 | d[5],
+                d[6] << 4 | d[7],
+            ),
+            _ => return Err(ParseColorError::InvalidLength(len)),
+        };
+        Ok(Color::rgba(r, g, b, a))
+    }
+
+    /// Get the color as a hexadecimal string, written `#rrggbbaa`
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+
+    /// Construct an opaque color from its hue, saturation and value
+    ///
+    /// # Arguments
+    /// * hue - Hue, in degrees (0 .. 360), red at 0, green at 120, blue at 240
+    /// * saturation - Saturation (0 .. 1), gray at 0
+    /// * value - Value (0 .. 1), black at 0
+    ///
+    /// Return Color object constructed from the components
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let (saturation, value) = (saturation.max(0.).min(1.), value.max(0.).min(1.));
+        let sector = (hue % 360. + 360.) % 360. / 60.;
+        let chroma = value * saturation;
+        let x = chroma * (1. - (sector % 2. - 1.).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.),
+            1 => (x, chroma, 0.),
+            2 => (0., chroma, x),
+            3 => (0., x, chroma),
+            4 => (x, 0., chroma),
+            _ => (chroma, 0., x),
+        };
+        let min = value - chroma;
+        let component = |c: f32| ((c + min) * 255.).round() as u8;
+        Color::rgb(component(r), component(g), component(b))
+    }
+
+    /// Get the hue, saturation and value of the color, ignoring its alpha
+    ///
+    /// Return the hue in degrees (0 .. 360), and the saturation and value (0 .. 1).
+    /// Grays have a hue of 0.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (r, g, b) = (
+            f32::from(self.r) / 255.,
+            f32::from(self.g) / 255.,
+            f32::from(self.b) / 255.,
+        );
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let hue = if delta == 0. {
+            0.
+        } else if max == r {
+            60. * (((g - b) / delta) % 6. + 6.) % 6.
+        } else if max == g {
+            60. * ((b - r) / delta + 2.)
+        } else {
+            60. * ((r - g) / delta + 4.)
+        };
+        let saturation = if max == 0. { 0. } else { delta / max };
+        (hue, saturation, max)
+    }
+
+    /// Blend two colors, alpha included
+    ///
+    /// # Arguments
+    /// * a - Color returned when t is 0
+    /// * b - Color returned when t is 1
+    /// * t - Position between the two colors, clamped between 0 and 1
+    pub fn lerp(a: Color, b: Color, t: f32) -> Color {
+        let t = t.max(0.).min(1.);
+        let lerp = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+        Color::rgba(
+            lerp(a.r, b.r),
+            lerp(a.g, b.g),
+            lerp(a.b, b.b),
+            lerp(a.a, b.a),
+        )
+    }
+
     pub(super) fn raw(self) -> ffi::sfColor {
         unsafe { ::std::mem::transmute(self) }
     }
@@ -134,6 +253,50 @@ impl Color {
     };
 }
 
+/// Error parsing a [`Color`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string doesn't have 3, 4, 6 or 8 hexadecimal digits, it has this many.
+    InvalidLength(usize),
+    /// The string contains this character, which isn't a hexadecimal digit.
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::InvalidLength(len) => write!(
+                f,
+                "invalid color: expected 3, 4, 6 or 8 hexadecimal digits, found {}",
+                len
+            ),
+            ParseColorError::InvalidDigit(c) => {
+                write!(f, "invalid color: {:?} is not a hexadecimal digit", c)
+            }
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parse a hexadecimal color, see [`Color::from_hex`].
+    ///
+    /// With the `css-colors` feature, the names of the CSS colors are parsed too, see
+    /// [`Color::from_name`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "css-colors")]
+        {
+            if let Some(color) = Color::from_name(s) {
+                return Ok(color);
+            }
+        }
+        Color::from_hex(s)
+    }
+}
+
 impl From<u32> for Color {
     /// Construct the color from 32-bit unsigned integer.
     ///
@@ -155,7 +318,12 @@ impl Add for Color {
 
     /// Calculate the component-wise saturated addition of two colors.
     fn add(self, other: Color) -> Color {
-        unsafe { Color::from_raw(ffi::sfColor_add(self.raw(), other.raw())) }
+        Color::rgba(
+            self.r.saturating_add(other.r),
+            self.g.saturating_add(other.g),
+            self.b.saturating_add(other.b),
+            self.a.saturating_add(other.a),
+        )
     }
 }
 
@@ -170,7 +338,12 @@ impl Sub for Color {
 
     /// Component-wise subtraction of two colors. Components below 0 are clamped to 0.
     fn sub(self, other: Self) -> Self {
-        unsafe { Self::from_raw(ffi::sfColor_subtract(self.raw(), other.raw())) }
+        Color::rgba(
+            self.r.saturating_sub(other.r),
+            self.g.saturating_sub(other.g),
+            self.b.saturating_sub(other.b),
+            self.a.saturating_sub(other.a),
+        )
     }
 }
 
@@ -187,7 +360,13 @@ impl Mul for Color {
     ///
     /// For each `X` in `rgba`, `result.X = a.X * b.X / 255`.
     fn mul(self, other: Color) -> Color {
-        unsafe { Color::from_raw(ffi::sfColor_modulate(self.raw(), other.raw())) }
+        let modulate = |a: u8, b: u8| (u16::from(a) * u16::from(b) / 255) as u8;
+        Color::rgba(
+            modulate(self.r, other.r),
+            modulate(self.g, other.g),
+            modulate(self.b, other.b),
+            modulate(self.a, other.a),
+        )
     }
 }
 
@@ -196,3 +375,61 @@ impl MulAssign for Color {
         *self = *self * rhs;
     }
 }
+
+#[test]
+fn test_color_math() {
+    assert_eq!(Color::from_hsv(0., 1., 1.), Color::RED);
+    assert_eq!(Color::from_hsv(480., 1., 1.), Color::GREEN);
+    assert_eq!(Color::from_hsv(200., 0., 0.5), Color::rgb(128, 128, 128));
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+    let (h, s, v) = Color::rgb(0, 128, 255).to_hsv();
+    assert!(close(h, 209.88235) && close(s, 1.) && close(v, 1.));
+    assert_eq!(Color::from_hsv(h, s, v), Color::rgb(0, 128, 255));
+    assert_eq!(Color::BLUE.to_hsv().0, 240.);
+
+    assert_eq!(
+        Color::lerp(Color::BLACK, Color::WHITE, 0.5),
+        Color::rgb(128, 128, 128)
+    );
+    assert_eq!(
+        Color::lerp(Color::RED, Color::TRANSPARENT, 2.),
+        Color::TRANSPARENT
+    );
+    assert_eq!(
+        Color::rgb(200, 10, 0) + Color::rgb(100, 10, 0),
+        Color::rgb(255, 20, 0)
+    );
+    assert_eq!(
+        Color::rgb(200, 10, 0) - Color::rgb(100, 20, 0),
+        Color::rgba(100, 0, 0, 0)
+    );
+    assert_eq!(
+        Color::WHITE * Color::rgba(255, 128, 0, 128),
+        Color::rgba(255, 128, 0, 128)
+    );
+}
+
+#[test]
+fn test_from_hex() {
+    assert_eq!(Color::from_hex("#1a2B3c"), Ok(Color::rgb(0x1a, 0x2b, 0x3c)));
+    assert_eq!(
+        Color::from_hex("1a2b3c80"),
+        Ok(Color::rgba(0x1a, 0x2b, 0x3c, 0x80))
+    );
+    assert_eq!(Color::from_hex("#abc"), Ok(Color::rgb(0xaa, 0xbb, 0xcc)));
+    assert_eq!(
+        Color::from_hex("#abc0"),
+        Ok(Color::rgba(0xaa, 0xbb, 0xcc, 0))
+    );
+    assert_eq!(Color::from_hex(""), Err(ParseColorError::InvalidLength(0)));
+    assert_eq!(
+        Color::from_hex("#123456789"),
+        Err(ParseColorError::InvalidLength(9))
+    );
+    assert_eq!(
+        Color::from_hex("#12g"),
+        Err(ParseColorError::InvalidDigit('g'))
+    );
+    assert_eq!(Color::rgba(0x1a, 0x2b, 0x3c, 0xff).to_hex(), "#1a2b3cff");
+    assert_eq!("#ff0000".parse(), Ok(Color::RED));
+}
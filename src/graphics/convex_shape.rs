@@ -101,11 +101,7 @@ impl<'s> ConvexShape<'s> {
 }
 
 impl<'s> Drawable for ConvexShape<'s> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_convex_shape(self, states)
     }
 }
@@ -0,0 +1,264 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, Polyline, PrimitiveType, RenderStates, RenderTarget,
+    Transformable, Vertex,
+};
+use crate::system::Vector2f;
+
+/// A smooth curve, made of straight segments when drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Curve {
+    /// A quadratic Bézier curve, pulled towards a single control point.
+    Quadratic {
+        /// Where the curve starts.
+        start: Vector2f,
+        /// The point the curve is pulled towards.
+        control: Vector2f,
+        /// Where the curve ends.
+        end: Vector2f,
+    },
+    /// A cubic Bézier curve, leaving its start towards the first control point, and
+    /// reaching its end from the second one.
+    Cubic {
+        /// Where the curve starts.
+        start: Vector2f,
+        /// The point the curve is pulled towards at its start.
+        control1: Vector2f,
+        /// The point the curve is pulled towards at its end.
+        control2: Vector2f,
+        /// Where the curve ends.
+        end: Vector2f,
+    },
+    /// A Catmull-Rom spline, going smoothly through all of its points.
+    CatmullRom(Vec<Vector2f>),
+}
+
+impl Curve {
+    /// Get the points of the segments approximating the curve.
+    ///
+    /// # Arguments
+    /// * subdivisions - The number of segments each Bézier curve, or each span between two
+    ///   points of a spline, is cut into
+    pub fn points(&self, subdivisions: u32) -> Vec<Vector2f> {
+        let subdivisions = subdivisions.max(1);
+        let steps = (0..=subdivisions).map(move |i| i as f32 / subdivisions as f32);
+        match *self {
+            Curve::Quadratic {
+                start,
+                control,
+                end,
+            } => steps
+                .map(|t| {
+                    let u = 1. - t;
+                    start * (u * u) + control * (2. * u * t) + end * (t * t)
+                })
+                .collect(),
+            Curve::Cubic {
+                start,
+                control1,
+                control2,
+                end,
+            } => steps
+                .map(|t| {
+                    let u = 1. - t;
+                    start * (u * u * u)
+                        + control1 * (3. * u * u * t)
+                        + control2 * (3. * u * t * t)
+                        + end * (t * t * t)
+                })
+                .collect(),
+            Curve::CatmullRom(ref points) => {
+                if points.len() < 2 {
+                    return points.clone();
+                }
+                let last = points.len() - 1;
+                let mut curve = vec![points[0]];
+                for i in 0..last {
+                    // The ends of the spline are repeated to get tangents there
+                    let p0 = points[i.saturating_sub(1)];
+                    let (p1, p2) = (points[i], points[i + 1]);
+                    let p3 = points[(i + 2).min(last)];
+                    curve.extend(steps.clone().skip(1).map(|t| {
+                        let (t2, t3) = (t * t, t * t * t);
+                        (p1 * 2.
+                            + (p2 - p0) * t
+                            + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * t2
+                            + (p1 * 3. - p0 - p2 * 3. + p3) * t3)
+                            * 0.5
+                    }));
+                }
+                curve
+            }
+        }
+    }
+}
+
+/// A curve drawn as a thick line, and optionally filled.
+///
+/// The curve is cut into straight segments, drawn by a [`Polyline`] which sets the
+/// thickness, joins, caps and dashes of the line. Paths, roads and ropes are curves drawn
+/// this way.
+///
+/// Filling closes the curve between its ends, then fans triangles out of the center of its
+/// points: it only works for curves enclosing a convex, or star-shaped, area.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, Curve, CurveShape};
+/// use sfml::system::Vector2f;
+///
+/// let mut rope = CurveShape::new(Curve::CatmullRom(vec![
+///     Vector2f::new(100., 100.),
+///     Vector2f::new(200., 180.),
+///     Vector2f::new(300., 160.),
+///     Vector2f::new(380., 240.),
+/// ]));
+/// rope.set_subdivisions(24);
+/// rope.line_mut().set_thickness(4.);
+/// rope.line_mut().set_color(Color::rgb(150, 110, 60));
+/// // window.draw(&rope);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CurveShape {
+    curve: Curve,
+    subdivisions: u32,
+    line: Polyline,
+    fill_color: Color,
+    fill: Vec<Vertex>,
+    transformable: BasicTransformable,
+}
+
+impl CurveShape {
+    /// Create a new curve, drawn as a white line 1 pixel thick, without filling.
+    ///
+    /// Each Bézier curve, or span of a spline, is cut into 16 segments.
+    pub fn new(curve: Curve) -> Self {
+        let mut shape = CurveShape {
+            curve,
+            subdivisions: 16,
+            line: Polyline::new(Vec::new(), 1.),
+            fill_color: Color::TRANSPARENT,
+            fill: Vec::new(),
+            transformable: BasicTransformable::new(),
+        };
+        shape.update();
+        shape
+    }
+    /// Set the curve to draw.
+    pub fn set_curve(&mut self, curve: Curve) {
+        self.curve = curve;
+        self.update();
+    }
+    /// Get the curve drawn.
+    pub fn curve(&self) -> &Curve {
+        &self.curve
+    }
+    /// Set the number of segments each Bézier curve, or span of a spline, is cut into.
+    ///
+    /// More segments make smoother curves.
+    pub fn set_subdivisions(&mut self, subdivisions: u32) {
+        self.subdivisions = subdivisions.max(1);
+        self.update();
+    }
+    /// Get the number of segments each Bézier curve, or span of a spline, is cut into.
+    pub fn subdivisions(&self) -> u32 {
+        self.subdivisions
+    }
+    /// Get the line the curve is drawn with.
+    pub fn line(&self) -> &Polyline {
+        &self.line
+    }
+    /// Get the line the curve is drawn with, to change its thickness, color or style.
+    ///
+    /// The points of the line are overwritten whenever the curve changes. A thickness of
+    /// zero leaves the line out, for filled curves without an outline.
+    pub fn line_mut(&mut self) -> &mut Polyline {
+        &mut self.line
+    }
+    /// Set the color the inside of the curve is filled with.
+    ///
+    /// The curve is not filled by default, its fill color is transparent.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        for vertex in &mut self.fill {
+            vertex.color = color;
+        }
+    }
+    /// Get the color the inside of the curve is filled with.
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    fn update(&mut self) {
+        let points = self.curve.points(self.subdivisions);
+        self.fill = fan(&points, self.fill_color);
+        self.line.set_points(points);
+    }
+}
+
+impl Drawable for CurveShape {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        if self.fill_color.a > 0 {
+            target.draw_primitives(&self.fill, PrimitiveType::Triangles, states);
+        }
+        target.draw_with_renderstates(&self.line, states);
+    }
+}
+
+delegate_transformable!(impl for CurveShape => transformable);
+
+/// Build the triangles filling the closed polygon of `points`, fanning out of its center.
+fn fan(points: &[Vector2f], color: Color) -> Vec<Vertex> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    let sum = points.iter().fold(Vector2f::new(0., 0.), |sum, &p| sum + p);
+    let center = sum * (1. / points.len() as f32);
+    let mut vertices = Vec::with_capacity(points.len() * 3);
+    for (i, &point) in points.iter().enumerate() {
+        let next = points[(i + 1) % points.len()];
+        vertices.extend_from_slice(&[
+            Vertex::with_pos_color(center, color),
+            Vertex::with_pos_color(point, color),
+            Vertex::with_pos_color(next, color),
+        ]);
+    }
+    vertices
+}
+
+#[test]
+fn test_curve_points() {
+    let close = |a: Vector2f, b: Vector2f| (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4;
+    let quadratic = Curve::Quadratic {
+        start: Vector2f::new(0., 0.),
+        control: Vector2f::new(10., 20.),
+        end: Vector2f::new(20., 0.),
+    };
+    let points = quadratic.points(4);
+    assert_eq!(points.len(), 5);
+    assert!(close(points[2], Vector2f::new(10., 10.)));
+    assert!(close(points[4], Vector2f::new(20., 0.)));
+    let cubic = Curve::Cubic {
+        start: Vector2f::new(0., 0.),
+        control1: Vector2f::new(0., 10.),
+        control2: Vector2f::new(10., 10.),
+        end: Vector2f::new(10., 0.),
+    };
+    assert!(close(cubic.points(2)[1], Vector2f::new(5., 7.5)));
+    // Splines go through all of their points
+    let through = [
+        Vector2f::new(0., 0.),
+        Vector2f::new(10., 5.),
+        Vector2f::new(20., -5.),
+    ];
+    let spline = Curve::CatmullRom(through.to_vec()).points(8);
+    assert_eq!(spline.len(), 2 * 8 + 1);
+    assert!(close(spline[8], through[1]));
+    assert!(close(spline[16], through[2]));
+}
@@ -159,11 +159,7 @@ impl<'s> Shape<'s> for CustomShape<'s> {
 }
 
 impl<'s> Drawable for CustomShape<'s> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_shape(self, states)
     }
 }
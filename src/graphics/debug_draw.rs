@@ -0,0 +1,254 @@
+use crate::graphics::{
+    Color, Drawable, FloatRect, PrimitiveType, RenderStates, RenderTarget, Shape, Sprite, Text,
+    Transformable, Vertex,
+};
+use crate::system::Vector2f;
+
+bitflags! {
+    /// Categories of lines drawn by a [`DebugDraw`].
+    pub struct DebugCategories: u32 {
+        /// The bounding rectangles of the objects, in global coordinates. Cyan by default.
+        const GLOBAL_BOUNDS = 1;
+        /// The bounds of the objects in local coordinates, moved, rotated and scaled
+        /// along with them. Yellow by default.
+        const LOCAL_BOUNDS = 2;
+        /// A cross on the origin of the objects. Magenta by default.
+        const ORIGINS = 4;
+        /// The local X and Y axes of the objects, from their origin. Red and green by
+        /// default.
+        const AXES = 8;
+    }
+}
+
+/// Every category, in the order of their colors and vertices.
+const CATEGORIES: [DebugCategories; 4] = [
+    DebugCategories::GLOBAL_BOUNDS,
+    DebugCategories::LOCAL_BOUNDS,
+    DebugCategories::ORIGINS,
+    DebugCategories::AXES,
+];
+/// Half the size of the cross drawn on origins.
+const CROSS_SIZE: f32 = 4.;
+
+/// Draws the bounds, origins and axes of objects, to debug layout and collisions.
+///
+/// Objects are added to the debug draw once per frame, after they are moved, and the
+/// debug draw is drawn on top of them with the same view. Each category of lines has
+/// its own color, and can be hidden and shown at runtime.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{
+///     CircleShape, DebugCategories, DebugDraw, RenderTarget, RenderWindow, Transformable,
+/// };
+/// use sfml::window::{Event, Key};
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut debug = DebugDraw::new();
+/// let mut ball = CircleShape::new(20., 30);
+/// ball.set_origin((20., 20.));
+/// loop {
+///     while let Some(event) = window.poll_event() {
+///         if let Event::KeyPressed { code: Key::F4, .. } = event {
+///             debug.toggle(DebugCategories::LOCAL_BOUNDS | DebugCategories::AXES);
+///         }
+///     }
+///     ball.rotate(1.);
+///     debug.clear();
+///     debug.add_shape(&ball);
+///     window.draw(&ball);
+///     window.draw(&debug);
+///     window.display();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DebugDraw {
+    enabled: DebugCategories,
+    colors: [Color; 4],
+    y_axis_color: Color,
+    axis_length: f32,
+    lines: [Vec<Vertex>; 4],
+}
+
+impl DebugDraw {
+    /// Create a new empty debug draw, with every category shown.
+    pub fn new() -> Self {
+        DebugDraw {
+            enabled: DebugCategories::all(),
+            colors: [Color::CYAN, Color::YELLOW, Color::MAGENTA, Color::RED],
+            y_axis_color: Color::GREEN,
+            axis_length: 20.,
+            lines: Default::default(),
+        }
+    }
+    /// Show or hide categories of lines.
+    ///
+    /// Hidden lines are still added, so that they can be shown again at once.
+    pub fn set_enabled(&mut self, categories: DebugCategories, enabled: bool) {
+        self.enabled.set(categories, enabled);
+    }
+    /// Show hidden categories of lines, and hide shown ones.
+    pub fn toggle(&mut self, categories: DebugCategories) {
+        self.enabled.toggle(categories);
+    }
+    /// Get the categories of lines shown.
+    pub fn enabled(&self) -> DebugCategories {
+        self.enabled
+    }
+    /// Set the color of categories of lines, for the objects added from then on.
+    ///
+    /// The color of the axes is the color of the X axis, see [`set_y_axis_color`].
+    ///
+    /// [`set_y_axis_color`]: DebugDraw::set_y_axis_color
+    pub fn set_color(&mut self, categories: DebugCategories, color: Color) {
+        for (i, category) in CATEGORIES.iter().enumerate() {
+            if categories.contains(*category) {
+                self.colors[i] = color;
+            }
+        }
+    }
+    /// Set the color of the Y axis, for the objects added from then on.
+    pub fn set_y_axis_color(&mut self, color: Color) {
+        self.y_axis_color = color;
+    }
+    /// Set the length of the axes in local coordinates, 20 by default.
+    ///
+    /// The axes are scaled along with the objects.
+    pub fn set_axis_length(&mut self, length: f32) {
+        self.axis_length = length;
+    }
+    /// Get the length of the axes in local coordinates.
+    pub fn axis_length(&self) -> f32 {
+        self.axis_length
+    }
+    /// Add the bounds, origin and axes of an object
+    ///
+    /// # Arguments
+    /// * object - The object, giving the transform
+    /// * local_bounds - The bounds of the object in local coordinates
+    pub fn add<T: Transformable + ?Sized>(&mut self, object: &T, local_bounds: &FloatRect) {
+        let transform = object.transform();
+        let quad = transform.transform_quad(local_bounds);
+        let global_bounds = bounding_rect(&quad);
+        push_outline(&mut self.lines[0], &corners(&global_bounds), self.colors[0]);
+        push_outline(&mut self.lines[1], &quad, self.colors[1]);
+        // The corners of a square on the origin are the origin and the ends of the axes
+        let origin = object.origin();
+        let length = self.axis_length;
+        let axes = transform.transform_quad(&FloatRect::new(origin.x, origin.y, length, length));
+        push_cross(&mut self.lines[2], axes[0], self.colors[2]);
+        push_line(&mut self.lines[3], axes[0], axes[1], self.colors[3]);
+        push_line(&mut self.lines[3], axes[0], axes[3], self.y_axis_color);
+    }
+    /// Add the bounds, origin and axes of a shape.
+    pub fn add_shape<'s, S: Shape<'s>>(&mut self, shape: &S) {
+        self.add(shape, &shape.local_bounds());
+    }
+    /// Add the bounds, origin and axes of a sprite.
+    pub fn add_sprite(&mut self, sprite: &Sprite) {
+        self.add(sprite, &sprite.local_bounds());
+    }
+    /// Add the bounds, origin and axes of a text.
+    pub fn add_text(&mut self, text: &Text) {
+        self.add(text, &text.local_bounds());
+    }
+    /// Add a rectangle in global coordinates, such as a hitbox, as global bounds.
+    pub fn add_rect(&mut self, rect: &FloatRect) {
+        push_outline(&mut self.lines[0], &corners(rect), self.colors[0]);
+    }
+    /// Remove every object added.
+    pub fn clear(&mut self) {
+        for lines in &mut self.lines {
+            lines.clear();
+        }
+    }
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drawable for DebugDraw {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        for (category, lines) in CATEGORIES.iter().zip(&self.lines) {
+            if self.enabled.contains(*category) {
+                target.draw_primitives(lines, PrimitiveType::Lines, states);
+            }
+        }
+    }
+}
+
+/// Get the top left, top right, bottom right and bottom left corners of a rectangle.
+fn corners(rect: &FloatRect) -> [Vector2f; 4] {
+    let (right, bottom) = (rect.left + rect.width, rect.top + rect.height);
+    [
+        Vector2f::new(rect.left, rect.top),
+        Vector2f::new(right, rect.top),
+        Vector2f::new(right, bottom),
+        Vector2f::new(rect.left, bottom),
+    ]
+}
+
+/// Get the smallest rectangle containing the corners of a quad.
+fn bounding_rect(quad: &[Vector2f; 4]) -> FloatRect {
+    let (mut min, mut max) = (quad[0], quad[0]);
+    for point in &quad[1..] {
+        min = Vector2f::new(min.x.min(point.x), min.y.min(point.y));
+        max = Vector2f::new(max.x.max(point.x), max.y.max(point.y));
+    }
+    FloatRect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+}
+
+fn push_line(vertices: &mut Vec<Vertex>, start: Vector2f, end: Vector2f, color: Color) {
+    vertices.push(Vertex::with_pos_color(start, color));
+    vertices.push(Vertex::with_pos_color(end, color));
+}
+
+fn push_outline(vertices: &mut Vec<Vertex>, quad: &[Vector2f; 4], color: Color) {
+    for i in 0..4 {
+        push_line(vertices, quad[i], quad[(i + 1) % 4], color);
+    }
+}
+
+fn push_cross(vertices: &mut Vec<Vertex>, center: Vector2f, color: Color) {
+    let (x, y) = (Vector2f::new(CROSS_SIZE, 0.), Vector2f::new(0., CROSS_SIZE));
+    push_line(vertices, center - x, center + x, color);
+    push_line(vertices, center - y, center + y, color);
+}
+
+#[test]
+fn test_debug_draw_geometry() {
+    let quad = [
+        Vector2f::new(0., -1.),
+        Vector2f::new(2., 0.),
+        Vector2f::new(0., 3.),
+        Vector2f::new(-1., 0.),
+    ];
+    assert_eq!(bounding_rect(&quad), FloatRect::new(-1., -1., 3., 4.));
+    let rect = FloatRect::new(1., 2., 3., 4.);
+    assert_eq!(bounding_rect(&corners(&rect)), rect);
+
+    let mut vertices = Vec::new();
+    push_outline(&mut vertices, &quad, Color::WHITE);
+    assert_eq!(vertices.len(), 8);
+    // The last side closes the outline
+    assert_eq!(vertices[6].position, quad[3]);
+    assert_eq!(vertices[7].position, quad[0]);
+
+    let mut debug = DebugDraw::new();
+    debug.toggle(DebugCategories::AXES);
+    assert_eq!(
+        debug.enabled(),
+        DebugCategories::all() - DebugCategories::AXES
+    );
+    debug.set_color(
+        DebugCategories::ORIGINS | DebugCategories::AXES,
+        Color::WHITE,
+    );
+    assert_eq!(debug.colors[2], Color::WHITE);
+    assert_eq!(debug.colors[3], Color::WHITE);
+    assert_eq!(debug.colors[0], Color::CYAN);
+}
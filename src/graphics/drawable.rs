@@ -1,11 +1,42 @@
 use crate::graphics::{RenderStates, RenderTarget};
 
 /// The trait drawable is inherited by each object who can be drawn in a [`RenderTarget`]
+///
+/// Implementing `Drawable` for your own types allows them to be drawn like the built-in
+/// sprites, shapes and texts, with [`RenderTarget::draw`] and
+/// [`RenderTarget::draw_with_renderstates`]. This is typically how composite game objects
+/// encapsulate their own rendering: their `draw` function draws their parts to the target.
+///
+/// The render states passed to `draw` are the ones the object is drawn with. They can be
+/// handed over to the parts unmodified, or changed first, for example to combine the
+/// transform of the object with the one of the states, or to set a shader.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Drawable, RenderStates, RenderTarget, Sprite, Text, Transform};
+///
+/// struct Button<'s> {
+///     transform: Transform,
+///     background: Sprite<'s>,
+///     label: Text<'s>,
+/// }
+///
+/// impl<'s> Drawable for Button<'s> {
+///     fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+///         let mut transform = states.transform;
+///         transform.combine(&self.transform);
+///         let states = RenderStates { transform, ..states };
+///         target.draw_with_renderstates(&self.background, states);
+///         target.draw_with_renderstates(&self.label, states);
+///     }
+/// }
+/// ```
 pub trait Drawable {
     /// Draw a drawable object into a [`RenderTarget`]
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    );
+    ///
+    /// # Arguments
+    /// * target - Render target to draw to
+    /// * states - Current render states
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates);
 }
@@ -62,8 +62,8 @@ impl Font {
     /// * characterSize - Character size, in pixels
     ///
     /// Return the kerning offset, in pixels
-    pub fn kerning(&self, first: u32, second: u32, character_size: u32) -> i32 {
-        unsafe { ffi::sfFont_getKerning(self.raw(), first, second, character_size) as i32 }
+    pub fn kerning(&self, first: u32, second: u32, character_size: u32) -> f32 {
+        unsafe { ffi::sfFont_getKerning(self.raw(), first, second, character_size) }
     }
 
     /// Get the line spacing value
@@ -72,8 +72,8 @@ impl Font {
     /// * characterSize - Character size, in pixels
     ///
     /// Return the line spacing, in pixels
-    pub fn line_spacing(&self, character_size: u32) -> i32 {
-        unsafe { ffi::sfFont_getLineSpacing(self.raw(), character_size) as i32 }
+    pub fn line_spacing(&self, character_size: u32) -> f32 {
+        unsafe { ffi::sfFont_getLineSpacing(self.raw(), character_size) }
     }
 
     /// Get a glyph in a font
@@ -82,6 +82,7 @@ impl Font {
     /// * codePoint - Unicode code point of the character to get
     /// * characterSize - Character size, in pixels
     /// * bold - Retrieve the bold version or the regular one?
+    /// * outline_thickness - Thickness of outline (when != 0 the glyph will not be filled)
     ///
     /// Return the corresponding glyph
     pub fn glyph(
@@ -111,10 +112,19 @@ impl Font {
         }
     }
     /// Returns the position of the underline.
+    ///
+    /// Underline position is the vertical offset to apply between the
+    /// baseline and the underline.
+    ///
+    /// # Arguments
+    /// * characterSize - Character size, in pixels
     pub fn underline_position(&self, character_size: u32) -> f32 {
         unsafe { ffi::sfFont_getUnderlinePosition(self.raw(), character_size) }
     }
     /// Returns the thickness of the underline.
+    ///
+    /// # Arguments
+    /// * characterSize - Character size, in pixels
     pub fn underline_thickness(&self, character_size: u32) -> f32 {
         unsafe { ffi::sfFont_getUnderlineThickness(self.raw(), character_size) }
     }
@@ -0,0 +1,130 @@
+use crate::graphics::Font;
+use std::ops::Range;
+
+/// Code point that no font maps, used to get the glyph fonts display for missing characters.
+const MISSING: u32 = 0x10_FFFF;
+
+/// An ordered list of fonts, falling back from one to the next for missing characters.
+///
+/// No single font covers every script: a Latin font has no CJK characters, and few fonts
+/// have symbols or emoji. Drawn with such a font, the missing characters show up as boxes.
+/// A font set resolves each character to the first of its fonts that has it, and splits
+/// strings into runs of characters using the same font, which can then be drawn with one
+/// [`Text`] each, or as a whole with [`RichText::push_with_fonts`].
+///
+/// CSFML can't tell whether a font has a character. A font is considered to lack a
+/// character when the glyph it gives for it has the metrics of the glyph it gives for
+/// characters it doesn't have, usually an empty box. Characters that no font has are
+/// resolved to the first font.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Font, FontSet, RichText, TextSpan};
+///
+/// let latin = Font::from_file("sansation.ttf").unwrap();
+/// let japanese = Font::from_file("NotoSansJP-Regular.otf").unwrap();
+/// let mut fonts = FontSet::new();
+/// fonts.push(&latin);
+/// fonts.push(&japanese);
+/// let mut text = RichText::new(&latin);
+/// text.push_with_fonts(TextSpan::new("Score: 100 点", 24), &fonts);
+/// ```
+///
+/// [`Text`]: crate::graphics::Text
+/// [`RichText::push_with_fonts`]: crate::graphics::RichText::push_with_fonts
+#[derive(Debug, Clone, Default)]
+pub struct FontSet<'f> {
+    fonts: Vec<&'f Font>,
+}
+
+impl<'f> FontSet<'f> {
+    /// Create a new empty font set.
+    pub fn new() -> Self {
+        FontSet { fonts: Vec::new() }
+    }
+    /// Add a font at the end of the set, to be used when the previous ones lack a character.
+    pub fn push(&mut self, font: &'f Font) {
+        self.fonts.push(font);
+    }
+    /// Get the fonts of the set, in order.
+    pub fn fonts(&self) -> &[&'f Font] {
+        &self.fonts
+    }
+    /// Return the number of fonts in the set.
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+    /// Tell whether the set has no fonts.
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+    /// Tell whether a font has a character, given at a character size.
+    pub fn has_glyph(font: &Font, codepoint: u32, character_size: u32) -> bool {
+        let glyph = font.glyph(codepoint, character_size, false, 0.);
+        let missing = font.glyph(MISSING, character_size, false, 0.);
+        glyph.advance != missing.advance || glyph.bounds != missing.bounds
+    }
+    /// Get the first font of the set that has a character.
+    ///
+    /// Returns the first font if none has it, and `None` if the set is empty.
+    pub fn font_for(&self, c: char, character_size: u32) -> Option<&'f Font> {
+        let index = self.index_for(c, character_size)?;
+        Some(self.fonts[index])
+    }
+    /// Split a string into runs of characters resolved to the same font.
+    ///
+    /// Whitespace stays in the run it is in, so that words of a same script aren't split.
+    /// Returns no runs if the set is empty.
+    pub fn runs<'s>(&self, string: &'s str, character_size: u32) -> Vec<(&'f Font, &'s str)> {
+        if self.fonts.is_empty() {
+            return Vec::new();
+        }
+        split_runs(string, |c| self.index_for(c, character_size).unwrap_or(0))
+            .into_iter()
+            .map(|(index, range)| (self.fonts[index], &string[range]))
+            .collect()
+    }
+
+    fn index_for(&self, c: char, character_size: u32) -> Option<usize> {
+        if self.fonts.is_empty() {
+            return None;
+        }
+        let index = self
+            .fonts
+            .iter()
+            .position(|font| Self::has_glyph(font, c as u32, character_size));
+        Some(index.unwrap_or(0))
+    }
+}
+
+/// Split `string` into runs of characters with the same font index, as given by `font`.
+fn split_runs<F>(string: &str, font: F) -> Vec<(usize, Range<usize>)>
+where
+    F: Fn(char) -> usize,
+{
+    let mut runs: Vec<(usize, Range<usize>)> = Vec::new();
+    for (i, c) in string.char_indices() {
+        let end = i + c.len_utf8();
+        if let Some(&mut (index, ref mut range)) = runs.last_mut() {
+            if c.is_whitespace() || font(c) == index {
+                range.end = end;
+                continue;
+            }
+        }
+        let index = if c.is_whitespace() { 0 } else { font(c) };
+        runs.push((index, i..end));
+    }
+    runs
+}
+
+#[test]
+fn test_split_runs() {
+    let font = |c: char| if c.is_ascii() { 0 } else { 1 };
+    assert_eq!(
+        split_runs("Score: 100 点点 ok", font),
+        [(0, 0..11), (1, 11..18), (0, 18..20)]
+    );
+    assert_eq!(split_runs(" 点", font), [(0, 0..1), (1, 1..4)]);
+    assert!(split_runs("", font).is_empty());
+}
@@ -0,0 +1,312 @@
+use crate::graphics::{Image, ImageFormat, RenderTarget, RenderTexture, RenderWindow, Texture};
+use crate::system::SfBox;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Number of frames waiting to be written before the writer is considered behind.
+const QUEUE_LENGTH: usize = 8;
+
+/// What became of a frame given to a [`FrameRecorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordStatus {
+    /// The frame was copied, and handed over to the writer.
+    Recorded,
+    /// The writer is behind, so the frame was dropped before it was copied.
+    Dropped,
+    /// The frame couldn't be copied, or the writer stopped on an error returned by
+    /// [`FrameRecorder::finish`].
+    Failed,
+}
+
+/// A frame recorded by a [`FrameRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedFrame {
+    /// The number of the frame, counting from 0.
+    pub index: u64,
+    /// The width of the frame, in pixels.
+    pub width: u32,
+    /// The height of the frame, in pixels.
+    pub height: u32,
+    /// The pixels of the frame, in RGBA order, row by row from the top.
+    pub pixels: Vec<u8>,
+}
+
+/// Records the frames of a window or a render texture, for trailers and for the baselines
+/// of visual regression tests.
+///
+/// Each recorded frame is copied from the graphics card on the render thread, which is
+/// the only one able to read it. Then it is handed over to a writer thread, which encodes
+/// it to a numbered image file, or gives it to a callback. A few frames can wait to be
+/// written: when the writer falls behind, the next frames are dropped, so that the render
+/// thread never stalls, and recording reports them as [`RecordStatus::Dropped`]. To get
+/// every frame instead, for visual regression tests for example, recording can wait for
+/// the writer, see [`set_blocking`].
+///
+/// Dropping the recorder, or calling [`finish`], waits for the frames waiting to be
+/// written.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, FrameRecorder, ImageFormat, RenderTarget, RenderWindow};
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut recorder = FrameRecorder::to_files("capture", ImageFormat::Png).unwrap();
+/// for _ in 0..600 {
+///     window.clear(Color::BLACK);
+///     // window.draw(&world);
+///     recorder.record_window(&window);
+///     window.display();
+/// }
+/// // capture/frame_000000.png to capture/frame_000599.png
+/// recorder.finish().unwrap();
+/// ```
+///
+/// [`finish`]: FrameRecorder::finish
+/// [`set_blocking`]: FrameRecorder::set_blocking
+#[derive(Debug)]
+pub struct FrameRecorder {
+    sender: Option<SyncSender<RecordedFrame>>,
+    writer: Option<JoinHandle<io::Result<()>>>,
+    /// Number of frames handed over to the writer and not written yet.
+    pending: Arc<AtomicUsize>,
+    blocking: bool,
+    texture: Option<SfBox<Texture>>,
+    frame_count: u64,
+    dropped_count: u64,
+}
+
+impl FrameRecorder {
+    /// Create a recorder writing the frames to numbered image files in a directory
+    ///
+    /// The directory is created if needed, and the frames are written to
+    /// `frame_000000.png`, `frame_000001.png`, and so on, with the extension of the
+    /// format.
+    ///
+    /// # Arguments
+    /// * directory - The directory to write the frames to
+    /// * format - The file format of the frames
+    pub fn to_files<P: AsRef<Path>>(directory: P, format: ImageFormat) -> io::Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+        Ok(Self::spawn(move |frame: RecordedFrame| {
+            let path = frame_path(&directory, frame.index, format);
+            format.save(&frame.pixels, frame.width, frame.height, &path)
+        }))
+    }
+    /// Create a recorder handing the frames over to a callback
+    ///
+    /// The callback is called from the writer thread, in the order the frames were
+    /// recorded. It can pipe them to a video encoder, or compare them to a baseline.
+    pub fn with_callback<F>(mut callback: F) -> Self
+    where
+        F: FnMut(RecordedFrame) + Send + 'static,
+    {
+        Self::spawn(move |frame| {
+            callback(frame);
+            Ok(())
+        })
+    }
+    /// Set whether recording waits for the writer when it falls behind
+    ///
+    /// By default, frames are dropped while the writer is behind, so that recording never
+    /// stalls the render thread. A blocking recorder waits for the writer instead, and
+    /// records every frame.
+    pub fn set_blocking(&mut self, blocking: bool) {
+        self.blocking = blocking;
+    }
+    /// Record the current contents of a window
+    ///
+    /// Call it once the frame is drawn, before [`RenderWindow::display`].
+    ///
+    /// Return what became of the frame, see [`RecordStatus`].
+    pub fn record_window(&mut self, window: &RenderWindow) -> RecordStatus {
+        if self.is_behind() {
+            return RecordStatus::Dropped;
+        }
+        let size = window.size();
+        let reuse = self.texture.as_ref().map_or(false, |t| t.size() == size);
+        if !reuse {
+            self.texture = Texture::new(size.x, size.y);
+        }
+        let image = match self.texture {
+            Some(ref mut texture) => {
+                texture.update_from_render_window(window, 0, 0);
+                texture.copy_to_image()
+            }
+            None => None,
+        };
+        self.record_image(image)
+    }
+    /// Record the current contents of a render texture
+    ///
+    /// Call it once the frame is drawn, after [`RenderTexture::display`].
+    ///
+    /// Return what became of the frame, see [`RecordStatus`].
+    pub fn record_texture(&mut self, texture: &RenderTexture) -> RecordStatus {
+        if self.is_behind() {
+            return RecordStatus::Dropped;
+        }
+        self.record_image(texture.capture_to_image())
+    }
+    /// Get the number of frames recorded so far.
+    ///
+    /// The frames are numbered from 0 in the order they are recorded, dropped frames
+    /// don't count.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+    /// Get the number of frames dropped so far, while the writer was behind.
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
+    /// Wait for the frames waiting to be written, and stop recording
+    ///
+    /// Return the first error met writing the frames, if any.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.stop()
+    }
+
+    fn spawn<F>(mut write: F) -> Self
+    where
+        F: FnMut(RecordedFrame) -> io::Result<()> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel::<RecordedFrame>(QUEUE_LENGTH);
+        let pending = Arc::new(AtomicUsize::new(0));
+        let writer = {
+            let pending = Arc::clone(&pending);
+            thread::Builder::new()
+                .name("sfml-frame-recorder".into())
+                .spawn(move || {
+                    receiver.iter().try_for_each(|frame| {
+                        let result = write(frame);
+                        let _ = pending.fetch_sub(1, Ordering::SeqCst);
+                        result
+                    })
+                })
+                .expect("Failed to spawn the frame recorder thread")
+        };
+        FrameRecorder {
+            sender: Some(sender),
+            writer: Some(writer),
+            pending,
+            blocking: false,
+            texture: None,
+            frame_count: 0,
+            dropped_count: 0,
+        }
+    }
+    /// Whether the frame about to be recorded must be dropped, before it is copied.
+    fn is_behind(&mut self) -> bool {
+        let behind = !self.blocking && self.pending.load(Ordering::SeqCst) >= QUEUE_LENGTH;
+        if behind {
+            self.dropped_count += 1;
+        }
+        behind
+    }
+    fn record_image(&mut self, image: Option<Image>) -> RecordStatus {
+        let image = match image {
+            Some(image) => image,
+            None => return RecordStatus::Failed,
+        };
+        let size = image.size();
+        self.send(RecordedFrame {
+            index: self.frame_count,
+            width: size.x,
+            height: size.y,
+            pixels: image.pixels().to_vec(),
+        })
+    }
+    fn send(&mut self, frame: RecordedFrame) -> RecordStatus {
+        // Counted before the writer can be done with it
+        let _ = self.pending.fetch_add(1, Ordering::SeqCst);
+        // The writer only hangs up after an error. Unless the recorder is blocking, there
+        // is room for the frame, see `is_behind`.
+        let sent = self
+            .sender
+            .as_ref()
+            .map_or(false, |sender| sender.send(frame).is_ok());
+        if sent {
+            self.frame_count += 1;
+            RecordStatus::Recorded
+        } else {
+            let _ = self.pending.fetch_sub(1, Ordering::SeqCst);
+            RecordStatus::Failed
+        }
+    }
+    fn stop(&mut self) -> io::Result<()> {
+        drop(self.sender.take());
+        match self.writer.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "the frame recorder thread panicked",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for FrameRecorder {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+/// Get the path of the file frame `index` is written to.
+fn frame_path(directory: &Path, index: u64, format: ImageFormat) -> PathBuf {
+    directory.join(format!("frame_{:06}.{}", index, format.extension()))
+}
+
+#[test]
+fn test_frame_recorder() {
+    use std::sync::{Arc, Mutex};
+
+    assert_eq!(
+        frame_path(Path::new("capture"), 42, ImageFormat::Png),
+        Path::new("capture").join("frame_000042.png")
+    );
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let mut recorder = {
+        let frames = Arc::clone(&frames);
+        FrameRecorder::with_callback(move |frame| frames.lock().unwrap().push(frame.index))
+    };
+    recorder.set_blocking(true);
+    for _ in 0..20 {
+        let frame = RecordedFrame {
+            index: recorder.frame_count(),
+            width: 1,
+            height: 1,
+            pixels: vec![0; 4],
+        };
+        assert!(!recorder.is_behind());
+        assert_eq!(recorder.send(frame), RecordStatus::Recorded);
+    }
+    recorder.finish().unwrap();
+    assert_eq!(*frames.lock().unwrap(), (0..20).collect::<Vec<_>>());
+
+    // The writer waits for `go`, and falls behind
+    let (go, wait) = mpsc::channel();
+    let mut recorder = FrameRecorder::with_callback(move |_| wait.recv().unwrap());
+    for index in 0..QUEUE_LENGTH as u64 {
+        assert!(!recorder.is_behind());
+        let frame = RecordedFrame {
+            index,
+            width: 1,
+            height: 1,
+            pixels: vec![0; 4],
+        };
+        assert_eq!(recorder.send(frame), RecordStatus::Recorded);
+    }
+    assert!(recorder.is_behind());
+    assert_eq!(recorder.dropped_count(), 1);
+    for _ in 0..QUEUE_LENGTH {
+        go.send(()).unwrap();
+    }
+    recorder.finish().unwrap();
+}
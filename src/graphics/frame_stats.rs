@@ -0,0 +1,128 @@
+use crate::graphics::{Font, Shape, Sprite, Text, Texture};
+use std::cell::Cell;
+
+/// What a render target drew during a frame, see [`RenderTarget::frame_stats`].
+///
+/// A draw call is a single batch of vertices sent to the graphics card. Each call is
+/// costly, and so is switching textures between two calls: many draw calls, or as many
+/// texture switches as draw calls, are the first things to look at when drawing is slow.
+/// Sprites sharing a texture can be batched with a [`SpriteBatch`], tiles with a
+/// [`TileMap`].
+///
+/// Shapes and texts are counted the way SFML draws them: shapes with an outline are two
+/// draw calls, and the vertices of a text are counted for each of its visible characters.
+///
+/// [`RenderTarget::frame_stats`]: crate::graphics::RenderTarget::frame_stats
+/// [`SpriteBatch`]: crate::graphics::SpriteBatch
+/// [`TileMap`]: crate::graphics::TileMap
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FrameStats {
+    /// The number of draw calls.
+    pub draw_calls: u32,
+    /// The number of vertices drawn.
+    pub vertices: u32,
+    /// The number of times a draw call used another texture than the previous one.
+    pub texture_switches: u32,
+    /// The number of times the target was cleared.
+    pub clears: u32,
+}
+
+/// Counts what a render target draws, frame after frame.
+#[derive(Debug, Default)]
+pub(super) struct StatsCounter {
+    current: Cell<FrameStats>,
+    last: Cell<FrameStats>,
+    /// Address of the texture, or font, of the last draw call, 0 for no texture.
+    texture: Cell<usize>,
+}
+
+impl StatsCounter {
+    pub(super) fn text(&self, text: &Text) {
+        // Counted on the string as stored, this runs on every draw
+        let glyphs = text
+            .unicode_string()
+            .iter()
+            .filter(|&&c| std::char::from_u32(c).map_or(false, |c| !c.is_whitespace()))
+            .count();
+        let calls = if text.outline_thickness() != 0. { 2 } else { 1 };
+        let font = text.font().map_or(0, |font| {
+            let font: *const Font = font;
+            font as usize
+        });
+        self.draw(calls, glyphs * 6, font);
+    }
+    pub(super) fn shape<'s, S: Shape<'s>>(&self, shape: &S) {
+        // A triangle fan for the fill, a triangle strip for the outline
+        let points = shape.point_count() as usize;
+        let (calls, vertices) = if shape.outline_thickness() != 0. {
+            (2, points + 2 + (points + 1) * 2)
+        } else {
+            (1, points + 2)
+        };
+        self.draw(calls, vertices, address(shape.texture()));
+    }
+    pub(super) fn sprite(&self, sprite: &Sprite) {
+        self.draw(1, 4, address(sprite.texture()));
+    }
+    pub(super) fn vertices(&self, count: usize, texture: Option<&Texture>) {
+        // SFML skips drawing nothing
+        if count > 0 {
+            self.draw(1, count, address(texture));
+        }
+    }
+    pub(super) fn clear(&self) {
+        let mut stats = self.current.get();
+        stats.clears += 1;
+        self.current.set(stats);
+    }
+    /// Keep the statistics of the frame that was just displayed, and start a new one.
+    pub(super) fn end_frame(&self) {
+        self.last.set(self.current.take());
+    }
+    pub(super) fn last(&self) -> FrameStats {
+        self.last.get()
+    }
+
+    fn draw(&self, calls: u32, vertices: usize, texture: usize) {
+        let mut stats = self.current.get();
+        stats.draw_calls += calls;
+        stats.vertices = stats.vertices.saturating_add(vertices as u32);
+        if texture != self.texture.replace(texture) {
+            stats.texture_switches += 1;
+        }
+        self.current.set(stats);
+    }
+}
+
+fn address(texture: Option<&Texture>) -> usize {
+    texture.map_or(0, |texture| {
+        let texture: *const Texture = texture;
+        texture as usize
+    })
+}
+
+#[test]
+fn test_stats_counter() {
+    let counter = StatsCounter::default();
+    counter.clear();
+    counter.vertices(6, None);
+    counter.vertices(0, None);
+    counter.vertices(3, None);
+    assert_eq!(counter.last(), FrameStats::default());
+    counter.end_frame();
+    let stats = FrameStats {
+        draw_calls: 2,
+        vertices: 9,
+        texture_switches: 0,
+        clears: 1,
+    };
+    assert_eq!(counter.last(), stats);
+    counter.end_frame();
+    assert_eq!(counter.last(), FrameStats::default());
+    // Binding a first texture is a switch too, drawing with it again isn't
+    counter.draw(1, 4, 0x10);
+    counter.draw(1, 4, 0x10);
+    counter.draw(1, 4, 0x20);
+    counter.end_frame();
+    assert_eq!(counter.last().texture_switches, 2);
+}
@@ -0,0 +1,314 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, FloatRect, PrimitiveType, RenderStates, RenderTarget,
+    Transformable, Vertex,
+};
+use crate::system::Vector2f;
+use std::f32::consts::PI;
+
+/// How the inside of a [`GradientShape`] is colored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gradient {
+    /// A color for each point of the shape, blended in between. The center of the shape
+    /// gets the average of the colors. Points without a color are white.
+    Points(Vec<Color>),
+    /// Colors blending along a line, and constant across it.
+    Linear {
+        /// Where the gradient starts, in local coordinates.
+        start: Vector2f,
+        /// Where the gradient ends, in local coordinates.
+        end: Vector2f,
+        /// Color at the start of the gradient, and before it.
+        start_color: Color,
+        /// Color at the end of the gradient, and after it.
+        end_color: Color,
+    },
+    /// Colors blending from a center outwards.
+    Radial {
+        /// Center of the gradient, in local coordinates.
+        center: Vector2f,
+        /// Distance from the center at which the outer color is reached.
+        radius: f32,
+        /// Color at the center.
+        inner_color: Color,
+        /// Color at the radius, and beyond.
+        outer_color: Color,
+    },
+}
+
+impl Gradient {
+    /// Get the color of the gradient at a point, in local coordinates.
+    ///
+    /// Per point gradients have no color of their own, and return white.
+    pub fn color_at(&self, point: Vector2f) -> Color {
+        match *self {
+            Gradient::Points(_) => Color::WHITE,
+            Gradient::Linear {
+                start,
+                end,
+                start_color,
+                end_color,
+            } => {
+                let axis = end - start;
+                let length = axis.x * axis.x + axis.y * axis.y;
+                let t = if length > 0. {
+                    ((point - start).x * axis.x + (point - start).y * axis.y) / length
+                } else {
+                    0.
+                };
+                Color::lerp(start_color, end_color, t)
+            }
+            Gradient::Radial {
+                center,
+                radius,
+                inner_color,
+                outer_color,
+            } => {
+                let offset = point - center;
+                let distance = (offset.x * offset.x + offset.y * offset.y).sqrt();
+                let t = if radius > 0. { distance / radius } else { 1. };
+                Color::lerp(inner_color, outer_color, t)
+            }
+        }
+    }
+}
+
+/// Number of bands each triangle of a shape is cut into, from the center to the edges,
+/// so that radial gradients look round.
+const BANDS: usize = 8;
+
+/// A convex shape filled with a gradient, instead of a single color.
+///
+/// The shapes of SFML have a single fill color, modulated by their texture. A gradient
+/// shape blends colors across its area: one color per point, a linear gradient, or a
+/// radial one. It is made of triangles whose vertices take the colors of the gradient.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, Gradient, GradientShape};
+/// use sfml::system::Vector2f;
+///
+/// // A sky, from deep blue at the top to orange at the horizon
+/// let mut sky = GradientShape::rectangle(Vector2f::new(800., 400.));
+/// sky.set_gradient(Gradient::Linear {
+///     start: Vector2f::new(0., 0.),
+///     end: Vector2f::new(0., 400.),
+///     start_color: Color::rgb(20, 30, 90),
+///     end_color: Color::rgb(250, 160, 60),
+/// });
+/// // window.draw(&sky);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GradientShape {
+    points: Vec<Vector2f>,
+    gradient: Gradient,
+    transformable: BasicTransformable,
+    vertices: Vec<Vertex>,
+}
+
+impl GradientShape {
+    /// Create a new shape out of the points of a convex polygon, filled with white.
+    pub fn from_points(points: Vec<Vector2f>) -> Self {
+        let mut shape = GradientShape {
+            points,
+            gradient: Gradient::Points(Vec::new()),
+            transformable: BasicTransformable::new(),
+            vertices: Vec::new(),
+        };
+        shape.update();
+        shape
+    }
+    /// Create a new rectangle, with its top left corner at the origin.
+    ///
+    /// Its points are the top left, top right, bottom right and bottom left corners.
+    pub fn rectangle(size: Vector2f) -> Self {
+        Self::from_points(vec![
+            Vector2f::new(0., 0.),
+            Vector2f::new(size.x, 0.),
+            size,
+            Vector2f::new(0., size.y),
+        ])
+    }
+    /// Create a new circle, with its center at (`radius`, `radius`) like a [`CircleShape`].
+    ///
+    /// [`CircleShape`]: crate::graphics::CircleShape
+    pub fn circle(radius: f32, point_count: u32) -> Self {
+        let count = point_count.max(3);
+        let points = (0..count)
+            .map(|i| {
+                let angle = i as f32 * 2. * PI / count as f32 - PI / 2.;
+                Vector2f::new(radius + angle.cos() * radius, radius + angle.sin() * radius)
+            })
+            .collect();
+        Self::from_points(points)
+    }
+    /// Set the points of the shape, a convex polygon.
+    pub fn set_points(&mut self, points: Vec<Vector2f>) {
+        self.points = points;
+        self.update();
+    }
+    /// Get the points of the shape.
+    pub fn points(&self) -> &[Vector2f] {
+        &self.points
+    }
+    /// Set how the inside of the shape is colored.
+    pub fn set_gradient(&mut self, gradient: Gradient) {
+        self.gradient = gradient;
+        self.update();
+    }
+    /// Get how the inside of the shape is colored.
+    pub fn gradient(&self) -> &Gradient {
+        &self.gradient
+    }
+    /// Get the local bounding rectangle of the shape
+    ///
+    /// The returned rectangle is in local coordinates, which means
+    /// that it ignores the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn local_bounds(&self) -> FloatRect {
+        let mut points = self.points.iter();
+        let first = match points.next() {
+            Some(&first) => first,
+            None => return FloatRect::new(0., 0., 0., 0.),
+        };
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Vector2f::new(min.x.min(p.x), min.y.min(p.y)),
+                Vector2f::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+        FloatRect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+    /// Get the global bounding rectangle of the shape
+    ///
+    /// The returned rectangle is in global coordinates, which means
+    /// that it takes into account the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(&self.local_bounds())
+    }
+    /// Get the vertices of the shape, as a list of triangles.
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    fn update(&mut self) {
+        self.vertices = triangulate(&self.points, &self.gradient);
+    }
+}
+
+impl Drawable for GradientShape {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::Triangles, states);
+    }
+}
+
+delegate_transformable!(impl for GradientShape => transformable);
+
+/// Cut a convex polygon into triangles fanning out of its centroid, in bands, colored
+/// by `gradient`.
+fn triangulate(points: &[Vector2f], gradient: &Gradient) -> Vec<Vertex> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    let count = points.len() as f32;
+    let sum = points.iter().fold(Vector2f::new(0., 0.), |sum, &p| sum + p);
+    let center = Vector2f::new(sum.x / count, sum.y / count);
+    // Colors of the points, and of the center, for per point gradients
+    let point_colors: Vec<Color> = match gradient {
+        Gradient::Points(colors) => (0..points.len())
+            .map(|i| colors.get(i).cloned().unwrap_or(Color::WHITE))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let center_color = if point_colors.is_empty() {
+        Color::WHITE
+    } else {
+        let n = point_colors.len() as u32;
+        let total = point_colors.iter().fold([0u32; 4], |t, c| {
+            [
+                t[0] + u32::from(c.r),
+                t[1] + u32::from(c.g),
+                t[2] + u32::from(c.b),
+                t[3] + u32::from(c.a),
+            ]
+        });
+        Color::rgba(
+            (total[0] / n) as u8,
+            (total[1] / n) as u8,
+            (total[2] / n) as u8,
+            (total[3] / n) as u8,
+        )
+    };
+    let mut vertices = Vec::with_capacity(points.len() * (BANDS * 2 - 1) * 3);
+    for i in 0..points.len() {
+        let j = (i + 1) % points.len();
+        // Point of the edge (i, j) at `s`, `t` of the way from the center
+        let vertex = |t: f32, s: f32| {
+            let edge = points[i] + (points[j] - points[i]) * s;
+            let position = center + (edge - center) * t;
+            let color = if point_colors.is_empty() {
+                gradient.color_at(position)
+            } else {
+                let edge_color = Color::lerp(point_colors[i], point_colors[j], s);
+                Color::lerp(center_color, edge_color, t)
+            };
+            Vertex::with_pos_color(position, color)
+        };
+        for band in 0..BANDS {
+            let inner = band as f32 / BANDS as f32;
+            let outer = (band + 1) as f32 / BANDS as f32;
+            if band == 0 {
+                vertices.extend_from_slice(&[vertex(0., 0.), vertex(outer, 0.), vertex(outer, 1.)]);
+                continue;
+            }
+            let (a, b) = (vertex(inner, 0.), vertex(inner, 1.));
+            let (c, d) = (vertex(outer, 0.), vertex(outer, 1.));
+            vertices.extend_from_slice(&[a, c, d, a, d, b]);
+        }
+    }
+    vertices
+}
+
+#[test]
+fn test_gradient() {
+    let linear = Gradient::Linear {
+        start: Vector2f::new(0., 0.),
+        end: Vector2f::new(0., 100.),
+        start_color: Color::BLACK,
+        end_color: Color::WHITE,
+    };
+    assert_eq!(
+        linear.color_at(Vector2f::new(40., 50.)),
+        Color::rgb(128, 128, 128)
+    );
+    assert_eq!(linear.color_at(Vector2f::new(0., 200.)), Color::WHITE);
+    let radial = Gradient::Radial {
+        center: Vector2f::new(10., 10.),
+        radius: 10.,
+        inner_color: Color::RED,
+        outer_color: Color::TRANSPARENT,
+    };
+    assert_eq!(radial.color_at(Vector2f::new(10., 10.)), Color::RED);
+    assert_eq!(radial.color_at(Vector2f::new(30., 10.)), Color::TRANSPARENT);
+
+    let square = [
+        Vector2f::new(0., 0.),
+        Vector2f::new(2., 0.),
+        Vector2f::new(2., 2.),
+        Vector2f::new(0., 2.),
+    ];
+    let colors = Gradient::Points(vec![Color::RED, Color::RED, Color::BLUE, Color::BLUE]);
+    let vertices = triangulate(&square, &colors);
+    assert_eq!(vertices.len(), 4 * (BANDS * 2 - 1) * 3);
+    assert_eq!(vertices[0].position, Vector2f::new(1., 1.));
+    assert_eq!(vertices[0].color, Color::rgba(127, 0, 127, 255));
+    // Outer corner of the last band of the first triangle
+    assert_eq!(vertices[3 + 6 * 6 + 1].color, Color::RED);
+}
@@ -1,14 +1,23 @@
 use crate::graphics::csfml_graphics_sys as ffi;
-use crate::graphics::{Color, IntRect};
+#[cfg(feature = "image")]
+use crate::graphics::EncodeOptions;
+use crate::graphics::{Color, ImageFormat, IntRect};
 use crate::inputstream::InputStream;
+#[cfg(not(feature = "image"))]
+use crate::path_conv::path_to_cstring;
 use crate::sf_bool_ext::SfBoolExt;
 use crate::system::Vector2u;
 use csfml_system_sys::sfBool;
 use std::ffi::CString;
 use std::io::{Read, Seek};
+#[cfg(not(feature = "image"))]
+use std::path::Path;
 use std::slice;
 
 /// Loading, manipulating and saving images.
+///
+/// With the `image` feature enabled, images convert from and to the images of the `image`
+/// crate, to load the formats SFML can't read, or process them with its filters.
 #[derive(Debug)]
 pub struct Image {
     image: *mut ffi::sfImage,
@@ -138,6 +147,63 @@ impl Image {
         unsafe { ffi::sfImage_saveToFile(self.image, c_str.as_ptr()) }.to_bool()
     }
 
+    /// Save the image to `path`, like [`save_to_file`](Image::save_to_file).
+    #[cfg(not(feature = "image"))]
+    pub(super) fn save_to_path(&self, path: &Path) -> bool {
+        match path_to_cstring(path) {
+            Some(path) => unsafe { ffi::sfImage_saveToFile(self.image, path.as_ptr()) }.to_bool(),
+            None => false,
+        }
+    }
+
+    /// Encode the image to a file format in memory
+    ///
+    /// BMP and TGA images are encoded in Rust. With the `image` feature, so are PNG and
+    /// JPEG images. Otherwise, as CSFML can only save images to files, they are saved to a
+    /// file of a new private temporary directory by [`save_to_file`], and read back.
+    /// This function fails if the image is empty.
+    ///
+    /// # Arguments
+    /// * format - The file format to encode the image to
+    ///
+    /// Return Some(bytes) or None if the image is empty, too big for the format, or if
+    /// encoding failed
+    ///
+    /// [`save_to_file`]: Image::save_to_file
+    pub fn save_to_memory(&self, format: ImageFormat) -> Option<Vec<u8>> {
+        let size = self.size();
+        if size.x == 0 || size.y == 0 {
+            return None;
+        }
+        format.encode(self.pixels(), size.x, size.y)
+    }
+
+    /// Encode the image to a file format in memory, with the JPEG quality or the PNG
+    /// compression level of `options`
+    ///
+    /// This requires the `image` feature. See [`save_to_memory`].
+    ///
+    /// # Arguments
+    /// * format - The file format to encode the image to
+    /// * options - The settings of the encoder
+    ///
+    /// Return Some(bytes) or None if the image is empty, too big for the format, or if
+    /// encoding failed
+    ///
+    /// [`save_to_memory`]: Image::save_to_memory
+    #[cfg(feature = "image")]
+    pub fn save_to_memory_with(
+        &self,
+        format: ImageFormat,
+        options: &EncodeOptions,
+    ) -> Option<Vec<u8>> {
+        let size = self.size();
+        if size.x == 0 || size.y == 0 {
+            return None;
+        }
+        format.encode_with(self.pixels(), size.x, size.y, options)
+    }
+
     /// Return the size of an image
     ///
     /// Return the size in pixels
@@ -188,13 +254,128 @@ impl Image {
     }
 
     /// Return the memory buffer of this image.
+    ///
+    /// Same as [`pixels`].
+    ///
+    /// [`pixels`]: Image::pixels
     pub fn pixel_data(&self) -> &[u8] {
-        unsafe {
-            let size = self.size();
-            let pixels = ffi::sfImage_getPixelsPtr(self.image);
+        self.pixels()
+    }
 
-            slice::from_raw_parts(pixels, (size.x * size.y * 4) as usize)
+    /// Get the pixels of the image
+    ///
+    /// The pixels are stored row by row, from the top left corner, as 4 bytes each: the red,
+    /// green, blue and alpha components, so the pixel at (x, y) starts at
+    /// `(y * width + x) * 4`.
+    pub fn pixels(&self) -> &[u8] {
+        let len = self.pixels_len();
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(ffi::sfImage_getPixelsPtr(self.image), len) }
+    }
+
+    /// Get the pixels of the image, to modify them directly
+    ///
+    /// This is much faster than calling [`set_pixel`] for each pixel. The layout of the
+    /// pixels is the same as for [`pixels`].
+    ///
+    /// [`set_pixel`]: Image::set_pixel
+    /// [`pixels`]: Image::pixels
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        let len = self.pixels_len();
+        if len == 0 {
+            return &mut [];
         }
+        // The pixels belong to the image, which we borrow mutably
+        unsafe { slice::from_raw_parts_mut(ffi::sfImage_getPixelsPtr(self.image) as *mut u8, len) }
+    }
+
+    /// Iterate over the pixels of the image, row by row, along with their coordinates
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// # use sfml::graphics::Image;
+    /// let image = Image::from_file("level.png").unwrap();
+    /// for (x, y, color) in image.enumerate_pixels() {
+    ///     if color.a == 0 {
+    ///         println!("Hole at {}, {}", x, y);
+    ///     }
+    /// }
+    /// ```
+    pub fn enumerate_pixels(&self) -> impl Iterator<Item = (u32, u32, Color)> + '_ {
+        enumerate_colors(self.pixels(), self.size().x)
+    }
+
+    /// Get the pixels of a row of the image
+    ///
+    /// # Panics
+    /// Panics if `y` is out of the image.
+    pub fn row(&self, y: u32) -> &[u8] {
+        let range = self.row_range(y);
+        &self.pixels()[range]
+    }
+
+    /// Get the pixels of a row of the image, to modify them directly
+    ///
+    /// # Panics
+    /// Panics if `y` is out of the image.
+    pub fn row_mut(&mut self, y: u32) -> &mut [u8] {
+        let range = self.row_range(y);
+        &mut self.pixels_mut()[range]
+    }
+
+    /// Iterate over the rows of the image, from the top
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        let stride = self.size().x as usize * 4;
+        self.pixels().chunks(stride.max(1))
+    }
+
+    /// Iterate over the rows of the image, from the top, to modify them directly
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        let stride = self.size().x as usize * 4;
+        self.pixels_mut().chunks_mut(stride.max(1))
+    }
+
+    /// Fill a row of the image with a color
+    ///
+    /// # Panics
+    /// Panics if `y` is out of the image.
+    pub fn fill_row(&mut self, y: u32, color: Color) {
+        for pixel in self.row_mut(y).chunks_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    /// Copy a row of the image onto another one
+    ///
+    /// # Panics
+    /// Panics if `source` or `dest` is out of the image.
+    pub fn copy_row(&mut self, source: u32, dest: u32) {
+        let source = self.row_range(source);
+        let dest = self.row_range(dest);
+        let len = source.len();
+        let pixels = self.pixels_mut();
+        // Distinct rows never overlap
+        if source.start < dest.start {
+            let (before, after) = pixels.split_at_mut(dest.start);
+            after[..len].copy_from_slice(&before[source]);
+        } else if source.start > dest.start {
+            let (before, after) = pixels.split_at_mut(source.start);
+            before[dest].copy_from_slice(&after[..len]);
+        }
+    }
+
+    fn pixels_len(&self) -> usize {
+        let size = self.size();
+        size.x as usize * size.y as usize * 4
+    }
+    fn row_range(&self, y: u32) -> std::ops::Range<usize> {
+        let size = self.size();
+        assert!(y < size.y, "Row {} out of an image of height {}", y, size.y);
+        let stride = size.x as usize * 4;
+        y as usize * stride..(y as usize + 1) * stride
     }
 
     /// Flip an image horizontally (left <-> right)
@@ -207,6 +388,76 @@ impl Image {
         unsafe { ffi::sfImage_flipVertically(self.image) }
     }
 
+    /// Rotate the image a quarter turn clockwise
+    ///
+    /// The width and height of the image are swapped.
+    pub fn rotate90(&mut self) {
+        self.rotate(1)
+    }
+
+    /// Rotate the image a half turn
+    pub fn rotate180(&mut self) {
+        self.rotate(2)
+    }
+
+    /// Rotate the image a quarter turn counterclockwise
+    ///
+    /// The width and height of the image are swapped.
+    pub fn rotate270(&mut self) {
+        self.rotate(3)
+    }
+
+    /// Create a resized copy of the image
+    ///
+    /// # Arguments
+    /// * width - Width of the copy
+    /// * height - Height of the copy
+    /// * filter - How to compute the pixels of the copy from the pixels of the image
+    ///
+    /// Return Some(Image) or None if the copy or the image is empty
+    pub fn resized(&self, width: u32, height: u32, filter: ResizeFilter) -> Option<Image> {
+        let size = self.size();
+        if size.x == 0 || size.y == 0 {
+            return None;
+        }
+        let pixels = resize_pixels(self.pixels(), size, Vector2u::new(width, height), filter);
+        Image::create_from_pixels(width, height, &pixels)
+    }
+
+    /// Create a copy of a part of the image
+    ///
+    /// The part of `rect` outside of the image is left out.
+    ///
+    /// # Arguments
+    /// * rect - Area of the image to copy
+    ///
+    /// Return Some(Image) or None if `rect` doesn't overlap the image
+    pub fn cropped(&self, rect: &IntRect) -> Option<Image> {
+        let size = self.size();
+        let bounds = IntRect::new(0, 0, size.x as i32, size.y as i32);
+        let area = bounds.intersection(rect)?;
+        let stride = size.x as usize * 4;
+        let (left, width) = (area.left as usize * 4, area.width as usize * 4);
+        let mut pixels = Vec::with_capacity(width * area.height as usize);
+        for y in area.top..area.top + area.height {
+            let start = y as usize * stride + left;
+            pixels.extend_from_slice(&self.pixels()[start..start + width]);
+        }
+        Image::create_from_pixels(area.width as u32, area.height as u32, &pixels)
+    }
+
+    fn rotate(&mut self, quarter_turns: u32) {
+        let size = self.size();
+        let pixels = rotate_pixels(self.pixels(), size, quarter_turns);
+        let (width, height) = if quarter_turns % 2 == 1 {
+            (size.y, size.x)
+        } else {
+            (size.x, size.y)
+        };
+        let image = Image::create_from_pixels(width, height, &pixels);
+        *self = image.expect("Failed to create rotated Image");
+    }
+
     /// Copy pixels from an image onto another
     ///
     /// This function does a slow pixel copy and should not be
@@ -271,3 +522,180 @@ impl Drop for Image {
         unsafe { ffi::sfImage_destroy(self.image) }
     }
 }
+
+#[cfg(feature = "image")]
+impl<'a> From<&'a image::DynamicImage> for Image {
+    /// Convert an image of the `image` crate, or panic! if there is not enough memory
+    fn from(source: &'a image::DynamicImage) -> Self {
+        match source.as_rgba8() {
+            Some(rgba) => Image::from(rgba),
+            None => Image::from(&source.to_rgba8()),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::DynamicImage> for Image {
+    /// Convert an image of the `image` crate, or panic! if there is not enough memory
+    fn from(source: image::DynamicImage) -> Self {
+        Image::from(&source)
+    }
+}
+
+#[cfg(feature = "image")]
+impl<'a> From<&'a image::RgbaImage> for Image {
+    /// Convert an image of the `image` crate, or panic! if there is not enough memory
+    fn from(source: &'a image::RgbaImage) -> Self {
+        let mut image = Image::new(source.width(), source.height());
+        let len = image.pixels().len();
+        image.pixels_mut().copy_from_slice(&source.as_raw()[..len]);
+        image
+    }
+}
+
+#[cfg(feature = "image")]
+impl<'a> From<&'a Image> for image::RgbaImage {
+    fn from(source: &'a Image) -> Self {
+        let size = source.size();
+        image::RgbaImage::from_raw(size.x, size.y, source.pixels().to_vec())
+            .expect("Image pixels don't match its size")
+    }
+}
+
+#[cfg(feature = "image")]
+impl<'a> From<&'a Image> for image::DynamicImage {
+    fn from(source: &'a Image) -> Self {
+        image::DynamicImage::ImageRgba8(source.into())
+    }
+}
+
+/// How to compute the pixels of a resized image, see [`Image::resized`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResizeFilter {
+    /// Take the nearest pixel of the original image. Keeps pixel art crisp.
+    Nearest,
+    /// Blend the four nearest pixels of the original image. Looks smoother on photos.
+    Bilinear,
+}
+
+/// Rotate `pixels`, the pixels of an image of `size`, `quarter_turns` times clockwise.
+fn rotate_pixels(pixels: &[u8], size: Vector2u, quarter_turns: u32) -> Vec<u8> {
+    let (w, h) = (size.x as usize, size.y as usize);
+    let mut rotated = Vec::with_capacity(pixels.len());
+    let source = |x: usize, y: usize| &pixels[(y * w + x) * 4..(y * w + x) * 4 + 4];
+    match quarter_turns % 4 {
+        0 => rotated.extend_from_slice(pixels),
+        1 => {
+            for y in 0..w {
+                for x in 0..h {
+                    rotated.extend_from_slice(source(y, h - 1 - x));
+                }
+            }
+        }
+        2 => {
+            for y in 0..h {
+                for x in 0..w {
+                    rotated.extend_from_slice(source(w - 1 - x, h - 1 - y));
+                }
+            }
+        }
+        _ => {
+            for y in 0..w {
+                for x in 0..h {
+                    rotated.extend_from_slice(source(w - 1 - y, x));
+                }
+            }
+        }
+    }
+    rotated
+}
+
+/// Resize `pixels`, the pixels of an image of `size`, to `new_size`.
+fn resize_pixels(
+    pixels: &[u8],
+    size: Vector2u,
+    new_size: Vector2u,
+    filter: ResizeFilter,
+) -> Vec<u8> {
+    let (w, h) = (size.x as usize, size.y as usize);
+    let mut resized = Vec::with_capacity(new_size.x as usize * new_size.y as usize * 4);
+    let scale_x = size.x as f32 / new_size.x as f32;
+    let scale_y = size.y as f32 / new_size.y as f32;
+    let channel = |x: usize, y: usize, c: usize| f32::from(pixels[(y * w + x) * 4 + c]);
+    for y in 0..new_size.y {
+        for x in 0..new_size.x {
+            // Center of the pixel, in the original image
+            let sx = (x as f32 + 0.5) * scale_x;
+            let sy = (y as f32 + 0.5) * scale_y;
+            match filter {
+                ResizeFilter::Nearest => {
+                    let (x, y) = ((sx as usize).min(w - 1), (sy as usize).min(h - 1));
+                    resized.extend_from_slice(&pixels[(y * w + x) * 4..(y * w + x) * 4 + 4]);
+                }
+                ResizeFilter::Bilinear => {
+                    let fx = (sx - 0.5).max(0.);
+                    let fy = (sy - 0.5).max(0.);
+                    let (x0, y0) = ((fx as usize).min(w - 1), (fy as usize).min(h - 1));
+                    let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+                    let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+                    for c in 0..4 {
+                        let top = channel(x0, y0, c) * (1. - tx) + channel(x1, y0, c) * tx;
+                        let bottom = channel(x0, y1, c) * (1. - tx) + channel(x1, y1, c) * tx;
+                        resized.push((top * (1. - ty) + bottom * ty).round() as u8);
+                    }
+                }
+            }
+        }
+    }
+    resized
+}
+
+/// Iterate over `pixels`, stored row by row in rows of `width` pixels, with their coordinates.
+fn enumerate_colors(pixels: &[u8], width: u32) -> impl Iterator<Item = (u32, u32, Color)> + '_ {
+    pixels.chunks(4).enumerate().map(move |(i, p)| {
+        let i = i as u32;
+        (i % width, i / width, Color::rgba(p[0], p[1], p[2], p[3]))
+    })
+}
+
+#[test]
+fn test_enumerate_colors() {
+    let pixels = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+    let colors: Vec<_> = enumerate_colors(&pixels, 2).collect();
+    assert_eq!(
+        colors,
+        [
+            (0, 0, Color::rgba(1, 2, 3, 4)),
+            (1, 0, Color::rgba(5, 6, 7, 8)),
+            (0, 1, Color::rgba(9, 10, 11, 12)),
+        ]
+    );
+}
+
+#[test]
+fn test_rotate_pixels() {
+    // 1 2 3
+    // 4 5 6
+    let pixels: Vec<u8> = (1..=6).flat_map(|v| vec![v; 4]).collect();
+    let ids = |pixels: Vec<u8>| pixels.chunks(4).map(|p| p[0]).collect::<Vec<_>>();
+    let size = Vector2u::new(3, 2);
+    assert_eq!(ids(rotate_pixels(&pixels, size, 1)), [4, 1, 5, 2, 6, 3]);
+    assert_eq!(ids(rotate_pixels(&pixels, size, 2)), [6, 5, 4, 3, 2, 1]);
+    assert_eq!(ids(rotate_pixels(&pixels, size, 3)), [3, 6, 2, 5, 1, 4]);
+}
+
+#[test]
+fn test_resize_pixels() {
+    let pixels = [0, 0, 0, 255, 200, 200, 200, 255];
+    let size = Vector2u::new(2, 1);
+    let nearest = resize_pixels(&pixels, size, Vector2u::new(4, 1), ResizeFilter::Nearest);
+    assert_eq!(
+        nearest.chunks(4).map(|p| p[0]).collect::<Vec<_>>(),
+        [0, 0, 200, 200]
+    );
+    let bilinear = resize_pixels(&pixels, size, Vector2u::new(4, 1), ResizeFilter::Bilinear);
+    assert_eq!(
+        bilinear.chunks(4).map(|p| p[0]).collect::<Vec<_>>(),
+        [0, 50, 150, 200]
+    );
+}
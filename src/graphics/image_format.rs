@@ -0,0 +1,279 @@
+use crate::byte_order::ByteOrder;
+#[cfg(not(feature = "image"))]
+use crate::graphics::Image;
+#[cfg(not(feature = "image"))]
+use crate::temp_dir::TempDir;
+use std::fs;
+#[cfg(feature = "image")]
+use std::fs::File;
+use std::io;
+#[cfg(feature = "image")]
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// File formats an [`Image`] can be encoded to in memory, see [`Image::save_to_memory`].
+///
+/// With the `image` feature, PNG and JPEG are encoded in Rust by the [image] crate, with the
+/// settings of [`EncodeOptions`]. Otherwise they are encoded by SFML, which picks the
+/// compression level of PNG and the quality of JPEG (90): CSFML has no setting for either,
+/// and only saves images to files.
+///
+/// [image]: https://docs.rs/image
+/// [`EncodeOptions`]: crate::graphics::EncodeOptions
+///
+/// [`Image`]: crate::graphics::Image
+/// [`Image::save_to_memory`]: crate::graphics::Image::save_to_memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    /// Portable Network Graphics, compressed.
+    Png,
+    /// JPEG, without the alpha channel.
+    Jpeg,
+    /// Windows bitmap, 32 bits per pixel.
+    Bmp,
+    /// Truevision TGA, uncompressed, 32 bits per pixel.
+    ///
+    /// TGA images are at most 65535 pixels wide and high.
+    Tga,
+}
+
+impl ImageFormat {
+    /// Get the usual file extension of the format, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Tga => "tga",
+        }
+    }
+    /// Encode RGBA `pixels` of an image of `width` by `height` pixels.
+    ///
+    /// Returns `None` if the image is too big for the format, or on failure.
+    pub(super) fn encode(self, pixels: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(feature = "image")]
+            ImageFormat::Png | ImageFormat::Jpeg => {
+                let mut out = Vec::new();
+                let options = EncodeOptions::default();
+                encode_with_image(self, pixels, width, height, &options, &mut out).map(|()| out)
+            }
+            #[cfg(not(feature = "image"))]
+            ImageFormat::Png | ImageFormat::Jpeg => encode_with_sfml(self, pixels, width, height),
+            ImageFormat::Bmp => Some(encode_bmp(pixels, width, height)),
+            ImageFormat::Tga => encode_tga(pixels, width, height),
+        }
+    }
+    /// Encode RGBA `pixels` like [`encode`](ImageFormat::encode), straight to the file at
+    /// `path`, which is overwritten.
+    ///
+    /// PNG and JPEG images are written by their encoder as they are encoded. Without the
+    /// `image` feature, SFML saves them to the file itself, without a temporary copy.
+    pub(super) fn save(
+        self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        path: &Path,
+    ) -> io::Result<()> {
+        let failed = || io::Error::new(io::ErrorKind::Other, "failed to encode the image");
+        match self {
+            #[cfg(feature = "image")]
+            ImageFormat::Png | ImageFormat::Jpeg => {
+                let mut file = BufWriter::new(File::create(path)?);
+                let options = EncodeOptions::default();
+                encode_with_image(self, pixels, width, height, &options, &mut file)
+                    .ok_or_else(failed)?;
+                file.flush()
+            }
+            #[cfg(not(feature = "image"))]
+            ImageFormat::Png | ImageFormat::Jpeg => {
+                let image = Image::create_from_pixels(width, height, pixels).ok_or_else(failed)?;
+                if image.save_to_path(path) {
+                    Ok(())
+                } else {
+                    Err(failed())
+                }
+            }
+            ImageFormat::Bmp | ImageFormat::Tga => {
+                fs::write(path, self.encode(pixels, width, height).ok_or_else(failed)?)
+            }
+        }
+    }
+    /// Encode RGBA `pixels` like [`encode`](ImageFormat::encode), with `options`.
+    #[cfg(feature = "image")]
+    pub(super) fn encode_with(
+        self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        options: &EncodeOptions,
+    ) -> Option<Vec<u8>> {
+        match self {
+            ImageFormat::Png | ImageFormat::Jpeg => {
+                let mut out = Vec::new();
+                encode_with_image(self, pixels, width, height, options, &mut out).map(|()| out)
+            }
+            _ => self.encode(pixels, width, height),
+        }
+    }
+}
+
+/// Settings for encoding images, see [`Image::save_to_memory_with`].
+///
+/// This requires the `image` feature.
+///
+/// [`Image::save_to_memory_with`]: crate::graphics::Image::save_to_memory_with
+#[cfg(feature = "image")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncodeOptions {
+    /// Quality of JPEG images, from 1 (smallest files) to 100 (best quality).
+    /// The default is 90, like SFML.
+    pub jpeg_quality: u8,
+    /// How hard to compress PNG images. The default is [`PngCompression::Default`].
+    pub png_compression: PngCompression,
+}
+
+#[cfg(feature = "image")]
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        EncodeOptions {
+            jpeg_quality: 90,
+            png_compression: PngCompression::Default,
+        }
+    }
+}
+
+/// Compression level of PNG images, see [`EncodeOptions`].
+///
+/// PNG compression is lossless, the levels only trade encoding time for the size of the
+/// file.
+///
+/// This requires the `image` feature.
+#[cfg(feature = "image")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PngCompression {
+    /// Fast encoding, bigger files. Good for screenshots taken while the game runs.
+    Fast,
+    /// A balance between encoding time and file size.
+    Default,
+    /// The smallest files, slow encoding.
+    Best,
+}
+
+/// Encode to `out` with the `image` crate.
+#[cfg(feature = "image")]
+fn encode_with_image<W: Write>(
+    format: ImageFormat,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    options: &EncodeOptions,
+    mut out: W,
+) -> Option<()> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    use image::{ColorType, ImageEncoder};
+
+    let result =
+        match format {
+            ImageFormat::Jpeg => {
+                let quality = options.jpeg_quality.max(1).min(100);
+                JpegEncoder::new_with_quality(&mut out, quality).write_image(
+                    pixels,
+                    width,
+                    height,
+                    ColorType::Rgba8,
+                )
+            }
+            _ => {
+                let compression = match options.png_compression {
+                    PngCompression::Fast => CompressionType::Fast,
+                    PngCompression::Default => CompressionType::Default,
+                    PngCompression::Best => CompressionType::Best,
+                };
+                PngEncoder::new_with_quality(&mut out, compression, FilterType::Adaptive)
+                    .write_image(pixels, width, height, ColorType::Rgba8)
+            }
+        };
+    result.ok()
+}
+
+/// Encode through SFML, which can only save images to files.
+#[cfg(not(feature = "image"))]
+fn encode_with_sfml(
+    format: ImageFormat,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+) -> Option<Vec<u8>> {
+    let dir = TempDir::new().ok()?;
+    let path = dir.path().join(format!("image.{}", format.extension()));
+    let image = Image::create_from_pixels(width, height, pixels)?;
+    if image.save_to_file(path.to_str()?) {
+        fs::read(&path).ok()
+    } else {
+        None
+    }
+}
+
+fn encode_bmp(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    const HEADERS: u32 = 14 + 40;
+    let size = HEADERS + pixels.len() as u32;
+    let mut out = Vec::with_capacity(size as usize);
+    out.extend_from_slice(b"BM");
+    out.extend_from_slice(&size.le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&HEADERS.le_bytes());
+    out.extend_from_slice(&40u32.le_bytes());
+    out.extend_from_slice(&width.le_bytes());
+    out.extend_from_slice(&height.le_bytes());
+    // 1 plane, 32 bits per pixel, no compression
+    out.extend_from_slice(&1u16.le_bytes());
+    out.extend_from_slice(&32u16.le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&(pixels.len() as u32).le_bytes());
+    // 72 DPI, no palette
+    out.extend_from_slice(&2835u32.le_bytes());
+    out.extend_from_slice(&2835u32.le_bytes());
+    out.extend_from_slice(&[0; 8]);
+    // Rows go from the bottom up
+    for row in pixels.chunks(width.max(1) as usize * 4).rev() {
+        push_bgra(&mut out, row);
+    }
+    out
+}
+
+fn encode_tga(pixels: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    let max = u32::from(u16::max_value());
+    if width > max || height > max {
+        return None;
+    }
+    let (width, height) = (width as u16, height as u16);
+    let mut out = Vec::with_capacity(18 + pixels.len());
+    // No id nor color map, uncompressed true color
+    out.extend_from_slice(&[0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    out.extend_from_slice(&width.le_bytes());
+    out.extend_from_slice(&height.le_bytes());
+    // 32 bits per pixel, 8 of them alpha, rows from the top down
+    out.extend_from_slice(&[32, 0x28]);
+    push_bgra(&mut out, pixels);
+    Some(out)
+}
+
+fn push_bgra(out: &mut Vec<u8>, pixels: &[u8]) {
+    for p in pixels.chunks(4) {
+        out.extend_from_slice(&[p[2], p[1], p[0], p[3]]);
+    }
+}
+
+#[test]
+fn test_encode() {
+    let bmp = ImageFormat::Bmp.encode(&[255, 0, 0, 255], 1, 1).unwrap();
+    assert_eq!(&bmp[..2], b"BM");
+    assert_eq!(&bmp[54..], [0, 0, 255, 255]);
+    let tga = ImageFormat::Tga.encode(&[255, 0, 0, 255], 1, 1).unwrap();
+    assert_eq!(&tga[18..], [0, 0, 255, 255]);
+    // The size of a TGA image is 16 bits
+    assert_eq!(ImageFormat::Tga.encode(&[], 65_536, 0), None);
+}
@@ -2,44 +2,88 @@
 
 extern crate csfml_graphics_sys;
 
+pub use self::animated_image::{AnimatedImage, AnimatedImageSprite, AnimationFrame};
+pub use self::animation::{AnimatedSprite, Animation, AnimationMode};
+pub use self::arc_shape::ArcShape;
 pub use self::blend_mode::BlendMode;
+pub use self::camera::Camera;
 pub use self::circle_shape::CircleShape;
-pub use self::color::Color;
+pub use self::color::{Color, ParseColorError};
 pub use self::convex_shape::{ConvexShape, ConvexShapePoints};
+pub use self::curve_shape::{Curve, CurveShape};
 pub use self::custom_shape::{CustomShape, CustomShapePoints};
+pub use self::debug_draw::{DebugCategories, DebugDraw};
 pub use self::drawable::Drawable;
 pub use self::font::{Font, Info as FontInfo};
+pub use self::font_set::FontSet;
+pub use self::frame_recorder::{FrameRecorder, RecordStatus, RecordedFrame};
+pub use self::frame_stats::FrameStats;
 pub use self::glyph::Glyph;
-pub use self::image::Image;
+pub use self::gradient_shape::{Gradient, GradientShape};
+pub use self::image::{Image, ResizeFilter};
+pub use self::image_format::ImageFormat;
+#[cfg(feature = "image")]
+pub use self::image_format::{EncodeOptions, PngCompression};
+pub use self::nine_slice_sprite::{NineSliceSprite, SliceFill, SliceMargins};
+pub use self::polyline::{LineCap, LineJoin, Polyline};
+pub use self::post_effect::{Bloom, ChromaticAberration, GaussianBlur, PostEffect, Vignette};
 pub use self::primitive_type::PrimitiveType;
 pub use self::rect::{FloatRect, IntRect, Rect};
 pub use self::rectangle_shape::RectangleShape;
-pub use self::render_states::RenderStates;
+pub use self::render_states::{RenderStates, RenderStatesBuilder};
 pub use self::render_target::RenderTarget;
 pub use self::render_texture::RenderTexture;
-pub use self::render_window::RenderWindow;
+pub use self::render_window::{RenderWindow, RenderWindowBuilder, WindowCreationError};
+pub use self::rich_text::{RichText, TextSpan};
+pub use self::rounded_rectangle_shape::{CornerRadii, RoundedRectangleShape};
 pub use self::shader::Shader;
 pub use self::shape::Shape;
 pub use self::sprite::Sprite;
+pub use self::sprite_batch::SpriteBatch;
+pub use self::stats_overlay::StatsOverlay;
 pub use self::text::Text;
+pub use self::text_layout::TextLayout;
 pub use self::text_style::TextStyle;
 pub use self::texture::Texture;
-pub use self::transform::Transform;
-pub use self::transformable::Transformable;
+pub use self::texture_atlas::TextureAtlas;
+pub use self::tile_map::TileMap;
+pub use self::tiled_texture::TiledTexture;
+pub use self::transform::{Decomposition, Transform};
+pub use self::transformable::{BasicTransformable, Transformable};
 pub use self::vertex::Vertex;
 pub use self::vertex_array::{VertexArray, Vertices};
+pub use self::vertex_buffer::{VertexBuffer, VertexBufferUsage};
 pub use self::view::View;
 
+// Declared first, so that its `delegate_transformable!` macro is in scope in the others
+#[macro_use]
+mod transformable;
+mod animated_image;
+mod animation;
+mod arc_shape;
 pub mod blend_mode;
+mod camera;
 mod circle_shape;
 mod color;
 mod convex_shape;
+#[cfg(feature = "css-colors")]
+mod css_colors;
+mod curve_shape;
 mod custom_shape;
+mod debug_draw;
 mod drawable;
 mod font;
+mod font_set;
+mod frame_recorder;
+mod frame_stats;
 pub mod glsl;
 mod glyph;
+mod gradient_shape;
 mod image;
+mod image_format;
+mod nine_slice_sprite;
+mod polyline;
+mod post_effect;
 mod primitive_type;
 mod rect;
 mod rectangle_shape;
@@ -47,14 +91,23 @@ mod render_states;
 mod render_target;
 mod render_texture;
 mod render_window;
+mod rich_text;
+mod rounded_rectangle_shape;
+mod scissor;
 mod shader;
 mod shape;
 mod sprite;
+mod sprite_batch;
+mod stats_overlay;
 mod text;
+mod text_layout;
 mod text_style;
 mod texture;
+mod texture_atlas;
+mod tile_map;
+mod tiled_texture;
 mod transform;
-mod transformable;
 mod vertex;
 mod vertex_array;
+mod vertex_buffer;
 mod view;
@@ -0,0 +1,392 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, FloatRect, IntRect, PrimitiveType, RenderStates,
+    RenderTarget, Texture, Transformable, Vertex,
+};
+use crate::system::Vector2f;
+
+/// Widths of the borders of a [`NineSliceSprite`], in pixels of its texture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SliceMargins {
+    /// Width of the left border.
+    pub left: u32,
+    /// Height of the top border.
+    pub top: u32,
+    /// Width of the right border.
+    pub right: u32,
+    /// Height of the bottom border.
+    pub bottom: u32,
+}
+
+impl SliceMargins {
+    /// Create margins of the same width on all sides.
+    pub fn uniform(margin: u32) -> Self {
+        SliceMargins {
+            left: margin,
+            top: margin,
+            right: margin,
+            bottom: margin,
+        }
+    }
+}
+
+/// How the slices of a [`NineSliceSprite`] fill the areas bigger than them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SliceFill {
+    /// Stretch the slice over the whole area.
+    Stretch,
+    /// Repeat the slice at its size, cutting the last repetition short.
+    Tile,
+    /// Leave the area empty, for frames with a transparent center.
+    Empty,
+}
+
+/// A sprite that keeps its borders intact when resized, for UI panels and buttons.
+///
+/// The texture rectangle of the sprite is cut into nine slices by its margins: four
+/// corners, four edges and the center. When the sprite is resized, the corners keep their
+/// size, the edges only stretch along their length, and the center fills the rest, so
+/// that a single small texture makes panels of any size with crisp borders. The edges and
+/// the center can be stretched or tiled.
+///
+/// When the sprite is smaller than its margins, they are shrunk to fit.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{NineSliceSprite, SliceFill, SliceMargins, Texture, Transformable};
+///
+/// let texture = Texture::from_file("panel.png").unwrap();
+/// let mut panel = NineSliceSprite::new(&texture, SliceMargins::uniform(8));
+/// panel.set_center_fill(SliceFill::Tile);
+/// panel.set_size((300., 120.));
+/// panel.set_position((20., 20.));
+/// // window.draw(&panel);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NineSliceSprite<'t> {
+    texture: &'t Texture,
+    texture_rect: IntRect,
+    margins: SliceMargins,
+    size: Vector2f,
+    edge_fill: SliceFill,
+    center_fill: SliceFill,
+    color: Color,
+    transformable: BasicTransformable,
+    vertices: Vec<Vertex>,
+}
+
+impl<'t> NineSliceSprite<'t> {
+    /// Create a new sprite showing the whole texture, at its size.
+    ///
+    /// # Arguments
+    /// * texture - The texture to slice
+    /// * margins - Widths of the borders of the texture
+    pub fn new(texture: &'t Texture, margins: SliceMargins) -> Self {
+        let size = texture.size();
+        let mut sprite = NineSliceSprite {
+            texture,
+            texture_rect: IntRect::new(0, 0, size.x as i32, size.y as i32),
+            margins,
+            size: Vector2f::new(size.x as f32, size.y as f32),
+            edge_fill: SliceFill::Stretch,
+            center_fill: SliceFill::Stretch,
+            color: Color::WHITE,
+            transformable: BasicTransformable::new(),
+            vertices: Vec::new(),
+        };
+        sprite.update_vertices();
+        sprite
+    }
+    /// Set the part of the texture to slice, for textures holding several panels.
+    ///
+    /// The margins are measured from the sides of this rectangle.
+    pub fn set_texture_rect(&mut self, rect: &IntRect) {
+        self.texture_rect = *rect;
+        self.update_vertices();
+    }
+    /// Get the part of the texture that is sliced.
+    pub fn texture_rect(&self) -> IntRect {
+        self.texture_rect
+    }
+    /// Set the widths of the borders of the texture.
+    pub fn set_margins(&mut self, margins: SliceMargins) {
+        self.margins = margins;
+        self.update_vertices();
+    }
+    /// Get the widths of the borders of the texture.
+    pub fn margins(&self) -> SliceMargins {
+        self.margins
+    }
+    /// Set the size of the sprite, in pixels.
+    pub fn set_size<S: Into<Vector2f>>(&mut self, size: S) {
+        self.size = size.into();
+        self.update_vertices();
+    }
+    /// Get the size of the sprite, in pixels.
+    pub fn size(&self) -> Vector2f {
+        self.size
+    }
+    /// Set how the edges fill their length. They are stretched by default.
+    pub fn set_edge_fill(&mut self, fill: SliceFill) {
+        self.edge_fill = fill;
+        self.update_vertices();
+    }
+    /// Get how the edges fill their length.
+    pub fn edge_fill(&self) -> SliceFill {
+        self.edge_fill
+    }
+    /// Set how the center fills the inside of the borders. It is stretched by default.
+    pub fn set_center_fill(&mut self, fill: SliceFill) {
+        self.center_fill = fill;
+        self.update_vertices();
+    }
+    /// Get how the center fills the inside of the borders.
+    pub fn center_fill(&self) -> SliceFill {
+        self.center_fill
+    }
+    /// Set the color the texture is modulated with.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+        for vertex in &mut self.vertices {
+            vertex.color = color;
+        }
+    }
+    /// Get the color the texture is modulated with.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+    /// Get the local bounding rectangle of the sprite.
+    ///
+    /// The returned rectangle is in local coordinates, which means
+    /// that it ignores the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn local_bounds(&self) -> FloatRect {
+        FloatRect::new(0., 0., self.size.x, self.size.y)
+    }
+    /// Get the global bounding rectangle of the sprite.
+    ///
+    /// The returned rectangle is in global coordinates, which means
+    /// that it takes into account the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(&self.local_bounds())
+    }
+
+    fn update_vertices(&mut self) {
+        self.vertices.clear();
+        let slices = slices(
+            self.size,
+            self.texture_rect,
+            self.margins,
+            self.edge_fill,
+            self.center_fill,
+        );
+        for (dest, tex) in slices {
+            let vertex = |x: f32, y: f32, u: f32, v: f32| Vertex {
+                position: Vector2f::new(x, y),
+                color: self.color,
+                tex_coords: Vector2f::new(u, v),
+            };
+            let (right, bottom) = (dest.left + dest.width, dest.top + dest.height);
+            let (tex_right, tex_bottom) = (tex.left + tex.width, tex.top + tex.height);
+            let top_left = vertex(dest.left, dest.top, tex.left, tex.top);
+            let bottom_left = vertex(dest.left, bottom, tex.left, tex_bottom);
+            let top_right = vertex(right, dest.top, tex_right, tex.top);
+            let bottom_right = vertex(right, bottom, tex_right, tex_bottom);
+            self.vertices.extend_from_slice(&[
+                top_left,
+                bottom_left,
+                top_right,
+                top_right,
+                bottom_left,
+                bottom_right,
+            ]);
+        }
+    }
+}
+
+impl<'t> Drawable for NineSliceSprite<'t> {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            texture: Some(self.texture),
+            ..states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::Triangles, states);
+    }
+}
+
+delegate_transformable!(impl<'t> for NineSliceSprite<'t> => transformable);
+
+/// A span of a slice along an axis: where it is drawn and which texture pixels it shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Span {
+    start: f32,
+    len: f32,
+    tex_start: f32,
+    tex_len: f32,
+}
+
+/// Cut an axis of `len` pixels, showing `tex_len` texture pixels from `tex_start`, into the
+/// spans of the two borders and the middle. With `tile`, the middle is repeated instead of
+/// stretched, and may be made of several spans.
+fn axis_spans(
+    len: f32,
+    tex_start: f32,
+    tex_len: f32,
+    m0: f32,
+    m1: f32,
+    tile: bool,
+) -> [Vec<Span>; 3] {
+    let scale = if m0 + m1 > len && m0 + m1 > 0. {
+        len / (m0 + m1)
+    } else {
+        1.
+    };
+    let (d0, d1) = (m0 * scale, m1 * scale);
+    let middle_len = len - d0 - d1;
+    let tex_middle = tex_len - m0 - m1;
+    let first = Span {
+        start: 0.,
+        len: d0,
+        tex_start,
+        tex_len: m0,
+    };
+    let last = Span {
+        start: len - d1,
+        len: d1,
+        tex_start: tex_start + tex_len - m1,
+        tex_len: m1,
+    };
+    let mut middle = Vec::new();
+    if tile && tex_middle > 0. {
+        let mut start = 0.;
+        while start < middle_len {
+            let piece = tex_middle.min(middle_len - start);
+            middle.push(Span {
+                start: d0 + start,
+                len: piece,
+                tex_start: tex_start + m0,
+                tex_len: piece,
+            });
+            start += piece;
+        }
+    } else if middle_len > 0. {
+        middle.push(Span {
+            start: d0,
+            len: middle_len,
+            tex_start: tex_start + m0,
+            tex_len: tex_middle,
+        });
+    }
+    [vec![first], middle, vec![last]]
+}
+
+/// Compute the areas of the slices of a nine-slice sprite, along with the areas of the
+/// texture they show.
+fn slices(
+    size: Vector2f,
+    rect: IntRect,
+    margins: SliceMargins,
+    edge_fill: SliceFill,
+    center_fill: SliceFill,
+) -> Vec<(FloatRect, FloatRect)> {
+    let spans = |tile| {
+        let xs = axis_spans(
+            size.x,
+            rect.left as f32,
+            rect.width as f32,
+            margins.left as f32,
+            margins.right as f32,
+            tile,
+        );
+        let ys = axis_spans(
+            size.y,
+            rect.top as f32,
+            rect.height as f32,
+            margins.top as f32,
+            margins.bottom as f32,
+            tile,
+        );
+        (xs, ys)
+    };
+    let stretched = spans(false);
+    let tiled = spans(true);
+    let mut slices = Vec::new();
+    for row in 0..3 {
+        for column in 0..3 {
+            let fill = match (row == 1, column == 1) {
+                (true, true) => center_fill,
+                (false, false) => SliceFill::Stretch,
+                _ => edge_fill,
+            };
+            let (xs, ys) = match fill {
+                SliceFill::Empty => continue,
+                SliceFill::Stretch => (&stretched.0[column], &stretched.1[row]),
+                SliceFill::Tile => (&tiled.0[column], &tiled.1[row]),
+            };
+            for y in ys {
+                for x in xs {
+                    if x.len <= 0. || y.len <= 0. {
+                        continue;
+                    }
+                    slices.push((
+                        FloatRect::new(x.start, y.start, x.len, y.len),
+                        FloatRect::new(x.tex_start, y.tex_start, x.tex_len, y.tex_len),
+                    ));
+                }
+            }
+        }
+    }
+    slices
+}
+
+#[test]
+fn test_slices() {
+    // 30x30 texture with 10 pixel borders, drawn 50x25
+    let rect = IntRect::new(0, 0, 30, 30);
+    let margins = SliceMargins::uniform(10);
+    let stretched = slices(
+        Vector2f::new(50., 25.),
+        rect,
+        margins,
+        SliceFill::Stretch,
+        SliceFill::Stretch,
+    );
+    assert_eq!(stretched.len(), 9);
+    // Center
+    assert_eq!(
+        stretched[4],
+        (
+            FloatRect::new(10., 10., 30., 5.),
+            FloatRect::new(10., 10., 10., 10.)
+        )
+    );
+    // Tiled top edge: 30 pixels of 10 pixel tiles
+    let tiled = slices(
+        Vector2f::new(50., 25.),
+        rect,
+        margins,
+        SliceFill::Tile,
+        SliceFill::Empty,
+    );
+    assert_eq!(tiled.len(), 12);
+    assert_eq!(
+        tiled[3],
+        (
+            FloatRect::new(30., 0., 10., 10.),
+            FloatRect::new(10., 0., 10., 10.)
+        )
+    );
+    // Shrunk margins
+    let small = slices(
+        Vector2f::new(10., 30.),
+        rect,
+        margins,
+        SliceFill::Stretch,
+        SliceFill::Stretch,
+    );
+    assert_eq!(small[1].0, FloatRect::new(5., 0., 5., 10.));
+}
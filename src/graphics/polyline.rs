@@ -0,0 +1,525 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, PrimitiveType, RenderStates, RenderTarget, Transformable,
+    Vertex,
+};
+use crate::system::Vector2f;
+use std::f32::consts::PI;
+
+/// How the segments of a [`Polyline`] are joined at its inner points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// Extend the outer edges of the segments until they meet in a sharp corner.
+    ///
+    /// Corners sharper than about 30 degrees would make long spikes, they are beveled
+    /// instead.
+    Miter,
+    /// Cut the corner straight across.
+    Bevel,
+    /// Round the corner off.
+    Round,
+}
+
+/// How a [`Polyline`] ends at its first and last points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// Stop square at the end points.
+    Butt,
+    /// Stop square, half the thickness of the line past the end points.
+    Square,
+    /// End with a half circle around the end points.
+    Round,
+}
+
+/// Longest a miter may be before it is beveled, in halves of the thickness of the line.
+const MITER_LIMIT: f32 = 4.;
+
+/// A line going through a list of points, of any thickness.
+///
+/// Lines drawn with [`PrimitiveType::Lines`] are always one pixel thick. A polyline is
+/// made of triangles instead, following its points like a ribbon, with its segments joined
+/// and its ends capped in a few ways. It can also be dashed, or dotted, with
+/// [`set_dash_pattern`].
+///
+/// The segments are drawn independently, and overlap on the inner side of the joins:
+/// translucent lines are darker there.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, LineCap, LineJoin, Polyline};
+/// use sfml::system::Vector2f;
+///
+/// let mut path = Polyline::new(
+///     vec![
+///         Vector2f::new(100., 100.),
+///         Vector2f::new(300., 150.),
+///         Vector2f::new(350., 400.),
+///     ],
+///     8.,
+/// );
+/// path.set_join(LineJoin::Round);
+/// path.set_cap(LineCap::Round);
+/// path.set_color(Color::YELLOW);
+/// // window.draw(&path);
+/// ```
+///
+/// [`PrimitiveType::Lines`]: crate::graphics::PrimitiveType::Lines
+/// [`set_dash_pattern`]: Polyline::set_dash_pattern
+#[derive(Debug, Clone)]
+pub struct Polyline {
+    points: Vec<Vector2f>,
+    thickness: f32,
+    join: LineJoin,
+    cap: LineCap,
+    dash_pattern: Vec<f32>,
+    dash_offset: f32,
+    color: Color,
+    transformable: BasicTransformable,
+    vertices: Vec<Vertex>,
+}
+
+impl Polyline {
+    /// Create a new white line with mitered joins and butt caps
+    ///
+    /// # Arguments
+    /// * points - The points the line goes through
+    /// * thickness - The thickness of the line
+    pub fn new(points: Vec<Vector2f>, thickness: f32) -> Self {
+        let mut line = Polyline {
+            points,
+            thickness,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            dash_pattern: Vec::new(),
+            dash_offset: 0.,
+            color: Color::WHITE,
+            transformable: BasicTransformable::new(),
+            vertices: Vec::new(),
+        };
+        line.update();
+        line
+    }
+    /// Set the points the line goes through.
+    pub fn set_points(&mut self, points: Vec<Vector2f>) {
+        self.points = points;
+        self.update();
+    }
+    /// Add a point at the end of the line.
+    pub fn push_point<P: Into<Vector2f>>(&mut self, point: P) {
+        self.points.push(point.into());
+        self.update();
+    }
+    /// Get the points the line goes through.
+    pub fn points(&self) -> &[Vector2f] {
+        &self.points
+    }
+    /// Set the thickness of the line.
+    pub fn set_thickness(&mut self, thickness: f32) {
+        self.thickness = thickness;
+        self.update();
+    }
+    /// Get the thickness of the line.
+    pub fn thickness(&self) -> f32 {
+        self.thickness
+    }
+    /// Set how the segments are joined. They are mitered by default.
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.join = join;
+        self.update();
+    }
+    /// Get how the segments are joined.
+    pub fn join(&self) -> LineJoin {
+        self.join
+    }
+    /// Set how the line ends. It has butt caps by default.
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.cap = cap;
+        self.update();
+    }
+    /// Get how the line ends.
+    pub fn cap(&self) -> LineCap {
+        self.cap
+    }
+    /// Set the lengths of the dashes and of the gaps between them, in turn.
+    ///
+    /// The pattern starts with a dash, and repeats along the whole line. Patterns of an odd
+    /// length are repeated twice, so that `[4.]` makes dashes and gaps of 4 pixels. Each
+    /// dash gets the caps of the line: with round caps, dashes of length zero are dots.
+    ///
+    /// An empty pattern, the default, makes a solid line.
+    pub fn set_dash_pattern(&mut self, pattern: &[f32]) {
+        self.dash_pattern = pattern.iter().map(|&length| length.max(0.)).collect();
+        if self.dash_pattern.len() % 2 == 1 {
+            let pattern = self.dash_pattern.clone();
+            self.dash_pattern.extend(pattern);
+        }
+        self.update();
+    }
+    /// Get the lengths of the dashes and of the gaps between them, in turn.
+    pub fn dash_pattern(&self) -> &[f32] {
+        &self.dash_pattern
+    }
+    /// Set how far into the dash pattern the line starts.
+    ///
+    /// Increasing the offset over time makes the dashes march backwards along the line,
+    /// like the outline of a selection.
+    pub fn set_dash_offset(&mut self, offset: f32) {
+        self.dash_offset = offset;
+        self.update();
+    }
+    /// Get how far into the dash pattern the line starts.
+    pub fn dash_offset(&self) -> f32 {
+        self.dash_offset
+    }
+    /// Set the color of the line.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+        for vertex in &mut self.vertices {
+            vertex.color = color;
+        }
+    }
+    /// Get the color of the line.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+    /// Get the vertices of the line, as a list of triangles.
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    fn update(&mut self) {
+        let (points, dashes, offset) = (&self.points, &self.dash_pattern, self.dash_offset);
+        let runs = if dashes.iter().any(|&dash| dash > 0.) {
+            dash_runs(points, dashes, offset)
+        } else {
+            vec![(points.clone(), None)]
+        };
+        self.vertices.clear();
+        for (run, direction) in runs {
+            self.vertices.extend(ribbon(
+                &run,
+                direction,
+                self.thickness,
+                self.join,
+                self.cap,
+                self.color,
+            ));
+        }
+    }
+}
+
+impl Drawable for Polyline {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::Triangles, states);
+    }
+}
+
+delegate_transformable!(impl for Polyline => transformable);
+
+fn dot(a: Vector2f, b: Vector2f) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Vector2f, b: Vector2f) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn rotate(v: Vector2f, angle: f32) -> Vector2f {
+    let (sin, cos) = angle.sin_cos();
+    Vector2f::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// Push the triangles of a fan around `center`, sweeping `angle` radians from `from`.
+fn fan(center: Vector2f, from: Vector2f, angle: f32, color: Color, vertices: &mut Vec<Vertex>) {
+    let steps = (angle.abs() / (PI / 8.)).ceil().max(1.) as u32;
+    let mut previous = center + from;
+    for i in 1..=steps {
+        let next = center + rotate(from, angle * i as f32 / steps as f32);
+        vertices.extend_from_slice(&[
+            Vertex::with_pos_color(center, color),
+            Vertex::with_pos_color(previous, color),
+            Vertex::with_pos_color(next, color),
+        ]);
+        previous = next;
+    }
+}
+
+/// Build the triangles of a line of some `thickness` going through `points`.
+///
+/// A line of a single point is only drawn with a `direction`, and is nothing but its caps.
+fn ribbon(
+    points: &[Vector2f],
+    direction: Option<Vector2f>,
+    thickness: f32,
+    join: LineJoin,
+    cap: LineCap,
+    color: Color,
+) -> Vec<Vertex> {
+    let mut points = points.to_vec();
+    points.dedup();
+    let half = thickness / 2.;
+    let mut vertices = Vec::new();
+    if points.is_empty() || (points.len() < 2 && direction.is_none()) || half <= 0. {
+        return vertices;
+    }
+    let vertex = |position: Vector2f| Vertex::with_pos_color(position, color);
+    // Directions and normals of the segments
+    let mut directions: Vec<Vector2f> = points
+        .windows(2)
+        .map(|pair| {
+            let d = pair[1] - pair[0];
+            d * (1. / dot(d, d).sqrt())
+        })
+        .collect();
+    if directions.is_empty() {
+        directions.extend(direction);
+    }
+    let normal = |d: Vector2f| Vector2f::new(-d.y, d.x) * half;
+    for (pair, &d) in points.windows(2).zip(&directions) {
+        let n = normal(d);
+        let (a, b) = (pair[0], pair[1]);
+        vertices.extend_from_slice(&[
+            vertex(a + n),
+            vertex(a - n),
+            vertex(b + n),
+            vertex(b + n),
+            vertex(a - n),
+            vertex(b - n),
+        ]);
+    }
+    for (i, pair) in directions.windows(2).enumerate() {
+        let (d0, d1) = (pair[0], pair[1]);
+        let turn = cross(d0, d1);
+        if turn.abs() < 1e-6 && dot(d0, d1) > 0. {
+            continue;
+        }
+        // The gap to fill is on the outer side of the turn
+        let side = if turn > 0. { -1. } else { 1. };
+        let (p, n0, n1) = (points[i + 1], normal(d0) * side, normal(d1) * side);
+        let miter = n0 + n1;
+        let miter_len = dot(miter, miter).sqrt();
+        let join = match join {
+            LineJoin::Miter if miter_len > 0. => {
+                // Length of the miter, from the point to the corner
+                let unit = miter * (1. / miter_len);
+                let len = half * half / dot(unit, n0);
+                if len > half * MITER_LIMIT {
+                    LineJoin::Bevel
+                } else {
+                    let corner = p + unit * len;
+                    vertices.extend_from_slice(&[
+                        vertex(p),
+                        vertex(p + n0),
+                        vertex(corner),
+                        vertex(p),
+                        vertex(corner),
+                        vertex(p + n1),
+                    ]);
+                    continue;
+                }
+            }
+            LineJoin::Miter => LineJoin::Bevel,
+            join => join,
+        };
+        if join == LineJoin::Round {
+            fan(
+                p,
+                n0,
+                cross(n0, n1).atan2(dot(n0, n1)),
+                color,
+                &mut vertices,
+            );
+        } else {
+            vertices.extend_from_slice(&[vertex(p), vertex(p + n0), vertex(p + n1)]);
+        }
+    }
+    let ends = [
+        (points[0], -directions[0]),
+        (points[points.len() - 1], directions[directions.len() - 1]),
+    ];
+    for &(p, out) in &ends {
+        // Normal on the side the cap starts from, a quarter turn clockwise before `out`
+        let n = Vector2f::new(out.y, -out.x) * half;
+        match cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                let e = out * half;
+                vertices.extend_from_slice(&[
+                    vertex(p + n),
+                    vertex(p + n + e),
+                    vertex(p - n),
+                    vertex(p - n),
+                    vertex(p + n + e),
+                    vertex(p - n + e),
+                ]);
+            }
+            LineCap::Round => fan(p, n, PI, color, &mut vertices),
+        }
+    }
+    vertices
+}
+
+/// Cut the line going through `points` into its dashes, along with the direction each
+/// dash starts in. The pattern has an even length, and isn't only made of zeros.
+fn dash_runs(
+    points: &[Vector2f],
+    pattern: &[f32],
+    offset: f32,
+) -> Vec<(Vec<Vector2f>, Option<Vector2f>)> {
+    let is_dash = |index: usize| index % 2 == 0;
+    // Where the line starts in the pattern
+    let total: f32 = pattern.iter().sum();
+    let mut phase = (offset % total + total) % total;
+    let mut index = 0;
+    // Dashes of length zero at the very start are kept, as dots
+    while phase > pattern[index] || (pattern[index] > 0. && phase >= pattern[index]) {
+        phase -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    let mut left = pattern[index] - phase;
+    let mut runs = Vec::new();
+    let mut run = Vec::new();
+    let mut direction = None;
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let length = dot(b - a, b - a).sqrt();
+        if length <= 0. {
+            continue;
+        }
+        let d = (b - a) * (1. / length);
+        if direction.is_none() && is_dash(index) {
+            run.push(a);
+            direction = Some(d);
+        }
+        let mut pos = 0.;
+        loop {
+            if left > length - pos {
+                left -= length - pos;
+                if is_dash(index) {
+                    run.push(b);
+                }
+                break;
+            }
+            pos += left;
+            let p = a + d * pos;
+            if is_dash(index) {
+                run.push(p);
+                runs.push((std::mem::replace(&mut run, Vec::new()), direction));
+            }
+            index = (index + 1) % pattern.len();
+            left = pattern[index];
+            if is_dash(index) {
+                run.push(p);
+                direction = Some(d);
+            }
+        }
+    }
+    // A dash starting right at the end of the line is left out
+    if run.iter().any(|&p| p != run[0]) {
+        runs.push((run, direction));
+    }
+    runs
+}
+
+#[test]
+fn test_ribbon() {
+    let close = |a: Vector2f, b: Vector2f| (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4;
+    // Right, then down
+    let points = [
+        Vector2f::new(0., 0.),
+        Vector2f::new(10., 0.),
+        Vector2f::new(10., 10.),
+    ];
+    let miter = ribbon(
+        &points,
+        None,
+        2.,
+        LineJoin::Miter,
+        LineCap::Butt,
+        Color::WHITE,
+    );
+    assert_eq!(miter.len(), 2 * 6 + 6);
+    assert!(close(miter[0].position, Vector2f::new(0., 1.)));
+    assert!(close(miter[14].position, Vector2f::new(11., -1.)));
+    let bevel = ribbon(
+        &points,
+        None,
+        2.,
+        LineJoin::Bevel,
+        LineCap::Butt,
+        Color::WHITE,
+    );
+    assert_eq!(bevel.len(), 2 * 6 + 3);
+    assert!(close(bevel[13].position, Vector2f::new(10., -1.)));
+    assert!(close(bevel[14].position, Vector2f::new(11., 0.)));
+    // Square caps reach half the thickness past the ends
+    let square = ribbon(
+        &points,
+        None,
+        2.,
+        LineJoin::Bevel,
+        LineCap::Square,
+        Color::WHITE,
+    );
+    assert!(square
+        .iter()
+        .any(|v| close(v.position, Vector2f::new(-1., -1.))));
+    assert!(square
+        .iter()
+        .any(|v| close(v.position, Vector2f::new(11., 11.))));
+    // Round caps go around the ends
+    let round = ribbon(
+        &points[..2],
+        None,
+        2.,
+        LineJoin::Round,
+        LineCap::Round,
+        Color::WHITE,
+    );
+    assert_eq!(round.len(), 6 + 2 * 8 * 3);
+    assert!(round
+        .iter()
+        .any(|v| close(v.position, Vector2f::new(-1., 0.))));
+    // Nothing to draw with a single point
+    assert!(ribbon(
+        &points[..1],
+        None,
+        2.,
+        LineJoin::Miter,
+        LineCap::Round,
+        Color::WHITE
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_dash_runs() {
+    let line = [Vector2f::new(0., 0.), Vector2f::new(10., 0.)];
+    let starts = |runs: &[(Vec<Vector2f>, Option<Vector2f>)]| -> Vec<(f32, f32)> {
+        runs.iter()
+            .map(|(run, _)| (run[0].x, run[run.len() - 1].x))
+            .collect()
+    };
+    assert_eq!(
+        starts(&dash_runs(&line, &[2., 3.], 0.)),
+        [(0., 2.), (5., 7.)]
+    );
+    assert_eq!(
+        starts(&dash_runs(&line, &[2., 3.], 1.)),
+        [(0., 1.), (4., 6.), (9., 10.)]
+    );
+    // Dots, with the direction of the line
+    let dots = dash_runs(&line, &[0., 5.], 0.);
+    assert_eq!(starts(&dots), [(0., 0.), (5., 5.), (10., 10.)]);
+    assert_eq!(dots[1].1, Some(Vector2f::new(1., 0.)));
+    // Dashes go around corners
+    let corner = [line[0], line[1], Vector2f::new(10., 10.)];
+    let runs = dash_runs(&corner, &[15., 5.], 0.);
+    assert_eq!(runs[0].0, [line[0], line[1], Vector2f::new(10., 5.)]);
+}
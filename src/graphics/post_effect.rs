@@ -0,0 +1,429 @@
+use crate::graphics::{
+    BlendMode, Color, RenderStates, RenderTarget, RenderTexture, Shader, Sprite, Texture,
+};
+use crate::system::{Vector2f, Vector2u};
+
+/// A full screen effect, applied to a rendered scene.
+///
+/// Post effects are applied once the scene has been drawn to a [`RenderTexture`]: the
+/// effect reads the texture of the scene, and draws the result to another render texture,
+/// which can be displayed or fed to the next effect.
+///
+/// The effects of this crate run as fragment shaders, and require [`Shader::is_available`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{
+///     GaussianBlur, PostEffect, RenderTarget, RenderTexture, RenderWindow, Sprite, Vignette,
+/// };
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut scene = RenderTexture::new(800, 600, false).unwrap();
+/// let mut blurred = RenderTexture::new(800, 600, false).unwrap();
+/// let mut output = RenderTexture::new(800, 600, false).unwrap();
+/// let mut blur = GaussianBlur::new(4.).unwrap();
+/// let mut vignette = Vignette::new().unwrap();
+/// // ... draw the scene
+/// scene.display();
+/// blur.apply(scene.texture(), &mut blurred);
+/// vignette.apply(blurred.texture(), &mut output);
+/// window.draw(&Sprite::with_texture(output.texture()));
+/// ```
+///
+/// [`Shader::is_available`]: crate::graphics::Shader::is_available
+pub trait PostEffect {
+    /// Apply the effect to `source`, drawing the result over the whole of `target`.
+    ///
+    /// `target` must have the size of `source`, and be another render texture than the
+    /// one `source` belongs to.
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture);
+}
+
+/// Draw `source` over the whole of `target` through `shader`.
+fn pass(
+    source: &Texture,
+    target: &mut RenderTexture,
+    shader: &Shader,
+    blend_mode: BlendMode,
+    clear: bool,
+) {
+    if clear {
+        target.clear(Color::TRANSPARENT);
+    }
+    let states = RenderStates {
+        blend_mode,
+        shader: Some(shader),
+        ..Default::default()
+    };
+    target.draw_with_renderstates(&Sprite::with_texture(source), states);
+    target.display();
+}
+
+/// Get the render texture in `slot`, created anew if it doesn't have the given size.
+fn buffer(slot: &mut Option<RenderTexture>, size: Vector2u) -> Option<&mut RenderTexture> {
+    let fits = match slot {
+        Some(buffer) => buffer.size() == size,
+        None => false,
+    };
+    if !fits {
+        *slot = Some(RenderTexture::new(size.x, size.y, false)?);
+    }
+    slot.as_mut()
+}
+
+/// Create a fragment shader reading its input from the current texture.
+fn fragment_shader(source: &str) -> Option<Shader<'static>> {
+    let mut shader = Shader::from_memory(None, None, Some(source))?;
+    shader.set_uniform_current_texture("texture");
+    Some(shader)
+}
+
+/// Radius of the biggest blur, in pixels, with the default spacing.
+const MAX_BLUR_RADIUS: usize = 15;
+
+const BLUR: &str = "
+uniform sampler2D texture;
+uniform vec2 direction;
+uniform float weights[16];
+
+void main()
+{
+    vec2 uv = gl_TexCoord[0].xy;
+    vec4 color = texture2D(texture, uv) * weights[0];
+    for (int i = 1; i < 16; ++i)
+    {
+        vec2 offset = direction * float(i);
+        color += (texture2D(texture, uv + offset) + texture2D(texture, uv - offset)) * weights[i];
+    }
+    gl_FragColor = color * gl_Color;
+}
+";
+
+const THRESHOLD: &str = "
+uniform sampler2D texture;
+uniform float threshold;
+
+void main()
+{
+    vec4 color = texture2D(texture, gl_TexCoord[0].xy);
+    float luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    gl_FragColor = vec4(color.rgb * smoothstep(threshold, threshold + 0.1, luminance), 1.0);
+}
+";
+
+const INTENSITY: &str = "
+uniform sampler2D texture;
+uniform float intensity;
+
+void main()
+{
+    gl_FragColor = vec4(texture2D(texture, gl_TexCoord[0].xy).rgb * intensity, 1.0);
+}
+";
+
+const VIGNETTE: &str = "
+uniform sampler2D texture;
+uniform float radius;
+uniform float softness;
+uniform float intensity;
+
+void main()
+{
+    vec2 uv = gl_TexCoord[0].xy;
+    vec4 color = texture2D(texture, uv);
+    float shade = smoothstep(radius, radius - softness, distance(uv, vec2(0.5)));
+    gl_FragColor = vec4(color.rgb * mix(1.0, shade, intensity), color.a) * gl_Color;
+}
+";
+
+const CHROMATIC_ABERRATION: &str = "
+uniform sampler2D texture;
+uniform vec2 offset;
+
+void main()
+{
+    vec2 uv = gl_TexCoord[0].xy;
+    vec2 shift = offset * (uv - vec2(0.5)) * 2.0;
+    vec4 color = texture2D(texture, uv);
+    float red = texture2D(texture, uv + shift).r;
+    float blue = texture2D(texture, uv - shift).b;
+    gl_FragColor = vec4(red, color.g, blue, color.a) * gl_Color;
+}
+";
+
+/// Separable gaussian blur, in a horizontal and a vertical pass.
+#[derive(Debug)]
+pub struct GaussianBlur {
+    shader: Shader<'static>,
+    radius: f32,
+    buffer: Option<RenderTexture>,
+}
+
+impl GaussianBlur {
+    /// Create a new blur
+    ///
+    /// # Arguments
+    /// * radius - How far, in pixels, the color of a pixel spreads. Blurs wider than
+    ///   15 pixels skip pixels, and may show bands.
+    ///
+    /// Return Some(GaussianBlur) or None if the shader fails to compile
+    pub fn new(radius: f32) -> Option<Self> {
+        Some(GaussianBlur {
+            shader: fragment_shader(BLUR)?,
+            radius,
+            buffer: None,
+        })
+    }
+    /// Set how far, in pixels, the color of a pixel spreads.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+    /// Get how far, in pixels, the color of a pixel spreads.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+impl PostEffect for GaussianBlur {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) {
+        let size = source.size();
+        let (weights, spacing) = gaussian_weights(self.radius);
+        self.shader.set_uniform_array_float("weights", &weights);
+        let buffer = match buffer(&mut self.buffer, size) {
+            Some(buffer) => buffer,
+            None => return,
+        };
+        let horizontal = Vector2f::new(spacing / size.x.max(1) as f32, 0.);
+        self.shader.set_uniform_vec2("direction", horizontal);
+        pass(source, buffer, &self.shader, BlendMode::NONE, true);
+        let vertical = Vector2f::new(0., spacing / size.y.max(1) as f32);
+        self.shader.set_uniform_vec2("direction", vertical);
+        pass(
+            buffer.texture(),
+            target,
+            &self.shader,
+            BlendMode::NONE,
+            true,
+        );
+    }
+}
+
+/// Makes the bright parts of a scene glow, by adding a blurred copy of them to it.
+#[derive(Debug)]
+pub struct Bloom {
+    threshold_shader: Shader<'static>,
+    intensity_shader: Shader<'static>,
+    blur: GaussianBlur,
+    threshold: f32,
+    intensity: f32,
+    bright: Option<RenderTexture>,
+    blurred: Option<RenderTexture>,
+}
+
+impl Bloom {
+    /// Create a new bloom, making parts brighter than 0.7 glow over 8 pixels.
+    ///
+    /// Return Some(Bloom) or None if a shader fails to compile
+    pub fn new() -> Option<Self> {
+        Some(Bloom {
+            threshold_shader: fragment_shader(THRESHOLD)?,
+            intensity_shader: fragment_shader(INTENSITY)?,
+            blur: GaussianBlur::new(8.)?,
+            threshold: 0.7,
+            intensity: 1.,
+            bright: None,
+            blurred: None,
+        })
+    }
+    /// Set the luminance, between 0 and 1, above which parts of the scene glow.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+    /// Get the luminance above which parts of the scene glow.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+    /// Set how strong the glow is, 1 by default.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity;
+    }
+    /// Get how strong the glow is.
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+    /// Set how far, in pixels, the glow spreads.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.blur.set_radius(radius);
+    }
+    /// Get how far, in pixels, the glow spreads.
+    pub fn radius(&self) -> f32 {
+        self.blur.radius()
+    }
+}
+
+impl PostEffect for Bloom {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) {
+        let size = source.size();
+        let bright = match buffer(&mut self.bright, size) {
+            Some(bright) => bright,
+            None => return,
+        };
+        let blurred = match buffer(&mut self.blurred, size) {
+            Some(blurred) => blurred,
+            None => return,
+        };
+        self.threshold_shader
+            .set_uniform_float("threshold", self.threshold);
+        pass(
+            source,
+            bright,
+            &self.threshold_shader,
+            BlendMode::NONE,
+            true,
+        );
+        self.blur.apply(bright.texture(), blurred);
+        target.clear(Color::TRANSPARENT);
+        target.draw(&Sprite::with_texture(source));
+        self.intensity_shader
+            .set_uniform_float("intensity", self.intensity);
+        pass(
+            blurred.texture(),
+            target,
+            &self.intensity_shader,
+            BlendMode::ADD,
+            false,
+        );
+    }
+}
+
+/// Darkens the edges of a scene.
+#[derive(Debug)]
+pub struct Vignette {
+    shader: Shader<'static>,
+    radius: f32,
+    softness: f32,
+    intensity: f32,
+}
+
+impl Vignette {
+    /// Create a new vignette, darkening from 0.75 to 0.3 of the way to the edges.
+    ///
+    /// Return Some(Vignette) or None if the shader fails to compile
+    pub fn new() -> Option<Self> {
+        Some(Vignette {
+            shader: fragment_shader(VIGNETTE)?,
+            radius: 0.75,
+            softness: 0.45,
+            intensity: 1.,
+        })
+    }
+    /// Set the distance from the center, in halves of the scene, past which the scene
+    /// is fully darkened.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+    /// Get the distance from the center past which the scene is fully darkened.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+    /// Set the width of the transition, in halves of the scene.
+    pub fn set_softness(&mut self, softness: f32) {
+        self.softness = softness;
+    }
+    /// Get the width of the transition.
+    pub fn softness(&self) -> f32 {
+        self.softness
+    }
+    /// Set how dark the edges get, from 0 (not at all) to 1 (black).
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity;
+    }
+    /// Get how dark the edges get.
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+}
+
+impl PostEffect for Vignette {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) {
+        self.shader.set_uniform_float("radius", self.radius / 2.);
+        self.shader
+            .set_uniform_float("softness", self.softness / 2.);
+        self.shader.set_uniform_float("intensity", self.intensity);
+        pass(source, target, &self.shader, BlendMode::NONE, true);
+    }
+}
+
+/// Splits the red and blue channels of a scene apart towards its edges, like a cheap lens.
+#[derive(Debug)]
+pub struct ChromaticAberration {
+    shader: Shader<'static>,
+    strength: f32,
+}
+
+impl ChromaticAberration {
+    /// Create a new chromatic aberration, splitting the channels by 3 pixels at the edges.
+    ///
+    /// Return Some(ChromaticAberration) or None if the shader fails to compile
+    pub fn new() -> Option<Self> {
+        Some(ChromaticAberration {
+            shader: fragment_shader(CHROMATIC_ABERRATION)?,
+            strength: 3.,
+        })
+    }
+    /// Set how far apart, in pixels, the channels are at the edges of the scene.
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength;
+    }
+    /// Get how far apart, in pixels, the channels are at the edges of the scene.
+    pub fn strength(&self) -> f32 {
+        self.strength
+    }
+}
+
+impl PostEffect for ChromaticAberration {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) {
+        let size = source.size();
+        let offset = Vector2f::new(
+            self.strength / size.x.max(1) as f32,
+            self.strength / size.y.max(1) as f32,
+        );
+        self.shader.set_uniform_vec2("offset", offset);
+        pass(source, target, &self.shader, BlendMode::NONE, true);
+    }
+}
+
+/// Compute the weights of the samples of a blur of `radius` pixels, from the center
+/// outwards, and the spacing of the samples, in pixels.
+///
+/// The weights of both sides and the center add up to 1.
+fn gaussian_weights(radius: f32) -> ([f32; MAX_BLUR_RADIUS + 1], f32) {
+    let radius = radius.max(0.);
+    let spacing = (radius / MAX_BLUR_RADIUS as f32).max(1.);
+    let samples = (radius / spacing).round() as usize;
+    let mut weights = [0.; MAX_BLUR_RADIUS + 1];
+    let sigma = (samples as f32 / 2.).max(0.5);
+    for (i, weight) in weights.iter_mut().enumerate().take(samples + 1) {
+        let x = i as f32;
+        *weight = (-x * x / (2. * sigma * sigma)).exp();
+    }
+    let total = weights[0] + 2. * weights[1..].iter().sum::<f32>();
+    for weight in &mut weights {
+        *weight /= total;
+    }
+    (weights, spacing)
+}
+
+#[test]
+fn test_gaussian_weights() {
+    let (weights, spacing) = gaussian_weights(4.);
+    assert_eq!(spacing, 1.);
+    let total = weights[0] + 2. * weights[1..].iter().sum::<f32>();
+    assert!((total - 1.).abs() < 1e-5);
+    assert!(weights[0] > weights[1] && weights[4] > 0. && weights[5] == 0.);
+    let (weights, spacing) = gaussian_weights(30.);
+    assert_eq!(spacing, 2.);
+    assert!(weights[15] > 0.);
+    assert_eq!(gaussian_weights(0.).0[0], 1.);
+}
@@ -67,11 +67,7 @@ impl<'s> Default for RectangleShape<'s> {
 }
 
 impl<'s> Drawable for RectangleShape<'s> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_rectangle_shape(self, states);
     }
 }
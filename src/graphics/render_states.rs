@@ -48,18 +48,18 @@ use std::ptr;
 /// [`RenderTarget::draw_with_renderstates`]: crate::graphics::RenderTarget::draw_with_renderstates
 /// [`Drawable`]: crate::graphics::Drawable
 #[derive(Default, Debug, Clone, Copy)]
-pub struct RenderStates<'texture, 'shader, 'shader_texture: 'shader> {
+pub struct RenderStates<'a> {
     /// Blending mode.
     pub blend_mode: BlendMode,
     /// Transform
     pub transform: Transform,
     /// Texture
-    pub texture: Option<&'texture Texture>,
+    pub texture: Option<&'a Texture>,
     /// Shader
-    pub shader: Option<&'shader Shader<'shader_texture>>,
+    pub shader: Option<&'a Shader<'a>>,
 }
 
-impl<'texture, 'shader, 'shader_texture> RenderStates<'texture, 'shader, 'shader_texture> {
+impl<'a> RenderStates<'a> {
     /// Create a new RenderStates.
     ///
     /// # Arguments
@@ -72,8 +72,8 @@ impl<'texture, 'shader, 'shader_texture> RenderStates<'texture, 'shader, 'shader
     pub fn new(
         blend_mode: BlendMode,
         transform: Transform,
-        texture: Option<&'texture Texture>,
-        shader: Option<&'shader Shader<'shader_texture>>,
+        texture: Option<&'a Texture>,
+        shader: Option<&'a Shader<'a>>,
     ) -> Self {
         Self {
             blend_mode,
@@ -82,6 +82,28 @@ impl<'texture, 'shader, 'shader_texture> RenderStates<'texture, 'shader, 'shader
             shader,
         }
     }
+    /// Start building render states from the default ones.
+    ///
+    /// ```no_run
+    /// # use sfml::graphics::*;
+    /// # let mut window: RenderWindow = unimplemented!();
+    /// # let (texture, shader): (Texture, Shader) = unimplemented!();
+    /// # let vertices: [Vertex; 3] = unimplemented!();
+    /// window.draw_primitives(
+    ///     &vertices,
+    ///     PrimitiveType::Triangles,
+    ///     RenderStates::builder()
+    ///         .blend(BlendMode::ADD)
+    ///         .texture(&texture)
+    ///         .shader(&shader)
+    ///         .build(),
+    /// );
+    /// ```
+    pub fn builder() -> RenderStatesBuilder<'a> {
+        RenderStatesBuilder {
+            states: RenderStates::default(),
+        }
+    }
     pub(super) fn raw(&self) -> ffi::sfRenderStates {
         ffi::sfRenderStates {
             blendMode: self.blend_mode.raw(),
@@ -97,3 +119,44 @@ impl<'texture, 'shader, 'shader_texture> RenderStates<'texture, 'shader, 'shader
         }
     }
 }
+
+/// Builds [`RenderStates`] one state at a time, starting from the default ones.
+///
+/// Created by [`RenderStates::builder`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct RenderStatesBuilder<'a> {
+    states: RenderStates<'a>,
+}
+
+impl<'a> RenderStatesBuilder<'a> {
+    /// Set the blend mode.
+    pub fn blend(mut self, blend_mode: BlendMode) -> Self {
+        self.states.blend_mode = blend_mode;
+        self
+    }
+    /// Set the transform.
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.states.transform = transform;
+        self
+    }
+    /// Set the texture.
+    pub fn texture(mut self, texture: &'a Texture) -> Self {
+        self.states.texture = Some(texture);
+        self
+    }
+    /// Set the shader.
+    pub fn shader(mut self, shader: &'a Shader<'a>) -> Self {
+        self.states.shader = Some(shader);
+        self
+    }
+    /// Get the render states built.
+    pub fn build(self) -> RenderStates<'a> {
+        self.states
+    }
+}
+
+impl<'a> From<RenderStatesBuilder<'a>> for RenderStates<'a> {
+    fn from(builder: RenderStatesBuilder<'a>) -> Self {
+        builder.build()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::graphics::{
-    CircleShape, Color, ConvexShape, CustomShape, Drawable, IntRect, PrimitiveType, RectangleShape,
-    RenderStates, Sprite, Text, Vertex, VertexArray, View,
+    CircleShape, Color, ConvexShape, CustomShape, Drawable, FrameStats, IntRect, PrimitiveType,
+    RectangleShape, RenderStates, Sprite, Text, Vertex, VertexArray, VertexBuffer, View,
 };
 use crate::system::{Vector2f, Vector2i, Vector2u};
 
@@ -22,6 +22,31 @@ use crate::system::{Vector2f, Vector2i, Vector2u};
 /// When doing so, make sure that OpenGL states are not messed up by calling the
 /// [`push_gl_states`]/[`pop_gl_states`] functions.
 ///
+/// `RenderTarget` is implemented by [`RenderWindow`] and [`RenderTexture`], and can be
+/// used as a trait object, so that rendering code can be written once against
+/// `&mut dyn RenderTarget`, and draw to a window as well as to a texture.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, RenderTarget, RenderTexture, RenderWindow, Sprite, View};
+///
+/// fn draw_scene(target: &mut dyn RenderTarget, camera: &View, sprite: &Sprite) {
+///     target.clear(Color::BLACK);
+///     target.set_view(camera);
+///     target.draw(sprite);
+/// }
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let mut minimap: RenderTexture = unimplemented!();
+/// # let camera: View = unimplemented!();
+/// # let sprite: Sprite = unimplemented!();
+/// draw_scene(&mut window, &camera, &sprite);
+/// draw_scene(&mut minimap, &camera, &sprite);
+/// ```
+///
+/// [`RenderWindow`]: crate::graphics::RenderWindow
+/// [`RenderTexture`]: crate::graphics::RenderTexture
 /// [`push_gl_states`]: RenderTarget::push_gl_states
 /// [`pop_gl_states`]: RenderTarget::pop_gl_states
 pub trait RenderTarget {
@@ -148,14 +173,14 @@ pub trait RenderTarget {
     ///
     /// # Arguments
     /// * object - Object to draw
-    fn draw(&mut self, object: &Drawable);
+    fn draw(&mut self, object: &dyn Drawable);
 
     /// Draw a drawable object to the render-target with a [`RenderStates`]
     ///
     /// # Arguments
     /// * object - Object to draw
     /// * renderStates - The renderStates to associate to the object
-    fn draw_with_renderstates(&mut self, object: &Drawable, render_states: RenderStates);
+    fn draw_with_renderstates(&mut self, object: &dyn Drawable, render_states: RenderStates);
 
     /// Get the size of the rendering region of a window
     ///
@@ -164,6 +189,43 @@ pub trait RenderTarget {
     /// Return the size in pixels
     fn size(&self) -> Vector2u;
 
+    /// Restrict drawing to a rectangle of the target, or lift the restriction with `None`
+    ///
+    /// The rectangle is in pixels from the top left corner of the target, it
+    /// doesn't move with the view. Nothing is drawn outside of it, and clearing
+    /// the target only clears the rectangle. This is useful for the scrollable
+    /// areas of user interfaces.
+    ///
+    /// The OpenGL scissor test is enabled around each draw call to do so.
+    /// There is no clip rectangle by default.
+    ///
+    /// Render targets that don't support clipping ignore the rectangle, which is
+    /// what this method does unless it is implemented.
+    ///
+    /// # Arguments
+    /// * rect - The rectangle to draw in, or `None` to draw everywhere
+    fn set_clip_rect(&mut self, rect: Option<IntRect>) {
+        let _ = rect;
+    }
+
+    /// Get the rectangle drawing is restricted to, if any
+    ///
+    /// `None` unless the render target implements clipping.
+    fn clip_rect(&self) -> Option<IntRect> {
+        None
+    }
+
+    /// Get what was drawn during the last frame
+    ///
+    /// The statistics are counted from one call to `display` to the next, so they
+    /// describe the last frame displayed, not the one being drawn.
+    /// They are all zero until the first frame is displayed, and for render
+    /// targets that don't count what they draw, which is what this method returns
+    /// unless it is implemented.
+    fn frame_stats(&self) -> FrameStats {
+        FrameStats::default()
+    }
+
     /// Save the current OpenGL render states and matrices
     ///
     /// This function can be used when you mix SFML drawing
@@ -220,6 +282,9 @@ pub trait RenderTarget {
     /// Draw VertexArray
     fn draw_vertex_array(&self, vertex_array: &VertexArray, rs: RenderStates);
 
+    /// Draw VertexBuffer
+    fn draw_vertex_buffer(&self, vertex_buffer: &VertexBuffer, rs: RenderStates);
+
     /// draw primitives
     fn draw_primitives(&self, vertices: &[Vertex], ty: PrimitiveType, rs: RenderStates);
 }
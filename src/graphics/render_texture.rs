@@ -1,16 +1,47 @@
 use crate::graphics::csfml_graphics_sys as ffi;
+use crate::graphics::frame_stats::StatsCounter;
+use crate::graphics::scissor::Scissor;
 use crate::graphics::{
-    CircleShape, Color, ConvexShape, CustomShape, Drawable, IntRect, PrimitiveType, RectangleShape,
-    RenderStates, RenderTarget, Sprite, Text, Texture, Vertex, VertexArray, View,
+    CircleShape, Color, ConvexShape, CustomShape, Drawable, FrameStats, Image, IntRect,
+    PrimitiveType, RectangleShape, RenderStates, RenderTarget, Sprite, Text, Texture, Vertex,
+    VertexArray, VertexBuffer, View,
 };
 use crate::sf_bool_ext::SfBoolExt;
 use crate::system::{Vector2f, Vector2i, Vector2u};
-use csfml_system_sys::sfBool;
+use crate::window::ContextSettings;
+use csfml_system_sys::{sfBool, sfTrue};
 
 /// Target for off-screen 2D rendering into a texture
+///
+/// # Rendering without a window
+///
+/// A render texture doesn't need any window: it creates its own OpenGL context, and SFML
+/// creates the contexts it needs for textures and shaders by itself. This makes it
+/// usable for server-side generation of thumbnails, or for golden-image tests in CI,
+/// without any window popping up.
+///
+/// A [`Context`] is only needed to make OpenGL calls of your own on a thread with no
+/// active window or render texture.
+///
+/// SFML still needs a graphics driver: on Linux, contexts are created through the X
+/// server, so a headless machine needs a virtual one such as Xvfb.
+///
+/// ```no_run
+/// use sfml::graphics::{CircleShape, Color, RenderTarget, RenderTexture};
+///
+/// let mut target = RenderTexture::new(256, 256, false).expect("Failed to create the target");
+/// target.clear(Color::TRANSPARENT);
+/// target.draw(&CircleShape::new(128., 60));
+/// target.display();
+/// assert!(target.capture_to_file("thumbnail.png"));
+/// ```
+///
+/// [`Context`]: crate::window::Context
 #[derive(Debug)]
 pub struct RenderTexture {
     render_texture: *mut ffi::sfRenderTexture,
+    clip_rect: Option<IntRect>,
+    stats: StatsCounter,
 }
 
 impl RenderTexture {
@@ -31,6 +62,39 @@ impl RenderTexture {
         } else {
             Some(RenderTexture {
                 render_texture: tex,
+                clip_rect: None,
+                stats: StatsCounter::default(),
+            })
+        }
+    }
+
+    /// Construct a new render texture with the given context settings
+    ///
+    /// Unlike [`new`], this allows requesting an anti-aliasing level, a stencil
+    /// buffer, or an sRGB capable framebuffer, with the `srgb_capable` setting.
+    ///
+    /// [`new`]: RenderTexture::new
+    ///
+    /// # Arguments
+    /// * width - Width of the render texture
+    /// * height - Height of the render texture
+    /// * settings - Settings of the OpenGL context attached to the render texture
+    ///
+    /// Return Some(RenderTexture) or None
+    pub fn with_settings(
+        width: u32,
+        height: u32,
+        settings: &ContextSettings,
+    ) -> Option<RenderTexture> {
+        let tex =
+            unsafe { ffi::sfRenderTexture_createWithSettings(width, height, &settings.raw()) };
+        if tex.is_null() {
+            None
+        } else {
+            Some(RenderTexture {
+                render_texture: tex,
+                clip_rect: None,
+                stats: StatsCounter::default(),
             })
         }
     }
@@ -38,10 +102,15 @@ impl RenderTexture {
     /// Update the contents of the target texture
     pub fn display(&self) {
         unsafe { ffi::sfRenderTexture_display(self.render_texture) }
+        self.stats.end_frame();
     }
 
     /// Activate or deactivate a render texture as the current target for rendering
     ///
+    /// A render texture is active only on the current thread, and only one target
+    /// can be active on a thread at a time. Drawing to the render texture activates
+    /// it, so this is only needed before making OpenGL calls of your own.
+    ///
     /// # Arguments
     /// * active - true to activate, false to deactivate
     pub fn set_active(&mut self, active: bool) -> bool {
@@ -58,6 +127,29 @@ impl RenderTexture {
         unsafe { &*(tex as *const Texture) }
     }
 
+    /// Copy the current contents of the render texture to an image
+    ///
+    /// Call it once the frame is drawn, after [`display`]. The pixels are copied back
+    /// from the graphics card, which is slow.
+    ///
+    /// Return `None` if the image couldn't be created.
+    ///
+    /// [`display`]: RenderTexture::display
+    pub fn capture_to_image(&self) -> Option<Image> {
+        self.texture().copy_to_image()
+    }
+
+    /// Save the current contents of the render texture to an image file
+    ///
+    /// The format of the image is chosen from the extension of the file, see
+    /// [`Image::save_to_file`].
+    ///
+    /// Return true if the image was saved successfully
+    pub fn capture_to_file(&self, filename: &str) -> bool {
+        self.capture_to_image()
+            .map_or(false, |image| image.save_to_file(filename))
+    }
+
     /// Enable or disable the smooth filter on a render texture
     ///
     /// # Arguments
@@ -95,13 +187,31 @@ impl RenderTexture {
     pub unsafe fn generate_mipmap(&mut self) -> bool {
         ffi::sfRenderTexture_generateMipmap(self.render_texture).to_bool()
     }
+    /// Restrict drawing to the clip rectangle, if any, until the returned value is dropped
+    fn clip(&self) -> Option<Scissor> {
+        self.clip_rect.map(|rect| {
+            let _ = unsafe { ffi::sfRenderTexture_setActive(self.render_texture, sfTrue) };
+            Scissor::new(rect, self.size().y)
+        })
+    }
 }
 
 impl RenderTarget for RenderTexture {
+    fn set_clip_rect(&mut self, rect: Option<IntRect>) {
+        self.clip_rect = rect;
+    }
+    fn clip_rect(&self) -> Option<IntRect> {
+        self.clip_rect
+    }
+    fn frame_stats(&self) -> FrameStats {
+        self.stats.last()
+    }
     fn size(&self) -> Vector2u {
         unsafe { Vector2u::from_raw(ffi::sfRenderTexture_getSize(self.render_texture)) }
     }
     fn clear(&mut self, color: Color) {
+        let _clip = self.clip();
+        self.stats.clear();
         unsafe { ffi::sfRenderTexture_clear(self.render_texture, color.raw()) }
     }
     fn set_view(&mut self, view: &View) {
@@ -159,27 +269,37 @@ impl RenderTarget for RenderTexture {
             ))
         }
     }
-    fn draw(&mut self, object: &Drawable) {
+    fn draw(&mut self, object: &dyn Drawable) {
         object.draw(self, RenderStates::default());
     }
-    fn draw_with_renderstates(&mut self, object: &Drawable, render_states: RenderStates) {
+    fn draw_with_renderstates(&mut self, object: &dyn Drawable, render_states: RenderStates) {
         object.draw(self, render_states);
     }
     fn draw_text(&self, text: &Text, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.text(text);
         unsafe { ffi::sfRenderTexture_drawText(self.render_texture, text.raw(), &rs.raw()) }
     }
     fn draw_shape(&self, shape: &CustomShape, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(shape);
         unsafe { ffi::sfRenderTexture_drawShape(self.render_texture, shape.raw(), &rs.raw()) }
     }
     fn draw_sprite(&self, sprite: &Sprite, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.sprite(sprite);
         unsafe { ffi::sfRenderTexture_drawSprite(self.render_texture, sprite.raw(), &rs.raw()) }
     }
     fn draw_circle_shape(&self, circle_shape: &CircleShape, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(circle_shape);
         unsafe {
             ffi::sfRenderTexture_drawCircleShape(self.render_texture, circle_shape.raw(), &rs.raw())
         }
     }
     fn draw_rectangle_shape(&self, rectangle_shape: &RectangleShape, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(rectangle_shape);
         unsafe {
             ffi::sfRenderTexture_drawRectangleShape(
                 self.render_texture,
@@ -189,16 +309,34 @@ impl RenderTarget for RenderTexture {
         }
     }
     fn draw_convex_shape(&self, convex_shape: &ConvexShape, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(convex_shape);
         unsafe {
             ffi::sfRenderTexture_drawConvexShape(self.render_texture, convex_shape.raw(), &rs.raw())
         }
     }
     fn draw_vertex_array(&self, vertex_array: &VertexArray, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.vertices(vertex_array.vertex_count(), rs.texture);
         unsafe {
             ffi::sfRenderTexture_drawVertexArray(self.render_texture, vertex_array.raw(), &rs.raw())
         }
     }
+    fn draw_vertex_buffer(&self, vertex_buffer: &VertexBuffer, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats
+            .vertices(vertex_buffer.vertex_count() as usize, rs.texture);
+        unsafe {
+            ffi::sfRenderTexture_drawVertexBuffer(
+                self.render_texture,
+                vertex_buffer.raw(),
+                &rs.raw(),
+            )
+        }
+    }
     fn draw_primitives(&self, vertices: &[Vertex], ty: PrimitiveType, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.vertices(vertices.len(), rs.texture);
         let len = vertices.len();
         unsafe {
             ffi::sfRenderTexture_drawPrimitives(
@@ -1,12 +1,17 @@
 use crate::graphics::csfml_graphics_sys as ffi;
+use crate::graphics::frame_stats::StatsCounter;
+use crate::graphics::scissor::Scissor;
 use crate::graphics::{
-    CircleShape, Color, ConvexShape, CustomShape, Drawable, IntRect, PrimitiveType, RectangleShape,
-    RenderStates, RenderTarget, Sprite, Text, Vertex, VertexArray, View,
+    CircleShape, Color, ConvexShape, CustomShape, Drawable, FrameStats, Image, IntRect,
+    PrimitiveType, RectangleShape, RenderStates, RenderTarget, Sprite, Text, Texture, Vertex,
+    VertexArray, VertexBuffer, View,
 };
 use crate::sf_bool_ext::SfBoolExt;
 use crate::system::{Vector2f, Vector2i, Vector2u};
 use crate::window::{ContextSettings, Event, Handle, Style, VideoMode};
 use csfml_system_sys::*;
+use std::error::Error;
+use std::fmt;
 
 /// [`Window`] that can serve as a target for 2D drawing.
 ///
@@ -14,10 +19,35 @@ use csfml_system_sys::*;
 /// It defines an OS window that can be painted using the other classes
 /// of the graphics module.
 ///
+/// # Multiple windows and threads
+///
+/// Several render windows can be open at the same time, for tools with several
+/// viewports such as editors. Each has its own OpenGL context, and drawing to a window,
+/// or clearing it, makes its context active on the current thread: windows can be drawn
+/// one after the other without activating them by hand. Only raw OpenGL calls need the
+/// right context to be activated first, with [`RenderWindow::set_active`].
+///
+/// A render window belongs to the thread that created it: its events can only be
+/// polled there, and the type is neither `Send` nor `Sync`. To render away from the
+/// main thread, create a [`RenderTexture`] on the rendering thread, and send its
+/// pixels back.
+///
+/// ```compile_fail
+/// use sfml::graphics::RenderWindow;
+/// use std::thread;
+///
+/// let window = RenderWindow::builder().build().unwrap();
+/// // A window can't be moved to another thread
+/// thread::spawn(move || window.is_open());
+/// ```
+///
 /// [`Window`]: ::window::Window
+/// [`RenderTexture`]: crate::graphics::RenderTexture
 #[derive(Debug)]
 pub struct RenderWindow {
     render_window: *mut ffi::sfRenderWindow,
+    clip_rect: Option<IntRect>,
+    stats: StatsCounter,
 }
 
 impl RenderWindow {
@@ -39,25 +69,71 @@ impl RenderWindow {
     /// * title - Title of the render window
     /// * style - Window style
     /// * settings - Additional settings for the underlying OpenGL context
+    ///
+    /// See [`RenderWindow::builder`] to set up the window one setting at a time.
     pub fn new<V: Into<VideoMode>>(
         mode: V,
         title: &str,
         style: Style,
         settings: &ContextSettings,
     ) -> RenderWindow {
+        Self::create(mode.into(), title, style, settings).expect("Failed to create RenderWindow")
+    }
+
+    /// Start building a render window, one setting at a time
+    ///
+    /// The builder starts from a 800x600 window titled "SFML", with the default style
+    /// and context settings. Unlike [`RenderWindow::new`], building the window returns
+    /// an error rather than panicking when it can't be created.
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// use sfml::graphics::{Image, RenderWindow};
+    /// use sfml::window::{ContextSettings, Style};
+    ///
+    /// let icon = Image::from_file("icon.png").unwrap();
+    /// let window = RenderWindow::builder()
+    ///     .title("My game")
+    ///     .size(1280, 720)
+    ///     .style(Style::CLOSE | Style::TITLEBAR)
+    ///     .settings(ContextSettings {
+    ///         antialiasing_level: 4,
+    ///         ..Default::default()
+    ///     })
+    ///     .position((100, 100).into())
+    ///     .icon(&icon)
+    ///     .vertical_sync(true)
+    ///     .build()
+    ///     .expect("Failed to create the window");
+    /// ```
+    pub fn builder() -> RenderWindowBuilder {
+        RenderWindowBuilder::default()
+    }
+
+    fn create(
+        mode: VideoMode,
+        title: &str,
+        style: Style,
+        settings: &ContextSettings,
+    ) -> Option<RenderWindow> {
         let utf32 = crate::unicode_conv::str_to_csfml(title);
         let sf_render_win: *mut ffi::sfRenderWindow = unsafe {
             ffi::sfRenderWindow_createUnicode(
-                mode.into().raw(),
+                mode.raw(),
                 utf32.as_ptr() as _,
                 style.bits(),
                 &settings.raw(),
             )
         };
-        assert!(!sf_render_win.is_null(), "Failed to create RenderWindow");
-        RenderWindow {
-            render_window: sf_render_win,
+        if sf_render_win.is_null() {
+            return None;
         }
+        Some(RenderWindow {
+            render_window: sf_render_win,
+            clip_rect: None,
+            stats: StatsCounter::default(),
+        })
     }
 
     /// Create a render window from an existing platform-specific window handle
@@ -80,6 +156,8 @@ impl RenderWindow {
         assert!(!sf_render_win.is_null(), "Failed to create Window");
         RenderWindow {
             render_window: sf_render_win,
+            clip_rect: None,
+            stats: StatsCounter::default(),
         }
     }
 
@@ -167,6 +245,7 @@ impl RenderWindow {
     ///
     pub fn display(&mut self) {
         unsafe { ffi::sfRenderWindow_display(self.render_window) }
+        self.stats.end_frame();
     }
 
     /// Limit the framerate to a maximum fixed frequency
@@ -283,12 +362,16 @@ impl RenderWindow {
 
     /// Activate or deactivate a render window as the current target for OpenGL rendering
     ///
-    /// A window is active only on the current thread, if you want to
-    /// make it active on another thread you have to deactivate it
-    /// on the previous thread first if it was active.
+    /// A window is active only on the current thread.
     /// Only one window can be active on a thread at a time, thus
     /// the window previously active (if any) automatically gets deactivated.
     ///
+    /// Drawing to the window activates it, so this is only needed before making
+    /// OpenGL calls of your own. Deactivating it lets another context, such as a
+    /// [`Context`], be used on the thread.
+    ///
+    /// [`Context`]: crate::window::Context
+    ///
     /// # Arguments
     /// * active - true to activate, false to deactivate
     ///
@@ -385,12 +468,78 @@ impl RenderWindow {
     pub fn request_focus(&self) {
         unsafe { ffi::sfRenderWindow_requestFocus(self.render_window) }
     }
+    /// Copy the current contents of the window to an image
+    ///
+    /// This is a slow operation, made for screenshots: the pixels are copied to a texture,
+    /// then back from the graphics card. Call it once the frame is drawn, before
+    /// [`display`], as the contents of the window are undefined after it.
+    ///
+    /// Return `None` if the texture or the image couldn't be created.
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// use sfml::graphics::{Color, RenderTarget, RenderWindow};
+    /// use sfml::window::{Event, Key};
+    ///
+    /// # let mut window: RenderWindow = unimplemented!();
+    /// let mut screenshot = false;
+    /// while let Some(event) = window.poll_event() {
+    ///     if let Event::KeyPressed { code: Key::F12, .. } = event {
+    ///         screenshot = true;
+    ///     }
+    /// }
+    /// window.clear(Color::BLACK);
+    /// // window.draw(&world);
+    /// if screenshot && !window.capture_to_file("screenshot.png") {
+    ///     eprintln!("Failed to save the screenshot");
+    /// }
+    /// window.display();
+    /// ```
+    ///
+    /// [`display`]: RenderWindow::display
+    pub fn capture_to_image(&self) -> Option<Image> {
+        let size = self.size();
+        let mut texture = Texture::new(size.x, size.y)?;
+        texture.update_from_render_window(self, 0, 0);
+        texture.copy_to_image()
+    }
+
+    /// Save the current contents of the window to an image file
+    ///
+    /// The format of the image is chosen from the extension of the file, see
+    /// [`Image::save_to_file`], and its contents are copied like [`capture_to_image`] does.
+    ///
+    /// Return true if the image was saved successfully
+    ///
+    /// [`capture_to_image`]: RenderWindow::capture_to_image
+    pub fn capture_to_file(&self, filename: &str) -> bool {
+        self.capture_to_image()
+            .map_or(false, |image| image.save_to_file(filename))
+    }
+
+    /// Restrict drawing to the clip rectangle, if any, until the returned value is dropped
+    fn clip(&self) -> Option<Scissor> {
+        self.clip_rect.map(|rect| {
+            let _ = unsafe { ffi::sfRenderWindow_setActive(self.render_window, sfTrue) };
+            Scissor::new(rect, self.size().y)
+        })
+    }
     pub(super) fn raw(&self) -> *const ffi::sfRenderWindow {
         self.render_window
     }
 }
 
 impl RenderTarget for RenderWindow {
+    fn set_clip_rect(&mut self, rect: Option<IntRect>) {
+        self.clip_rect = rect;
+    }
+    fn clip_rect(&self) -> Option<IntRect> {
+        self.clip_rect
+    }
+    fn frame_stats(&self) -> FrameStats {
+        self.stats.last()
+    }
     fn push_gl_states(&mut self) {
         unsafe { ffi::sfRenderWindow_pushGLStates(self.render_window) }
     }
@@ -458,28 +607,36 @@ impl RenderTarget for RenderWindow {
     fn size(&self) -> Vector2u {
         unsafe { Vector2u::from_raw(ffi::sfRenderWindow_getSize(self.render_window)) }
     }
-    fn draw(&mut self, object: &Drawable) {
+    fn draw(&mut self, object: &dyn Drawable) {
         object.draw(self, RenderStates::default());
     }
-    fn draw_with_renderstates(&mut self, object: &Drawable, render_states: RenderStates) {
+    fn draw_with_renderstates(&mut self, object: &dyn Drawable, render_states: RenderStates) {
         object.draw(self, render_states);
     }
     fn draw_text(&self, text: &Text, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats.text(text);
         unsafe {
             ffi::sfRenderWindow_drawText(self.render_window, text.raw(), &render_states.raw())
         }
     }
     fn draw_shape(&self, shape: &CustomShape, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(shape);
         unsafe {
             ffi::sfRenderWindow_drawShape(self.render_window, shape.raw(), &render_states.raw())
         }
     }
     fn draw_sprite(&self, sprite: &Sprite, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats.sprite(sprite);
         unsafe {
             ffi::sfRenderWindow_drawSprite(self.render_window, sprite.raw(), &render_states.raw())
         }
     }
     fn draw_circle_shape(&self, circle_shape: &CircleShape, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(circle_shape);
         unsafe {
             ffi::sfRenderWindow_drawCircleShape(
                 self.render_window,
@@ -489,6 +646,8 @@ impl RenderTarget for RenderWindow {
         }
     }
     fn draw_rectangle_shape(&self, rectangle_shape: &RectangleShape, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(rectangle_shape);
         unsafe {
             ffi::sfRenderWindow_drawRectangleShape(
                 self.render_window,
@@ -498,6 +657,8 @@ impl RenderTarget for RenderWindow {
         }
     }
     fn draw_convex_shape(&self, convex_shape: &ConvexShape, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats.shape(convex_shape);
         unsafe {
             ffi::sfRenderWindow_drawConvexShape(
                 self.render_window,
@@ -507,6 +668,9 @@ impl RenderTarget for RenderWindow {
         }
     }
     fn draw_vertex_array(&self, vertex_array: &VertexArray, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats
+            .vertices(vertex_array.vertex_count(), render_states.texture);
         unsafe {
             ffi::sfRenderWindow_drawVertexArray(
                 self.render_window,
@@ -515,7 +679,21 @@ impl RenderTarget for RenderWindow {
             )
         }
     }
+    fn draw_vertex_buffer(&self, vertex_buffer: &VertexBuffer, render_states: RenderStates) {
+        let _clip = self.clip();
+        self.stats
+            .vertices(vertex_buffer.vertex_count() as usize, render_states.texture);
+        unsafe {
+            ffi::sfRenderWindow_drawVertexBuffer(
+                self.render_window,
+                vertex_buffer.raw(),
+                &render_states.raw(),
+            )
+        }
+    }
     fn draw_primitives(&self, vertices: &[Vertex], ty: PrimitiveType, rs: RenderStates) {
+        let _clip = self.clip();
+        self.stats.vertices(vertices.len(), rs.texture);
         let len = vertices.len();
         unsafe {
             ffi::sfRenderWindow_drawPrimitives(
@@ -528,6 +706,8 @@ impl RenderTarget for RenderWindow {
         }
     }
     fn clear(&mut self, color: Color) {
+        let _clip = self.clip();
+        self.stats.clear();
         unsafe { ffi::sfRenderWindow_clear(self.render_window, color.raw()) }
     }
 }
@@ -539,3 +719,173 @@ impl Drop for RenderWindow {
         }
     }
 }
+
+/// Builds a [`RenderWindow`] one setting at a time.
+///
+/// Created by [`RenderWindow::builder`].
+#[derive(Debug, Clone)]
+pub struct RenderWindowBuilder {
+    title: String,
+    mode: VideoMode,
+    style: Style,
+    settings: ContextSettings,
+    position: Option<Vector2i>,
+    icon: Option<(Vector2u, Vec<u8>)>,
+    framerate_limit: u32,
+    vertical_sync: bool,
+}
+
+impl Default for RenderWindowBuilder {
+    fn default() -> Self {
+        RenderWindowBuilder {
+            title: "SFML".into(),
+            mode: VideoMode::new(800, 600, 32),
+            style: Style::DEFAULT,
+            settings: ContextSettings::default(),
+            position: None,
+            icon: None,
+            framerate_limit: 0,
+            vertical_sync: false,
+        }
+    }
+}
+
+impl RenderWindowBuilder {
+    /// Set the title of the window.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.into();
+        self
+    }
+    /// Set the size of the rendering area of the window, in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.mode.width = width;
+        self.mode.height = height;
+        self
+    }
+    /// Set the video mode, giving the size and the pixel depth of the window.
+    ///
+    /// Fullscreen windows need one of the [`VideoMode::fullscreen_modes`].
+    pub fn video_mode(mut self, mode: VideoMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Set the style of the window, [`Style::DEFAULT`] by default.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    /// Set the settings of the OpenGL context of the window.
+    pub fn settings(mut self, settings: ContextSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+    /// Set the position of the window on the desktop, rather than letting the system
+    /// place it.
+    pub fn position(mut self, position: Vector2i) -> Self {
+        self.position = Some(position);
+        self
+    }
+    /// Set the icon of the window.
+    pub fn icon(mut self, icon: &Image) -> Self {
+        self.icon = Some((icon.size(), icon.pixels().to_vec()));
+        self
+    }
+    /// Limit the framerate, in frames per second, 0 for no limit.
+    ///
+    /// See [`RenderWindow::set_framerate_limit`].
+    pub fn framerate_limit(mut self, limit: u32) -> Self {
+        self.framerate_limit = limit;
+        self
+    }
+    /// Enable vertical synchronization, disabled by default.
+    ///
+    /// See [`RenderWindow::set_vertical_sync_enabled`].
+    pub fn vertical_sync(mut self, enabled: bool) -> Self {
+        self.vertical_sync = enabled;
+        self
+    }
+    /// Create the window.
+    pub fn build(self) -> Result<RenderWindow, WindowCreationError> {
+        self.validate()?;
+        let mut window = RenderWindow::create(self.mode, &self.title, self.style, &self.settings)
+            .ok_or(WindowCreationError::Failed)?;
+        if let Some(position) = self.position {
+            window.set_position(position);
+        }
+        if let Some((size, pixels)) = &self.icon {
+            window.set_icon(size.x, size.y, pixels);
+        }
+        if self.framerate_limit > 0 {
+            window.set_framerate_limit(self.framerate_limit);
+        }
+        if self.vertical_sync {
+            window.set_vertical_sync_enabled(true);
+        }
+        Ok(window)
+    }
+
+    fn validate(&self) -> Result<(), WindowCreationError> {
+        if self.framerate_limit > 0 && self.vertical_sync {
+            return Err(WindowCreationError::FramerateLimitWithVerticalSync);
+        }
+        if self.mode.width == 0 || self.mode.height == 0 {
+            return Err(WindowCreationError::InvalidVideoMode(self.mode));
+        }
+        if self.style.contains(Style::FULLSCREEN) && !self.mode.is_valid() {
+            return Err(WindowCreationError::InvalidVideoMode(self.mode));
+        }
+        Ok(())
+    }
+}
+
+/// Error building a [`RenderWindow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowCreationError {
+    /// The video mode has no area, or isn't a fullscreen mode for a fullscreen window.
+    InvalidVideoMode(VideoMode),
+    /// Both a framerate limit and vertical synchronization were asked for.
+    ///
+    /// They don't mix well and can make the window stutter, so only one of them
+    /// should be used.
+    FramerateLimitWithVerticalSync,
+    /// The window or its OpenGL context couldn't be created.
+    Failed,
+}
+
+impl fmt::Display for WindowCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowCreationError::InvalidVideoMode(mode) => write!(
+                f,
+                "invalid video mode {}x{}x{}",
+                mode.width, mode.height, mode.bits_per_pixel
+            ),
+            WindowCreationError::FramerateLimitWithVerticalSync => {
+                f.write_str("a framerate limit can't be used with vertical synchronization")
+            }
+            WindowCreationError::Failed => f.write_str("failed to create the window"),
+        }
+    }
+}
+
+impl Error for WindowCreationError {}
+
+#[test]
+fn test_render_window_builder() {
+    let builder = RenderWindow::builder().title("Test").size(0, 600);
+    assert_eq!(
+        builder.validate(),
+        Err(WindowCreationError::InvalidVideoMode(VideoMode::new(
+            0, 600, 32
+        )))
+    );
+    let builder = builder
+        .size(320, 240)
+        .framerate_limit(60)
+        .vertical_sync(true);
+    assert_eq!(
+        builder.validate(),
+        Err(WindowCreationError::FramerateLimitWithVerticalSync)
+    );
+    assert_eq!(builder.framerate_limit(0).validate(), Ok(()));
+}
@@ -0,0 +1,250 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, FloatRect, Font, FontSet, RenderStates, RenderTarget,
+    Text, TextStyle, Transformable,
+};
+use crate::system::Vector2f;
+
+/// A run of characters sharing the same style, in a [`RichText`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    /// The characters of the span. It may contain line breaks.
+    pub string: String,
+    /// Fill color of the characters.
+    pub color: Color,
+    /// Style of the characters: bold, italic, underlined.
+    pub style: TextStyle,
+    /// Size of the characters, in pixels.
+    pub character_size: u32,
+}
+
+impl TextSpan {
+    /// Create a new span of regular white characters.
+    ///
+    /// # Arguments
+    /// * string - The characters of the span
+    /// * character_size - The size of the characters
+    pub fn new(string: &str, character_size: u32) -> Self {
+        TextSpan {
+            string: string.to_owned(),
+            color: Color::WHITE,
+            style: TextStyle::REGULAR,
+            character_size,
+        }
+    }
+}
+
+/// Part of a span that sits on a single line.
+#[derive(Debug)]
+struct Piece<'s> {
+    text: Text<'s>,
+    line: usize,
+}
+
+/// Text made of several differently styled spans, laid out as a single block.
+///
+/// A [`Text`] has a single color, style and character size. A rich text renders a sequence
+/// of [`TextSpan`]s one after the other, each one with its own color, style and character
+/// size, and aligns them on common baselines: on a line mixing character sizes, the smaller
+/// characters sit on the same baseline as the bigger ones, and the line is as high as its
+/// biggest characters require.
+///
+/// All the spans use the font of the text, unless they are pushed with a [`FontSet`] to
+/// fall back on for the characters it lacks. Line breaks may appear anywhere in the spans.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, Font, RichText, TextSpan, TextStyle, Transformable};
+///
+/// let font = Font::from_file("sansation.ttf").unwrap();
+/// let mut text = RichText::new(&font);
+/// text.push(TextSpan::new("You found ", 20));
+/// text.push(TextSpan {
+///     color: Color::YELLOW,
+///     style: TextStyle::BOLD,
+///     ..TextSpan::new("the Golden Key", 24)
+/// });
+/// text.push(TextSpan::new("!\nPress E to continue.", 20));
+/// text.set_position((20., 400.));
+/// // window.draw(&text);
+/// ```
+#[derive(Debug)]
+pub struct RichText<'s> {
+    font: &'s Font,
+    spans: Vec<TextSpan>,
+    pieces: Vec<Piece<'s>>,
+    transformable: BasicTransformable,
+}
+
+impl<'s> RichText<'s> {
+    /// Create a new empty rich text, drawn with `font`.
+    pub fn new(font: &'s Font) -> Self {
+        RichText {
+            font,
+            spans: Vec::new(),
+            pieces: Vec::new(),
+            transformable: BasicTransformable::new(),
+        }
+    }
+    /// Append a span at the end of the text.
+    pub fn push(&mut self, span: TextSpan) {
+        let font = self.font;
+        self.add_pieces(&span, font, &span.string);
+        self.spans.push(span);
+        self.layout();
+    }
+    /// Append a span at the end of the text, drawing each of its characters with the first
+    /// font of `fonts` that has it.
+    ///
+    /// If `fonts` is empty, the span is drawn with the font of the text.
+    pub fn push_with_fonts(&mut self, span: TextSpan, fonts: &FontSet<'s>) {
+        let runs = fonts.runs(&span.string, span.character_size);
+        if runs.is_empty() {
+            let font = self.font;
+            self.add_pieces(&span, font, &span.string);
+        }
+        for (font, run) in runs {
+            self.add_pieces(&span, font, run);
+        }
+        self.spans.push(span);
+        self.layout();
+    }
+    /// Remove all the spans.
+    pub fn clear(&mut self) {
+        self.spans.clear();
+        self.pieces.clear();
+    }
+    /// Get the spans of the text.
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+    /// Get the font of the text.
+    pub fn font(&self) -> &'s Font {
+        self.font
+    }
+    /// Get the local bounding rectangle of the text
+    ///
+    /// The returned rectangle is in local coordinates, which means
+    /// that it ignores the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn local_bounds(&self) -> FloatRect {
+        let mut bounds: Option<(f32, f32, f32, f32)> = None;
+        for piece in &self.pieces {
+            if piece.text.string().is_empty() {
+                continue;
+            }
+            let rect = piece.text.global_bounds();
+            let (right, bottom) = (rect.left + rect.width, rect.top + rect.height);
+            bounds = Some(match bounds {
+                None => (rect.left, rect.top, right, bottom),
+                Some((l, t, r, b)) => (
+                    l.min(rect.left),
+                    t.min(rect.top),
+                    r.max(right),
+                    b.max(bottom),
+                ),
+            });
+        }
+        let (left, top, right, bottom) = bounds.unwrap_or_default();
+        FloatRect::new(left, top, right - left, bottom - top)
+    }
+    /// Get the global bounding rectangle of the text
+    ///
+    /// The returned rectangle is in global coordinates, which means
+    /// that it takes into account the transformations (translation, rotation, scale, ...)
+    /// that are applied to the entity.
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(&self.local_bounds())
+    }
+
+    fn add_pieces(&mut self, span: &TextSpan, font: &'s Font, string: &str) {
+        let mut line = self.pieces.last().map_or(0, |piece| piece.line);
+        for (i, part) in string.split('\n').enumerate() {
+            if i > 0 {
+                line += 1;
+            }
+            let mut text = Text::new(part, font, span.character_size);
+            text.set_fill_color(span.color);
+            text.set_style(span.style);
+            self.pieces.push(Piece { text, line });
+        }
+    }
+    fn layout(&mut self) {
+        let metrics: Vec<_> = self
+            .pieces
+            .iter_mut()
+            .map(|piece| {
+                piece.text.set_position((0., 0.));
+                let text = &piece.text;
+                let advance = text.find_character_pos(text.string().chars().count()).x;
+                (piece.line, text.character_size(), advance)
+            })
+            .collect();
+        let font = self.font;
+        let positions = arrange(&metrics, |size| font.line_spacing(size));
+        for (piece, position) in self.pieces.iter_mut().zip(positions) {
+            piece.text.set_position(position);
+        }
+    }
+}
+
+impl<'s> Drawable for RichText<'s> {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        for piece in &self.pieces {
+            target.draw_with_renderstates(&piece.text, states);
+        }
+    }
+}
+
+delegate_transformable!(impl<'s> for RichText<'s> => transformable);
+
+/// Compute the positions of pieces of text, given their line, character size and advance.
+///
+/// The pieces of a line are put one after the other, and moved down so that their
+/// baselines, which SFML puts at the character size below the top of a text, line up.
+fn arrange<F>(pieces: &[(usize, u32, f32)], line_spacing: F) -> Vec<Vector2f>
+where
+    F: Fn(u32) -> f32,
+{
+    let mut positions = Vec::with_capacity(pieces.len());
+    let mut top = 0.;
+    let mut start = 0;
+    while start < pieces.len() {
+        let line = pieces[start].0;
+        let end = start
+            + pieces[start..]
+                .iter()
+                .take_while(|piece| piece.0 == line)
+                .count();
+        let max_size = pieces[start..end].iter().map(|p| p.1).max().unwrap_or(0);
+        let mut x = 0.;
+        for &(_, size, advance) in &pieces[start..end] {
+            positions.push(Vector2f::new(x, top + (max_size - size) as f32));
+            x += advance;
+        }
+        top += line_spacing(max_size);
+        start = end;
+    }
+    positions
+}
+
+#[test]
+fn test_arrange() {
+    let pieces = [(0, 20, 50.), (0, 30, 40.), (0, 20, 10.), (1, 20, 30.)];
+    let positions = arrange(&pieces, |size| size as f32 + 5.);
+    assert_eq!(
+        positions,
+        [
+            Vector2f::new(0., 10.),
+            Vector2f::new(50., 0.),
+            Vector2f::new(90., 10.),
+            Vector2f::new(0., 35.),
+        ]
+    );
+}
@@ -0,0 +1,324 @@
+use crate::graphics::{
+    Color, ConvexShape, Drawable, FloatRect, IntRect, RenderStates, RenderTarget, Shape, Texture,
+    Transform, Transformable,
+};
+use crate::system::Vector2f;
+use std::f32::consts::FRAC_PI_2;
+
+/// Radii of the corners of a [`RoundedRectangleShape`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    /// Radius of the top left corner.
+    pub top_left: f32,
+    /// Radius of the top right corner.
+    pub top_right: f32,
+    /// Radius of the bottom right corner.
+    pub bottom_right: f32,
+    /// Radius of the bottom left corner.
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// Create radii of the same length for all corners.
+    pub fn uniform(radius: f32) -> Self {
+        CornerRadii {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+}
+
+/// Specialized shape representing a rectangle with rounded corners
+///
+/// Each corner is a quarter of a circle, with its own radius, made of a given number of
+/// points. When the radii of two corners on the same side add up to more than the length
+/// of the side, all the radii are shrunk to fit, so that the shape always stays convex.
+///
+/// Its top left corner is at the origin of the shape, like a [`RectangleShape`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Color, CornerRadii, RoundedRectangleShape, Shape, Transformable};
+///
+/// // A tab, only rounded at the top
+/// let mut tab = RoundedRectangleShape::new((120., 40.), CornerRadii::uniform(10.), 8);
+/// tab.set_radii(CornerRadii {
+///     bottom_left: 0.,
+///     bottom_right: 0.,
+///     ..tab.radii()
+/// });
+/// tab.set_fill_color(Color::rgb(60, 60, 70));
+/// tab.set_outline_thickness(2.);
+/// tab.set_position((20., 20.));
+/// // window.draw(&tab);
+/// ```
+///
+/// [`RectangleShape`]: crate::graphics::RectangleShape
+#[derive(Debug, Clone)]
+pub struct RoundedRectangleShape<'s> {
+    shape: ConvexShape<'s>,
+    size: Vector2f,
+    radii: CornerRadii,
+    corner_point_count: u32,
+}
+
+impl<'s> RoundedRectangleShape<'s> {
+    /// Create a new rounded rectangle
+    ///
+    /// # Arguments
+    /// * size - The size of the rectangle
+    /// * radii - The radii of the corners
+    /// * corner_point_count - The number of points of each rounded corner, at least 2
+    pub fn new<S: Into<Vector2f>>(
+        size: S,
+        radii: CornerRadii,
+        corner_point_count: u32,
+    ) -> RoundedRectangleShape<'s> {
+        let mut shape = RoundedRectangleShape {
+            shape: ConvexShape::new(0),
+            size: size.into(),
+            radii,
+            corner_point_count: corner_point_count.max(2),
+        };
+        shape.update();
+        shape
+    }
+
+    /// Create a new rounded rectangle with a texture
+    ///
+    /// # Arguments
+    /// * size - The size of the rectangle
+    /// * radii - The radii of the corners
+    /// * corner_point_count - The number of points of each rounded corner, at least 2
+    /// * texture - The texture to apply to the rectangle
+    pub fn with_texture<S: Into<Vector2f>>(
+        size: S,
+        radii: CornerRadii,
+        corner_point_count: u32,
+        texture: &'s Texture,
+    ) -> RoundedRectangleShape<'s> {
+        let mut shape = Self::new(size, radii, corner_point_count);
+        shape.set_texture(texture, true);
+        shape
+    }
+
+    /// Set the size of the rectangle
+    pub fn set_size<S: Into<Vector2f>>(&mut self, size: S) {
+        self.size = size.into();
+        self.update();
+    }
+
+    /// Get the size of the rectangle
+    pub fn size(&self) -> Vector2f {
+        self.size
+    }
+
+    /// Set the radii of the corners
+    pub fn set_radii(&mut self, radii: CornerRadii) {
+        self.radii = radii;
+        self.update();
+    }
+
+    /// Get the radii of the corners, as they were set
+    pub fn radii(&self) -> CornerRadii {
+        self.radii
+    }
+
+    /// Set the number of points of each rounded corner
+    ///
+    /// More points make smoother corners. There are at least 2 points per corner, a corner
+    /// with a radius of zero only has one.
+    pub fn set_corner_point_count(&mut self, count: u32) {
+        self.corner_point_count = count.max(2);
+        self.update();
+    }
+
+    /// Get the number of points of each rounded corner
+    pub fn corner_point_count(&self) -> u32 {
+        self.corner_point_count
+    }
+
+    fn update(&mut self) {
+        let points = corner_points(self.size, self.radii, self.corner_point_count);
+        self.shape.set_point_count(points.len() as u32);
+        for (i, point) in points.into_iter().enumerate() {
+            self.shape.set_point(i as u32, point);
+        }
+    }
+}
+
+impl<'s> Drawable for RoundedRectangleShape<'s> {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        target.draw_convex_shape(&self.shape, states);
+    }
+}
+
+impl<'s> Transformable for RoundedRectangleShape<'s> {
+    fn set_position<P: Into<Vector2f>>(&mut self, position: P) {
+        self.shape.set_position(position)
+    }
+    fn set_rotation(&mut self, angle: f32) {
+        self.shape.set_rotation(angle)
+    }
+    fn set_scale<S: Into<Vector2f>>(&mut self, scale: S) {
+        self.shape.set_scale(scale)
+    }
+    fn set_origin<O: Into<Vector2f>>(&mut self, origin: O) {
+        self.shape.set_origin(origin)
+    }
+    fn position(&self) -> Vector2f {
+        self.shape.position()
+    }
+    fn rotation(&self) -> f32 {
+        self.shape.rotation()
+    }
+    fn get_scale(&self) -> Vector2f {
+        self.shape.get_scale()
+    }
+    fn origin(&self) -> Vector2f {
+        self.shape.origin()
+    }
+    fn move_<O: Into<Vector2f>>(&mut self, offset: O) {
+        self.shape.move_(offset)
+    }
+    fn rotate(&mut self, angle: f32) {
+        self.shape.rotate(angle)
+    }
+    fn scale<F: Into<Vector2f>>(&mut self, factors: F) {
+        self.shape.scale(factors)
+    }
+    fn transform(&self) -> Transform {
+        self.shape.transform()
+    }
+    fn inverse_transform(&self) -> Transform {
+        self.shape.inverse_transform()
+    }
+}
+
+impl<'s> Shape<'s> for RoundedRectangleShape<'s> {
+    fn set_texture(&mut self, texture: &'s Texture, reset_rect: bool) {
+        self.shape.set_texture(texture, reset_rect)
+    }
+    fn disable_texture(&mut self) {
+        self.shape.disable_texture()
+    }
+    fn set_texture_rect(&mut self, rect: &IntRect) {
+        self.shape.set_texture_rect(rect)
+    }
+    fn set_fill_color(&mut self, color: Color) {
+        self.shape.set_fill_color(color)
+    }
+    fn set_outline_color(&mut self, color: Color) {
+        self.shape.set_outline_color(color)
+    }
+    fn set_outline_thickness(&mut self, thickness: f32) {
+        self.shape.set_outline_thickness(thickness)
+    }
+    fn texture(&self) -> Option<&'s Texture> {
+        self.shape.texture()
+    }
+    fn texture_rect(&self) -> IntRect {
+        self.shape.texture_rect()
+    }
+    fn fill_color(&self) -> Color {
+        self.shape.fill_color()
+    }
+    fn outline_color(&self) -> Color {
+        self.shape.outline_color()
+    }
+    fn outline_thickness(&self) -> f32 {
+        self.shape.outline_thickness()
+    }
+    fn point_count(&self) -> u32 {
+        self.shape.point_count()
+    }
+    fn point(&self, index: u32) -> Vector2f {
+        self.shape.point(index)
+    }
+    fn local_bounds(&self) -> FloatRect {
+        self.shape.local_bounds()
+    }
+    fn global_bounds(&self) -> FloatRect {
+        self.shape.global_bounds()
+    }
+}
+
+/// Compute the points of a rounded rectangle, clockwise from the left end of its top left
+/// corner, with `count` points per rounded corner.
+fn corner_points(size: Vector2f, radii: CornerRadii, count: u32) -> Vec<Vector2f> {
+    let (width, height) = (size.x.max(0.), size.y.max(0.));
+    let radius = |r: f32| r.max(0.);
+    let (tl, tr) = (radius(radii.top_left), radius(radii.top_right));
+    let (br, bl) = (radius(radii.bottom_right), radius(radii.bottom_left));
+    // Shrink all the radii by the same factor until they fit on every side
+    let fit = |side: f32, r0: f32, r1: f32| if r0 + r1 > side { side / (r0 + r1) } else { 1. };
+    let factor = fit(width, tl, tr)
+        .min(fit(width, bl, br))
+        .min(fit(height, tl, bl))
+        .min(fit(height, tr, br));
+    let corners = [
+        (tl * factor, Vector2f::new(0., 0.), Vector2f::new(1., 1.)),
+        (
+            tr * factor,
+            Vector2f::new(width, 0.),
+            Vector2f::new(-1., 1.),
+        ),
+        (
+            br * factor,
+            Vector2f::new(width, height),
+            Vector2f::new(-1., -1.),
+        ),
+        (
+            bl * factor,
+            Vector2f::new(0., height),
+            Vector2f::new(1., -1.),
+        ),
+    ];
+    let count = count.max(2);
+    let mut points = Vec::with_capacity(4 * count as usize);
+    for (i, &(radius, corner, inward)) in corners.iter().enumerate() {
+        if radius <= 0. {
+            points.push(corner);
+            continue;
+        }
+        let center = Vector2f::new(corner.x + inward.x * radius, corner.y + inward.y * radius);
+        // Each corner starts where the previous one ended, a quarter turn further
+        let start = FRAC_PI_2 * (i as f32 + 2.);
+        for j in 0..count {
+            let angle = start + FRAC_PI_2 * j as f32 / (count - 1) as f32;
+            points.push(Vector2f::new(
+                center.x + angle.cos() * radius,
+                center.y + angle.sin() * radius,
+            ));
+        }
+    }
+    points
+}
+
+#[test]
+fn test_corner_points() {
+    let close = |a: Vector2f, b: Vector2f| (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4;
+    let size = Vector2f::new(100., 50.);
+    let points = corner_points(size, CornerRadii::uniform(10.), 3);
+    assert_eq!(points.len(), 12);
+    assert!(close(points[0], Vector2f::new(0., 10.)));
+    assert!(close(points[2], Vector2f::new(10., 0.)));
+    assert!(close(points[3], Vector2f::new(90., 0.)));
+    assert!(close(points[6], Vector2f::new(100., 40.)));
+    assert!(close(points[11], Vector2f::new(0., 40.)));
+    // Square corners are a single point
+    let radii = CornerRadii {
+        bottom_left: 0.,
+        ..CornerRadii::uniform(10.)
+    };
+    let points = corner_points(size, radii, 3);
+    assert_eq!(points.len(), 10);
+    assert_eq!(points[9], Vector2f::new(0., 50.));
+    // Radii too big for the height are shrunk
+    let points = corner_points(size, CornerRadii::uniform(50.), 2);
+    assert!(close(points[1], Vector2f::new(25., 0.)));
+    assert!(close(points[2], Vector2f::new(75., 0.)));
+}
@@ -0,0 +1,48 @@
+use crate::graphics::csfml_graphics_sys as ffi;
+use crate::graphics::IntRect;
+
+/// Restricts drawing to a rectangle of the active render target, until dropped.
+pub(super) struct Scissor;
+
+impl Scissor {
+    /// Enable the scissor test on the active OpenGL context.
+    ///
+    /// # Arguments
+    /// * rect - The rectangle to draw in, in pixels from the top left corner of the target
+    /// * height - The height of the target, in pixels
+    pub(super) fn new(rect: IntRect, height: u32) -> Self {
+        let (x, y, width, height) = gl_rect(rect, height);
+        unsafe {
+            ffi::glEnable(ffi::GL_SCISSOR_TEST);
+            ffi::glScissor(x, y, width, height);
+        }
+        Scissor
+    }
+}
+
+impl Drop for Scissor {
+    fn drop(&mut self) {
+        unsafe { ffi::glDisable(ffi::GL_SCISSOR_TEST) }
+    }
+}
+
+/// Convert a rectangle from the top left corner of a target `height` pixels high, to the
+/// bottom left origin of OpenGL.
+fn gl_rect(rect: IntRect, height: u32) -> (i32, i32, i32, i32) {
+    let (width, rect_height) = (rect.width.max(0), rect.height.max(0));
+    (
+        rect.left,
+        height as i32 - rect.top - rect_height,
+        width,
+        rect_height,
+    )
+}
+
+#[test]
+fn test_gl_rect() {
+    assert_eq!(
+        gl_rect(IntRect::new(10, 20, 100, 50), 600),
+        (10, 530, 100, 50)
+    );
+    assert_eq!(gl_rect(IntRect::new(0, 0, -5, 600), 600), (0, 0, 0, 600));
+}
@@ -4,19 +4,28 @@ use crate::graphics::{
     Transformable,
 };
 use crate::sf_bool_ext::SfBoolExt;
-use crate::system::Vector2f;
+use crate::system::{SfBox, Vector2f};
 use csfml_system_sys::{sfBool, sfTrue};
 use std::marker::PhantomData;
 use std::ptr;
+use std::sync::Arc;
 
 /// Drawable representation of a texture
 ///
 /// Sprite is a drawable type that allows to easily
 /// display a [`Texture`] (or a part of it) on a render target.
+///
+/// A sprite either borrows its texture, for the lifetime `'s`, or shares the ownership of
+/// it through an [`Arc`], see [`with_texture_arc`]. A sprite sharing its texture can be
+/// a `Sprite<'static>`, and be stored and moved around freely.
+///
+/// [`with_texture_arc`]: Sprite::with_texture_arc
 #[derive(Debug)]
 pub struct Sprite<'s> {
     sprite: *mut ffi::sfSprite,
     texture: PhantomData<&'s Texture>,
+    /// Keeps the texture alive when it is shared rather than borrowed.
+    shared_texture: Option<Arc<SfBox<Texture>>>,
 }
 
 impl<'s> Sprite<'s> {
@@ -29,6 +38,7 @@ impl<'s> Sprite<'s> {
         Sprite {
             sprite: sp,
             texture: PhantomData,
+            shared_texture: None,
         }
     }
 
@@ -41,6 +51,16 @@ impl<'s> Sprite<'s> {
         sprite
     }
 
+    /// Create a new sprite with a texture it shares the ownership of
+    ///
+    /// The texture lives as long as the sprite, or any other owner of it,
+    /// so the sprite can have any lifetime, including `'static`.
+    pub fn with_texture_arc(texture: Arc<SfBox<Texture>>) -> Sprite<'s> {
+        let mut sprite = Sprite::new();
+        sprite.set_texture_arc(texture, true);
+        sprite
+    }
+
     /// Change the source texture of a sprite
     ///
     /// The texture argument refers to a texture that must
@@ -63,6 +83,25 @@ impl<'s> Sprite<'s> {
         unsafe {
             ffi::sfSprite_setTexture(self.sprite, texture.raw(), sfBool::from_bool(reset_rect))
         }
+        self.shared_texture = None;
+    }
+
+    /// Change the source texture of a sprite, sharing the ownership of it
+    ///
+    /// Unlike [`set_texture`], the sprite keeps the texture alive for as
+    /// long as it uses it.
+    ///
+    /// [`set_texture`]: Sprite::set_texture
+    ///
+    /// # Arguments
+    /// * texture - New texture
+    /// * reset_rect - Should the texture rect be reset to the size
+    ///   of the new texture?
+    pub fn set_texture_arc(&mut self, texture: Arc<SfBox<Texture>>, reset_rect: bool) {
+        unsafe {
+            ffi::sfSprite_setTexture(self.sprite, texture.raw(), sfBool::from_bool(reset_rect))
+        }
+        self.shared_texture = Some(texture);
     }
 
     /// Disable Texturing
@@ -70,6 +109,7 @@ impl<'s> Sprite<'s> {
     /// Disable the current texture and reset the texture rect
     pub fn disable_texture(&mut self) {
         unsafe { ffi::sfSprite_setTexture(self.sprite, ptr::null_mut(), sfTrue) }
+        self.shared_texture = None;
     }
 
     /// Set the global color of a sprite
@@ -92,7 +132,7 @@ impl<'s> Sprite<'s> {
     /// modify the texture when you retrieve it with this function.
     ///
     /// Return an Option to the sprite's texture
-    pub fn texture(&self) -> Option<&'s Texture> {
+    pub fn texture(&self) -> Option<&Texture> {
         unsafe {
             let ptr = ffi::sfSprite_getTexture(self.sprite);
             if ptr.is_null() {
@@ -173,16 +213,13 @@ impl<'s> Clone for Sprite<'s> {
         Sprite {
             sprite: sp,
             texture: PhantomData,
+            shared_texture: self.shared_texture.clone(),
         }
     }
 }
 
 impl<'s> Drawable for Sprite<'s> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_sprite(self, states)
     }
 }
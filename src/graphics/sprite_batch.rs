@@ -0,0 +1,159 @@
+use crate::graphics::{
+    Color, Drawable, IntRect, PrimitiveType, RenderStates, RenderTarget, Sprite, Texture,
+    Transform, Transformable, Vertex,
+};
+use crate::system::Vector2f;
+
+/// Draws many sprites sharing a texture with a single draw call.
+///
+/// Each call to [`RenderTarget::draw`] goes through CSFML and issues a draw call to the
+/// graphics driver, which becomes the bottleneck when thousands of sprites are drawn every
+/// frame. A batch accumulates the geometry of its sprites into one array of vertices,
+/// transformed on the CPU, and draws them all at once.
+///
+/// All the sprites of a batch are drawn with the texture of the batch, whatever the texture
+/// they were given themselves. Sprites are drawn in the order they were added. The batch
+/// only copies the geometry of the sprites: it must be [`clear`]ed and filled again when they
+/// move.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{RenderTarget, RenderWindow, Sprite, SpriteBatch, Texture};
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let texture = Texture::from_file("particles.png").unwrap();
+/// let particles: Vec<Sprite> = (0..10_000).map(|_| Sprite::with_texture(&texture)).collect();
+/// let mut batch = SpriteBatch::new(&texture);
+/// loop {
+///     // ... move the particles
+///     batch.clear();
+///     for particle in &particles {
+///         batch.add(particle);
+///     }
+///     window.draw(&batch);
+///     window.display();
+/// }
+/// ```
+///
+/// [`clear`]: SpriteBatch::clear
+#[derive(Debug, Clone)]
+pub struct SpriteBatch<'t> {
+    texture: &'t Texture,
+    vertices: Vec<Vertex>,
+}
+
+impl<'t> SpriteBatch<'t> {
+    /// Create a new empty batch, drawing with `texture`.
+    pub fn new(texture: &'t Texture) -> Self {
+        SpriteBatch {
+            texture,
+            vertices: Vec::new(),
+        }
+    }
+    /// Add a sprite to the batch.
+    ///
+    /// Its texture rectangle, color and transform are copied; its texture is ignored.
+    pub fn add(&mut self, sprite: &Sprite) {
+        self.add_rect(sprite.texture_rect(), &sprite.transform(), sprite.color());
+    }
+    /// Add a textured rectangle to the batch, the same as a sprite with the given texture
+    /// rectangle, transform and color would be.
+    ///
+    /// This skips reading the attributes of a sprite back from CSFML.
+    ///
+    /// # Arguments
+    /// * texture_rect - Part of the texture to display
+    /// * transform - Transform from the rectangle's local coordinates to the target's
+    /// * color - Color the texture is modulated with
+    pub fn add_rect(&mut self, texture_rect: IntRect, transform: &Transform, color: Color) {
+        self.vertices
+            .extend_from_slice(&quad(texture_rect, transform, color));
+    }
+    /// Remove all the sprites from the batch.
+    ///
+    /// The memory is kept, so that filling the batch again doesn't reallocate.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+    /// Return the number of sprites in the batch.
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 6
+    }
+    /// Tell whether the batch has no sprites.
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+    /// Get the texture the batch draws with.
+    pub fn texture(&self) -> &'t Texture {
+        self.texture
+    }
+    /// Get the vertices of the batch, two triangles per sprite.
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+}
+
+impl<'t> Drawable for SpriteBatch<'t> {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        let states = RenderStates {
+            texture: Some(self.texture),
+            ..states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::Triangles, states);
+    }
+}
+
+/// Apply `transform` to `point`, without going through CSFML.
+fn transform_point(transform: &Transform, point: Vector2f) -> Vector2f {
+    let m = &transform.0.matrix;
+    Vector2f::new(
+        m[0] * point.x + m[1] * point.y + m[2],
+        m[3] * point.x + m[4] * point.y + m[5],
+    )
+}
+
+/// Build the two triangles of a sprite, the way SFML lays out its vertices.
+fn quad(texture_rect: IntRect, transform: &Transform, color: Color) -> [Vertex; 6] {
+    let width = texture_rect.width.abs() as f32;
+    let height = texture_rect.height.abs() as f32;
+    let left = texture_rect.left as f32;
+    let right = left + texture_rect.width as f32;
+    let top = texture_rect.top as f32;
+    let bottom = top + texture_rect.height as f32;
+    let vertex = |x, y, u, v| Vertex {
+        position: transform_point(transform, Vector2f::new(x, y)),
+        color,
+        tex_coords: Vector2f::new(u, v),
+    };
+    let top_left = vertex(0., 0., left, top);
+    let bottom_left = vertex(0., height, left, bottom);
+    let top_right = vertex(width, 0., right, top);
+    let bottom_right = vertex(width, height, right, bottom);
+    [
+        top_left,
+        bottom_left,
+        top_right,
+        top_right,
+        bottom_left,
+        bottom_right,
+    ]
+}
+
+#[test]
+fn test_quad() {
+    use crate::graphics::csfml_graphics_sys::sfTransform;
+
+    // Translated by (10, 20), scaled by 2
+    let transform = Transform(sfTransform {
+        matrix: [2., 0., 10., 0., 2., 20., 0., 0., 1.],
+    });
+    // Flipped horizontally
+    let vertices = quad(IntRect::new(32, 0, -16, 8), &transform, Color::RED);
+    let bottom_right = vertices[5];
+    assert_eq!(bottom_right.position, Vector2f::new(42., 36.));
+    assert_eq!(bottom_right.tex_coords, Vector2f::new(16., 8.));
+    assert_eq!(bottom_right.color, Color::RED);
+    assert_eq!(vertices[0].position, Vector2f::new(10., 20.));
+    assert_eq!(vertices[0].tex_coords, Vector2f::new(32., 0.));
+}
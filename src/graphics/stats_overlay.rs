@@ -0,0 +1,291 @@
+use crate::graphics::{
+    BasicTransformable, Color, Drawable, PrimitiveType, RenderStates, RenderTarget, Transformable,
+    Vertex,
+};
+use crate::system::{Time, Vector2f};
+use std::collections::VecDeque;
+
+/// Number of frames shown by the graph, and averaged for the frame rate.
+const HISTORY: usize = 120;
+/// Size of a pixel of the built-in font, and horizontal padding of the overlay.
+const PIXEL: f32 = 2.;
+/// Height of a line of text, glyphs being 5 pixels high.
+const LINE_HEIGHT: f32 = 7. * PIXEL;
+/// Height of the graph, showing frame times up to twice the 60 FPS budget.
+const GRAPH_HEIGHT: f32 = 40.;
+/// Frame time at the top of the graph, in milliseconds.
+const GRAPH_MAX_MS: f32 = 2000. / 60.;
+
+/// A small panel showing the frame rate, the frame time and a graph of the last frames.
+///
+/// The overlay is told how long each frame took, and optionally how many draw calls it
+/// made, see [`RenderTarget::frame_stats`], once per frame. It draws its text with a tiny
+/// built-in font, so it needs neither a font file nor a texture.
+///
+/// Bars of the graph are green for frames within the 60 FPS budget, yellow within the 30
+/// FPS budget, and red beyond. The overlay can be hidden and shown at runtime, it keeps
+/// tracking frames while hidden.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{RenderTarget, RenderWindow, StatsOverlay};
+/// use sfml::system::Clock;
+/// use sfml::window::{Event, Key};
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut stats = StatsOverlay::new();
+/// let mut clock = Clock::start();
+/// loop {
+///     while let Some(event) = window.poll_event() {
+///         if let Event::KeyPressed { code: Key::F3, .. } = event {
+///             stats.toggle();
+///         }
+///     }
+///     stats.update(clock.restart());
+///     stats.set_draw_calls(Some(window.frame_stats().draw_calls));
+///     // window.draw(&world);
+///     let view = window.default_view().to_owned();
+///     window.set_view(&view);
+///     window.draw(&stats);
+///     window.display();
+/// }
+/// ```
+///
+/// [`RenderTarget::frame_stats`]: crate::graphics::RenderTarget::frame_stats
+#[derive(Debug, Clone)]
+pub struct StatsOverlay {
+    frame_times: VecDeque<f32>,
+    draw_calls: Option<u32>,
+    visible: bool,
+    transformable: BasicTransformable,
+    vertices: Vec<Vertex>,
+}
+
+impl StatsOverlay {
+    /// Create a new visible overlay, with no frame tracked yet.
+    pub fn new() -> Self {
+        let mut overlay = StatsOverlay {
+            frame_times: VecDeque::with_capacity(HISTORY),
+            draw_calls: None,
+            visible: true,
+            transformable: BasicTransformable::new(),
+            vertices: Vec::new(),
+        };
+        overlay.update_vertices();
+        overlay
+    }
+    /// Track a frame, given how long it took.
+    pub fn update(&mut self, frame_time: Time) {
+        if self.frame_times.len() == HISTORY {
+            let _ = self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time.as_seconds() * 1000.);
+        if self.visible {
+            self.update_vertices();
+        }
+    }
+    /// Set the number of draw calls to show, or `None` to hide it.
+    pub fn set_draw_calls(&mut self, draw_calls: Option<u32>) {
+        self.draw_calls = draw_calls;
+        if self.visible {
+            self.update_vertices();
+        }
+    }
+    /// Get the number of draw calls shown.
+    pub fn draw_calls(&self) -> Option<u32> {
+        self.draw_calls
+    }
+    /// Show or hide the overlay.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        if visible {
+            self.update_vertices();
+        }
+    }
+    /// Tell whether the overlay is shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+    /// Show the overlay if it is hidden, hide it otherwise.
+    pub fn toggle(&mut self) {
+        self.set_visible(!self.visible);
+    }
+    /// Get the average frame rate over the last frames, or 0 if no frame was tracked.
+    pub fn fps(&self) -> f32 {
+        let average = self.average_frame_time_ms();
+        if average > 0. {
+            1000. / average
+        } else {
+            0.
+        }
+    }
+    /// Get the average time of the last frames.
+    pub fn average_frame_time(&self) -> Time {
+        Time::microseconds((self.average_frame_time_ms() * 1000.) as i64)
+    }
+    /// Get the longest time of the last frames.
+    pub fn max_frame_time(&self) -> Time {
+        let max = self.frame_times.iter().fold(0f32, |max, &ms| max.max(ms));
+        Time::microseconds((max * 1000.) as i64)
+    }
+
+    fn average_frame_time_ms(&self) -> f32 {
+        if self.frame_times.is_empty() {
+            return 0.;
+        }
+        self.frame_times.iter().sum::<f32>() / self.frame_times.len() as f32
+    }
+    fn update_vertices(&mut self) {
+        let mut lines = vec![
+            format!("{:.0} FPS", self.fps()),
+            format!("{:.1} MS", self.average_frame_time_ms()),
+        ];
+        if let Some(draw_calls) = self.draw_calls {
+            lines.push(format!("{} DRAWS", draw_calls));
+        }
+        let width = HISTORY as f32 + 2. * PIXEL;
+        let text_height = lines.len() as f32 * LINE_HEIGHT + PIXEL;
+        let height = text_height + GRAPH_HEIGHT + PIXEL;
+        self.vertices.clear();
+        let background = Color::rgba(0, 0, 0, 160);
+        push_quad(
+            &mut self.vertices,
+            Vector2f::new(0., 0.),
+            Vector2f::new(width, height),
+            background,
+        );
+        for (i, line) in lines.iter().enumerate() {
+            let origin = Vector2f::new(PIXEL, PIXEL + i as f32 * LINE_HEIGHT);
+            push_text(&mut self.vertices, line, origin, Color::WHITE);
+        }
+        let times: Vec<f32> = self.frame_times.iter().cloned().collect();
+        let graph = Vector2f::new(PIXEL, text_height + GRAPH_HEIGHT);
+        push_graph(&mut self.vertices, &times, graph);
+    }
+}
+
+impl Default for StatsOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drawable for StatsOverlay {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        if !self.visible {
+            return;
+        }
+        let mut transform = states.transform;
+        transform.combine(&self.transformable.transform());
+        let states = RenderStates {
+            transform,
+            ..states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::Triangles, states);
+    }
+}
+
+delegate_transformable!(impl for StatsOverlay => transformable);
+
+/// Get the 3x5 bitmap of a character of the built-in font, three bits per row from the
+/// top, the leftmost pixel being the highest bit. Characters the font lacks are blank.
+fn glyph(c: char) -> u16 {
+    let rows: [u8; 5] = match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        _ => [0; 5],
+    };
+    rows.iter().fold(0, |bits, &row| bits << 3 | u16::from(row))
+}
+
+fn push_quad(vertices: &mut Vec<Vertex>, position: Vector2f, size: Vector2f, color: Color) {
+    let vertex = |x: f32, y: f32| Vertex::with_pos_color((position.x + x, position.y + y), color);
+    let (top_left, top_right) = (vertex(0., 0.), vertex(size.x, 0.));
+    let (bottom_left, bottom_right) = (vertex(0., size.y), vertex(size.x, size.y));
+    vertices.extend_from_slice(&[
+        top_left,
+        top_right,
+        bottom_left,
+        bottom_left,
+        top_right,
+        bottom_right,
+    ]);
+}
+
+/// Add a quad per lit pixel of a line of text of the built-in font, from its top left.
+fn push_text(vertices: &mut Vec<Vertex>, text: &str, origin: Vector2f, color: Color) {
+    let pixel = Vector2f::new(PIXEL, PIXEL);
+    for (i, c) in text.chars().enumerate() {
+        let bits = glyph(c);
+        let left = origin.x + i as f32 * 4. * PIXEL;
+        for bit in 0..15 {
+            if bits & (1 << (14 - bit)) != 0 {
+                let (x, y) = ((bit % 3) as f32, (bit / 3) as f32);
+                let position = Vector2f::new(left + x * PIXEL, origin.y + y * PIXEL);
+                push_quad(vertices, position, pixel, color);
+            }
+        }
+    }
+}
+
+/// Add a bar per frame time, in milliseconds, standing on `bottom_left`, one unit wide.
+fn push_graph(vertices: &mut Vec<Vertex>, times: &[f32], bottom_left: Vector2f) {
+    for (i, &ms) in times.iter().enumerate() {
+        let color = if ms <= 1000. / 60. {
+            Color::GREEN
+        } else if ms <= 1000. / 30. {
+            Color::YELLOW
+        } else {
+            Color::RED
+        };
+        let height = (ms / GRAPH_MAX_MS).max(0.).min(1.) * GRAPH_HEIGHT;
+        let position = Vector2f::new(bottom_left.x + i as f32, bottom_left.y - height);
+        push_quad(vertices, position, Vector2f::new(1., height), color);
+    }
+}
+
+#[test]
+fn test_stats_drawing() {
+    assert_eq!(glyph('7'), 0b111_001_001_001_001);
+    assert_eq!(glyph('s'), glyph('S'));
+    assert_eq!(glyph('?'), 0);
+
+    let mut vertices = Vec::new();
+    // The 1 has 8 lit pixels, the 7 has 7, the space none
+    push_text(&mut vertices, "1 7", Vector2f::new(10., 20.), Color::WHITE);
+    assert_eq!(vertices.len(), 15 * 6);
+    // The top left pixel of the 7 is 8 pixels to the right, as the space is a glyph too
+    assert_eq!(
+        vertices[8 * 6].position,
+        Vector2f::new(10. + 8. * PIXEL, 20.)
+    );
+
+    vertices.clear();
+    push_graph(&mut vertices, &[10., 25., 100.], Vector2f::new(0., 50.));
+    assert_eq!(vertices.len(), 3 * 6);
+    assert_eq!(vertices[0].color, Color::GREEN);
+    assert_eq!(vertices[6].color, Color::YELLOW);
+    // Frames slower than the top of the graph are cut
+    assert_eq!(vertices[12].position, Vector2f::new(2., 50. - GRAPH_HEIGHT));
+    assert_eq!(vertices[12].color, Color::RED);
+}
@@ -3,19 +3,30 @@ use crate::graphics::{
     Color, Drawable, FloatRect, Font, RenderStates, RenderTarget, TextStyle, Transform,
     Transformable,
 };
-use crate::system::Vector2f;
+use crate::system::{SfBox, Vector2f};
 use std::marker::PhantomData;
 use std::str;
+use std::sync::Arc;
 
 /// Graphical text
 ///
 /// Text is a drawable type that allows to easily
 /// display some text with custom style and color on a render target.
+///
+/// A text either borrows its font, for the lifetime `'s`, or shares the ownership of
+/// it through an [`Arc`], see [`with_font_arc`]. A text sharing its font can be
+/// a `Text<'static>`, and be stored and moved around freely.
+///
+/// [`with_font_arc`]: Text::with_font_arc
 #[derive(Debug)]
 pub struct Text<'s> {
     text: *mut ffi::sfText,
     string_length: usize,
+    /// CSFML 2.5 doesn't export `sfText_getLineSpacing`, so the factor is kept here.
+    line_spacing: f32,
     font: PhantomData<&'s Font>,
+    /// Keeps the font alive when it is shared rather than borrowed.
+    shared_font: Option<Arc<SfBox<Font>>>,
 }
 
 impl<'s> Text<'s> {
@@ -35,6 +46,23 @@ impl<'s> Text<'s> {
         text
     }
 
+    /// Create a new text with a font it shares the ownership of
+    ///
+    /// The font lives as long as the text, or any other owner of it,
+    /// so the text can have any lifetime, including `'static`.
+    ///
+    /// # Arguments
+    /// * string - The string of the text
+    /// * font - The font to display the Text
+    /// * characterSize - The size of the Text
+    pub fn with_font_arc(string: &str, font: Arc<SfBox<Font>>, character_size: u32) -> Text<'s> {
+        let mut text = Text::default();
+        text.set_string(string);
+        text.set_font_arc(font);
+        text.set_character_size(character_size);
+        text
+    }
+
     /// Set the string of a text
     ///
     /// A text's string is empty by default.
@@ -52,13 +80,17 @@ impl<'s> Text<'s> {
 
     /// Get the string of a text
     pub fn string(&self) -> String {
+        self.unicode_string()
+            .iter()
+            .map(|&i| ::std::char::from_u32(i).unwrap())
+            .collect()
+    }
+
+    /// Get the string of a text as it is stored, in UTF-32, without copying it
+    pub(super) fn unicode_string(&self) -> &[u32] {
         unsafe {
             let utf32: *const u32 = ffi::sfText_getUnicodeString(self.text);
-            let slice: &[u32] = ::std::slice::from_raw_parts(utf32, self.string_length);
-            slice
-                .iter()
-                .map(|&i| ::std::char::from_u32(i).unwrap())
-                .collect()
+            ::std::slice::from_raw_parts(utf32, self.string_length)
         }
     }
 
@@ -81,6 +113,20 @@ impl<'s> Text<'s> {
     /// font - New font
     pub fn set_font(&mut self, font: &'s Font) {
         unsafe { ffi::sfText_setFont(self.text, font.raw()) }
+        self.shared_font = None;
+    }
+
+    /// Set the font of a text, sharing the ownership of it
+    ///
+    /// Unlike [`set_font`], the text keeps the font alive for as
+    /// long as it uses it.
+    ///
+    /// [`set_font`]: Text::set_font
+    ///
+    /// font - New font
+    pub fn set_font_arc(&mut self, font: Arc<SfBox<Font>>) {
+        unsafe { ffi::sfText_setFont(self.text, font.raw()) }
+        self.shared_font = Some(font);
     }
 
     /// Set the style of a text
@@ -116,7 +162,7 @@ impl<'s> Text<'s> {
     /// If the text has no font attached, a None is returned.
     /// The returned pointer is const, which means that you can't
     /// modify the font when you retrieve it with this function.
-    pub fn font(&self) -> Option<&'s Font> {
+    pub fn font(&self) -> Option<&Font> {
         unsafe {
             let raw = ffi::sfText_getFont(self.text);
 
@@ -168,6 +214,50 @@ impl<'s> Text<'s> {
         unsafe { ffi::sfText_getOutlineThickness(self.text) }
     }
 
+    /// Set the letter spacing factor
+    ///
+    /// The default spacing between letters is defined by the font.
+    /// This factor doesn't directly apply to the existing
+    /// spacing between each character, it rather adds a fixed
+    /// space between them which is calculated from the font
+    /// metrics and the character size.
+    /// Note that factors below 1 (including negative numbers) bring
+    /// characters closer to each other.
+    /// By default the letter spacing factor is 1.
+    ///
+    /// # Arguments
+    /// * spacing_factor - New letter spacing factor
+    pub fn set_letter_spacing(&mut self, spacing_factor: f32) {
+        unsafe { ffi::sfText_setLetterSpacing(self.text, spacing_factor) }
+    }
+
+    /// Set the line spacing factor
+    ///
+    /// The default spacing between lines is defined by the font.
+    /// This method enables you to set a factor for the spacing
+    /// between lines. By default the line spacing factor is 1.
+    ///
+    /// # Arguments
+    /// * spacing_factor - New line spacing factor
+    pub fn set_line_spacing(&mut self, spacing_factor: f32) {
+        self.line_spacing = spacing_factor;
+        unsafe { ffi::sfText_setLineSpacing(self.text, spacing_factor) }
+    }
+
+    /// Get the size of the letter spacing factor
+    ///
+    /// Return the size of the letter spacing factor
+    pub fn letter_spacing(&self) -> f32 {
+        unsafe { ffi::sfText_getLetterSpacing(self.text) }
+    }
+
+    /// Get the size of the line spacing factor
+    ///
+    /// Return the size of the line spacing factor
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+
     /// Return the position of the index-th character in a text
     ///
     /// This function computes the visual position of a character
@@ -222,7 +312,9 @@ impl<'s> Default for Text<'s> {
         Self {
             text,
             string_length: 0,
+            line_spacing: 1.,
             font: PhantomData,
+            shared_font: None,
         }
     }
 }
@@ -235,20 +327,18 @@ impl<'s> Clone for Text<'s> {
             panic!("Not enough memory to clone Text")
         } else {
             Text {
-                text: self.text,
+                text: sp,
                 string_length: self.string_length,
+                line_spacing: self.line_spacing,
                 font: PhantomData,
+                shared_font: self.shared_font.clone(),
             }
         }
     }
 }
 
 impl<'s> Drawable for Text<'s> {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_text(self, states)
     }
 }
@@ -0,0 +1,157 @@
+use crate::graphics::{Font, TextStyle};
+use crate::system::Vector2f;
+
+/// Measures and wraps text before it is drawn.
+///
+/// A [`Text`] can only be measured once it is built, and never wraps its lines by itself.
+/// A layout computes the size a string takes when drawn with a given font, character size
+/// and style, and breaks strings into lines fitting a given width, so that dialog boxes,
+/// tooltips and the like can be sized and filled correctly.
+///
+/// The measures follow the way SFML lays out a [`Text`]: the glyphs advance one after the
+/// other, adjusted by the kerning of the font, and the lines are spaced by the line spacing
+/// of the font.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{Font, Text, TextLayout};
+///
+/// let font = Font::from_file("sansation.ttf").unwrap();
+/// let layout = TextLayout::new(&font, 20);
+/// let dialog = layout.wrap("It's dangerous to go alone! Take this.", 200.);
+/// let size = layout.measure(&dialog);
+/// // Size the dialog box after `size`
+/// let text = Text::new(&dialog, &font, 20);
+/// ```
+///
+/// [`Text`]: crate::graphics::Text
+#[derive(Debug, Clone, Copy)]
+pub struct TextLayout<'f> {
+    font: &'f Font,
+    character_size: u32,
+    style: TextStyle,
+}
+
+impl<'f> TextLayout<'f> {
+    /// Create a new layout measuring regular characters of `font`.
+    ///
+    /// # Arguments
+    /// * font - The font the text is drawn with
+    /// * character_size - The size of the characters, in pixels
+    pub fn new(font: &'f Font, character_size: u32) -> Self {
+        TextLayout {
+            font,
+            character_size,
+            style: TextStyle::REGULAR,
+        }
+    }
+    /// Set the style of the characters to measure.
+    ///
+    /// Only the bold style changes the size of a text.
+    pub fn set_style(&mut self, style: TextStyle) {
+        self.style = style;
+    }
+    /// Get the style of the characters to measure.
+    pub fn style(&self) -> TextStyle {
+        self.style
+    }
+    /// Set the size of the characters to measure, in pixels.
+    pub fn set_character_size(&mut self, character_size: u32) {
+        self.character_size = character_size;
+    }
+    /// Get the size of the characters to measure, in pixels.
+    pub fn character_size(&self) -> u32 {
+        self.character_size
+    }
+    /// Get the width of a single line of text, in pixels.
+    pub fn line_width(&self, line: &str) -> f32 {
+        let bold = self.style.contains(TextStyle::BOLD);
+        let mut width = 0.;
+        let mut previous = None;
+        for c in line.chars() {
+            let c = c as u32;
+            if let Some(previous) = previous {
+                width += self.font.kerning(previous, c, self.character_size);
+            }
+            width += self.font.glyph(c, self.character_size, bold, 0.).advance;
+            previous = Some(c);
+        }
+        width
+    }
+    /// Get the size of a text, which may span several lines, in pixels.
+    ///
+    /// The width is the one of the widest line, the height is the line spacing of the font
+    /// times the number of lines.
+    pub fn measure(&self, string: &str) -> Vector2f {
+        let mut size = Vector2f::new(0., 0.);
+        let line_spacing = self.font.line_spacing(self.character_size);
+        for line in string.split('\n') {
+            size.x = size.x.max(self.line_width(line));
+            size.y += line_spacing;
+        }
+        size
+    }
+    /// Break a string into lines no wider than `max_width` pixels.
+    ///
+    /// Lines are broken between words, at spaces, which are dropped. A word too wide to fit
+    /// on a line of its own is broken between characters. The line breaks already in the
+    /// string are kept.
+    pub fn wrap(&self, string: &str, max_width: f32) -> String {
+        wrap_with(string, max_width, |line| self.line_width(line))
+    }
+}
+
+/// Break `string` into lines no wider than `max_width`, as measured by `width`.
+fn wrap_with<F>(string: &str, max_width: f32, width: F) -> String
+where
+    F: Fn(&str) -> f32,
+{
+    let mut wrapped = String::with_capacity(string.len());
+    for (i, paragraph) in string.split('\n').enumerate() {
+        if i > 0 {
+            wrapped.push('\n');
+        }
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            if !line.is_empty() {
+                let candidate = format!("{} {}", line, word);
+                if width(&candidate) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                wrapped.push_str(&line);
+                wrapped.push('\n');
+                line.clear();
+            }
+            // `word` starts a new line, break it if it doesn't fit
+            for c in word.chars() {
+                line.push(c);
+                if line.chars().count() > 1 && width(&line) > max_width {
+                    line.truncate(line.len() - c.len_utf8());
+                    wrapped.push_str(&line);
+                    wrapped.push('\n');
+                    line.clear();
+                    line.push(c);
+                }
+            }
+        }
+        wrapped.push_str(&line);
+    }
+    wrapped
+}
+
+#[test]
+fn test_wrap() {
+    // Monospace, 10 pixels per character
+    let width = |line: &str| line.chars().count() as f32 * 10.;
+    assert_eq!(
+        wrap_with("the quick brown fox", 100., width),
+        "the quick\nbrown fox"
+    );
+    assert_eq!(
+        wrap_with("a\nsupercalifragilistic b", 80., width),
+        "a\nsupercal\nifragili\nstic b"
+    );
+    assert_eq!(wrap_with("", 80., width), "");
+}
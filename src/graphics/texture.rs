@@ -194,6 +194,78 @@ impl Texture {
         SfBox::new(tex as *mut Self)
     }
 
+    /// Create a new texture from an image of the `image` crate
+    ///
+    /// The pixels are uploaded straight to the texture, without going through an [`Image`].
+    ///
+    /// # Arguments
+    /// * image - Image to upload to the texture
+    ///
+    /// Returns `None` on failure.
+    ///
+    /// [`Image`]: crate::graphics::Image
+    #[cfg(feature = "image")]
+    pub fn from_dynamic_image(image: &image::DynamicImage) -> Option<SfBox<Texture>> {
+        let converted;
+        let rgba = match image.as_rgba8() {
+            Some(rgba) => rgba,
+            None => {
+                converted = image.to_rgba8();
+                &converted
+            }
+        };
+        let (width, height) = rgba.dimensions();
+        let mut texture = Texture::new(width, height)?;
+        texture.update_from_pixels(rgba.as_raw(), width, height, 0, 0);
+        Some(texture)
+    }
+
+    /// Create a new texture by rasterizing an SVG file
+    ///
+    /// Vector art is drawn at the size it is needed at, and stays sharp whatever the
+    /// resolution of the screen. The drawing is scaled to fill the whole texture: give a
+    /// size with the aspect ratio of the drawing to avoid stretching it.
+    ///
+    /// # Arguments
+    /// * filename - Path of the SVG file to load
+    /// * target_size - Size of the texture, in pixels
+    ///
+    /// Returns `None` if the file can't be read or parsed, or on failure.
+    #[cfg(feature = "resvg")]
+    pub fn from_svg_file(filename: &str, target_size: Vector2u) -> Option<SfBox<Texture>> {
+        let data = std::fs::read(filename).ok()?;
+        Self::from_svg_memory(&data, target_size)
+    }
+
+    /// Create a new texture by rasterizing an SVG document held in memory
+    ///
+    /// See [`from_svg_file`].
+    ///
+    /// # Arguments
+    /// * data - The SVG document, as text or gzip compressed
+    /// * target_size - Size of the texture, in pixels
+    ///
+    /// Returns `None` if the document can't be parsed, or on failure.
+    ///
+    /// [`from_svg_file`]: Texture::from_svg_file
+    #[cfg(feature = "resvg")]
+    pub fn from_svg_memory(data: &[u8], target_size: Vector2u) -> Option<SfBox<Texture>> {
+        use resvg::{tiny_skia, usvg};
+
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default()).ok()?;
+        let mut pixmap = tiny_skia::Pixmap::new(target_size.x, target_size.y)?;
+        let size = tree.size();
+        let transform = tiny_skia::Transform::from_scale(
+            target_size.x as f32 / size.width(),
+            target_size.y as f32 / size.height(),
+        );
+        resvg::render(&tree, transform, &mut pixmap.as_mut());
+        let pixels = unpremultiply(pixmap.data());
+        let mut texture = Texture::new(target_size.x, target_size.y)?;
+        texture.update_from_pixels(&pixels, target_size.x, target_size.y, 0, 0);
+        Some(texture)
+    }
+
     /// Update a texture from the contents of a window
     ///
     /// # Arguments
@@ -226,22 +298,66 @@ impl Texture {
 
     /// Update a part of the texture from an array of pixels.
     ///
-    /// The size of the pixel array must match the width and height arguments,
-    /// and it must contain 32-bits RGBA pixels.
+    /// The pixel array must contain `width * height` 32-bits RGBA pixels, which are copied
+    /// to the area of the texture starting at (`x`, `y`). This allows updating only the
+    /// parts of a texture that changed, rather than the whole texture.
+    ///
+    /// # Arguments
+    /// * pixels - Array of pixels to copy to the texture
+    /// * width - Width of the pixel region contained in pixels
+    /// * height - Height of the pixel region contained in pixels
+    /// * x - X offset in the texture where to copy the source pixels
+    /// * y - Y offset in the texture where to copy the source pixels
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel array is shorter than `width * height * 4` bytes, or if the area
+    /// to update doesn't fit in the texture.
+    pub fn update_from_pixels(&mut self, pixels: &[u8], width: u32, height: u32, x: u32, y: u32) {
+        assert!(
+            pixels.len() as u64 >= u64::from(width) * u64::from(height) * 4,
+            "Not enough pixels to update a {}x{} area",
+            width,
+            height
+        );
+        self.assert_fits(width, height, x, y);
+        unsafe {
+            ffi::sfTexture_updateFromPixels(self.raw_mut(), pixels.as_ptr(), width, height, x, y)
+        }
+    }
 
-    /// No additional check is performed on the size of the pixel array or the bounds of the
-    /// area to update, passing invalid arguments will lead to an _undefined behavior_.
-    ///
-    /// This function does nothing if pixels is null or if the texture was not previously created.
-    pub unsafe fn update_from_pixels(
-        &mut self,
-        pixels: &[u8],
-        width: u32,
-        height: u32,
-        x: u32,
-        y: u32,
-    ) {
-        ffi::sfTexture_updateFromPixels(self.raw_mut(), pixels.as_ptr(), width, height, x, y)
+    /// Update a part of this texture from another texture
+    ///
+    /// The whole source texture is copied to the area of this texture
+    /// starting at (`x`, `y`).
+    ///
+    /// # Arguments
+    /// * texture - Source texture to copy to this texture
+    /// * x - X offset in this texture where to copy the source texture
+    /// * y - Y offset in this texture where to copy the source texture
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source texture doesn't fit in this texture at the given offset.
+    pub fn update_from_texture(&mut self, texture: &Texture, x: u32, y: u32) {
+        let size = texture.size();
+        self.assert_fits(size.x, size.y, x, y);
+        unsafe { ffi::sfTexture_updateFromTexture(self.raw_mut(), texture.raw(), x, y) }
+    }
+
+    fn assert_fits(&self, width: u32, height: u32, x: u32, y: u32) {
+        let size = self.size();
+        assert!(
+            u64::from(x) + u64::from(width) <= u64::from(size.x)
+                && u64::from(y) + u64::from(height) <= u64::from(size.y),
+            "A {}x{} area at ({}, {}) doesn't fit in a {}x{} texture",
+            width,
+            height,
+            x,
+            y,
+            size.x,
+            size.y
+        );
     }
 
     /// Enable or disable the smooth filter on a texture
@@ -294,7 +410,11 @@ impl Texture {
     /// order for the setting to take effect.
     ///
     /// This option is only useful in conjunction with an sRGB capable framebuffer.
-    /// This can be requested during window creation.
+    /// This can be requested during window creation, or render texture creation with
+    /// [`RenderTexture::with_settings`], by setting [`ContextSettings::srgb_capable`].
+    ///
+    /// [`RenderTexture::with_settings`]: crate::graphics::RenderTexture::with_settings
+    /// [`ContextSettings::srgb_capable`]: crate::window::ContextSettings::srgb_capable
     pub fn set_srgb(&mut self, srgb: bool) {
         unsafe { ffi::sfTexture_setSrgb(self.raw_mut(), SfBoolExt::from_bool(srgb)) }
     }
@@ -319,6 +439,17 @@ impl Texture {
     pub fn generate_mipmap(&mut self) -> bool {
         unsafe { ffi::sfTexture_generateMipmap(self.raw_mut()).to_bool() }
     }
+
+    /// Swap the contents of this texture with those of another.
+    ///
+    /// This is cheap, no pixel is copied: it allows double-buffering a texture that is
+    /// streamed, filling one while the other one is drawn.
+    ///
+    /// # Arguments
+    /// * right - Instance to swap with
+    pub fn swap(&mut self, right: &mut Texture) {
+        unsafe { ffi::sfTexture_swap(self.raw_mut(), right.raw_mut()) }
+    }
 }
 
 impl ToOwned for Texture {
@@ -336,3 +467,30 @@ impl Dispose for Texture {
         ffi::sfTexture_destroy(ptr as _)
     }
 }
+
+/// Convert RGBA pixels with premultiplied alpha, as rendered by resvg, to straight alpha.
+#[cfg(feature = "resvg")]
+fn unpremultiply(pixels: &[u8]) -> Vec<u8> {
+    let mut straight = Vec::with_capacity(pixels.len());
+    for p in pixels.chunks(4) {
+        let alpha = u16::from(p[3]);
+        if alpha == 0 {
+            straight.extend_from_slice(&[0, 0, 0, 0]);
+            continue;
+        }
+        for &c in &p[..3] {
+            straight.push(((u16::from(c) * 255 + alpha / 2) / alpha).min(255) as u8);
+        }
+        straight.push(p[3]);
+    }
+    straight
+}
+
+#[cfg(feature = "resvg")]
+#[test]
+fn test_unpremultiply() {
+    assert_eq!(
+        unpremultiply(&[64, 32, 0, 128, 10, 10, 10, 0, 255, 0, 0, 255]),
+        [128, 64, 0, 128, 0, 0, 0, 0, 255, 0, 0, 255]
+    );
+}
@@ -0,0 +1,208 @@
+use crate::graphics::{Color, Image, IntRect, Sprite, Texture};
+use crate::system::{SfBox, Vector2u};
+use std::collections::HashMap;
+
+/// Many images packed into a single texture, each one available by name.
+///
+/// Switching textures is one of the most expensive things a draw can do. Drawing sprites
+/// that all use the same texture avoids it, and lets a [`SpriteBatch`] draw them with a
+/// single draw call. An atlas packs images together into one texture, and remembers where
+/// each one ended up, so that sprites can be made out of them by name.
+///
+/// The images are packed on shelves: sorted by height, and laid out from left to right in
+/// rows as high as their first image. The atlas is as narrow as a power of two allows,
+/// without going over the maximum size of a texture. A padding of transparent pixels can
+/// be kept around the images, so that smoothing doesn't blend them with their neighbors.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{RenderTarget, RenderWindow, TextureAtlas};
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let atlas = TextureAtlas::from_files(&["player.png", "enemy.png", "coin.png"], 1).unwrap();
+/// let player = atlas.sprite("player.png").unwrap();
+/// let coin = atlas.sprite("coin.png").unwrap();
+/// window.draw(&player);
+/// window.draw(&coin);
+/// ```
+///
+/// [`SpriteBatch`]: crate::graphics::SpriteBatch
+#[derive(Debug)]
+pub struct TextureAtlas {
+    texture: SfBox<Texture>,
+    regions: HashMap<String, IntRect>,
+}
+
+impl TextureAtlas {
+    /// Pack images into a new atlas
+    ///
+    /// An image given twice under the same name is only packed once, the last one is kept.
+    ///
+    /// # Arguments
+    /// * images - The images to pack, along with the names to get them by
+    /// * padding - Number of transparent pixels to keep between the images
+    ///
+    /// Returns `None` if there are no images, if they don't fit in a texture, or on failure.
+    pub fn from_images<'a, I>(images: I, padding: u32) -> Option<TextureAtlas>
+    where
+        I: IntoIterator<Item = (&'a str, &'a Image)>,
+    {
+        let mut unique: Vec<(&str, &Image)> = Vec::new();
+        for (name, image) in images {
+            match unique.iter_mut().find(|entry| entry.0 == name) {
+                Some(entry) => entry.1 = image,
+                None => unique.push((name, image)),
+            }
+        }
+        let sizes: Vec<Vector2u> = unique.iter().map(|entry| entry.1.size()).collect();
+        let (size, positions) = pack(&sizes, padding, Texture::maximum_size())?;
+        let mut atlas = Image::from_color(size.x, size.y, Color::TRANSPARENT)?;
+        let mut regions = HashMap::with_capacity(unique.len());
+        for ((name, image), (size, position)) in unique.iter().zip(sizes.iter().zip(positions)) {
+            let rect = IntRect::new(0, 0, size.x as i32, size.y as i32);
+            atlas.copy_image(image, position.x, position.y, &rect, false);
+            let region = IntRect::new(
+                position.x as i32,
+                position.y as i32,
+                size.x as i32,
+                size.y as i32,
+            );
+            let _ = regions.insert((*name).to_owned(), region);
+        }
+        let texture = Texture::from_image(&atlas)?;
+        Some(TextureAtlas { texture, regions })
+    }
+
+    /// Load image files and pack them into a new atlas
+    ///
+    /// Each image is named after the path it was loaded from.
+    ///
+    /// # Arguments
+    /// * filenames - Paths of the image files to load
+    /// * padding - Number of transparent pixels to keep between the images
+    ///
+    /// Returns `None` if a file fails to load, and in the cases [`from_images`] does.
+    ///
+    /// [`from_images`]: TextureAtlas::from_images
+    pub fn from_files(filenames: &[&str], padding: u32) -> Option<TextureAtlas> {
+        let images = filenames
+            .iter()
+            .map(|filename| Image::from_file(filename))
+            .collect::<Option<Vec<_>>>()?;
+        Self::from_images(filenames.iter().cloned().zip(images.iter()), padding)
+    }
+
+    /// Get the texture holding all the images
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Get the texture holding all the images, to change its settings
+    pub fn texture_mut(&mut self) -> &mut Texture {
+        &mut self.texture
+    }
+
+    /// Get the area of the texture an image was packed in
+    ///
+    /// Returns `None` if the atlas has no image with this name.
+    pub fn region(&self, name: &str) -> Option<IntRect> {
+        self.regions.get(name).cloned()
+    }
+
+    /// Create a sprite displaying an image of the atlas
+    ///
+    /// Returns `None` if the atlas has no image with this name.
+    pub fn sprite(&self, name: &str) -> Option<Sprite<'_>> {
+        let region = self.region(name)?;
+        let mut sprite = Sprite::with_texture(&self.texture);
+        sprite.set_texture_rect(&region);
+        Some(sprite)
+    }
+
+    /// Get the names of the images of the atlas, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.regions.keys().map(|name| name.as_str())
+    }
+
+    /// Return the number of images in the atlas
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Tell whether the atlas has no images
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+/// Pack rectangles of `sizes` on shelves, keeping `padding` pixels between them.
+///
+/// Returns the size of the packed area, the narrowest power of two up to `max_size` that
+/// fits all the rectangles, and their positions, in the order of `sizes`.
+fn pack(sizes: &[Vector2u], padding: u32, max_size: u32) -> Option<(Vector2u, Vec<Vector2u>)> {
+    if sizes.is_empty() {
+        return None;
+    }
+    let area: u64 = sizes
+        .iter()
+        .map(|size| u64::from(size.x + padding) * u64::from(size.y + padding))
+        .sum();
+    let widest = sizes.iter().map(|size| size.x).max().unwrap_or(0);
+    let mut width = ((area as f64).sqrt() as u32)
+        .max(widest)
+        .next_power_of_two();
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].y.cmp(&sizes[a].y));
+    loop {
+        let width_limit = width.min(max_size);
+        if widest > width_limit {
+            return None;
+        }
+        let mut positions = vec![Vector2u::new(0, 0); sizes.len()];
+        let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+        let mut used_width = 0;
+        for &i in &order {
+            let size = sizes[i];
+            if x > 0 && x + size.x > width_limit {
+                x = 0;
+                y += shelf_height + padding;
+                shelf_height = 0;
+            }
+            positions[i] = Vector2u::new(x, y);
+            used_width = used_width.max(x + size.x);
+            shelf_height = shelf_height.max(size.y);
+            x += size.x + padding;
+        }
+        let height = y + shelf_height;
+        if height <= max_size {
+            return Some((Vector2u::new(used_width.max(1), height.max(1)), positions));
+        }
+        if width >= max_size {
+            return None;
+        }
+        width *= 2;
+    }
+}
+
+#[test]
+fn test_pack() {
+    let sizes = [
+        Vector2u::new(30, 10),
+        Vector2u::new(20, 20),
+        Vector2u::new(40, 15),
+    ];
+    let (size, positions) = pack(&sizes, 2, 1024).unwrap();
+    assert_eq!(size, Vector2u::new(62, 32));
+    assert_eq!(
+        positions,
+        [
+            Vector2u::new(0, 22),
+            Vector2u::new(0, 0),
+            Vector2u::new(22, 0)
+        ]
+    );
+    // Doesn't fit on a single shelf, nor in the maximum size
+    assert!(pack(&[Vector2u::new(600, 600); 4], 0, 1024).is_none());
+    assert!(pack(&[], 0, 1024).is_none());
+}
@@ -0,0 +1,295 @@
+use crate::graphics::{
+    Drawable, FloatRect, PrimitiveType, RenderStates, RenderTarget, Texture, Vertex,
+};
+use crate::system::{Vector2f, Vector2u};
+use std::cell::RefCell;
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+struct Chunk {
+    vertices: Vec<Vertex>,
+    dirty: bool,
+}
+
+/// A grid of tiles taken from a tileset texture, drawn a few chunks at a time.
+///
+/// Drawing one sprite per tile issues one draw call per tile, which is far too slow for
+/// maps of thousands of tiles. A tile map groups its tiles into square chunks, builds the
+/// vertices of every chunk once, and only draws the chunks the view of the target shows.
+/// Changing a tile only rebuilds the vertices of its chunk, the next time it is drawn.
+///
+/// Tiles are numbered from the top left corner of the tileset, row by row: with a tileset
+/// 8 tiles wide, tile 9 is the second of the second row. Cells holding [`TileMap::EMPTY`]
+/// are left blank.
+///
+/// The map is drawn with its top left corner at the origin of the render states'
+/// transform.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{RenderTarget, RenderWindow, Texture, TileMap};
+/// use sfml::system::Vector2u;
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let tileset = Texture::from_file("tileset.png").unwrap();
+/// let mut map = TileMap::new(&tileset, Vector2u::new(16, 16), Vector2u::new(1000, 1000));
+/// map.fill(0);
+/// for x in 0..1000 {
+///     map.set_tile(x, 500, 3);
+/// }
+/// window.draw(&map);
+/// ```
+#[derive(Debug)]
+pub struct TileMap<'t> {
+    tileset: &'t Texture,
+    tile_size: Vector2u,
+    size: Vector2u,
+    tiles: Vec<u32>,
+    chunk_size: u32,
+    chunks: RefCell<Vec<Chunk>>,
+}
+
+impl<'t> TileMap<'t> {
+    /// Tile number of the cells left blank.
+    pub const EMPTY: u32 = u32::max_value();
+
+    /// Create a new map with all its cells empty
+    ///
+    /// # Arguments
+    /// * tileset - The texture holding the tiles
+    /// * tile_size - The size of a tile, in pixels, in the tileset and on the map
+    /// * size - The number of tiles of the map, horizontally and vertically
+    pub fn new(tileset: &'t Texture, tile_size: Vector2u, size: Vector2u) -> Self {
+        let mut map = TileMap {
+            tileset,
+            tile_size,
+            size,
+            tiles: vec![Self::EMPTY; size.x as usize * size.y as usize],
+            chunk_size: 32,
+            chunks: RefCell::new(Vec::new()),
+        };
+        map.reset_chunks();
+        map
+    }
+
+    /// Set the tile of a cell
+    ///
+    /// # Panics
+    /// Panics if the cell is out of the map.
+    pub fn set_tile(&mut self, x: u32, y: u32, tile: u32) {
+        let index = self.index(x, y);
+        if self.tiles[index] != tile {
+            self.tiles[index] = tile;
+            let chunk = self.chunk_index(x, y);
+            self.chunks.get_mut()[chunk].dirty = true;
+        }
+    }
+
+    /// Get the tile of a cell
+    ///
+    /// # Panics
+    /// Panics if the cell is out of the map.
+    pub fn tile(&self, x: u32, y: u32) -> u32 {
+        self.tiles[self.index(x, y)]
+    }
+
+    /// Set the tiles of all the cells, row by row
+    ///
+    /// # Panics
+    /// Panics if there isn't exactly one tile per cell.
+    pub fn set_tiles(&mut self, tiles: &[u32]) {
+        assert_eq!(tiles.len(), self.tiles.len(), "One tile per cell is needed");
+        self.tiles.copy_from_slice(tiles);
+        self.reset_chunks();
+    }
+
+    /// Get the tiles of all the cells, row by row
+    pub fn tiles(&self) -> &[u32] {
+        &self.tiles
+    }
+
+    /// Set the tile of all the cells
+    pub fn fill(&mut self, tile: u32) {
+        for cell in &mut self.tiles {
+            *cell = tile;
+        }
+        self.reset_chunks();
+    }
+
+    /// Get the number of tiles of the map, horizontally and vertically
+    pub fn size(&self) -> Vector2u {
+        self.size
+    }
+
+    /// Get the size of a tile, in pixels
+    pub fn tile_size(&self) -> Vector2u {
+        self.tile_size
+    }
+
+    /// Get the texture holding the tiles
+    pub fn tileset(&self) -> &'t Texture {
+        self.tileset
+    }
+
+    /// Set the number of tiles on a side of a chunk, 32 by default
+    ///
+    /// Bigger chunks mean fewer draw calls, but more tiles drawn outside of the view, and
+    /// slower rebuilds when a tile changes.
+    pub fn set_chunk_size(&mut self, chunk_size: u32) {
+        self.chunk_size = chunk_size.max(1);
+        self.reset_chunks();
+    }
+
+    /// Get the number of tiles on a side of a chunk
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(
+            x < self.size.x && y < self.size.y,
+            "Cell ({}, {}) out of the map",
+            x,
+            y
+        );
+        y as usize * self.size.x as usize + x as usize
+    }
+    fn chunk_counts(&self) -> Vector2u {
+        let count = |tiles: u32| (tiles + self.chunk_size - 1) / self.chunk_size;
+        Vector2u::new(count(self.size.x), count(self.size.y))
+    }
+    fn chunk_index(&self, x: u32, y: u32) -> usize {
+        let counts = self.chunk_counts();
+        (y / self.chunk_size * counts.x + x / self.chunk_size) as usize
+    }
+    fn reset_chunks(&mut self) {
+        let counts = self.chunk_counts();
+        let chunk = Chunk {
+            vertices: Vec::new(),
+            dirty: true,
+        };
+        *self.chunks.get_mut() = vec![chunk; (counts.x * counts.y) as usize];
+    }
+    fn build_chunk(&self, chunk_x: u32, chunk_y: u32, vertices: &mut Vec<Vertex>) {
+        vertices.clear();
+        let columns = (self.tileset.size().x / self.tile_size.x.max(1)).max(1);
+        let xs = chunk_x * self.chunk_size..((chunk_x + 1) * self.chunk_size).min(self.size.x);
+        let ys = chunk_y * self.chunk_size..((chunk_y + 1) * self.chunk_size).min(self.size.y);
+        for y in ys {
+            for x in xs.clone() {
+                let tile = self.tiles[self.index(x, y)];
+                if tile != Self::EMPTY {
+                    vertices.extend_from_slice(&tile_quad(x, y, tile, self.tile_size, columns));
+                }
+            }
+        }
+    }
+}
+
+impl<'t> Drawable for TileMap<'t> {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        // Area of the map seen by the view, in the coordinates of the map
+        let view = target.view();
+        let (center, size) = (view.center(), view.size());
+        let half = if view.rotation() % 360. == 0. {
+            Vector2f::new(size.x.abs() / 2., size.y.abs() / 2.)
+        } else {
+            let radius = (size.x * size.x + size.y * size.y).sqrt() / 2.;
+            Vector2f::new(radius, radius)
+        };
+        let visible = FloatRect::new(
+            center.x - half.x,
+            center.y - half.y,
+            half.x * 2.,
+            half.y * 2.,
+        );
+        let visible = states.transform.inverse().transform_rect(&visible);
+        let counts = self.chunk_counts();
+        let chunk_pixels = Vector2f::new(
+            (self.chunk_size * self.tile_size.x) as f32,
+            (self.chunk_size * self.tile_size.y) as f32,
+        );
+        let (xs, ys) = visible_chunks(&visible, chunk_pixels, counts);
+        let states = RenderStates {
+            texture: Some(self.tileset),
+            ..states
+        };
+        let mut chunks = self.chunks.borrow_mut();
+        for y in ys {
+            for x in xs.clone() {
+                let chunk = &mut chunks[(y * counts.x + x) as usize];
+                if chunk.dirty {
+                    self.build_chunk(x, y, &mut chunk.vertices);
+                    chunk.dirty = false;
+                }
+                if !chunk.vertices.is_empty() {
+                    target.draw_primitives(&chunk.vertices, PrimitiveType::Triangles, states);
+                }
+            }
+        }
+    }
+}
+
+/// Build the two triangles of the tile of the cell (`x`, `y`), out of a tileset `columns`
+/// tiles wide.
+fn tile_quad(x: u32, y: u32, tile: u32, tile_size: Vector2u, columns: u32) -> [Vertex; 6] {
+    let (width, height) = (tile_size.x as f32, tile_size.y as f32);
+    let (left, top) = (x as f32 * width, y as f32 * height);
+    let u = (tile % columns) as f32 * width;
+    let v = (tile / columns) as f32 * height;
+    let vertex = |dx: f32, dy: f32| {
+        Vertex::with_pos_coords(
+            (left + dx * width, top + dy * height),
+            Vector2f::new(u + dx * width, v + dy * height),
+        )
+    };
+    [
+        vertex(0., 0.),
+        vertex(0., 1.),
+        vertex(1., 0.),
+        vertex(1., 0.),
+        vertex(0., 1.),
+        vertex(1., 1.),
+    ]
+}
+
+/// Get the ranges of chunks, of `chunk_size` pixels, overlapping the `visible` area.
+fn visible_chunks(
+    visible: &FloatRect,
+    chunk_size: Vector2f,
+    counts: Vector2u,
+) -> (Range<u32>, Range<u32>) {
+    let range = |start: f32, len: f32, chunk: f32, count: u32| {
+        if chunk <= 0. {
+            return 0..0;
+        }
+        let first = (start / chunk).floor().max(0.);
+        let end = ((start + len) / chunk).ceil().max(0.);
+        (first as u32).min(count)..(end as u32).min(count)
+    };
+    (
+        range(visible.left, visible.width, chunk_size.x, counts.x),
+        range(visible.top, visible.height, chunk_size.y, counts.y),
+    )
+}
+
+#[test]
+fn test_tile_quad() {
+    // Tile 9 of a tileset 8 tiles wide, in cell (2, 3)
+    let vertices = tile_quad(2, 3, 9, Vector2u::new(16, 16), 8);
+    assert_eq!(vertices[0].position, Vector2f::new(32., 48.));
+    assert_eq!(vertices[0].tex_coords, Vector2f::new(16., 16.));
+    assert_eq!(vertices[5].position, Vector2f::new(48., 64.));
+    assert_eq!(vertices[5].tex_coords, Vector2f::new(32., 32.));
+}
+
+#[test]
+fn test_visible_chunks() {
+    let chunk = Vector2f::new(100., 100.);
+    let counts = Vector2u::new(10, 5);
+    let visible = FloatRect::new(150., -50., 200., 120.);
+    assert_eq!(visible_chunks(&visible, chunk, counts), (1..4, 0..1));
+    let outside = FloatRect::new(2000., 0., 100., 100.);
+    assert_eq!(visible_chunks(&outside, chunk, counts).0.len(), 0);
+}
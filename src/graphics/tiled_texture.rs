@@ -0,0 +1,145 @@
+use crate::graphics::{
+    Drawable, Image, IntRect, RenderStates, RenderTarget, Sprite, Texture, Transformable,
+};
+use crate::system::{SfBox, Vector2u};
+
+#[derive(Debug)]
+struct Tile {
+    texture: SfBox<Texture>,
+    left: u32,
+    top: u32,
+}
+
+/// Texture split into several textures, to hold images larger than the graphics card allows.
+///
+/// Graphics cards limit the size of textures, see [`Texture::maximum_size`]: 8192 or 16384
+/// pixels wide and high on most desktop cards, and as few as 2048 on older ones. Creating
+/// a bigger texture simply fails. A tiled texture cuts an image into tiles that each fit in
+/// a texture, and draws them side by side, so that a huge background can be drawn as if it
+/// was held by a single texture.
+///
+/// A tiled texture is drawn with its top left corner at the origin of the render states'
+/// transform. With smoothing enabled, the pixels at the edges of the tiles are blended with
+/// the border instead of the neighbor tile, which may show thin seams when the texture is
+/// scaled.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{RenderStates, RenderTarget, RenderWindow, TiledTexture, Transform};
+///
+/// # let mut window: RenderWindow = unimplemented!();
+/// let background = TiledTexture::from_file("world-map.png").unwrap();
+/// let mut transform = Transform::IDENTITY;
+/// transform.translate(-1000., -500.);
+/// let mut states = RenderStates::default();
+/// states.transform = transform;
+/// window.draw_with_renderstates(&background, states);
+/// ```
+///
+/// [`Texture::maximum_size`]: crate::graphics::Texture::maximum_size
+#[derive(Debug)]
+pub struct TiledTexture {
+    tiles: Vec<Tile>,
+    size: Vector2u,
+}
+
+impl TiledTexture {
+    /// Create a tiled texture from an image, with tiles as large as the graphics card allows
+    ///
+    /// Returns `None` on failure.
+    pub fn from_image(image: &Image) -> Option<TiledTexture> {
+        Self::from_image_with_tile_size(image, Texture::maximum_size())
+    }
+
+    /// Create a tiled texture from an image, with tiles of at most `tile_size` pixels wide
+    /// and high
+    ///
+    /// Smaller tiles waste less memory on cards that round textures up to powers of two.
+    /// The tile size is capped to the maximum size of a texture.
+    ///
+    /// Returns `None` on failure.
+    pub fn from_image_with_tile_size(image: &Image, tile_size: u32) -> Option<TiledTexture> {
+        let tile_size = tile_size.min(Texture::maximum_size());
+        let size = image.size();
+        let tiles = tile_rects(size, tile_size)
+            .into_iter()
+            .map(|rect| {
+                let texture = Texture::from_image_with_rect(image, &rect)?;
+                Some(Tile {
+                    texture,
+                    left: rect.left as u32,
+                    top: rect.top as u32,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(TiledTexture { tiles, size })
+    }
+
+    /// Create a tiled texture from an image file
+    ///
+    /// # Arguments
+    /// * filename - Path of the image file to load
+    ///
+    /// Returns `None` on failure.
+    pub fn from_file(filename: &str) -> Option<TiledTexture> {
+        Self::from_image(&Image::from_file(filename)?)
+    }
+
+    /// Return the size of the whole texture, in pixels
+    pub fn size(&self) -> Vector2u {
+        self.size
+    }
+
+    /// Return the number of textures the texture is split into
+    pub fn tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Enable or disable the smooth filter on all the tiles
+    ///
+    /// # Arguments
+    /// * smooth - true to enable smoothing, false to disable it
+    pub fn set_smooth(&mut self, smooth: bool) {
+        for tile in &mut self.tiles {
+            tile.texture.set_smooth(smooth);
+        }
+    }
+}
+
+impl Drawable for TiledTexture {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        for tile in &self.tiles {
+            let mut sprite = Sprite::with_texture(&tile.texture);
+            sprite.set_position((tile.left as f32, tile.top as f32));
+            target.draw_with_renderstates(&sprite, states);
+        }
+    }
+}
+
+/// Cut an area of `size` pixels into rectangles of at most `tile_size` pixels wide and high,
+/// row by row.
+fn tile_rects(size: Vector2u, tile_size: u32) -> Vec<IntRect> {
+    let tile_size = tile_size.max(1);
+    let mut rects = Vec::new();
+    for top in (0..size.y).step_by(tile_size as usize) {
+        for left in (0..size.x).step_by(tile_size as usize) {
+            rects.push(IntRect::new(
+                left as i32,
+                top as i32,
+                tile_size.min(size.x - left) as i32,
+                tile_size.min(size.y - top) as i32,
+            ));
+        }
+    }
+    rects
+}
+
+#[test]
+fn test_tile_rects() {
+    let rects = tile_rects(Vector2u::new(5000, 3000), 2048);
+    assert_eq!(rects.len(), 6);
+    assert_eq!(rects[2], IntRect::new(4096, 0, 904, 2048));
+    assert_eq!(rects[5], IntRect::new(4096, 2048, 904, 952));
+    assert!(tile_rects(Vector2u::new(0, 100), 2048).is_empty());
+}
@@ -161,6 +161,99 @@ impl Transform {
     pub fn transform_rect(&self, rectangle: &FloatRect) -> FloatRect {
         unsafe { FloatRect::from_raw(ffi::sfTransform_transformRect(&self.0, rectangle.raw())) }
     }
+
+    /// Apply a transform to the corners of a rectangle
+    ///
+    /// Unlike [`transform_rect`], the rectangle keeps its orientation:
+    /// the corners of a rotated rectangle are rotated too.
+    ///
+    /// [`transform_rect`]: Transform::transform_rect
+    ///
+    /// # Arguments
+    /// rectangle - Rectangle to transform
+    ///
+    /// Return the transformed top left, top right, bottom right and
+    /// bottom left corners of the rectangle
+    pub fn transform_quad(&self, rectangle: &FloatRect) -> [Vector2f; 4] {
+        let (left, top) = (rectangle.left, rectangle.top);
+        let (right, bottom) = (left + rectangle.width, top + rectangle.height);
+        [
+            self.apply(left, top),
+            self.apply(right, top),
+            self.apply(right, bottom),
+            self.apply(left, bottom),
+        ]
+    }
+
+    /// Apply the inverse of a transform to a 2D point
+    ///
+    /// This finds which point of an object is under a point of the
+    /// screen, for picking, without computing the inverse transform.
+    /// If the transform can't be inverted, the point is returned as is.
+    ///
+    /// # Arguments
+    /// * point - Point to transform
+    ///
+    /// Return a transformed point
+    pub fn inverse_transform_point(&self, point: Vector2f) -> Vector2f {
+        let m = &self.0.matrix;
+        // Solve the two first rows of the matrix, with a last row of (0, 0, 1) for
+        // affine transforms, or projecting the point for the others
+        let (x, y) = (point.x, point.y);
+        let a = [m[0] - x * m[6], m[1] - x * m[7], x * m[8] - m[2]];
+        let b = [m[3] - y * m[6], m[4] - y * m[7], y * m[8] - m[5]];
+        let det = a[0] * b[1] - a[1] * b[0];
+        if det == 0. {
+            return point;
+        }
+        Vector2f::new(
+            (a[2] * b[1] - a[1] * b[2]) / det,
+            (a[0] * b[2] - a[2] * b[0]) / det,
+        )
+    }
+
+    /// Split a transform into a translation, a rotation and a scale
+    ///
+    /// They are the ones that, applied in the order of a [`Transformable`]
+    /// with no origin (scale, then rotation, then translation), give back
+    /// the transform. Shearing and projections can't be expressed this way,
+    /// and are lost. A mirrored transform gets a negative vertical scale.
+    ///
+    /// [`Transformable`]: crate::graphics::Transformable
+    pub fn decompose(&self) -> Decomposition {
+        let m = &self.0.matrix;
+        let scale_x = (m[0] * m[0] + m[3] * m[3]).sqrt();
+        let det = m[0] * m[4] - m[1] * m[3];
+        Decomposition {
+            translation: Vector2f::new(m[2], m[5]),
+            rotation: m[3].atan2(m[0]).to_degrees(),
+            scale: Vector2f::new(scale_x, if scale_x > 0. { det / scale_x } else { 0. }),
+        }
+    }
+
+    /// Transform a point without going through CSFML
+    fn apply(&self, x: f32, y: f32) -> Vector2f {
+        let m = &self.0.matrix;
+        let w = m[6] * x + m[7] * y + m[8];
+        let w = if w == 0. { 1. } else { w };
+        Vector2f::new(
+            (m[0] * x + m[1] * y + m[2]) / w,
+            (m[3] * x + m[4] * y + m[5]) / w,
+        )
+    }
+}
+
+/// The translation, rotation and scale a [`Transform`] is made of.
+///
+/// Returned by [`Transform::decompose`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decomposition {
+    /// The translation.
+    pub translation: Vector2f,
+    /// The rotation, in degrees, clockwise.
+    pub rotation: f32,
+    /// The scale factors along both axes.
+    pub scale: Vector2f,
 }
 
 impl Default for Transform {
@@ -168,3 +261,201 @@ impl Default for Transform {
         Self::IDENTITY
     }
 }
+
+/// Lay out a 3x3 transform as the 4x4 column-major matrix of OpenGL, like
+/// [`Transform::get_matrix`].
+#[cfg(any(feature = "glam", feature = "mint"))]
+fn to_4x4(m: &[f32; 9]) -> [f32; 16] {
+    [
+        m[0], m[3], 0., m[6], m[1], m[4], 0., m[7], 0., 0., 1., 0., m[2], m[5], 0., m[8],
+    ]
+}
+
+/// Keep the 2D part of a 4x4 column-major matrix, dropping everything about the z axis.
+#[cfg(any(feature = "glam", feature = "mint"))]
+fn from_4x4(m: &[f32; 16]) -> Transform {
+    Transform(ffi::sfTransform {
+        matrix: [m[0], m[4], m[12], m[1], m[5], m[13], m[3], m[7], m[15]],
+    })
+}
+
+#[cfg(feature = "glam")]
+impl From<Transform> for glam::Mat3 {
+    fn from(transform: Transform) -> Self {
+        let m = transform.0.matrix;
+        glam::Mat3::from_cols_array(&[m[0], m[3], m[6], m[1], m[4], m[7], m[2], m[5], m[8]])
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Mat3> for Transform {
+    fn from(matrix: glam::Mat3) -> Self {
+        let m = matrix.to_cols_array();
+        Transform(ffi::sfTransform {
+            matrix: [m[0], m[3], m[6], m[1], m[4], m[7], m[2], m[5], m[8]],
+        })
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Transform> for glam::Mat4 {
+    /// Convert to a 3D matrix leaving the z axis untouched, as sent to shaders
+    fn from(transform: Transform) -> Self {
+        glam::Mat4::from_cols_array(&to_4x4(&transform.0.matrix))
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Mat4> for Transform {
+    /// Convert the 2D part of a 3D matrix, anything about the z axis is dropped
+    fn from(matrix: glam::Mat4) -> Self {
+        from_4x4(&matrix.to_cols_array())
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<Transform> for nalgebra::Matrix3<f32> {
+    fn from(transform: Transform) -> Self {
+        let m = transform.0.matrix;
+        nalgebra::Matrix3::new(m[0], m[1], m[2], m[3], m[4], m[5], m[6], m[7], m[8])
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Matrix3<f32>> for Transform {
+    fn from(m: nalgebra::Matrix3<f32>) -> Self {
+        Transform(ffi::sfTransform {
+            matrix: [
+                m[(0, 0)],
+                m[(0, 1)],
+                m[(0, 2)],
+                m[(1, 0)],
+                m[(1, 1)],
+                m[(1, 2)],
+                m[(2, 0)],
+                m[(2, 1)],
+                m[(2, 2)],
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Transform> for mint::RowMatrix3<f32> {
+    fn from(transform: Transform) -> Self {
+        let m = transform.0.matrix;
+        let row = |i: usize| mint::Vector3 {
+            x: m[i * 3],
+            y: m[i * 3 + 1],
+            z: m[i * 3 + 2],
+        };
+        mint::RowMatrix3 {
+            x: row(0),
+            y: row(1),
+            z: row(2),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::RowMatrix3<f32>> for Transform {
+    fn from(m: mint::RowMatrix3<f32>) -> Self {
+        Transform(ffi::sfTransform {
+            matrix: [
+                m.x.x, m.x.y, m.x.z, m.y.x, m.y.y, m.y.z, m.z.x, m.z.y, m.z.z,
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Transform> for mint::ColumnMatrix3<f32> {
+    fn from(transform: Transform) -> Self {
+        let m = transform.0.matrix;
+        let column = |i: usize| mint::Vector3 {
+            x: m[i],
+            y: m[i + 3],
+            z: m[i + 6],
+        };
+        mint::ColumnMatrix3 {
+            x: column(0),
+            y: column(1),
+            z: column(2),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::ColumnMatrix3<f32>> for Transform {
+    fn from(m: mint::ColumnMatrix3<f32>) -> Self {
+        Transform(ffi::sfTransform {
+            matrix: [
+                m.x.x, m.y.x, m.z.x, m.x.y, m.y.y, m.z.y, m.x.z, m.y.z, m.z.z,
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Transform> for mint::ColumnMatrix4<f32> {
+    /// Convert to a 3D matrix leaving the z axis untouched, as sent to shaders
+    fn from(transform: Transform) -> Self {
+        let m = to_4x4(&transform.0.matrix);
+        let column = |i: usize| mint::Vector4 {
+            x: m[i * 4],
+            y: m[i * 4 + 1],
+            z: m[i * 4 + 2],
+            w: m[i * 4 + 3],
+        };
+        mint::ColumnMatrix4 {
+            x: column(0),
+            y: column(1),
+            z: column(2),
+            w: column(3),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::ColumnMatrix4<f32>> for Transform {
+    /// Convert the 2D part of a 3D matrix, anything about the z axis is dropped
+    fn from(m: mint::ColumnMatrix4<f32>) -> Self {
+        let (x, y, w) = (m.x, m.y, m.w);
+        from_4x4(&[
+            x.x, x.y, x.z, x.w, y.x, y.y, y.z, y.w, 0., 0., 1., 0., w.x, w.y, w.z, w.w,
+        ])
+    }
+}
+
+#[test]
+fn test_decompose() {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+    // Scale by (2, 3), rotate by 90 degrees, then translate by (10, 20)
+    let transform = Transform(ffi::sfTransform {
+        matrix: [0., -3., 10., 2., 0., 20., 0., 0., 1.],
+    });
+    let parts = transform.decompose();
+    assert_eq!(parts.translation, Vector2f::new(10., 20.));
+    assert!(close(parts.rotation, 90.));
+    assert!(close(parts.scale.x, 2.) && close(parts.scale.y, 3.));
+
+    let quad = transform.transform_quad(&FloatRect::new(0., 0., 1., 1.));
+    assert_eq!(quad[1], Vector2f::new(10., 22.));
+    assert_eq!(quad[2], Vector2f::new(7., 22.));
+    let back = transform.inverse_transform_point(quad[2]);
+    assert!(close(back.x, 1.) && close(back.y, 1.));
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_glam_round_trip() {
+    let transform = Transform(ffi::sfTransform {
+        matrix: [0., -3., 10., 2., 0., 20., 0., 0., 1.],
+    });
+    let mat3 = glam::Mat3::from(transform);
+    assert_eq!(mat3.to_cols_array()[6..8], [10., 20.]);
+    assert_eq!(Transform::from(mat3).0.matrix, transform.0.matrix);
+    let mat4 = glam::Mat4::from(transform);
+    assert_eq!(mat4.to_cols_array()[12..14], [10., 20.]);
+    assert_eq!(Transform::from(mat4).0.matrix, transform.0.matrix);
+}
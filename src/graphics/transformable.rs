@@ -1,3 +1,4 @@
+use crate::graphics::csfml_graphics_sys as ffi;
 use crate::graphics::Transform;
 use crate::system::Vector2f;
 
@@ -59,3 +60,167 @@ pub trait Transformable {
     /// Gets the inverse combined transform of the object.
     fn inverse_transform(&self) -> Transform;
 }
+
+/// A position, rotation, scale and origin, combined into a transform.
+///
+/// This is the [`Transformable`] that sprites, texts and shapes are made of, for drawables
+/// built in Rust: they keep one and combine its transform with the transform of the render
+/// states they are drawn with. Code handling transformable objects, like tweening or
+/// animation code, then moves and spins custom drawables the same way as the others.
+///
+/// # Usage example
+///
+/// ```
+/// use sfml::graphics::{BasicTransformable, Transformable};
+///
+/// let mut transformable = BasicTransformable::new();
+/// transformable.set_position((100., 50.));
+/// transformable.set_origin((10., 10.));
+/// transformable.rotate(450.);
+/// assert_eq!(transformable.rotation(), 90.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BasicTransformable {
+    position: Vector2f,
+    rotation: f32,
+    scale: Vector2f,
+    origin: Vector2f,
+}
+
+impl BasicTransformable {
+    /// Create a new transformable at the origin, not rotated nor scaled.
+    pub fn new() -> Self {
+        BasicTransformable {
+            position: Vector2f::new(0., 0.),
+            rotation: 0.,
+            scale: Vector2f::new(1., 1.),
+            origin: Vector2f::new(0., 0.),
+        }
+    }
+}
+
+impl Default for BasicTransformable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transformable for BasicTransformable {
+    fn set_position<P: Into<Vector2f>>(&mut self, position: P) {
+        self.position = position.into();
+    }
+    fn set_rotation(&mut self, angle: f32) {
+        self.rotation = (angle % 360. + 360.) % 360.;
+    }
+    fn set_scale<S: Into<Vector2f>>(&mut self, scale: S) {
+        self.scale = scale.into();
+    }
+    fn set_origin<O: Into<Vector2f>>(&mut self, origin: O) {
+        self.origin = origin.into();
+    }
+    fn position(&self) -> Vector2f {
+        self.position
+    }
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+    fn get_scale(&self) -> Vector2f {
+        self.scale
+    }
+    fn origin(&self) -> Vector2f {
+        self.origin
+    }
+    fn move_<O: Into<Vector2f>>(&mut self, offset: O) {
+        self.position += offset.into();
+    }
+    fn rotate(&mut self, angle: f32) {
+        self.set_rotation(self.rotation + angle);
+    }
+    fn scale<F: Into<Vector2f>>(&mut self, factors: F) {
+        let factors = factors.into();
+        self.scale = Vector2f::new(self.scale.x * factors.x, self.scale.y * factors.y);
+    }
+    fn transform(&self) -> Transform {
+        // Same as sf::Transformable::getTransform
+        let angle = -self.rotation.to_radians();
+        let (sine, cosine) = angle.sin_cos();
+        let (sxc, syc) = (self.scale.x * cosine, self.scale.y * cosine);
+        let (sxs, sys) = (self.scale.x * sine, self.scale.y * sine);
+        let (origin, position) = (self.origin, self.position);
+        let tx = -origin.x * sxc - origin.y * sys + position.x;
+        let ty = origin.x * sxs - origin.y * syc + position.y;
+        Transform(ffi::sfTransform {
+            matrix: [sxc, sys, tx, -sxs, syc, ty, 0., 0., 1.],
+        })
+    }
+    fn inverse_transform(&self) -> Transform {
+        self.transform().inverse()
+    }
+}
+
+/// Implement [`Transformable`] for a drawable by forwarding to one of its fields.
+macro_rules! delegate_transformable {
+    (impl for $ty:ty => $field:ident) => {
+        delegate_transformable!(impl<> for $ty => $field);
+    };
+    (impl<$($lt:lifetime),*> for $ty:ty => $field:ident) => {
+        impl<$($lt),*> $crate::graphics::Transformable for $ty {
+            fn set_position<P: Into<$crate::system::Vector2f>>(&mut self, position: P) {
+                self.$field.set_position(position)
+            }
+            fn set_rotation(&mut self, angle: f32) {
+                self.$field.set_rotation(angle)
+            }
+            fn set_scale<S: Into<$crate::system::Vector2f>>(&mut self, scale: S) {
+                self.$field.set_scale(scale)
+            }
+            fn set_origin<O: Into<$crate::system::Vector2f>>(&mut self, origin: O) {
+                self.$field.set_origin(origin)
+            }
+            fn position(&self) -> $crate::system::Vector2f {
+                self.$field.position()
+            }
+            fn rotation(&self) -> f32 {
+                self.$field.rotation()
+            }
+            fn get_scale(&self) -> $crate::system::Vector2f {
+                self.$field.get_scale()
+            }
+            fn origin(&self) -> $crate::system::Vector2f {
+                self.$field.origin()
+            }
+            fn move_<O: Into<$crate::system::Vector2f>>(&mut self, offset: O) {
+                self.$field.move_(offset)
+            }
+            fn rotate(&mut self, angle: f32) {
+                self.$field.rotate(angle)
+            }
+            fn scale<F: Into<$crate::system::Vector2f>>(&mut self, factors: F) {
+                self.$field.scale(factors)
+            }
+            fn transform(&self) -> $crate::graphics::Transform {
+                self.$field.transform()
+            }
+            fn inverse_transform(&self) -> $crate::graphics::Transform {
+                self.$field.inverse_transform()
+            }
+        }
+    };
+}
+
+#[test]
+fn test_basic_transformable() {
+    let mut transformable = BasicTransformable::new();
+    transformable.set_position((100., 50.));
+    transformable.set_origin((10., 0.));
+    transformable.set_scale((2., 2.));
+    transformable.rotate(-270.);
+    assert_eq!(transformable.rotation(), 90.);
+    let m = transformable.transform().0.matrix;
+    // The origin lands on the position, and x goes down once rotated
+    let point = |x: f32, y: f32| (m[0] * x + m[1] * y + m[2], m[3] * x + m[4] * y + m[5]);
+    let close =
+        |(x, y): (f32, f32), (ex, ey): (f32, f32)| (x - ex).abs() < 1e-4 && (y - ey).abs() < 1e-4;
+    assert!(close(point(10., 0.), (100., 50.)));
+    assert!(close(point(11., 0.), (100., 52.)));
+}
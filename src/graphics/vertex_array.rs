@@ -1,7 +1,9 @@
 use crate::graphics::csfml_graphics_sys::*;
 use crate::graphics::{Drawable, FloatRect, PrimitiveType, RenderStates, RenderTarget, Vertex};
+use std::iter::FromIterator;
 use std::mem;
 use std::ops::{Index, IndexMut};
+use std::slice::{self, IterMut};
 
 /// Define a set of one or more 2D primitives
 #[derive(Debug)]
@@ -68,6 +70,31 @@ impl VertexArray {
         unsafe { sfVertexArray_append(self.vertex_array, vertex.raw()) }
     }
 
+    /// Replace all the vertices of a vertex array with a copy of `vertices`
+    ///
+    /// The vertices are copied all at once, which is much faster than
+    /// appending them one by one.
+    ///
+    /// # Arguments
+    /// * vertices - Vertices to copy
+    pub fn set_vertices(&mut self, vertices: &[Vertex]) {
+        self.resize(vertices.len());
+        self.vertices_mut().copy_from_slice(vertices);
+    }
+
+    /// Add a copy of `vertices` at the end of a vertex array
+    ///
+    /// The vertices are copied all at once, which is much faster than
+    /// appending them one by one.
+    ///
+    /// # Arguments
+    /// * vertices - Vertices to add
+    pub fn extend_from_slice(&mut self, vertices: &[Vertex]) {
+        let start = self.vertex_count();
+        self.resize(start + vertices.len());
+        self.vertices_mut()[start..].copy_from_slice(vertices);
+    }
+
     /// Compute the bounding rectangle of a vertex array
     ///
     /// This function returns the axis-aligned rectangle that
@@ -110,6 +137,17 @@ impl VertexArray {
             pos: 0,
         }
     }
+    /// Return a mutable slice over all the vertice contained by the VertexArray
+    pub fn vertices_mut(&mut self) -> &mut [Vertex] {
+        let count = self.vertex_count();
+        if count == 0 {
+            return &mut [];
+        }
+        unsafe {
+            let first = sfVertexArray_getVertex(self.vertex_array, 0) as *mut Vertex;
+            slice::from_raw_parts_mut(first, count)
+        }
+    }
     pub(super) fn raw(&self) -> *const sfVertexArray {
         self.vertex_array
     }
@@ -144,17 +182,70 @@ impl<'a> Iterator for Vertices<'a> {
         if self.pos == point_count {
             None
         } else {
-            self.pos += 1;
-            unsafe {
+            let vertex = unsafe {
                 mem::transmute(sfVertexArray_getVertex(
                     self.vertex_array.vertex_array,
                     self.pos as usize,
                 ))
-            }
+            };
+            self.pos += 1;
+            vertex
         }
     }
 }
 
+impl<'a> IntoIterator for &'a VertexArray {
+    type Item = &'a Vertex;
+    type IntoIter = Vertices<'a>;
+
+    fn into_iter(self) -> Vertices<'a> {
+        self.vertices()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut VertexArray {
+    type Item = &'a mut Vertex;
+    type IntoIter = IterMut<'a, Vertex>;
+
+    fn into_iter(self) -> IterMut<'a, Vertex> {
+        self.vertices_mut().iter_mut()
+    }
+}
+
+impl Extend<Vertex> for VertexArray {
+    fn extend<I: IntoIterator<Item = Vertex>>(&mut self, vertices: I) {
+        for vertex in vertices {
+            self.append(&vertex);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Vertex> for VertexArray {
+    fn extend<I: IntoIterator<Item = &'a Vertex>>(&mut self, vertices: I) {
+        for vertex in vertices {
+            self.append(vertex);
+        }
+    }
+}
+
+impl FromIterator<Vertex> for VertexArray {
+    /// Create a vertex array of points out of vertices
+    fn from_iter<I: IntoIterator<Item = Vertex>>(vertices: I) -> Self {
+        let mut array = Self::default();
+        array.extend(vertices);
+        array
+    }
+}
+
+impl<'a> From<&'a [Vertex]> for VertexArray {
+    /// Create a vertex array of points holding a copy of vertices
+    fn from(vertices: &'a [Vertex]) -> Self {
+        let mut array = Self::default();
+        array.set_vertices(vertices);
+        array
+    }
+}
+
 impl Index<usize> for VertexArray {
     type Output = Vertex;
 
@@ -182,11 +273,7 @@ impl IndexMut<usize> for VertexArray {
 }
 
 impl Drawable for VertexArray {
-    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
-        &'a self,
-        target: &mut RenderTarget,
-        states: RenderStates<'texture, 'shader, 'shader_texture>,
-    ) {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
         target.draw_vertex_array(self, states)
     }
 }
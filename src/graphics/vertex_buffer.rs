@@ -0,0 +1,271 @@
+use crate::graphics::csfml_graphics_sys::*;
+use crate::graphics::{Drawable, PrimitiveType, RenderStates, RenderTarget, Vertex};
+use crate::sf_bool_ext::SfBoolExt;
+
+/// Usage specifiers of a [`VertexBuffer`]
+///
+/// If data is going to be updated once or more every frame, set the usage to `Stream`.
+/// If data is going to be set once and used for a long time without being modified,
+/// set the usage to `Static`.
+/// For everything else `Dynamic` should be a good compromise.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VertexBufferUsage {
+    /// Constantly changing data.
+    Stream = 0,
+    /// Occasionally changing data.
+    Dynamic = 1,
+    /// Rarely changing data.
+    Static = 2,
+}
+
+impl VertexBufferUsage {
+    fn raw(self) -> sfVertexBufferUsage {
+        self as sfVertexBufferUsage
+    }
+    unsafe fn from_raw(raw: sfVertexBufferUsage) -> Self {
+        ::std::mem::transmute(raw)
+    }
+}
+
+/// Vertex buffer storage for one or more 2D primitives
+///
+/// `VertexBuffer` is a simple wrapper around a dynamic buffer of vertices
+/// and a primitive type.
+///
+/// Unlike [`VertexArray`], the vertex data is stored in graphics memory.
+/// In situations where a large amount of vertex data would have to be transferred
+/// into graphics memory every frame, using `VertexBuffer` can help.
+/// By using a `VertexBuffer`, data that has not been changed between frames
+/// does not have to be re-transferred from system to graphics memory
+/// as would be the case with [`VertexArray`]. If data transfer is a bottleneck,
+/// this can lead to performance gains.
+///
+/// Using `VertexBuffer`, the user also has the ability to only modify
+/// a portion of the buffer in graphics memory. This way, a large buffer can be
+/// allocated at the start of the application and only the applicable portions
+/// of it need to be updated during the course of the application.
+///
+/// The usage hint ([`VertexBufferUsage`]) tells the graphics driver how often
+/// the data is going to be updated, so that it can store it where it suits best.
+///
+/// Vertex buffers need support from the graphics driver, check [`is_available`]
+/// before relying on them.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{PrimitiveType, Vertex, VertexBuffer, VertexBufferUsage};
+///
+/// let vertices: Vec<Vertex> = unimplemented!();
+/// let mut buffer = VertexBuffer::new(
+///     PrimitiveType::Triangles,
+///     vertices.len() as u32,
+///     VertexBufferUsage::Static,
+/// )
+/// .unwrap();
+/// buffer.update(&vertices, 0);
+/// // window.draw(&buffer);
+/// ```
+///
+/// [`VertexArray`]: crate::graphics::VertexArray
+/// [`is_available`]: VertexBuffer::is_available
+#[derive(Debug)]
+pub struct VertexBuffer {
+    vertex_buffer: *mut sfVertexBuffer,
+}
+
+impl VertexBuffer {
+    /// Create a new vertex buffer with a specific primitive type and usage specifier
+    ///
+    /// Creates the vertex buffer, allocating enough graphics memory to hold
+    /// `vertex_count` vertices, and sets its primitive type and usage specifier.
+    ///
+    /// # Arguments
+    /// * primitive_type - Type of primitive
+    /// * vertex_count - Amount of vertices
+    /// * usage - Usage specifier
+    ///
+    /// Return Some(VertexBuffer) or None if the buffer could not be created
+    pub fn new(
+        primitive_type: PrimitiveType,
+        vertex_count: u32,
+        usage: VertexBufferUsage,
+    ) -> Option<VertexBuffer> {
+        let buf = unsafe { sfVertexBuffer_create(vertex_count, primitive_type.raw(), usage.raw()) };
+        if buf.is_null() {
+            None
+        } else {
+            Some(VertexBuffer { vertex_buffer: buf })
+        }
+    }
+
+    /// Return the vertex count of a vertex buffer
+    pub fn vertex_count(&self) -> u32 {
+        unsafe { sfVertexBuffer_getVertexCount(self.vertex_buffer) }
+    }
+
+    /// Update a part of the buffer from an array of vertices
+    ///
+    /// `offset` is specified as the number of vertices to skip
+    /// from the beginning of the buffer.
+    ///
+    /// If `offset` is 0 and the number of vertices is equal to the size of
+    /// the currently created buffer, its whole contents are replaced.
+    ///
+    /// If `offset` is 0 and the number of vertices is greater than the
+    /// size of the currently created buffer, a new buffer is created
+    /// containing the vertex data.
+    ///
+    /// If `offset` is 0 and the number of vertices is less than the
+    /// size of the currently created buffer, only the corresponding
+    /// region is updated.
+    ///
+    /// If `offset` is not 0 and `offset` + the number of vertices is greater
+    /// than the size of the currently created buffer, the update fails.
+    ///
+    /// No additional check is performed on the size of the vertex
+    /// array, passing invalid arguments will lead to undefined behavior.
+    ///
+    /// # Arguments
+    /// * vertices - Array of vertices to copy to the buffer
+    /// * offset - Offset in the buffer to copy to
+    ///
+    /// Return true if the update was successful
+    pub fn update(&mut self, vertices: &[Vertex], offset: u32) -> bool {
+        unsafe {
+            sfVertexBuffer_update(
+                self.vertex_buffer,
+                vertices.as_ptr() as *const _,
+                vertices.len() as u32,
+                offset,
+            )
+        }
+        .to_bool()
+    }
+
+    /// Copy the contents of another buffer into this buffer
+    ///
+    /// # Arguments
+    /// * other - Vertex buffer whose contents to copy into this vertex buffer
+    ///
+    /// Return true if the copy was successful
+    pub fn update_from_vertex_buffer(&mut self, other: &VertexBuffer) -> bool {
+        unsafe { sfVertexBuffer_updateFromVertexBuffer(self.vertex_buffer, other.vertex_buffer) }
+            .to_bool()
+    }
+
+    /// Swap the contents of this vertex buffer with those of another
+    ///
+    /// # Arguments
+    /// * other - Instance to swap with
+    pub fn swap(&mut self, other: &mut VertexBuffer) {
+        unsafe { sfVertexBuffer_swap(self.vertex_buffer, other.vertex_buffer) }
+    }
+
+    /// Get the underlying OpenGL handle of the vertex buffer
+    ///
+    /// You shouldn't need to use this function, unless you have
+    /// very specific stuff to implement that SFML doesn't support,
+    /// or implement a temporary workaround until a bug is fixed.
+    ///
+    /// Return the OpenGL handle of the vertex buffer or 0 if not yet created
+    pub fn native_handle(&self) -> u32 {
+        unsafe { sfVertexBuffer_getNativeHandle(self.vertex_buffer) }
+    }
+
+    /// Set the type of primitives to draw
+    ///
+    /// This function defines how the vertices must be interpreted
+    /// when it's time to draw them.
+    ///
+    /// The default primitive type is Points.
+    ///
+    /// # Arguments
+    /// * type - Type of primitive
+    pub fn set_primitive_type(&mut self, primitive_type: PrimitiveType) {
+        unsafe { sfVertexBuffer_setPrimitiveType(self.vertex_buffer, primitive_type.raw()) }
+    }
+
+    /// Get the type of primitives drawn by the vertex buffer
+    ///
+    /// Return the primitive type
+    pub fn primitive_type(&self) -> PrimitiveType {
+        unsafe { PrimitiveType::from_raw(sfVertexBuffer_getPrimitiveType(self.vertex_buffer)) }
+    }
+
+    /// Set the usage specifier of this vertex buffer
+    ///
+    /// This function provides a hint about how this vertex buffer is
+    /// going to be used in terms of data update frequency.
+    ///
+    /// After changing the usage specifier, the vertex buffer has
+    /// to be updated with new data for the usage specifier to
+    /// take effect.
+    ///
+    /// The default usage type is Stream.
+    ///
+    /// # Arguments
+    /// * usage - Usage specifier
+    pub fn set_usage(&mut self, usage: VertexBufferUsage) {
+        unsafe { sfVertexBuffer_setUsage(self.vertex_buffer, usage.raw()) }
+    }
+
+    /// Get the usage specifier of this vertex buffer
+    ///
+    /// Return the usage specifier
+    pub fn usage(&self) -> VertexBufferUsage {
+        unsafe { VertexBufferUsage::from_raw(sfVertexBuffer_getUsage(self.vertex_buffer)) }
+    }
+
+    /// Bind a vertex buffer for rendering
+    ///
+    /// This function is not part of the graphics API, it mustn't be
+    /// used when drawing SFML entities. It must be used only if you
+    /// mix `VertexBuffer` with OpenGL code.
+    ///
+    /// Pass `None` to unbind any vertex buffer.
+    pub fn bind(vertex_buffer: Option<&Self>) {
+        unsafe {
+            sfVertexBuffer_bind(
+                vertex_buffer
+                    .map(|b| b.vertex_buffer as *const _)
+                    .unwrap_or(::std::ptr::null()),
+            )
+        }
+    }
+
+    /// Tell whether or not the system supports vertex buffers
+    ///
+    /// This function should always be called before using
+    /// the vertex buffer features. If it returns false, then
+    /// any attempt to use `VertexBuffer` will fail.
+    pub fn is_available() -> bool {
+        unsafe { sfVertexBuffer_isAvailable() }.to_bool()
+    }
+
+    pub(super) fn raw(&self) -> *const sfVertexBuffer {
+        self.vertex_buffer
+    }
+}
+
+impl Clone for VertexBuffer {
+    /// Return a new VertexBuffer or panic! if there is not enough memory
+    fn clone(&self) -> VertexBuffer {
+        let buf = unsafe { sfVertexBuffer_copy(self.vertex_buffer) };
+        assert!(!buf.is_null(), "Not enough memory to clone VertexBuffer");
+        VertexBuffer { vertex_buffer: buf }
+    }
+}
+
+impl Drawable for VertexBuffer {
+    fn draw(&self, target: &mut dyn RenderTarget, states: RenderStates) {
+        target.draw_vertex_buffer(self, states)
+    }
+}
+
+impl Drop for VertexBuffer {
+    fn drop(&mut self) {
+        unsafe { sfVertexBuffer_destroy(self.vertex_buffer) }
+    }
+}
@@ -1,5 +1,5 @@
 use csfml_system_sys::sfInputStream;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom};
 use std::os::raw::{c_longlong, c_void};
 use std::ptr;
 
@@ -15,9 +15,9 @@ unsafe extern "C" fn read<T: Read + Seek>(
         let mut chunk = stream.take(size as u64);
         let mut buf = vec![];
         let status = chunk.read_to_end(&mut buf);
-        if status.is_ok() {
-            ptr::copy_nonoverlapping(buf.as_ptr(), data as *mut u8, size as usize);
-            return status.unwrap() as i64;
+        if let Ok(read) = status {
+            ptr::copy_nonoverlapping(buf.as_ptr(), data as *mut u8, read);
+            return read as i64;
         }
     }
     -1
@@ -25,15 +25,22 @@ unsafe extern "C" fn read<T: Read + Seek>(
 
 unsafe extern "C" fn get_size<T: Read + Seek>(user_data: *mut c_void) -> c_longlong {
     let stream: &mut T = &mut *(user_data as *mut T);
-    let pos = stream.seek(SeekFrom::Current(0)).unwrap();
-    let size = stream.seek(SeekFrom::End(0)).unwrap();
-    let _ = stream.seek(SeekFrom::Start(pos));
-    size as i64
+    // Streams that can't seek to their end (see `SeekableReader`) don't know their size
+    let mut size = || -> io::Result<u64> {
+        let pos = stream.seek(SeekFrom::Current(0))?;
+        let size = stream.seek(SeekFrom::End(0))?;
+        let _ = stream.seek(SeekFrom::Start(pos))?;
+        Ok(size)
+    };
+    size().map(|size| size as i64).unwrap_or(-1)
 }
 
 unsafe extern "C" fn tell<T: Read + Seek>(user_data: *mut c_void) -> c_longlong {
     let stream: &mut T = &mut *(user_data as *mut T);
-    stream.seek(SeekFrom::Current(0)).unwrap() as i64
+    stream
+        .seek(SeekFrom::Current(0))
+        .map(|pos| pos as i64)
+        .unwrap_or(-1)
 }
 
 unsafe extern "C" fn seek<T: Read + Seek>(
@@ -32,6 +32,9 @@
     unused_results,
     trivial_casts
 )]
+// Clippy takes `Vec::as_ptr` for the inherent method of Rust 1.37, and misses that integer
+// limits and `Duration` constructors are already `const` on 1.31, which CI builds with
+#![allow(clippy::incompatible_msrv)]
 
 #[cfg(feature = "window")]
 #[macro_use]
@@ -40,9 +43,15 @@ extern crate csfml_system_sys;
 #[cfg(feature = "window")]
 extern crate csfml_window_sys;
 
+#[cfg(any(feature = "graphics", feature = "audio"))]
+mod byte_order;
 #[cfg(any(feature = "graphics", feature = "audio"))]
 mod inputstream;
+#[cfg(any(all(feature = "graphics", not(feature = "image")), feature = "audio"))]
+mod path_conv;
 mod sf_bool_ext;
+#[cfg(any(all(feature = "graphics", not(feature = "image")), feature = "audio"))]
+mod temp_dir;
 #[cfg(feature = "window")]
 mod unicode_conv;
 
@@ -1,12 +1,12 @@
 //! A FTP client.
 
-use network::csfml_network_sys as ffi;
-use network::IpAddress;
-use sf_bool_ext::SfBoolExt;
+use crate::network::csfml_network_sys as ffi;
+use crate::network::IpAddress;
+use crate::sf_bool_ext::SfBoolExt;
+use crate::system::Time;
 use std::ffi::{CStr, CString};
 use std::mem;
 use std::str;
-use system::Time;
 
 /// The differents FTP modes availables.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Copy)]
@@ -1,10 +1,10 @@
 //! A HTTP client
 
-use network::csfml_network_sys as ffi;
+use crate::network::csfml_network_sys as ffi;
+use crate::system::Time;
 use std::ffi::{CStr, CString};
 use std::mem;
 use std::str;
-use system::Time;
 
 /// Method type to send the request
 #[repr(u32)]
@@ -171,9 +171,8 @@ impl Default for Request {
     }
 }
 
-impl Raw for Request {
-    type Raw = *const ffi::sfHttpRequest;
-    fn raw(&self) -> Self::Raw {
+impl Request {
+    pub(crate) fn raw(&self) -> *const ffi::sfHttpRequest {
         self.request
     }
 }
@@ -1,7 +1,7 @@
-use network::csfml_network_sys as ffi;
+use crate::network::csfml_network_sys as ffi;
+use crate::system::Time;
 use std::ffi::{CStr, CString};
 use std::str;
-use system::Time;
 
 /// Encapsulate an IPv4 network address.
 #[derive(Clone, Copy)]
@@ -129,16 +129,12 @@ impl IpAddress {
     }
 }
 
-impl Raw for IpAddress {
-    type Raw = ffi::sfIpAddress;
-    fn raw(&self) -> Self::Raw {
+impl IpAddress {
+    pub(crate) fn raw(&self) -> ffi::sfIpAddress {
         self.ip
     }
-}
 
-impl FromRaw for IpAddress {
-    type RawFrom = ffi::sfIpAddress;
-    unsafe fn from_raw(raw: Self::RawFrom) -> Self {
+    pub(crate) unsafe fn from_raw(raw: ffi::sfIpAddress) -> Self {
         IpAddress { ip: raw }
     }
 }
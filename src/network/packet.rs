@@ -1,7 +1,8 @@
+use crate::network::csfml_network_sys as ffi;
+use crate::sf_bool_ext::SfBoolExt;
 use csfml_system_sys::sfBool;
-use network::csfml_network_sys as ffi;
-use sf_bool_ext::SfBoolExt;
 use std::ffi::CString;
+use std::slice;
 
 /// Utility type to build blocks of data to transfer over the network.
 pub struct Packet {
@@ -19,7 +20,7 @@ impl Packet {
     /// Clear a packet
     ///
     /// After calling Clear, the packet is empty.
-    pub fn clear(&self) {
+    pub fn clear(&mut self) {
         unsafe { ffi::sfPacket_clear(self.packet) }
     }
 
@@ -30,6 +31,25 @@ impl Packet {
         unsafe { ffi::sfPacket_getDataSize(self.packet) as u32 }
     }
 
+    /// Get the data contained in a packet
+    ///
+    /// This gives the raw bytes of the packet, as they are sent over the network.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let data = ffi::sfPacket_getData(self.packet);
+            if data.is_null() {
+                &[]
+            } else {
+                slice::from_raw_parts(data as *const u8, ffi::sfPacket_getDataSize(self.packet))
+            }
+        }
+    }
+
+    /// Append raw bytes to the end of a packet
+    pub fn append(&mut self, data: &[u8]) {
+        unsafe { ffi::sfPacket_append(self.packet, data.as_ptr() as *const _, data.len()) }
+    }
+
     /// Tell if the reading position has reached the
     /// end of a packet
     ///
@@ -110,52 +130,52 @@ impl Packet {
     }
 
     /// Function to insert data into a packet
-    pub fn write_bool(&self, data: bool) {
+    pub fn write_bool(&mut self, data: bool) {
         unsafe { ffi::sfPacket_writeBool(self.packet, sfBool::from_bool(data)) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_i8(&self, data: i8) {
+    pub fn write_i8(&mut self, data: i8) {
         unsafe { ffi::sfPacket_writeInt8(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_u8(&self, data: u8) {
+    pub fn write_u8(&mut self, data: u8) {
         unsafe { ffi::sfPacket_writeUint8(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_i16(&self, data: i16) {
+    pub fn write_i16(&mut self, data: i16) {
         unsafe { ffi::sfPacket_writeInt16(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_u16(&self, data: u16) {
+    pub fn write_u16(&mut self, data: u16) {
         unsafe { ffi::sfPacket_writeUint16(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_i32(&self, data: i32) {
+    pub fn write_i32(&mut self, data: i32) {
         unsafe { ffi::sfPacket_writeInt32(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_u32(&self, data: u32) {
+    pub fn write_u32(&mut self, data: u32) {
         unsafe { ffi::sfPacket_writeUint32(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_f32(&self, data: f32) {
+    pub fn write_f32(&mut self, data: f32) {
         unsafe { ffi::sfPacket_writeFloat(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_f64(&self, data: f64) {
+    pub fn write_f64(&mut self, data: f64) {
         unsafe { ffi::sfPacket_writeDouble(self.packet, data) }
     }
 
     /// Function to insert data into a packet
-    pub fn write_string(&self, string: &str) {
+    pub fn write_string(&mut self, string: &str) {
         let c_string = CString::new(string.as_bytes()).unwrap();
         unsafe { ffi::sfPacket_writeString(self.packet, c_string.as_ptr()) }
     }
@@ -175,23 +195,12 @@ impl Clone for Packet {
     }
 }
 
-impl Raw for Packet {
-    type Raw = *const ffi::sfPacket;
-    fn raw(&self) -> Self::Raw {
-        self.packet
-    }
-}
-
-impl RawMut for Packet {
-    type RawMut = *mut ffi::sfPacket;
-    fn raw_mut(&mut self) -> Self::RawMut {
+impl Packet {
+    pub(crate) fn raw_mut(&mut self) -> *mut ffi::sfPacket {
         self.packet
     }
-}
 
-impl FromRaw for Packet {
-    type RawFrom = *mut ffi::sfPacket;
-    unsafe fn from_raw(raw: Self::RawFrom) -> Self {
+    pub(crate) unsafe fn from_raw(raw: *mut ffi::sfPacket) -> Self {
         Packet { packet: raw }
     }
 }
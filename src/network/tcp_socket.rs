@@ -1,9 +1,9 @@
+use crate::network::csfml_network_sys as ffi;
+use crate::network::{IpAddress, Packet, SocketStatus};
+use crate::sf_bool_ext::SfBoolExt;
+use crate::system::Time;
 use csfml_system_sys::sfBool;
-use network::csfml_network_sys as ffi;
-use network::{IpAddress, Packet, SocketStatus};
-use sf_bool_ext::SfBoolExt;
 use std::{mem, ptr};
-use system::Time;
 
 /// Specialized socket using the TCP protocol
 pub struct TcpSocket {
@@ -171,16 +171,8 @@ impl Default for TcpSocket {
     }
 }
 
-impl Raw for TcpSocket {
-    type Raw = *const ffi::sfTcpSocket;
-    fn raw(&self) -> Self::Raw {
-        self.socket
-    }
-}
-
-impl RawMut for TcpSocket {
-    type RawMut = *mut ffi::sfTcpSocket;
-    fn raw_mut(&mut self) -> Self::RawMut {
+impl TcpSocket {
+    pub(crate) fn raw_mut(&mut self) -> *mut ffi::sfTcpSocket {
         self.socket
     }
 }
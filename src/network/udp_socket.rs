@@ -1,7 +1,7 @@
+use crate::network::csfml_network_sys as ffi;
+use crate::network::{IpAddress, Packet, SocketStatus};
+use crate::sf_bool_ext::SfBoolExt;
 use csfml_system_sys::sfBool;
-use network::csfml_network_sys as ffi;
-use network::{IpAddress, Packet, SocketStatus};
-use sf_bool_ext::SfBoolExt;
 use std::{mem, ptr};
 
 /// Specialized socket using the UDP protocol.
@@ -0,0 +1,22 @@
+use std::ffi::CString;
+use std::path::Path;
+
+/// Convert `path` to the nul-terminated filename expected by CSFML.
+///
+/// SFML opens files through the narrow (`char`) file API of the C++ standard library.
+/// On Unix the bytes of the path are passed through untouched. On other platforms the path
+/// has to be valid Unicode and is passed as UTF-8; note that on Windows SFML interprets it in
+/// the current ANSI code page, so only paths representable there (plain ASCII always is)
+/// can be opened.
+///
+/// Returns `None` if the path contains a nul byte, or can't be represented at all.
+pub(crate) fn path_to_cstring(path: &Path) -> Option<CString> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes()
+    };
+    #[cfg(not(unix))]
+    let bytes = path.to_str()?.as_bytes();
+    CString::new(bytes).ok()
+}
@@ -0,0 +1,73 @@
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fs::{self, DirBuilder};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A fresh directory for the temporary files SFML reads or writes, removed when dropped.
+///
+/// The directory gets an unpredictable name, and creating it fails if anything already
+/// exists at its path, so that nobody can prepare it (or a symbolic link in its place)
+/// beforehand. On Unix, only the current user can access it. Files inside it can then be
+/// opened by name safely, which is all SFML does.
+#[derive(Debug)]
+pub(crate) struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Create a new directory in the temporary directory of the system.
+    pub(crate) fn new() -> io::Result<Self> {
+        let mut builder = DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            let _ = builder.mode(0o700);
+        }
+        let base = env::temp_dir();
+        let mut attempts = 0;
+        loop {
+            let path = base.join(format!("sfml-{}-{:016x}", process::id(), random()));
+            match builder.create(&path) {
+                Ok(()) => return Ok(TempDir { path }),
+                Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < 8 => {
+                    attempts += 1
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    /// Get the path of the directory.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A random number, from the random keys of the hash maps of the standard library.
+fn random() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u32(now.subsec_nanos());
+    }
+    hasher.finish()
+}
+
+#[test]
+fn test_temp_dir() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().to_owned();
+    assert!(path.is_dir());
+    assert!(dir.path() != TempDir::new().unwrap().path());
+    fs::write(path.join("file"), b"data").unwrap();
+    drop(dir);
+    assert!(!path.exists());
+}
@@ -16,6 +16,9 @@ use csfml_window_sys as ffi;
 /// a valid context. No explicit activation is needed, all it has to do is to exist.
 /// Its destructor will take care of deactivating and freeing all the attached resources.
 ///
+/// Creating a context doesn't open any window, so it also fits programs that render
+/// off-screen only, with a `RenderTexture` of the graphics module.
+///
 /// # Usage example
 /// ```
 /// # use sfml::window::Context;
@@ -48,6 +51,13 @@ impl Context {
         let result = unsafe { ffi::sfContext_setActive(self.0, SfBoolExt::from_bool(active)) };
         result.to_bool()
     }
+    /// Get the id of the context active on the current thread, or 0 if there is none.
+    ///
+    /// Each context, including the ones of the windows and render textures, has its own
+    /// id. It tells which one OpenGL calls made from the current thread go to.
+    pub fn active_context_id() -> u64 {
+        unsafe { ffi::sfContext_getActiveContextId() }
+    }
     /// Get the settings of the context.
     ///
     /// Note that these settings may be different than the ones passed to the constructor;
@@ -32,6 +32,12 @@ use std::os::raw::c_uint;
 /// enabled. Depending on the system, this might be required for advanced OpenGL debugging.
 /// OpenGL debugging is disabled by default.
 ///
+/// `srgb_capable` requests a framebuffer which converts the colors written to it from
+/// linear to sRGB color space. Combined with textures converting their sRGB pixels to
+/// linear color space when they are sampled (see `Texture::set_srgb` in the graphics
+/// module), this gives a gamma-correct rendering of color-managed art.
+/// Check with [`Window::settings`] whether the framebuffer actually is sRGB capable.
+///
 /// Special Note for OS X: Apple only supports choosing between either a
 /// legacy context (OpenGL 2.1) or a core context (OpenGL version depends on the
 /// operating system version but is at least 3.2). Compatibility contexts are not supported.